@@ -0,0 +1,1091 @@
+//! Typed account lists for each adapter, in the order the adapter parses them
+use crate::Dex;
+use anchor_lang::prelude::*;
+
+/// Accounts consumed by one dex of a route, appended to the remaining accounts
+pub trait DexAccounts {
+    /// Whether the dex parses this account layout
+    fn supports(&self, dex: &Dex) -> bool;
+
+    fn to_account_metas(&self) -> Vec<AccountMeta>;
+}
+
+/// Unchecked account list for adapters with a variable layout (Sanctum, Sanctum router)
+pub struct RawAccounts(pub Vec<AccountMeta>);
+
+impl DexAccounts for RawAccounts {
+    fn supports(&self, _dex: &Dex) -> bool {
+        true
+    }
+
+    fn to_account_metas(&self) -> Vec<AccountMeta> {
+        self.0.clone()
+    }
+}
+
+macro_rules! dex_accounts {
+    ($($name:ident [$($dex:ident),+] { $($field:ident $(: $mut:tt)?),+ $(,)? })*) => {
+        $(
+            #[derive(Clone, Debug, Default)]
+            pub struct $name {
+                $(pub $field: Pubkey,)+
+            }
+
+            impl DexAccounts for $name {
+                fn supports(&self, dex: &Dex) -> bool {
+                    matches!(dex, $(Dex::$dex { .. })|+)
+                }
+
+                fn to_account_metas(&self) -> Vec<AccountMeta> {
+                    vec![$(dex_accounts!(@meta self.$field $(, $mut)?)),+]
+                }
+            }
+        )*
+    };
+    (@meta $key:expr) => {
+        AccountMeta::new_readonly($key, false)
+    };
+    (@meta $key:expr, mut) => {
+        AccountMeta::new($key, false)
+    };
+}
+
+dex_accounts! {
+    SplTokenSwapAccounts [SplTokenSwap] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        swap_info,
+        authority_acc_info,
+        token_a_account: mut,
+        token_b_account: mut,
+        pool_mint: mut,
+        pool_fee: mut,
+        token_program,
+    }
+    StableSwapAccounts [StableSwap] {
+        dex_program_id,
+        swap_authority_pubkey,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        swap_info,
+        swap_authority,
+        token_a_account: mut,
+        token_b_account: mut,
+        swap_admin_fee: mut,
+        token_program,
+    }
+    WhirlpoolAccounts [Whirlpool] {
+        dex_program_id,
+        swap_authority_pubkey,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        token_program,
+        whirlpool: mut,
+        token_vault_a: mut,
+        token_vault_b: mut,
+        tick_array0: mut,
+        tick_array1: mut,
+        tick_array2: mut,
+        oracle: mut,
+    }
+    MeteoraDynamicPoolAccounts [MeteoraDynamicpool] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        pool: mut,
+        a_vault: mut,
+        b_vault: mut,
+        a_token_vault: mut,
+        b_token_vault: mut,
+        a_vault_lp_mint: mut,
+        b_vault_lp_mint: mut,
+        a_vault_lp: mut,
+        b_vault_lp: mut,
+        admin_token_fee: mut,
+        vault_program,
+        token_program,
+    }
+    RaydiumSwapAccounts [RaydiumSwap] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        token_program,
+        amm_id: mut,
+        amm_authority,
+        amm_open_orders: mut,
+        amm_target_orders: mut,
+        pool_coin_token_account: mut,
+        pool_pc_token_account: mut,
+        serum_program_id,
+        serum_market: mut,
+        serum_bids: mut,
+        serum_asks: mut,
+        serum_event_queue: mut,
+        serum_coin_vault_account: mut,
+        serum_pc_vault_account: mut,
+        serum_vault_signer,
+    }
+    RaydiumStableAccounts [RaydiumStableSwap] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        token_program,
+        amm_id: mut,
+        amm_authority,
+        amm_open_orders: mut,
+        pool_coin_token_account: mut,
+        pool_pc_token_account: mut,
+        model_data_account: mut,
+        serum_program_id,
+        serum_market: mut,
+        serum_bids: mut,
+        serum_asks: mut,
+        serum_event_queue: mut,
+        serum_coin_vault_account: mut,
+        serum_pc_vault_account: mut,
+        serum_vault_signer,
+    }
+    RaydiumClmmAccounts [RaydiumClmmSwap] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        amm_config_id,
+        pool_id: mut,
+        input_vault: mut,
+        output_vault: mut,
+        observation_id: mut,
+        tick_array0: mut,
+        ex_bitmap: mut,
+        tick_array1: mut,
+        tick_array2: mut,
+        token_program,
+    }
+    AldrinV1Accounts [AldrinExchangeV1] {
+        dex_program_id,
+        swap_authority_pubkey,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        pool_info,
+        pool_authority,
+        pool_mint: mut,
+        pool_coin_token_vault: mut,
+        pool_pc_token_vault: mut,
+        pool_fee_account: mut,
+        token_program,
+    }
+    AldrinV2Accounts [AldrinExchangeV2] {
+        dex_program_id,
+        swap_authority_pubkey,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        pool_info,
+        pool_authority,
+        pool_mint: mut,
+        pool_coin_token_vault: mut,
+        pool_pc_token_vault: mut,
+        pool_fee_account: mut,
+        pool_curve,
+        token_program,
+    }
+    LifinityV2Accounts [LifinityV2] {
+        dex_program_id,
+        swap_authority_pubkey,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        authority,
+        amm_info: mut,
+        swap_source: mut,
+        swap_destination: mut,
+        pool_mint: mut,
+        fee_account: mut,
+        oracle_main_account,
+        oracle_sub_account,
+        oracle_pc_account,
+        token_program,
+    }
+    RaydiumClmmV2Accounts [RaydiumClmmSwapV2] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        amm_config_id,
+        pool_id: mut,
+        input_vault: mut,
+        output_vault: mut,
+        observation_id: mut,
+        token_program,
+        token_program_2022,
+        memo_program,
+        input_vault_mint,
+        output_vault_mint,
+        ex_bitmap: mut,
+        tick_array0: mut,
+        tick_array1: mut,
+        tick_array2: mut,
+    }
+    FluxBeamAccounts [FluxBeam] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        swap_info,
+        authority_acc_info,
+        token_a_account: mut,
+        token_b_account: mut,
+        pool_mint: mut,
+        pool_fee: mut,
+        source_mint,
+        destination_mint,
+        source_token_program,
+        destination_token_program,
+        token_program_2022,
+    }
+    MeteoraDlmmAccounts [MeteoraDlmm] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        lb_pair: mut,
+        bin_array_bitmap_extension,
+        reserve_x: mut,
+        reserve_y: mut,
+        token_x_mint,
+        token_y_mint,
+        oracle: mut,
+        host_fee_in: mut,
+        token_x_program,
+        token_y_program,
+        event_authority,
+        bin_array0: mut,
+        bin_array1: mut,
+        bin_array2: mut,
+    }
+    RaydiumCpmmAccounts [RaydiumCpmmSwap] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        authority,
+        amm_config,
+        pool_state: mut,
+        input_vault: mut,
+        output_vault: mut,
+        input_token_program,
+        output_token_program,
+        input_token_mint,
+        output_token_mint,
+        observation_state: mut,
+    }
+    OpenBookV2Accounts [OpenBookV2] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        market: mut,
+        market_authority,
+        bids: mut,
+        asks: mut,
+        market_base_vault: mut,
+        market_quote_vault: mut,
+        event_heap: mut,
+        oracle_a,
+        oracle_b,
+        token_program,
+        system_program,
+        open_orders_admin,
+        open_orders_account0: mut,
+        open_orders_account1: mut,
+        open_orders_account2: mut,
+    }
+    WhirlpoolV2Accounts [WhirlpoolV2] {
+        dex_program_id,
+        swap_authority_pubkey,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        token_program_a,
+        token_program_b,
+        memo_program,
+        whirlpool: mut,
+        token_mint_a,
+        token_mint_b,
+        token_vault_a: mut,
+        token_vault_b: mut,
+        tick_array0: mut,
+        tick_array1: mut,
+        tick_array2: mut,
+        oracle: mut,
+    }
+    PhoenixAccounts [Phoenix] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        log_authority: mut,
+        market: mut,
+        base_vault: mut,
+        quote_vault: mut,
+        token_program,
+    }
+    ObricV2Accounts [ObricV2] {
+        dex_program_id,
+        swap_authority_pubkey,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        trading_pair: mut,
+        second_reference_oracle,
+        third_reference_oracle,
+        reserve_x: mut,
+        reserve_y: mut,
+        reference_oracle: mut,
+        x_price_feed,
+        y_price_feed,
+        token_program,
+    }
+    StabbleSwapAccounts [StabbleSwap] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        mint_in,
+        mint_out,
+        vault_token_in: mut,
+        vault_token_out: mut,
+        beneficiary_token_out: mut,
+        pool_token_in: mut,
+        withdraw_authority,
+        vault,
+        vault_authority,
+        vault_program,
+        token_program,
+        token_2022_program,
+    }
+    MeteoraDynamicVaultAccounts [MeteoraVaultDeposit, MeteoraVaultWithdraw] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        vault: mut,
+        token_vault: mut,
+        lp_mint: mut,
+        token_program,
+    }
+    SarosAccounts [Saros] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        pool,
+        pool_authority,
+        pool_token_in: mut,
+        pool_token_out: mut,
+        pool_lp_token_mint: mut,
+        protocol_lp_token: mut,
+        token_program,
+    }
+    MeteoraLSTPoolAccounts [MeteoraLst] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        pool: mut,
+        a_vault: mut,
+        b_vault: mut,
+        a_token_vault: mut,
+        b_token_vault: mut,
+        a_vault_lp_mint: mut,
+        b_vault_lp_mint: mut,
+        a_vault_lp: mut,
+        b_vault_lp: mut,
+        admin_token_fee: mut,
+        vault_program,
+        token_program,
+        lst,
+    }
+    SolfiAccounts [Solfi] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        pair: mut,
+        pool_token_account_a: mut,
+        pool_token_account_b: mut,
+        token_program,
+        sysvar_instructions,
+    }
+    QualiaSwapAccounts [QualiaSwap] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        globale_state_id: mut,
+        log_account_id,
+        token_program,
+        token_in_vault: mut,
+        token_out_vault: mut,
+        sysvar,
+    }
+    ZeroFiAccounts [Zerofi] {
+        dex_program_id,
+        swap_authority_pubkey,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        pair: mut,
+        vault_info_base: mut,
+        vault_base: mut,
+        vault_info_quote: mut,
+        vault_quote: mut,
+        token_program,
+        sysvar_instructions,
+    }
+    VirtualsAccounts [Virtuals] {
+        dex_program_id,
+        swap_authority_pubkey,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        vpool: mut,
+        token_mint,
+        vpool_token_ata: mut,
+        platform_prototype: mut,
+        platform_prototype_virtuals_ata: mut,
+        vpool_virtuals_ata: mut,
+        token_program,
+    }
+    VertigoSwapAccounts [VertigoBuy, VertigoSell] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        pool: mut,
+        owner,
+        mint_a,
+        mint_b,
+        vault_a: mut,
+        vault_b: mut,
+        token_program_a,
+        token_program_b,
+        system_program,
+    }
+    PerpetualsAccounts [PerpetualsAddLiq, PerpetualsRemoveLiq] {
+        dex_program_id,
+        owner,
+        funding_or_receiving_account: mut,
+        jlp_token_account: mut,
+        perpetuals_vault_authority,
+        perpetuals_state,
+        perpetuals_pool: mut,
+        collateral_custody: mut,
+        doves_price_account,
+        pythnet_price_account,
+        custody_token_account: mut,
+        jlp_mint: mut,
+        token_program,
+        event_authority,
+    }
+    PerpetualsSwapAccounts [PerpetualsSwap] {
+        dex_program_id,
+        owner,
+        funding_account: mut,
+        receiving_account: mut,
+        perpetuals_vault_authority,
+        perpetuals_state,
+        perpetuals_pool: mut,
+        receiving_custody: mut,
+        receiving_custody_doves_price_account,
+        receiving_custody_pythnet_price_account,
+        receiving_custody_token_account: mut,
+        dispensing_custody: mut,
+        dispensing_custody_doves_price_account,
+        dispensing_custody_pythnet_price_account,
+        dispensing_custody_token_account: mut,
+        token_program,
+        event_authority,
+    }
+    LaunchpadAccounts [RaydiumLaunchpad, LetsBonkFun] {
+        dex_program_id,
+        swap_authority_pubkey,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        launchpad_authority,
+        global_config,
+        platform_config,
+        pool_state: mut,
+        base_vault: mut,
+        quote_vault: mut,
+        base_mint,
+        quote_mint,
+        base_token_program,
+        quote_token_program,
+        system_program,
+        platform_claim_fee_vault: mut,
+        creator_claim_fee_vault: mut,
+        event_authority,
+    }
+    WoofiAccounts [Woofi] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        wooficonfig: mut,
+        token_program,
+        token_a_wooracle: mut,
+        token_a_woopool: mut,
+        a_token_vault: mut,
+        token_a_price_update: mut,
+        token_b_wooracle: mut,
+        token_b_woopool: mut,
+        b_token_vault: mut,
+        token_b_price_update: mut,
+        quote_pool: mut,
+        quote_price_update: mut,
+        quote_token_vault: mut,
+        rebate_to: mut,
+    }
+    MeteoraDbcAccounts [MeteoraDbc] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        pool_authority,
+        config,
+        pool: mut,
+        base_vault: mut,
+        quote_vault: mut,
+        base_mint,
+        quote_mint,
+        token_base_program,
+        token_quote_program,
+        referral_token_account: mut,
+        event_authority,
+    }
+    MeteoraDlmmSwap2Accounts [MeteoraDlmmSwap2] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        lb_pair: mut,
+        bin_array_bitmap_extension,
+        reserve_x: mut,
+        reserve_y: mut,
+        token_x_mint,
+        token_y_mint,
+        oracle: mut,
+        host_fee_in: mut,
+        token_x_program,
+        token_y_program,
+        memo_program,
+        event_authority,
+        bin_array0: mut,
+        bin_array1: mut,
+        bin_array2: mut,
+    }
+    MeteoraDAMMV2SwapAccounts [MeteoraDAMMV2] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        pool_authority,
+        pool: mut,
+        input_token_account: mut,
+        output_token_account: mut,
+        token_a_vault: mut,
+        token_b_vault: mut,
+        token_a_mint,
+        token_b_mint,
+        token_a_program,
+        token_b_program,
+        referral_token_account: mut,
+        event_authority,
+    }
+    GavelSwapAccounts [Gavel] {
+        dex_program_id,
+        swap_authority_pubkey,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        log_authority,
+        pool: mut,
+        base_vault: mut,
+        quote_vault: mut,
+        token_program,
+    }
+    BoopfunBuyAccounts [BoopfunBuy] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        mint,
+        bonding_curve: mut,
+        trading_fees_vault: mut,
+        bonding_curve_vault: mut,
+        bonding_curve_sol_vault: mut,
+        config,
+        vault_authority,
+        wsol,
+        system_program,
+        token_program,
+        associated_token_program,
+    }
+    BoopfunSellAccounts [BoopfunSell] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        mint,
+        bonding_curve: mut,
+        trading_fees_vault: mut,
+        bonding_curve_vault: mut,
+        bonding_curve_sol_vault: mut,
+        seller_token_account: mut,
+        seller: mut,
+        recipient: mut,
+        config,
+        system_program,
+        token_program,
+        associated_token_program,
+    }
+    MeteoraDbc2Accounts [MeteoraDbc2] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        pool_authority,
+        config,
+        pool: mut,
+        base_vault: mut,
+        quote_vault: mut,
+        base_mint,
+        quote_mint,
+        token_base_program,
+        token_quote_program,
+        referral_token_account: mut,
+        event_authority,
+        sysvar_instructions,
+    }
+    GooseFxAccounts [GooseFX] {
+        dex_program_id,
+        swap_authority_pubkey,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        authority,
+        amm_config,
+        pool_state: mut,
+        input_vault: mut,
+        output_vault: mut,
+        input_token_program,
+        output_token_program,
+        input_token_mint,
+        output_token_mint,
+        observation_state: mut,
+    }
+    DooarAccounts [Dooar] {
+        dex_program_id,
+        swap_authority_pubkey,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        token_swap,
+        authority,
+        user_transfer_authority,
+        user_source: mut,
+        pool_source: mut,
+        pool_destination: mut,
+        user_destination: mut,
+        pool_mint: mut,
+        fee_account: mut,
+        refund_to,
+    }
+    NumeraireSwapAccounts [Numeraire] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        pool: mut,
+        in_mint,
+        out_mint,
+        in_vault: mut,
+        out_vault: mut,
+        numeraire_config,
+        token_program,
+        token_2022_program,
+    }
+    SaberDecimalWrapperAccounts [SaberDecimalWrapperDeposit, SaberDecimalWrapperWithdraw] {
+        dex_program_id,
+        swap_authority_pubkey,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        wrapper,
+        wrapper_mint: mut,
+        wrapper_underlying_tokens: mut,
+        owner: mut,
+        user_underlying_tokens: mut,
+        user_wrapped_tokens: mut,
+        token_program,
+    }
+    SarosDlmmAccounts [SarosDlmm] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        pair: mut,
+        token_mint_x,
+        token_mint_y,
+        bin_array_lower: mut,
+        bin_array_upper: mut,
+        token_vault_x: mut,
+        token_vault_y: mut,
+        token_program_x,
+        token_program_y,
+        memo_program,
+        pair_hook: mut,
+        rewarder_hook,
+        event_authority,
+        hook_bin_array_lower: mut,
+        hook_bin_array_upper: mut,
+    }
+    OneDexSwapAccounts [OneDexSwap] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        metadata_state,
+        pool_state: mut,
+        pool_auth_pubkey,
+        pool_token_in_account: mut,
+        pool_token_out_account: mut,
+        metadata_swap_fee_account: mut,
+        referrer_token_account: mut,
+        token_program,
+    }
+    ManifestAccounts [Manifest] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        market: mut,
+        system_program,
+        base_vault: mut,
+        quote_vault: mut,
+        token_program_base,
+        base_mint,
+        token_program_quote,
+        quote_mint,
+        global: mut,
+        global_vault: mut,
+    }
+    ByrealClmmSwapV2Accounts [ByrealClmm] {
+        dex_program_id,
+        swap_authority_pubkey,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        payer,
+        amm_config,
+        pool_state: mut,
+        input_token_account: mut,
+        output_token_account: mut,
+        input_vault: mut,
+        output_vault: mut,
+        observation_state: mut,
+        token_program,
+        token_program_2022,
+        memo_program,
+        input_vault_mint,
+        output_vault_mint,
+        tickarray_bitmap_extension: mut,
+        tick_array0: mut,
+        tick_array1: mut,
+        tick_array2: mut,
+        tick_array3: mut,
+        tick_array4: mut,
+        tick_array5: mut,
+    }
+    PancakeSwapV3SwapAccounts [PancakeSwapV3Swap] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        amm_config,
+        pool_state: mut,
+        input_vault: mut,
+        output_vault: mut,
+        observation_state: mut,
+        token_program,
+        tick_array0: mut,
+        ex_bitmap: mut,
+        tick_array1: mut,
+        tick_array2: mut,
+    }
+    PancakeSwapV3SwapV2Accounts [PancakeSwapV3SwapV2] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        amm_config,
+        pool_state: mut,
+        input_vault: mut,
+        output_vault: mut,
+        observation_state: mut,
+        token_program,
+        token_program_2022,
+        memo_program,
+        input_vault_mint,
+        output_vault_mint,
+        ex_bitmap: mut,
+        tick_array0: mut,
+        tick_array1: mut,
+        tick_array2: mut,
+    }
+    TesseraAccounts [Tessera] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        global_state,
+        pool_state: mut,
+        base_vault: mut,
+        quote_vault: mut,
+        base_mint,
+        quote_mint,
+        base_token_program,
+        quote_token_program,
+        sysvar_instructions,
+    }
+    SolRfqAccounts [SolRfq] {
+        dex_program_id,
+        swap_authority: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        maker: mut,
+        maker_mint,
+        taker_mint,
+        maker_send_token_account: mut,
+        maker_receive_token_account: mut,
+        maker_token_program,
+        taker_token_program,
+        system_program,
+    }
+    HumidifiAccounts [Humidifi] {
+        dex_program_id,
+        swap_authority_pubkey,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        humidifi_param,
+        pool: mut,
+        pool_base_token_account: mut,
+        pool_quote_token_account: mut,
+        clok,
+        token_program,
+        sysvar_instructions,
+    }
+    HeavenSwapAccounts [HeavenBuy, HeavenSell] {
+        dex_program_id,
+        swap_authority_pubkey,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        token_a_program,
+        token_b_program,
+        associated_token_program,
+        system_program,
+        liquidity_pool_state: mut,
+        user: mut,
+        token_a_mint,
+        token_b_mint,
+        user_token_a_vault: mut,
+        user_token_b_vault: mut,
+        token_a_vault: mut,
+        token_b_vault: mut,
+        protocol_config: mut,
+        instruction_sysvar_account_info,
+        chainlink_program,
+        chainlink_sol_usd_feed,
+    }
+    SolfiV2Accounts [SolfiV2] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        market: mut,
+        oracle,
+        global_config_account,
+        base_vault: mut,
+        quote_vault: mut,
+        base_mint,
+        quote_mint,
+        base_token_program,
+        quote_token_program,
+        instruction_sysvar,
+    }
+    PumpfunBuyV3Accounts [PumpfunBuy3] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        global,
+        fee_recipient: mut,
+        mint,
+        bonding_curve: mut,
+        associated_bonding_curve: mut,
+        system_program,
+        token_program,
+        creator_vault: mut,
+        event_authority,
+        global_volume_accumulator: mut,
+        user_volume_accumulator: mut,
+        fee_config,
+        fee_program,
+    }
+    PumpfunSellV3Accounts [PumpfunSell3] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        global,
+        fee_recipient: mut,
+        mint,
+        bonding_curve: mut,
+        associated_bonding_curve: mut,
+        system_program,
+        creator_vault: mut,
+        token_program,
+        event_authority,
+        fee_config,
+        fee_program,
+    }
+    PumpfunammBuyV3Accounts [PumpfunammBuy3] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        pool,
+        global_config,
+        base_mint,
+        quote_mint,
+        pool_base_token_account: mut,
+        pool_quote_token_account: mut,
+        protocol_fee_recipient,
+        protocol_fee_recipient_token_account: mut,
+        base_token_program,
+        quote_token_program,
+        system_program,
+        associated_token_program,
+        event_authority,
+        coin_creator_vault_ata: mut,
+        coin_creator_vault_authority,
+        global_volume_accumulator: mut,
+        user_volume_accumulator: mut,
+        fee_config,
+        fee_program,
+    }
+    PumpfunammSellV3Accounts [PumpfunammSell3] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        pool,
+        global_config,
+        base_mint,
+        quote_mint,
+        pool_base_token_account: mut,
+        pool_quote_token_account: mut,
+        protocol_fee_recipient,
+        protocol_fee_recipient_token_account: mut,
+        base_token_program,
+        quote_token_program,
+        system_program,
+        associated_token_program,
+        event_authority,
+        coin_creator_vault_ata: mut,
+        coin_creator_vault_authority,
+        fee_config,
+        fee_program,
+    }
+    GoonfiAccounts [Goonfi] {
+        dex_program_id,
+        swap_authority: mut,
+        swap_source_account: mut,
+        swap_destination_account: mut,
+        goonfi_param,
+        market: mut,
+        base_vault: mut,
+        quote_vault: mut,
+        blacklist,
+        sysvar_instructions,
+        token_program,
+    }
+    MoonitAccounts [MoonitBuy, MoonitSell] {
+        dex_program_id,
+        swap_authority_pubkey,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        sender: mut,
+        sender_token_account: mut,
+        curve_account: mut,
+        curve_token_account: mut,
+        dex_fee: mut,
+        helio_fee: mut,
+        mint,
+        config_account,
+        token_program,
+        associated_token_program,
+        system_program,
+        src_or_dst_token_account: mut,
+    }
+    RaydiumSwapV2Accounts [RaydiumSwapV2] {
+        dex_program_id,
+        swap_authority_pubkey: mut,
+        swap_source_token: mut,
+        swap_destination_token: mut,
+        token_program,
+        amm_id: mut,
+        amm_authority,
+        pool_coin_token_account: mut,
+        pool_pc_token_account: mut,
+    }
+    SwaapAccounts [Swaap] {
+        dex_program_id,
+        swap_authority: mut,
+        swap_source_account: mut,
+        swap_destination_account: mut,
+        safeguard_pool: mut,
+        base_vault: mut,
+        quote_vault: mut,
+        token_program,
+    }
+    SugarMoneyAccounts [SugarMoneyBuy, SugarMoneySell] {
+        dex_program_id,
+        swap_authority: mut,
+        swap_source_account: mut,
+        swap_destination_account: mut,
+        state,
+        mint,
+        bonding_curve: mut,
+        bonding_curve_sol_associated_account: mut,
+        bonding_curve_token_associated_account: mut,
+        fee_receiver: mut,
+        token_program,
+        associated_token_program,
+        system_program,
+        rent,
+        event_authority,
+    }
+    MeteoraDAMMV2Swap2Accounts [MeteoraDAMMV2Swap2] {
+        dex_program_id,
+        swap_authority: mut,
+        swap_source_account: mut,
+        swap_destination_account: mut,
+        pool_authority,
+        pool: mut,
+        token_a_vault: mut,
+        token_b_vault: mut,
+        token_a_mint,
+        token_b_mint,
+        token_a_program,
+        token_b_program,
+        referral_token_account: mut,
+        event_authority,
+        instruction_sysvar,
+    }
+}
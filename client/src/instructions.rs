@@ -0,0 +1,335 @@
+//! Ready-to-sign router instructions
+use crate::route::SwapPlan;
+use anchor_lang::InstructionData;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use dex_solana::{
+    BridgeToArgs, CommissionShare, CommissionSwapArgs, CommissionWrapUnwrapArgs, SwapArgsV2,
+    accounts, instruction,
+};
+use dex_solana::{ID, PlatformFeeWrapUnwrapArgs};
+
+fn build_instruction(
+    accounts: impl ToAccountMetas,
+    remaining_accounts: Vec<AccountMeta>,
+    data: impl InstructionData,
+) -> Instruction {
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.extend(remaining_accounts);
+    Instruction { program_id: ID, accounts: account_metas, data: data.data() }
+}
+
+pub fn swap(accounts: accounts::SwapAccounts, plan: SwapPlan, order_id: u64) -> Instruction {
+    build_instruction(
+        accounts,
+        plan.remaining_accounts,
+        instruction::Swap { data: plan.args, order_id },
+    )
+}
+
+pub fn swap_v3(
+    accounts: accounts::CommissionProxySwapAccountsV3,
+    plan: SwapPlan,
+    commission_info: u32,
+    platform_fee_rate: u16,
+    order_id: u64,
+) -> Instruction {
+    build_instruction(
+        accounts,
+        plan.remaining_accounts,
+        instruction::SwapV3 { args: plan.args, commission_info, platform_fee_rate, order_id },
+    )
+}
+
+pub fn swap_tob_v3(
+    accounts: accounts::CommissionProxySwapAccountsV3,
+    plan: SwapPlan,
+    commission_info: u32,
+    trim_rate: u8,
+    platform_fee_rate: u16,
+    order_id: u64,
+) -> Instruction {
+    build_instruction(
+        accounts,
+        plan.remaining_accounts,
+        instruction::SwapTobV3 {
+            args: plan.args,
+            commission_info,
+            trim_rate,
+            platform_fee_rate,
+            order_id,
+        },
+    )
+}
+
+pub fn swap_v3_with_limits(
+    accounts: accounts::CommissionProxySwapAccountsV3,
+    plan: SwapPlan<SwapArgsV2>,
+    commission_info: u32,
+    platform_fee_rate: u16,
+    order_id: u64,
+) -> Instruction {
+    build_instruction(
+        accounts,
+        plan.remaining_accounts,
+        instruction::SwapV3WithLimits {
+            args: plan.args,
+            commission_info,
+            platform_fee_rate,
+            order_id,
+        },
+    )
+}
+
+pub fn swap_tob_v3_with_limits(
+    accounts: accounts::CommissionProxySwapAccountsV3,
+    plan: SwapPlan<SwapArgsV2>,
+    commission_info: u32,
+    trim_rate: u8,
+    platform_fee_rate: u16,
+    order_id: u64,
+) -> Instruction {
+    build_instruction(
+        accounts,
+        plan.remaining_accounts,
+        instruction::SwapTobV3WithLimits {
+            args: plan.args,
+            commission_info,
+            trim_rate,
+            platform_fee_rate,
+            order_id,
+        },
+    )
+}
+
+/// The recipient accounts follow the route accounts, in the order of `commission_shares`.
+pub fn swap_v3_split_commission(
+    accounts: accounts::CommissionProxySwapAccountsV3,
+    plan: SwapPlan,
+    commission_info: u32,
+    commission_shares: Vec<CommissionShare>,
+    platform_fee_rate: u16,
+    order_id: u64,
+) -> Instruction {
+    let mut remaining_accounts = plan.remaining_accounts;
+    remaining_accounts
+        .extend(commission_shares.iter().map(|share| AccountMeta::new(share.recipient, false)));
+    build_instruction(
+        accounts,
+        remaining_accounts,
+        instruction::SwapV3SplitCommission {
+            args: plan.args,
+            commission_info,
+            commission_shares,
+            platform_fee_rate,
+            order_id,
+        },
+    )
+}
+
+/// The recipient accounts follow the route accounts, in the order of `commission_shares`.
+pub fn swap_tob_v3_split_commission(
+    accounts: accounts::CommissionProxySwapAccountsV3,
+    plan: SwapPlan,
+    commission_info: u32,
+    commission_shares: Vec<CommissionShare>,
+    platform_fee_rate: u16,
+    order_id: u64,
+) -> Instruction {
+    let mut remaining_accounts = plan.remaining_accounts;
+    remaining_accounts
+        .extend(commission_shares.iter().map(|share| AccountMeta::new(share.recipient, false)));
+    build_instruction(
+        accounts,
+        remaining_accounts,
+        instruction::SwapTobV3SplitCommission {
+            args: plan.args,
+            commission_info,
+            commission_shares,
+            platform_fee_rate,
+            order_id,
+        },
+    )
+}
+
+/// The adaptor accounts follow the route accounts, their count is fixed by
+/// `bridge_to_args.adaptor_id`.
+pub fn swap_and_bridge(
+    accounts: accounts::FromSwapAccounts,
+    plan: SwapPlan,
+    commission_info: u32,
+    platform_fee_rate: u16,
+    bridge_to_args: BridgeToArgs,
+    adaptor_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let mut remaining_accounts = plan.remaining_accounts;
+    remaining_accounts.extend(adaptor_accounts);
+    build_instruction(
+        accounts,
+        remaining_accounts,
+        instruction::SwapAndBridge {
+            args: plan.args,
+            commission_info,
+            platform_fee_rate,
+            bridge_to_args,
+        },
+    )
+}
+
+/// Sent by the bridge program as a CPI, see `receive_and_swap` in the router.
+pub fn receive_and_swap(
+    accounts: accounts::ToSwapAccounts,
+    plan: SwapPlan,
+    commission_info: u32,
+    platform_fee_rate: u16,
+    order_id: u64,
+) -> Instruction {
+    build_instruction(
+        accounts,
+        plan.remaining_accounts,
+        instruction::ReceiveAndSwap {
+            args: plan.args,
+            commission_info,
+            platform_fee_rate,
+            order_id,
+        },
+    )
+}
+
+/// Simulation only: the route result is returned as a borsh `QuoteResult` in the return data
+/// of a transaction failing with `QuoteOnly`.
+pub fn quote(
+    accounts: accounts::QuoteAccounts,
+    plan: SwapPlan,
+    commission_info: u32,
+    platform_fee_rate: u16,
+    trim_rate: u8,
+    charge_rate: u16,
+) -> Instruction {
+    build_instruction(
+        accounts,
+        plan.remaining_accounts,
+        instruction::Quote {
+            args: plan.args,
+            commission_info,
+            platform_fee_rate,
+            trim_rate,
+            charge_rate,
+        },
+    )
+}
+
+fn commission_swap_args(
+    plan: SwapPlan,
+    commission_rate: u16,
+    commission_direction: bool,
+) -> (CommissionSwapArgs, Vec<AccountMeta>) {
+    let args = CommissionSwapArgs {
+        amount_in: plan.args.amount_in,
+        expect_amount_out: plan.args.expect_amount_out,
+        min_return: plan.args.min_return,
+        amounts: plan.args.amounts,
+        routes: plan.args.routes,
+        commission_rate,
+        commission_direction,
+    };
+    (args, plan.remaining_accounts)
+}
+
+pub fn commission_spl_swap(
+    accounts: accounts::CommissionSPLAccounts,
+    plan: SwapPlan,
+    commission_rate: u16,
+    commission_direction: bool,
+    order_id: u64,
+) -> Instruction {
+    let (data, remaining_accounts) =
+        commission_swap_args(plan, commission_rate, commission_direction);
+    build_instruction(
+        accounts,
+        remaining_accounts,
+        instruction::CommissionSplSwap { data, order_id },
+    )
+}
+
+pub fn commission_sol_swap(
+    accounts: accounts::CommissionSOLAccounts,
+    plan: SwapPlan,
+    commission_rate: u16,
+    commission_direction: bool,
+    order_id: u64,
+) -> Instruction {
+    let (data, remaining_accounts) =
+        commission_swap_args(plan, commission_rate, commission_direction);
+    build_instruction(
+        accounts,
+        remaining_accounts,
+        instruction::CommissionSolSwap { data, order_id },
+    )
+}
+
+pub fn commission_wrap_unwrap(
+    accounts: accounts::CommissionWrapUnwrapAccounts,
+    data: CommissionWrapUnwrapArgs,
+    order_id: u64,
+) -> Instruction {
+    build_instruction(accounts, vec![], instruction::CommissionWrapUnwrap { data, order_id })
+}
+
+pub fn wrap_unwrap_v3(
+    accounts: accounts::PlatformFeeWrapUnwrapAccounts,
+    args: PlatformFeeWrapUnwrapArgs,
+) -> Instruction {
+    build_instruction(accounts, vec![], instruction::WrapUnwrapV3 { args })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Hop, RouteBuilder, WhirlpoolAccounts};
+    use anchor_lang::{AnchorDeserialize, Discriminator};
+    use dex_solana::{Dex, SwapArgs, whirlpool_program};
+
+    #[test]
+    pub fn test_swap_v3_instruction() {
+        let (payer, source, destination) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let whirlpool = WhirlpoolAccounts {
+            dex_program_id: whirlpool_program::ID,
+            swap_authority_pubkey: payer,
+            swap_source_token: source,
+            swap_destination_token: destination,
+            ..Default::default()
+        };
+        let plan = RouteBuilder::new(1000, 990, 980)
+            .route(1000, vec![Hop::new().dex(Dex::Whirlpool, 100, whirlpool)])
+            .build()
+            .unwrap();
+        let accounts = accounts::CommissionProxySwapAccountsV3 {
+            payer,
+            source_token_account: source,
+            destination_token_account: destination,
+            source_mint: Pubkey::new_unique(),
+            destination_mint: Pubkey::new_unique(),
+            commission_account: None,
+            platform_fee_account: None,
+            sa_authority: None,
+            source_token_sa: None,
+            destination_token_sa: None,
+            source_token_program: None,
+            destination_token_program: None,
+            associated_token_program: None,
+            system_program: None,
+        };
+
+        let ix = swap_v3(accounts, plan, 0, 0, 7);
+        assert_eq!(ix.program_id, ID);
+        assert_eq!(ix.accounts.len(), 14 + 12);
+        assert!(ix.accounts[0].is_signer);
+        assert_eq!(ix.accounts[14].pubkey, whirlpool_program::ID);
+        assert!(ix.data.starts_with(instruction::SwapV3::DISCRIMINATOR));
+        let args = SwapArgs::deserialize(&mut &ix.data[8..]).unwrap();
+        assert_eq!(args.amount_in, 1000);
+        assert_eq!(args.routes[0][0].dexes, vec![Dex::Whirlpool]);
+    }
+}
@@ -0,0 +1,12 @@
+//! Off-chain helpers to build dex-solana router instructions
+pub mod adapters;
+pub mod instructions;
+pub mod price;
+pub mod route;
+
+pub use adapters::*;
+pub use dex_solana::{
+    Dex, ForkQuote, OrderBookLimit, QuoteResult, Route, RouteV2, SwapArgs, SwapArgsV2, SwapResult,
+    SwapResultV1,
+};
+pub use route::{Fork, Hop, RouteBuilder, SwapPlan};
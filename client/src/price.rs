@@ -0,0 +1,88 @@
+//! Converts human prices into the native units of `OrderBookLimit::price`
+//!
+//! Prices are quote tokens per base token. The worst price is rounded against the taker:
+//! up when selling base, down when buying base.
+use anchor_lang::prelude::*;
+use dex_solana::adapters::manifest::MANIFEST_PRICE_SCALE;
+use dex_solana::error::ErrorCode;
+
+/// OpenBook V2 price in quote lots per base lot
+pub fn openbook_v2_price_lots(
+    price: f64,
+    base_decimals: u8,
+    quote_decimals: u8,
+    base_lot_size: i64,
+    quote_lot_size: i64,
+    is_base_in: bool,
+) -> Result<u64> {
+    require!(base_lot_size > 0 && quote_lot_size > 0, ErrorCode::InvalidOrderBookPrice);
+    let price_lots = price * 10f64.powi(quote_decimals as i32) * base_lot_size as f64
+        / (10f64.powi(base_decimals as i32) * quote_lot_size as f64);
+    to_native(price_lots, is_base_in)
+}
+
+/// Phoenix price in ticks, a base unit is `raw_base_units_per_base_unit` whole base tokens
+pub fn phoenix_price_in_ticks(
+    price: f64,
+    quote_decimals: u8,
+    raw_base_units_per_base_unit: u32,
+    tick_size_in_quote_atoms_per_base_unit: u64,
+    is_base_in: bool,
+) -> Result<u64> {
+    require!(tick_size_in_quote_atoms_per_base_unit > 0, ErrorCode::InvalidOrderBookPrice);
+    let price_in_ticks =
+        price * 10f64.powi(quote_decimals as i32) * raw_base_units_per_base_unit as f64
+            / tick_size_in_quote_atoms_per_base_unit as f64;
+    to_native(price_in_ticks, is_base_in)
+}
+
+/// Manifest price in quote atoms per base atom, scaled by `MANIFEST_PRICE_SCALE`
+pub fn manifest_price(
+    price: f64,
+    base_decimals: u8,
+    quote_decimals: u8,
+    is_base_in: bool,
+) -> Result<u64> {
+    let scaled_price = price
+        * 10f64.powi(quote_decimals as i32 - base_decimals as i32)
+        * MANIFEST_PRICE_SCALE as f64;
+    to_native(scaled_price, is_base_in)
+}
+
+fn to_native(value: f64, round_up: bool) -> Result<u64> {
+    let value = if round_up { value.ceil() } else { value.floor() };
+    require!(
+        value.is_finite() && value >= 1.0 && value <= u64::MAX as f64,
+        ErrorCode::InvalidOrderBookPrice
+    );
+    Ok(value as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_openbook_v2_price_lots() {
+        // SOL/USDC with base lot 0.001 SOL and quote lot 0.000001 USDC
+        assert_eq!(openbook_v2_price_lots(150.25, 9, 6, 1_000_000, 1, true).unwrap(), 150_250);
+        assert_eq!(openbook_v2_price_lots(150.2505, 9, 6, 1_000_000, 1, true).unwrap(), 150_251);
+        assert_eq!(openbook_v2_price_lots(150.2505, 9, 6, 1_000_000, 1, false).unwrap(), 150_250);
+        assert!(openbook_v2_price_lots(150.25, 9, 6, 0, 1, true).is_err());
+    }
+
+    #[test]
+    pub fn test_phoenix_price_in_ticks() {
+        // Tick size of 0.001 USDC per SOL
+        assert_eq!(phoenix_price_in_ticks(150.25, 6, 1, 1_000, false).unwrap(), 150_250);
+        assert_eq!(phoenix_price_in_ticks(150.2505, 6, 1, 1_000, true).unwrap(), 150_251);
+        assert!(phoenix_price_in_ticks(0.0, 6, 1, 1_000, true).is_err());
+    }
+
+    #[test]
+    pub fn test_manifest_price() {
+        // 150 USDC per SOL is 0.15 quote atoms per base atom
+        assert_eq!(manifest_price(150.0, 9, 6, false).unwrap(), 150_000_000_000);
+        assert!(manifest_price(f64::NAN, 9, 6, false).is_err());
+    }
+}
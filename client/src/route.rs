@@ -0,0 +1,408 @@
+//! Builds `SwapArgs` or `SwapArgsV2` together with the remaining accounts the router walks through
+use crate::adapters::DexAccounts;
+use crate::{Dex, OrderBookLimit, Route, RouteV2, SwapArgs, SwapArgsV2};
+use anchor_lang::prelude::*;
+use dex_solana::adapters::registry;
+use dex_solana::constants::{MAX_HOPS, TOTAL_WEIGHT};
+use dex_solana::error::ErrorCode;
+
+/// One dex of a hop: its weight, optional limits and the accounts it consumes
+pub struct Fork {
+    pub dex: Dex,
+    pub weight: u8,
+    pub min_out: u64,           // 0 if not set
+    pub sqrt_price_limit: u128, // 0 if not set
+    pub order_book_limit: OrderBookLimit,
+    pub accounts: Box<dyn DexAccounts>,
+}
+
+impl Fork {
+    pub fn new(dex: Dex, weight: u8, accounts: impl DexAccounts + 'static) -> Self {
+        Self {
+            dex,
+            weight,
+            min_out: 0,
+            sqrt_price_limit: 0,
+            order_book_limit: OrderBookLimit::default(),
+            accounts: Box::new(accounts),
+        }
+    }
+
+    pub fn min_out(mut self, min_out: u64) -> Self {
+        self.min_out = min_out;
+        self
+    }
+
+    pub fn sqrt_price_limit(mut self, sqrt_price_limit: u128) -> Self {
+        self.sqrt_price_limit = sqrt_price_limit;
+        self
+    }
+
+    /// Worst price in the venue's native units, see `crate::price`, and match limit (0 for default).
+    /// Manifest takes no match limit and reverts instead of partially filling past the price
+    pub fn order_book_limit(mut self, price: u64, match_limit: u8) -> Self {
+        self.order_book_limit = OrderBookLimit { price, match_limit };
+        self
+    }
+}
+
+/// One hop of a route, split between dexes by weight
+#[derive(Default)]
+pub struct Hop {
+    pub forks: Vec<Fork>,
+    /// Token account and input mint receiving the lot dust of a later order book hop
+    pub dust_refund: Option<(Pubkey, Pubkey)>,
+}
+
+impl Hop {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn dex(self, dex: Dex, weight: u8, accounts: impl DexAccounts + 'static) -> Self {
+        self.fork(Fork::new(dex, weight, accounts))
+    }
+
+    pub fn fork(mut self, fork: Fork) -> Self {
+        self.forks.push(fork);
+        self
+    }
+
+    /// Required on every hop after the first with a dex that fills whole lots (OpenBook V2,
+    /// Phoenix). `account` is a token account of `mint`, the hop's input mint, not owned by the SA
+    pub fn dust_refund(mut self, account: Pubkey, mint: Pubkey) -> Self {
+        self.dust_refund = Some((account, mint));
+        self
+    }
+
+    fn leaves_lot_dust(&self) -> bool {
+        self.forks.iter().any(|fork| registry::get_adapter(&fork.dex).leaves_lot_dust())
+    }
+
+    fn to_route(&self) -> RouteV2 {
+        let min_outs: Vec<u64> = self.forks.iter().map(|fork| fork.min_out).collect();
+        let sqrt_price_limits: Vec<u128> =
+            self.forks.iter().map(|fork| fork.sqrt_price_limit).collect();
+        let order_book_limits: Vec<OrderBookLimit> =
+            self.forks.iter().map(|fork| fork.order_book_limit).collect();
+        RouteV2 {
+            dexes: self.forks.iter().map(|fork| fork.dex).collect(),
+            weights: self.forks.iter().map(|fork| fork.weight).collect(),
+            min_outs: min_outs.iter().any(|x| *x > 0).then_some(min_outs),
+            sqrt_price_limits: sqrt_price_limits
+                .iter()
+                .any(|x| *x > 0)
+                .then_some(sqrt_price_limits),
+            order_book_limits: order_book_limits
+                .iter()
+                .any(|x| *x != OrderBookLimit::default())
+                .then_some(order_book_limits),
+        }
+    }
+}
+
+fn has_limits(route: &RouteV2) -> bool {
+    route.min_outs.is_some()
+        || route.sqrt_price_limits.is_some()
+        || route.order_book_limits.is_some()
+}
+
+/// Instruction args and the remaining accounts in the order the router consumes them
+pub struct SwapPlan<A = SwapArgs> {
+    pub args: A,
+    pub remaining_accounts: Vec<AccountMeta>,
+}
+
+/// Collects level 1 routes and checks them the same way `execute_swap` does
+pub struct RouteBuilder {
+    amount_in: u64,
+    expect_amount_out: u64,
+    min_return: u64,
+    routes: Vec<(u64, Vec<Hop>)>,
+}
+
+impl RouteBuilder {
+    pub fn new(amount_in: u64, expect_amount_out: u64, min_return: u64) -> Self {
+        Self { amount_in, expect_amount_out, min_return, routes: vec![] }
+    }
+
+    /// Add a level 1 route swapping `amount` through `hops`
+    pub fn route(mut self, amount: u64, hops: Vec<Hop>) -> Self {
+        self.routes.push((amount, hops));
+        self
+    }
+
+    /// Args for the instructions taking `Route`, which can not carry per-dex limits
+    pub fn build(self) -> Result<SwapPlan> {
+        let SwapPlan { args, remaining_accounts } = self.build_v2()?;
+        let routes = args
+            .routes
+            .into_iter()
+            .map(|hops| {
+                hops.into_iter()
+                    .map(|route| {
+                        require!(!has_limits(&route), ErrorCode::RouteLimitsRequireV2);
+                        Ok(Route { dexes: route.dexes, weights: route.weights })
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(SwapPlan {
+            args: SwapArgs {
+                amount_in: args.amount_in,
+                expect_amount_out: args.expect_amount_out,
+                min_return: args.min_return,
+                amounts: args.amounts,
+                routes,
+            },
+            remaining_accounts,
+        })
+    }
+
+    /// Args for the `*_with_limits` instructions, keeping the per-dex limits of the forks
+    pub fn build_v2(self) -> Result<SwapPlan<SwapArgsV2>> {
+        require!(self.amount_in > 0, ErrorCode::AmountInMustBeGreaterThanZero);
+        require!(self.min_return > 0, ErrorCode::MinReturnMustBeGreaterThanZero);
+        require!(self.expect_amount_out >= self.min_return, ErrorCode::InvalidExpectAmountOut);
+
+        let total_amounts = self
+            .routes
+            .iter()
+            .try_fold(0u64, |acc, (amount, _)| acc.checked_add(*amount))
+            .ok_or(ErrorCode::CalculationError)?;
+        require!(total_amounts == self.amount_in, ErrorCode::TotalAmountsMustBeEqualToAmountIn);
+
+        let mut amounts = Vec::with_capacity(self.routes.len());
+        let mut routes = Vec::with_capacity(self.routes.len());
+        let mut remaining_accounts = vec![];
+        for (amount, hops) in &self.routes {
+            require!(hops.len() <= MAX_HOPS, ErrorCode::TooManyHops);
+            for (index, hop) in hops.iter().enumerate() {
+                check_hop(index, hop)?;
+                for fork in &hop.forks {
+                    remaining_accounts.extend(fork.accounts.to_account_metas());
+                }
+                if index > 0 && hop.leaves_lot_dust() {
+                    let (account, mint) =
+                        hop.dust_refund.ok_or(ErrorCode::InvalidDustRefundAccount)?;
+                    remaining_accounts.push(AccountMeta::new(account, false));
+                    remaining_accounts.push(AccountMeta::new_readonly(mint, false));
+                }
+            }
+            amounts.push(*amount);
+            routes.push(hops.iter().map(Hop::to_route).collect());
+        }
+
+        Ok(SwapPlan {
+            args: SwapArgsV2 {
+                amount_in: self.amount_in,
+                expect_amount_out: self.expect_amount_out,
+                min_return: self.min_return,
+                amounts,
+                routes,
+            },
+            remaining_accounts,
+        })
+    }
+}
+
+fn check_hop(index: usize, hop: &Hop) -> Result<()> {
+    let total_weight = hop
+        .forks
+        .iter()
+        .try_fold(0u8, |acc, fork| acc.checked_add(fork.weight))
+        .ok_or(ErrorCode::CalculationError)?;
+    require!(total_weight == TOTAL_WEIGHT, ErrorCode::WeightsMustSumTo100);
+
+    for fork in &hop.forks {
+        require!(fork.accounts.supports(&fork.dex), ErrorCode::DexAdapterMismatch);
+        let adapter = registry::get_adapter(&fork.dex);
+        if fork.sqrt_price_limit > 0 {
+            require!(index == 0, ErrorCode::SqrtPriceLimitsOnlyAllowedOnFirstHop);
+            require!(adapter.supports_sqrt_price_limit(), ErrorCode::SqrtPriceLimitNotSupported);
+        }
+        if fork.order_book_limit != OrderBookLimit::default() {
+            require!(index == 0, ErrorCode::OrderBookLimitsOnlyAllowedOnFirstHop);
+            require!(adapter.supports_order_book_limit(), ErrorCode::OrderBookLimitNotSupported);
+        }
+
+        let account_metas = fork.accounts.to_account_metas();
+        require!(account_metas.len() >= adapter.accounts_len(), ErrorCode::InvalidAccountsLength);
+        if adapter.accounts_len() > 0 && !adapter.program_ids().is_empty() {
+            require!(
+                adapter.program_ids().contains(&account_metas[0].pubkey),
+                ErrorCode::InvalidProgramId
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{PhoenixAccounts, RaydiumSwapAccounts, WhirlpoolAccounts};
+    use dex_solana::{phoenix_program, raydium_swap_program, whirlpool_program};
+
+    fn whirlpool(source: Pubkey, destination: Pubkey) -> WhirlpoolAccounts {
+        WhirlpoolAccounts {
+            dex_program_id: whirlpool_program::ID,
+            swap_source_token: source,
+            swap_destination_token: destination,
+            ..Default::default()
+        }
+    }
+
+    fn raydium(source: Pubkey, destination: Pubkey) -> RaydiumSwapAccounts {
+        RaydiumSwapAccounts {
+            dex_program_id: raydium_swap_program::ID,
+            swap_source_token: source,
+            swap_destination_token: destination,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    pub fn test_build_split_route() {
+        let (source, middle, destination) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let plan = RouteBuilder::new(1000, 990, 980)
+            .route(
+                600,
+                vec![
+                    Hop::new()
+                        .dex(Dex::Whirlpool, 60, whirlpool(source, middle))
+                        .fork(Fork::new(Dex::RaydiumSwap, 40, raydium(source, middle)).min_out(5)),
+                    Hop::new().dex(Dex::Whirlpool, 100, whirlpool(middle, destination)),
+                ],
+            )
+            .route(400, vec![Hop::new().dex(Dex::RaydiumSwap, 100, raydium(source, destination))])
+            .build_v2()
+            .unwrap();
+
+        assert_eq!(plan.args.amounts, vec![600, 400]);
+        assert_eq!(plan.args.routes[0][0].weights, vec![60, 40]);
+        assert_eq!(plan.args.routes[0][0].min_outs, Some(vec![0, 5]));
+        assert_eq!(plan.args.routes[0][1].min_outs, None);
+        assert_eq!(plan.args.routes[0][0].sqrt_price_limits, None);
+        assert_eq!(plan.args.routes[0][0].order_book_limits, None);
+        assert_eq!(plan.remaining_accounts.len(), 12 + 19 + 12 + 19);
+        assert_eq!(plan.remaining_accounts[0].pubkey, whirlpool_program::ID);
+        assert_eq!(plan.remaining_accounts[12].pubkey, raydium_swap_program::ID);
+        assert!(plan.remaining_accounts[2].is_writable);
+        assert!(!plan.remaining_accounts[4].is_writable);
+    }
+
+    #[test]
+    pub fn test_build_order_book_hop_dust_refund() {
+        let (source, middle, destination) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let (refund, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let phoenix = PhoenixAccounts {
+            dex_program_id: phoenix_program::ID,
+            swap_source_token: middle,
+            swap_destination_token: destination,
+            ..Default::default()
+        };
+        let hops = || {
+            vec![
+                Hop::new().dex(Dex::Whirlpool, 100, whirlpool(source, middle)),
+                Hop::new().dex(Dex::Phoenix, 100, phoenix.clone()),
+            ]
+        };
+
+        // The refund account and mint follow the order book hop accounts
+        let mut with_refund = hops();
+        with_refund[1] =
+            Hop::new().dex(Dex::Phoenix, 100, phoenix.clone()).dust_refund(refund, mint);
+        let plan = RouteBuilder::new(1000, 990, 980).route(1000, with_refund).build().unwrap();
+        assert_eq!(plan.remaining_accounts.len(), 12 + 9 + 2);
+        assert_eq!(plan.remaining_accounts[21].pubkey, refund);
+        assert!(plan.remaining_accounts[21].is_writable);
+        assert_eq!(plan.remaining_accounts[22].pubkey, mint);
+        assert!(!plan.remaining_accounts[22].is_writable);
+
+        assert!(RouteBuilder::new(1000, 990, 980).route(1000, hops()).build().is_err());
+    }
+
+    #[test]
+    pub fn test_build_rejects_invalid_routes() {
+        let (source, destination) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let hop = || Hop::new().dex(Dex::Whirlpool, 100, whirlpool(source, destination));
+
+        assert!(RouteBuilder::new(1000, 990, 980).route(900, vec![hop()]).build().is_err());
+        assert!(RouteBuilder::new(1000, 970, 980).route(1000, vec![hop()]).build().is_err());
+        assert!(
+            RouteBuilder::new(1000, 990, 980)
+                .route(1000, vec![hop(), hop(), hop(), hop()])
+                .build()
+                .is_err()
+        );
+        assert!(
+            RouteBuilder::new(1000, 990, 980)
+                .route(
+                    1000,
+                    vec![Hop::new().dex(Dex::Whirlpool, 90, whirlpool(source, destination))]
+                )
+                .build()
+                .is_err()
+        );
+        // Account layout does not belong to the dex
+        assert!(
+            RouteBuilder::new(1000, 990, 980)
+                .route(
+                    1000,
+                    vec![Hop::new().dex(Dex::RaydiumSwap, 100, whirlpool(source, destination))]
+                )
+                .build()
+                .is_err()
+        );
+        // Wrong program id
+        let mut accounts = whirlpool(source, destination);
+        accounts.dex_program_id = Pubkey::new_unique();
+        assert!(
+            RouteBuilder::new(1000, 990, 980)
+                .route(1000, vec![Hop::new().dex(Dex::Whirlpool, 100, accounts)])
+                .build()
+                .is_err()
+        );
+        // Sqrt price limit on a dex without support
+        assert!(
+            RouteBuilder::new(1000, 990, 980)
+                .route(
+                    1000,
+                    vec![
+                        Hop::new().fork(
+                            Fork::new(Dex::RaydiumSwap, 100, raydium(source, destination))
+                                .sqrt_price_limit(1),
+                        )
+                    ],
+                )
+                .build()
+                .is_err()
+        );
+        // Order book limit on a dex without an order book
+        assert!(
+            RouteBuilder::new(1000, 990, 980)
+                .route(
+                    1000,
+                    vec![
+                        Hop::new().fork(
+                            Fork::new(Dex::RaydiumSwap, 100, raydium(source, destination))
+                                .order_book_limit(150_250, 0),
+                        )
+                    ],
+                )
+                .build()
+                .is_err()
+        );
+        // Per-dex limits only fit RouteV2
+        let limited = || {
+            vec![Hop::new().fork(
+                Fork::new(Dex::Whirlpool, 100, whirlpool(source, destination)).sqrt_price_limit(1),
+            )]
+        };
+        assert!(RouteBuilder::new(1000, 990, 980).route(1000, limited()).build().is_err());
+        assert!(RouteBuilder::new(1000, 990, 980).route(1000, limited()).build_v2().is_ok());
+    }
+}
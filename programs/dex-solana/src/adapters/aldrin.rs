@@ -1,4 +1,4 @@
-use crate::adapters::common::{before_check, invoke_process};
+use crate::adapters::common::before_check;
 use crate::error::ErrorCode;
 use crate::{HopAccounts, SWAP_SELECTOR, aldrin_v1_program, aldrin_v2_program};
 use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
@@ -7,7 +7,7 @@ use anchor_spl::token_interface::{Mint, TokenAccount};
 use arrayref::array_ref;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext, SwapInstruction};
 
 #[derive(
     AnchorDeserialize, AnchorSerialize, Clone, Debug, PartialEq, TryFromPrimitive, IntoPrimitive,
@@ -20,8 +20,59 @@ pub enum Side {
 
 const ARGS_LEN: usize = 25;
 
-pub struct AldrinProcessor;
-impl DexProcessor for AldrinProcessor {}
+pub struct AldrinExchangeV1Adapter;
+impl DexAdapter for AldrinExchangeV1Adapter {
+    fn accounts_len(&self) -> usize {
+        V1_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[aldrin_v1_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap_v1(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
+
+pub struct AldrinExchangeV2Adapter;
+impl DexAdapter for AldrinExchangeV2Adapter {
+    fn accounts_len(&self) -> usize {
+        V2_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[aldrin_v2_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap_v2(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
 
 pub struct AldrinSwapAccountsV1<'info> {
     pub dex_program_id: &'info AccountInfo<'info>,
@@ -120,24 +171,21 @@ impl<'info> AldrinSwapAccountsV2<'info> {
     }
 }
 
-pub fn swap_v1<'a>(
+pub fn build_swap_v1<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::AldrinSwapV1 amount_in: {}, offset: {}", amount_in, offset);
     require!(
-        remaining_accounts.len() >= *offset + V1_ACCOUNTS_LEN,
+        remaining_accounts.len() >= offset + V1_ACCOUNTS_LEN,
         ErrorCode::InvalidAccountsLength
     );
-    let mut swap_accounts = AldrinSwapAccountsV1::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &aldrin_v1_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = AldrinSwapAccountsV1::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.pool_info.key().log();
 
@@ -163,7 +211,7 @@ pub fn swap_v1<'a>(
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.extend_from_slice(SWAP_SELECTOR);
     data.extend_from_slice(&amount_in.to_le_bytes());
-    data.extend_from_slice(&1u64.to_le_bytes());
+    data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes());
     data.push(Side::into(side));
 
     let (user_coin_token_acc, user_pc_token_acc) = if swap_accounts.swap_source_token.mint
@@ -208,42 +256,29 @@ pub fn swap_v1<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &AldrinProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        V1_ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
 
-pub fn swap_v2<'a>(
+pub fn build_swap_v2<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::AldrinSwapV2 amount_in: {}, offset: {}", amount_in, offset);
     require!(
-        remaining_accounts.len() >= *offset + V2_ACCOUNTS_LEN,
+        remaining_accounts.len() >= offset + V2_ACCOUNTS_LEN,
         ErrorCode::InvalidAccountsLength
     );
-    let mut swap_accounts = AldrinSwapAccountsV2::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &aldrin_v2_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = AldrinSwapAccountsV2::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.pool_info.key().log();
 
@@ -269,7 +304,7 @@ pub fn swap_v2<'a>(
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.extend_from_slice(SWAP_SELECTOR);
     data.extend_from_slice(&amount_in.to_le_bytes());
-    data.extend_from_slice(&1u64.to_le_bytes());
+    data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes());
     data.push(Side::into(side));
 
     let (user_coin_token_acc, user_pc_token_acc) = if swap_accounts.swap_source_token.mint
@@ -316,22 +351,12 @@ pub fn swap_v2<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &AldrinProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        V2_ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
 
 #[cfg(test)]
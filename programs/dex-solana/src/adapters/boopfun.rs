@@ -1,4 +1,4 @@
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext, SwapInstruction};
 use crate::adapters::common::{before_check, invoke_process};
 use crate::error::ErrorCode;
 use crate::utils::{close_token_account, sync_wsol_account, transfer_sol};
@@ -101,8 +101,33 @@ pub struct BoopfunSellAccounts<'info> {
 const BUY_ACCOUNTS_LEN: usize = 15;
 const SELL_ACCOUNTS_LEN: usize = 16;
 
-pub struct BoopfunBuyProcessor;
-impl DexProcessor for BoopfunBuyProcessor {
+pub struct BoopfunBuyAdapter;
+impl DexAdapter for BoopfunBuyAdapter {
+    fn accounts_len(&self) -> usize {
+        BUY_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[boopfun_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_buy(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+            ctx.payer,
+        )
+    }
+
     fn before_invoke(&self, account_infos: &[AccountInfo]) -> Result<u64> {
         let source_token_account = account_infos.last().unwrap();
         let token_program = account_infos.get(11).unwrap();
@@ -140,6 +165,7 @@ impl DexProcessor for BoopfunBuyProcessor {
         _hop: usize,
         _owner_seeds: Option<&[&[&[u8]]]>,
         before_sa_authority_lamports: u64,
+        _expected_amount_out: u64,
     ) -> Result<u64> {
         if before_sa_authority_lamports > 0 {
             let payer = account_infos.get(14).unwrap();
@@ -363,26 +389,23 @@ impl<'info> BoopfunSellAccounts<'info> {
     }
 }
 
-pub fn buy<'a>(
+pub fn build_buy<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
     payer: Option<&AccountInfo<'a>>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::Boopfun amount_in: {}, offset: {}", amount_in, offset);
     require!(
-        remaining_accounts.len() >= *offset + BUY_ACCOUNTS_LEN,
+        remaining_accounts.len() >= offset + BUY_ACCOUNTS_LEN,
         ErrorCode::InvalidAccountsLength
     );
 
-    let mut swap_accounts = BoopfunBuyAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &boopfun_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = BoopfunBuyAccounts::parse_accounts(remaining_accounts, offset)?;
 
     // Record pool address
     swap_accounts.bonding_curve.key().log();
@@ -400,7 +423,7 @@ pub fn buy<'a>(
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.extend_from_slice(BOOPFUN_BUY_SELECTOR);
     data.extend_from_slice(&amount_in.to_le_bytes());
-    data.extend_from_slice(&1u64.to_le_bytes());
+    data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes());
 
     let accounts = vec![
         AccountMeta::new_readonly(swap_accounts.mint.key(), false),
@@ -440,22 +463,12 @@ pub fn buy<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &BoopfunBuyProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        BUY_ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
 
 pub fn sell<'a>(
@@ -475,10 +488,6 @@ pub fn sell<'a>(
 
     let mut swap_accounts = BoopfunSellAccounts::parse_accounts(remaining_accounts, *offset)?;
 
-    if swap_accounts.dex_program_id.key != &boopfun_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
-
     swap_accounts.bonding_curve.key().log();
 
     before_check(
@@ -499,7 +508,7 @@ pub fn sell<'a>(
     let mut data = Vec::with_capacity(24);
     data.extend_from_slice(BOOPFUN_SELL_SELECTOR);
     data.extend_from_slice(&amount_in.to_le_bytes());
-    data.extend_from_slice(&1u64.to_le_bytes());
+    data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes());
 
     let accounts = vec![
         AccountMeta::new_readonly(swap_accounts.mint.key(), false),
@@ -535,10 +544,9 @@ pub fn sell<'a>(
 
     let instruction = Instruction { program_id: boopfun_program::id(), accounts, data };
 
-    let dex_processor = &BoopfunSellProcessor { amount: expected_amount_out };
     let amount_out = invoke_process(
         amount_in,
-        dex_processor,
+        &BoopfunSellAdapter,
         &account_infos,
         &mut swap_accounts.swap_source_token,
         &mut swap_accounts.swap_destination_token,
@@ -549,15 +557,34 @@ pub fn sell<'a>(
         SELL_ACCOUNTS_LEN,
         proxy_swap,
         owner_seeds,
+        expected_amount_out,
     )?;
     Ok(amount_out)
 }
 
-pub struct BoopfunSellProcessor {
-    pub amount: u64,
-}
+pub struct BoopfunSellAdapter;
+
+impl DexAdapter for BoopfunSellAdapter {
+    fn accounts_len(&self) -> usize {
+        SELL_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[boopfun_program::ID] }
+    }
+
+    fn swap<'a>(&self, ctx: &mut SwapContext<'a, '_>, amount_in: u64) -> Result<u64> {
+        sell(
+            ctx.remaining_accounts,
+            amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
 
-impl DexProcessor for BoopfunSellProcessor {
     fn before_invoke(&self, account_infos: &[AccountInfo]) -> Result<u64> {
         let destination_token_account = account_infos.last().unwrap();
         let balance = destination_token_account.get_lamports();
@@ -570,6 +597,7 @@ impl DexProcessor for BoopfunSellProcessor {
         hop: usize,
         owner_seeds: Option<&[&[&[u8]]]>,
         _before_sa_authority_lamports: u64,
+        expected_amount_out: u64,
     ) -> Result<u64> {
         let destination_token_account = account_infos.last().unwrap();
         let authority = account_infos.get(6).unwrap();
@@ -585,7 +613,7 @@ impl DexProcessor for BoopfunSellProcessor {
         transfer_sol(
             authority.to_account_info(),
             destination_token_account.to_account_info(),
-            self.amount,
+            expected_amount_out,
             signer_seeds,
         )?;
         sync_wsol_account(
@@ -593,6 +621,6 @@ impl DexProcessor for BoopfunSellProcessor {
             token_program.to_account_info(),
             signer_seeds,
         )?;
-        Ok(self.amount)
+        Ok(expected_amount_out)
     }
 }
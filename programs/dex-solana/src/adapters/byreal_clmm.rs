@@ -1,15 +1,43 @@
-use crate::adapters::common::{before_check, invoke_process};
+use crate::adapters::common::before_check;
 use crate::error::ErrorCode;
 use crate::{HopAccounts, SWAP_V2_SELECTOR, byreal_clmm_program};
 use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
 use anchor_spl::token_interface::TokenAccount;
 use arrayref::array_ref;
 
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext, SwapInstruction};
 
 const ARGS_LEN: usize = 41;
-pub struct ByrealClmmSwapV2Processor;
-impl DexProcessor for ByrealClmmSwapV2Processor {}
+pub struct ByrealClmmAdapter;
+impl DexAdapter for ByrealClmmAdapter {
+    fn accounts_len(&self) -> usize {
+        ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[byreal_clmm_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap_v2(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+
+    fn supports_sqrt_price_limit(&self) -> bool {
+        true
+    }
+}
 pub struct ByrealClmmSwapV2Accounts<'info> {
     pub dex_program_id: &'info AccountInfo<'info>,
     pub swap_authority_pubkey: &'info AccountInfo<'info>,
@@ -98,21 +126,18 @@ impl<'info> ByrealClmmSwapV2Accounts<'info> {
     }
 }
 
-pub fn swap_v2<'a>(
+pub fn build_swap_v2<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::ByrealClmm amount_in: {}, offset: {}", amount_in, offset);
-    require!(remaining_accounts.len() >= *offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
-    let mut swap_accounts = ByrealClmmSwapV2Accounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &byreal_clmm_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    require!(remaining_accounts.len() >= offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
+    let swap_accounts = ByrealClmmSwapV2Accounts::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.pool_state.key().log();
 
@@ -195,27 +220,16 @@ pub fn swap_v2<'a>(
     let mut data = vec![0u8; ARGS_LEN];
     data[0..8].copy_from_slice(&SWAP_V2_SELECTOR[..]);
     data[8..16].copy_from_slice(&amount_in.to_le_bytes()); // amount
-    data[16..24].copy_from_slice(&1u64.to_le_bytes()); // other_amount_threshold
-    data[24..40].copy_from_slice(&0u128.to_le_bytes()); // sqrt_price_limit_x64
+    data[16..24].copy_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes()); // other_amount_threshold
+    data[24..40].copy_from_slice(&hop_accounts.sqrt_price_limit.to_le_bytes()); // sqrt_price_limit_x64
     data[40..41].copy_from_slice(&1u8.to_le_bytes()); // is_base_input
 
     let instruction = Instruction { program_id: *swap_accounts.dex_program_id.key, accounts, data };
 
-    let dex_processor = ByrealClmmSwapV2Processor {};
-    let amount_out = invoke_process(
-        amount_in,
-        &dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
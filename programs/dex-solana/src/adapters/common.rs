@@ -1,6 +1,7 @@
 use crate::constants::{ACTUAL_IN_LOWER_BOUND_DEN, ACTUAL_IN_LOWER_BOUND_NUM};
 use crate::error::ErrorCode;
-use crate::{HopAccounts, SA_AUTHORITY_SEED, ZERO_ADDRESS, authority_pda};
+use crate::utils::get_withheld_amount;
+use crate::{Dex, HopAccounts, SA_AUTHORITY_SEED, ZERO_ADDRESS, authority_pda};
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::program_pack::Pack;
 use anchor_lang::solana_program::{
@@ -11,17 +12,117 @@ use anchor_spl::token::spl_token::state::Account as SplTokenAccount;
 use anchor_spl::token_2022::spl_token_2022::state::Account as SplToken2022Account;
 use anchor_spl::token_interface::TokenAccount;
 
-pub trait DexProcessor {
+/// Per-swap state handed from the router to a dex adapter
+pub struct SwapContext<'a, 'b> {
+    pub dex: &'b Dex,
+    pub remaining_accounts: &'a [AccountInfo<'a>],
+    pub offset: &'b mut usize,
+    pub hop_accounts: &'b mut HopAccounts,
+    pub hop: usize,
+    pub proxy_swap: bool,
+    pub order_id: u64,
+    pub owner_seeds: Option<&'b [&'b [&'b [u8]]]>,
+    pub payer: Option<&'b AccountInfo<'a>>,
+}
+
+/// A dex instruction built from parsed accounts, with the token accounts it swaps between
+pub struct SwapInstruction<'a> {
+    pub instruction: Instruction,
+    pub account_infos: Vec<AccountInfo<'a>>,
+    pub swap_source_token: InterfaceAccount<'a, TokenAccount>,
+    pub swap_destination_token: InterfaceAccount<'a, TokenAccount>,
+}
+
+/// A dex integration: account layout, allowed programs, swap entry and CPI hooks.
+/// Adapters are registered by `Dex` in `adapters::registry`.
+pub trait DexAdapter: Sync {
+    /// Minimum number of remaining accounts consumed by one swap
+    fn accounts_len(&self) -> usize;
+
+    /// Programs allowed at the first account of the swap, empty if the adapter checks them itself
+    fn program_ids(&self) -> &[Pubkey];
+
+    /// Parse and check the accounts at `ctx.offset` and build the dex instruction for `amount_in`
+    fn build_swap<'a>(
+        &self,
+        _ctx: &mut SwapContext<'a, '_>,
+        _amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        Err(ErrorCode::SwapInstructionNotSupported.into())
+    }
+
+    /// Invoke the instruction from `build_swap`. Adapters that need more than one instruction or
+    /// custom accounting override this instead.
+    fn swap<'a>(&self, ctx: &mut SwapContext<'a, '_>, amount_in: u64) -> Result<u64> {
+        let mut swap_instruction = self.build_swap(ctx, amount_in)?;
+        invoke_process(
+            amount_in,
+            self,
+            &swap_instruction.account_infos,
+            &mut swap_instruction.swap_source_token,
+            &mut swap_instruction.swap_destination_token,
+            ctx.hop_accounts,
+            swap_instruction.instruction,
+            ctx.hop,
+            ctx.offset,
+            self.accounts_len(),
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+            0,
+        )
+    }
+
+    fn supports_exact_out(&self) -> bool {
+        false
+    }
+
+    /// Exact out swap, returns (amount_in, amount_out)
+    fn swap_exact_out<'a>(
+        &self,
+        _ctx: &mut SwapContext<'a, '_>,
+        _amount_out: u64,
+        _max_amount_in: u64,
+    ) -> Result<(u64, u64)> {
+        Err(ErrorCode::ExactOutNotSupported.into())
+    }
+
+    /// Output of swapping `amount_in` through the accounts at `offset`, computed from pool state
+    /// without moving tokens. `None` if the adapter can not quote on chain. A quoting adapter must
+    /// consume exactly `accounts_len` accounts.
+    fn quote<'a>(
+        &self,
+        _remaining_accounts: &'a [AccountInfo<'a>],
+        _offset: usize,
+        _amount_in: u64,
+    ) -> Result<Option<u64>> {
+        Ok(None)
+    }
+
+    fn supports_sqrt_price_limit(&self) -> bool {
+        false
+    }
+
+    fn supports_order_book_limit(&self) -> bool {
+        false
+    }
+
+    /// Swaps whole lots only, leaving the sub-lot remainder in `HopAccounts::dust_amount_in`
+    fn leaves_lot_dust(&self) -> bool {
+        false
+    }
+
     fn before_invoke(&self, _account_infos: &[AccountInfo]) -> Result<u64> {
         Ok(0)
     }
 
+    /// `expected_amount_out` is the output the caller computed for this swap, 0 if none
     fn after_invoke(
         &self,
         _account_infos: &[AccountInfo],
         _hop: usize,
         _owner_seeds: Option<&[&[&[u8]]]>,
         _before_sa_authority_lamports: u64,
+        _expected_amount_out: u64,
     ) -> Result<u64> {
         Ok(0)
     }
@@ -77,7 +178,7 @@ pub fn before_check(
     Ok(())
 }
 
-pub fn invoke_process<'info, T: DexProcessor>(
+pub fn invoke_process<'info, T: DexAdapter + ?Sized>(
     amount_in: u64,
     dex_processor: &T,
     account_infos: &[AccountInfo],
@@ -90,10 +191,13 @@ pub fn invoke_process<'info, T: DexProcessor>(
     accounts_len: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
+    expected_amount_out: u64,
 ) -> Result<u64> {
     // get before balances
     let before_source_balance = swap_source_token.amount;
     let before_destination_balance = swap_destination_token.amount;
+    let before_destination_withheld =
+        get_withheld_amount(&swap_destination_token.to_account_info())?;
 
     // before invoke hook
     let before_sa_authority_lamports = dex_processor.before_invoke(account_infos)?;
@@ -110,7 +214,13 @@ pub fn invoke_process<'info, T: DexProcessor>(
     execute_instruction(&instruction, account_infos, proxy_swap, hop, owner_seeds)?;
 
     // after invoke hook
-    dex_processor.after_invoke(account_infos, hop, owner_seeds, before_sa_authority_lamports)?;
+    dex_processor.after_invoke(
+        account_infos,
+        hop,
+        owner_seeds,
+        before_sa_authority_lamports,
+        expected_amount_out,
+    )?;
 
     // post swap check
     post_swap_check(
@@ -122,10 +232,11 @@ pub fn invoke_process<'info, T: DexProcessor>(
         amount_in,
         before_source_balance,
         before_destination_balance,
+        before_destination_withheld,
     )
 }
 
-pub fn invoke_processes<'info, T: DexProcessor>(
+pub fn invoke_processes<'info, T: DexAdapter + ?Sized>(
     amount_in: u64,
     dex_processor: &T,
     account_infos_arr: &[&[AccountInfo]],
@@ -145,6 +256,8 @@ pub fn invoke_processes<'info, T: DexProcessor>(
     // get before balances
     let before_source_balance = swap_source_token.amount;
     let before_destination_balance = swap_destination_token.amount;
+    let before_destination_withheld =
+        get_withheld_amount(&swap_destination_token.to_account_info())?;
 
     let account_infos: Vec<_> =
         account_infos_arr.iter().flat_map(|inner| inner.iter()).cloned().collect();
@@ -164,7 +277,13 @@ pub fn invoke_processes<'info, T: DexProcessor>(
     }
 
     // after invoke hook
-    dex_processor.after_invoke(&account_infos, hop, owner_seeds, before_sa_authority_lamports)?;
+    dex_processor.after_invoke(
+        &account_infos,
+        hop,
+        owner_seeds,
+        before_sa_authority_lamports,
+        0,
+    )?;
 
     // post swap check
     post_swap_check(
@@ -176,6 +295,60 @@ pub fn invoke_processes<'info, T: DexProcessor>(
         amount_in,
         before_source_balance,
         before_destination_balance,
+        before_destination_withheld,
+    )
+}
+
+pub fn invoke_process_exact_out<'info, T: DexAdapter + ?Sized>(
+    amount_out: u64,
+    max_amount_in: u64,
+    dex_processor: &T,
+    account_infos: &[AccountInfo],
+    swap_source_token: &mut InterfaceAccount<'info, TokenAccount>,
+    swap_destination_token: &mut InterfaceAccount<'info, TokenAccount>,
+    hop_accounts: &mut HopAccounts,
+    instruction: Instruction,
+    hop: usize,
+    offset: &mut usize,
+    accounts_len: usize,
+    proxy_swap: bool,
+    owner_seeds: Option<&[&[&[u8]]]>,
+) -> Result<(u64, u64)> {
+    // get before balances
+    let before_source_balance = swap_source_token.amount;
+    let before_destination_balance = swap_destination_token.amount;
+    let before_destination_withheld =
+        get_withheld_amount(&swap_destination_token.to_account_info())?;
+
+    // before invoke hook
+    let before_sa_authority_lamports = dex_processor.before_invoke(account_infos)?;
+
+    // Harden: prevent CPI from touching unexpected SA-owned token accounts
+    if proxy_swap || hop > 0 {
+        enforce_sa_token_allowlist(
+            account_infos,
+            &[swap_source_token.key(), swap_destination_token.key()],
+        )?;
+    }
+
+    // execute instruction
+    execute_instruction(&instruction, account_infos, proxy_swap, hop, owner_seeds)?;
+
+    // after invoke hook
+    dex_processor.after_invoke(account_infos, hop, owner_seeds, before_sa_authority_lamports, 0)?;
+
+    // post swap check
+    post_swap_check_exact_out(
+        swap_source_token,
+        swap_destination_token,
+        hop_accounts,
+        accounts_len,
+        offset,
+        amount_out,
+        max_amount_in,
+        before_source_balance,
+        before_destination_balance,
+        before_destination_withheld,
     )
 }
 
@@ -208,6 +381,7 @@ fn post_swap_check<'info>(
     amount_in: u64,
     before_source_balance: u64,
     before_destination_balance: u64,
+    before_destination_withheld: u64,
 ) -> Result<u64> {
     // 1. calculate & check actual amount in
     if swap_source_token.get_lamports() > 0 {
@@ -218,14 +392,19 @@ fn post_swap_check<'info>(
             .checked_sub(after_source_balance)
             .ok_or(ErrorCode::CalculationError)?;
 
-        // min_amount_in = 90% of amount_in
-        let min_amount_in = u64::try_from(
-            u128::from(amount_in)
-                .checked_mul(ACTUAL_IN_LOWER_BOUND_NUM)
-                .and_then(|v| v.checked_div(ACTUAL_IN_LOWER_BOUND_DEN))
-                .ok_or(ErrorCode::CalculationError)?,
-        )
-        .map_err(|_| ErrorCode::CalculationError)?;
+        // min_amount_in = 90% of amount_in, a fork stopped by its price limit may be partially filled
+        let min_amount_in =
+            if hop_accounts.sqrt_price_limit != 0 || hop_accounts.order_book_limit.price != 0 {
+                0
+            } else {
+                u64::try_from(
+                    u128::from(amount_in)
+                        .checked_mul(ACTUAL_IN_LOWER_BOUND_NUM)
+                        .and_then(|v| v.checked_div(ACTUAL_IN_LOWER_BOUND_DEN))
+                        .ok_or(ErrorCode::CalculationError)?,
+                )
+                .map_err(|_| ErrorCode::CalculationError)?
+            };
         if !(actual_amount_in <= amount_in && actual_amount_in >= min_amount_in) {
             msg!(
                 "InvalidActualAmountIn: actual_amount_in={}, amount_in={}",
@@ -243,15 +422,72 @@ fn post_swap_check<'info>(
         .checked_sub(before_destination_balance)
         .ok_or(ErrorCode::CalculationError)?;
     require!(actual_amount_out > 0, ErrorCode::AmountOutMustBeGreaterThanZero);
+    if actual_amount_out < hop_accounts.min_amount_out {
+        msg!(
+            "DexMinAmountOutNotReached: actual_amount_out={}, min_amount_out={}",
+            actual_amount_out,
+            hop_accounts.min_amount_out,
+        );
+        return Err(ErrorCode::DexMinAmountOutNotReached.into());
+    }
 
     // 3. update offset & hop accounts
     *offset += accounts_len;
     hop_accounts.from_account = swap_source_token.key();
     hop_accounts.to_account = swap_destination_token.key();
+    hop_accounts.transfer_fee = get_withheld_amount(&swap_destination_token.to_account_info())?
+        .checked_sub(before_destination_withheld)
+        .ok_or(ErrorCode::CalculationError)?;
 
     Ok(actual_amount_out)
 }
 
+fn post_swap_check_exact_out<'info>(
+    swap_source_token: &mut InterfaceAccount<'info, TokenAccount>,
+    swap_destination_token: &mut InterfaceAccount<'info, TokenAccount>,
+    hop_accounts: &mut HopAccounts,
+    accounts_len: usize,
+    offset: &mut usize,
+    amount_out: u64,
+    max_amount_in: u64,
+    before_source_balance: u64,
+    before_destination_balance: u64,
+    before_destination_withheld: u64,
+) -> Result<(u64, u64)> {
+    // 1. calculate & check actual amount in
+    swap_source_token.reload()?;
+    let after_source_balance = swap_source_token.amount;
+    let actual_amount_in = before_source_balance
+        .checked_sub(after_source_balance)
+        .ok_or(ErrorCode::CalculationError)?;
+    if actual_amount_in > max_amount_in {
+        msg!(
+            "MaxAmountInExceeded: actual_amount_in={}, max_amount_in={}",
+            actual_amount_in,
+            max_amount_in,
+        );
+        return Err(ErrorCode::MaxAmountInExceeded.into());
+    }
+
+    // 2. calculate & check actual amount out
+    swap_destination_token.reload()?;
+    let after_destination_balance = swap_destination_token.amount;
+    let actual_amount_out = after_destination_balance
+        .checked_sub(before_destination_balance)
+        .ok_or(ErrorCode::CalculationError)?;
+    require!(actual_amount_out >= amount_out, ErrorCode::ExactAmountOutNotReached);
+
+    // 3. update offset & hop accounts
+    *offset += accounts_len;
+    hop_accounts.from_account = swap_source_token.key();
+    hop_accounts.to_account = swap_destination_token.key();
+    hop_accounts.transfer_fee = get_withheld_amount(&swap_destination_token.to_account_info())?
+        .checked_sub(before_destination_withheld)
+        .ok_or(ErrorCode::CalculationError)?;
+
+    Ok((actual_amount_in, actual_amount_out))
+}
+
 fn enforce_sa_token_allowlist(
     account_infos: &[AccountInfo],
     allowed_sa_token_accounts: &[Pubkey],
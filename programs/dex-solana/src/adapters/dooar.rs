@@ -1,17 +1,41 @@
 use std::vec;
 
-use crate::adapters::common::{before_check, invoke_process};
+use crate::adapters::common::before_check;
 use crate::error::ErrorCode;
 use crate::{HopAccounts, dooar_program};
 use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
 use anchor_spl::token_interface::TokenAccount;
 use arrayref::array_ref;
 
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext, SwapInstruction};
 
 const ARGS_LEN: usize = 17;
-pub struct DooarProcessor;
-impl DexProcessor for DooarProcessor {}
+pub struct DooarAdapter;
+impl DexAdapter for DooarAdapter {
+    fn accounts_len(&self) -> usize {
+        ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[dooar_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
 pub struct DooarAccounts<'info> {
     pub dex_program_id: &'info AccountInfo<'info>,
     pub swap_authority_pubkey: &'info AccountInfo<'info>,
@@ -70,21 +94,18 @@ impl<'info> DooarAccounts<'info> {
     }
 }
 
-pub fn swap<'a>(
+pub fn build_swap<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::Dooar amount_in: {}, offset: {}", amount_in, offset);
-    require!(remaining_accounts.len() >= *offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
-    let mut swap_accounts = DooarAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &dooar_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    require!(remaining_accounts.len() >= offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
+    let swap_accounts = DooarAccounts::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.token_swap.key().log();
 
@@ -113,7 +134,7 @@ pub fn swap<'a>(
         AccountMeta::new(swap_accounts.fee_account.key(), false),
         AccountMeta::new_readonly(swap_accounts.refund_to.key(), false),
     ];
-    let account_infos = [
+    let account_infos = vec![
         swap_accounts.token_swap.to_account_info(),
         swap_accounts.authority.to_account_info(),
         swap_accounts.user_transfer_authority.to_account_info(),
@@ -129,25 +150,14 @@ pub fn swap<'a>(
     let mut data = vec![0u8; ARGS_LEN];
     data[0] = 1; // selector
     data[1..9].copy_from_slice(&amount_in.to_le_bytes());
-    data[9..17].copy_from_slice(&1u64.to_le_bytes());
+    data[9..17].copy_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes());
 
     let instruction = Instruction { program_id: *swap_accounts.dex_program_id.key, accounts, data };
 
-    let dex_processor = DooarProcessor {};
-    let amount_out = invoke_process(
-        amount_in,
-        &dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
@@ -1,17 +1,40 @@
-use crate::adapters::common::{before_check, invoke_process};
+use crate::adapters::common::before_check;
 use crate::error::ErrorCode;
 use crate::{HopAccounts, flux_beam_program};
 use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
 use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
 use arrayref::array_ref;
 
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext, SwapInstruction};
 
 const ARGS_LEN: usize = 17;
 
-pub struct FluxBeamProcessor;
+pub struct FluxBeamAdapter;
+impl DexAdapter for FluxBeamAdapter {
+    fn accounts_len(&self) -> usize {
+        ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[flux_beam_program::ID] }
+    }
 
-impl DexProcessor for FluxBeamProcessor {}
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
 
 pub struct FluxBeamAccounts<'info> {
     pub dex_program_id: &'info AccountInfo<'info>,
@@ -72,21 +95,18 @@ impl<'info> FluxBeamAccounts<'info> {
     }
 }
 
-pub fn swap<'a>(
+pub fn build_swap<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::FluxBeam amount_in: {}, offset: {}", amount_in, offset);
-    require!(remaining_accounts.len() >= *offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
-    let mut swap_accounts = FluxBeamAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &flux_beam_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    require!(remaining_accounts.len() >= offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
+    let swap_accounts = FluxBeamAccounts::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.swap_info.key().log();
 
@@ -122,7 +142,7 @@ pub fn swap<'a>(
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.push(1);
     data.extend_from_slice(&amount_in.to_le_bytes());
-    data.extend_from_slice(&1u64.to_le_bytes());
+    data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes());
 
     let accounts = vec![
         AccountMeta::new_readonly(swap_accounts.swap_info.key(), false),
@@ -161,22 +181,12 @@ pub fn swap<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &FluxBeamProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
 
 #[cfg(test)]
@@ -2,8 +2,8 @@ use anchor_lang::prelude::*;
 use anchor_lang::solana_program::instruction::Instruction;
 use anchor_spl::token_interface::TokenAccount;
 
-use super::common::DexProcessor;
-use crate::adapters::common::{before_check, invoke_process};
+use super::common::{DexAdapter, SwapContext, SwapInstruction};
+use crate::adapters::common::before_check;
 use crate::error::ErrorCode;
 use crate::{HopAccounts, gavel_program};
 use arrayref::array_ref;
@@ -54,28 +54,49 @@ impl<'info> GavelSwapAccounts<'info> {
     }
 }
 
-pub struct GavelProcessor;
-impl DexProcessor for GavelProcessor {}
+pub struct GavelAdapter;
+impl DexAdapter for GavelAdapter {
+    fn accounts_len(&self) -> usize {
+        SWAP_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[gavel_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
 
-pub fn swap<'a>(
+pub fn build_swap<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::GavelSwap amount_in: {}, offset: {}", amount_in, offset);
     require!(
-        remaining_accounts.len() >= *offset + SWAP_ACCOUNTS_LEN,
+        remaining_accounts.len() >= offset + SWAP_ACCOUNTS_LEN,
         ErrorCode::InvalidAccountsLength
     );
 
-    let mut swap_accounts = GavelSwapAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &gavel_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = GavelSwapAccounts::parse_accounts(remaining_accounts, offset)?;
 
     swap_accounts.pool.key().log();
 
@@ -110,7 +131,7 @@ pub fn swap<'a>(
     data.push(direction); //direction
     data.push(0u8); //exact_in
     data.extend_from_slice(&amount_in.to_le_bytes()); //amount_in
-    data.extend_from_slice(&1u64.to_le_bytes()); // min_amount_out
+    data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes()); // min_amount_out
 
     let accounts = vec![
         AccountMeta::new_readonly(swap_accounts.dex_program_id.key(), false),
@@ -139,20 +160,10 @@ pub fn swap<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &GavelProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        SWAP_ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
@@ -3,16 +3,40 @@ use anchor_spl::token_interface::{TokenAccount, TokenInterface};
 use arrayref::array_ref;
 use borsh::{BorshDeserialize, BorshSerialize};
 
-use crate::adapters::common::{before_check, invoke_process};
+use crate::adapters::common::before_check;
 use crate::error::ErrorCode;
 use crate::{GOONFI_SWAP_SELECTOR, HopAccounts, goonfi_program};
 
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext, SwapInstruction};
 
 const ARGS_LEN: usize = 19;
 
-pub struct GoonfiProcessor;
-impl DexProcessor for GoonfiProcessor {}
+pub struct GoonfiAdapter;
+impl DexAdapter for GoonfiAdapter {
+    fn accounts_len(&self) -> usize {
+        ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[goonfi_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
 
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct SwapParams {
@@ -71,24 +95,20 @@ impl<'info> GoonfiAccounts<'info> {
     }
 }
 
-pub fn swap<'a>(
+pub fn build_swap<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::GoonFi amount_in: {}, offset: {}", amount_in, offset);
 
-    require!(remaining_accounts.len() >= *offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
-
-    let mut swap_accounts = GoonfiAccounts::parse_accounts(remaining_accounts, *offset)?;
+    require!(remaining_accounts.len() >= offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
 
-    if swap_accounts.dex_program_id.key != &goonfi_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = GoonfiAccounts::parse_accounts(remaining_accounts, offset)?;
 
     swap_accounts.market.key().log();
 
@@ -121,8 +141,12 @@ pub fn swap<'a>(
     let unused = &goonfi_param_data[1..32];
     require!(unused == &[0u8; 31], ErrorCode::InvalidGoonfiParameters);
 
-    let swap_params: SwapParams =
-        SwapParams { is_user_bid: is_bid, bump: blacklist_bump, amount_in, minimum_amount_out: 1 };
+    let swap_params: SwapParams = SwapParams {
+        is_user_bid: is_bid,
+        bump: blacklist_bump,
+        amount_in,
+        minimum_amount_out: hop_accounts.min_amount_out.max(1),
+    };
 
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.extend_from_slice(GOONFI_SWAP_SELECTOR);
@@ -153,21 +177,10 @@ pub fn swap<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &GoonfiProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_account,
-        &mut swap_accounts.swap_destination_account,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_account,
+        swap_destination_token: swap_accounts.swap_destination_account,
+    })
 }
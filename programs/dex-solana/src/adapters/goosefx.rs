@@ -1,16 +1,40 @@
-use crate::adapters::common::{before_check, invoke_process};
+use crate::adapters::common::before_check;
 use crate::error::ErrorCode;
 use crate::{GAMMA_ORACLE_SWAP_SELECTOR, HopAccounts, goosefx_gamma_program};
 use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use arrayref::array_ref;
 
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext, SwapInstruction};
 
 const ARGS_LEN: usize = 24;
 
-pub struct GooseFxProcessor;
-impl DexProcessor for GooseFxProcessor {}
+pub struct GooseFXAdapter;
+impl DexAdapter for GooseFXAdapter {
+    fn accounts_len(&self) -> usize {
+        ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[goosefx_gamma_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
 
 pub struct GooseFxAccount<'info> {
     pub dex_program_id: &'info AccountInfo<'info>,
@@ -70,22 +94,18 @@ impl<'info> GooseFxAccount<'info> {
     }
 }
 
-pub fn swap<'a>(
+pub fn build_swap<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::goosefx_gamma amount_in: {}, offset: {}", amount_in, offset);
-    require!(remaining_accounts.len() >= *offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
-    let mut swap_accounts = GooseFxAccount::parse_accounts(remaining_accounts, *offset)?;
-
-    if swap_accounts.dex_program_id.key != &goosefx_gamma_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    require!(remaining_accounts.len() >= offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
+    let swap_accounts = GooseFxAccount::parse_accounts(remaining_accounts, offset)?;
 
     // log pool address
     swap_accounts.pool_state.key().log();
@@ -107,7 +127,7 @@ pub fn swap<'a>(
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.extend_from_slice(GAMMA_ORACLE_SWAP_SELECTOR);
     data.extend_from_slice(&amount_in.to_le_bytes()); // amount_in
-    data.extend_from_slice(&0u64.to_le_bytes()); // minimum_amount_out (set to 0, allow any slippage)
+    data.extend_from_slice(&hop_accounts.min_amount_out.to_le_bytes()); // minimum_amount_out
 
     // Build account info
     let accounts = vec![
@@ -145,20 +165,10 @@ pub fn swap<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &GooseFxProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
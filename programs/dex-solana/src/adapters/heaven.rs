@@ -5,12 +5,35 @@ use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
 use anchor_spl::token_interface::TokenAccount;
 use arrayref::array_ref;
 
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext};
 
 const ARGS_LEN: usize = 28;
 
-struct HeavenProcessor;
-impl DexProcessor for HeavenProcessor {}
+pub struct HeavenAdapter {
+    pub is_buy: bool,
+}
+impl DexAdapter for HeavenAdapter {
+    fn accounts_len(&self) -> usize {
+        ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[heaven_program::ID] }
+    }
+
+    fn swap<'a>(&self, ctx: &mut SwapContext<'a, '_>, amount_in: u64) -> Result<u64> {
+        swap_handler(
+            self.is_buy,
+            ctx.remaining_accounts,
+            amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
 
 pub struct HeavenSwapAccounts<'info> {
     pub dex_program_id: &'info AccountInfo<'info>,
@@ -101,9 +124,6 @@ pub fn swap_handler<'a>(
     msg!("Dex::Heaven amount_in: {}, offset: {}", amount_in, offset);
     require!(remaining_accounts.len() >= *offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
     let mut swap_accounts = HeavenSwapAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &heaven_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
     // log pool address
     swap_accounts.liquidity_pool_state.key().log();
 
@@ -120,7 +140,7 @@ pub fn swap_handler<'a>(
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.extend_from_slice(if is_buy { HEAVEN_BUY_SELECTOR } else { HEAVEN_SELL_SELECTOR });
     data.extend_from_slice(&amount_in.to_le_bytes()); // amount_in
-    data.extend_from_slice(&1u64.to_le_bytes()); // minimum_amount_out
+    data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes()); // minimum_amount_out
     data.extend_from_slice(&0u32.to_le_bytes()); // encoded_user_defined_event_data
 
     let account_metas = vec![
@@ -167,7 +187,7 @@ pub fn swap_handler<'a>(
         data,
     };
 
-    let dex_processor = &HeavenProcessor;
+    let dex_processor = &HeavenAdapter { is_buy };
     let amount_out = invoke_process(
         amount_in,
         dex_processor,
@@ -181,6 +201,7 @@ pub fn swap_handler<'a>(
         ACCOUNTS_LEN,
         proxy_swap,
         owner_seeds,
+        0,
     )?;
 
     Ok(amount_out)
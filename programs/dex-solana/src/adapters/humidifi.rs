@@ -3,17 +3,40 @@ use anchor_spl::token_interface::{TokenAccount, TokenInterface};
 use arrayref::array_ref;
 use borsh::{BorshDeserialize, BorshSerialize};
 
-use crate::adapters::common::{before_check, invoke_process};
+use crate::adapters::common::before_check;
 use crate::error::ErrorCode;
 use crate::{HUMIDIFI_IX_DATA_KEY, HUMIDIFI_SWAP_SELECTOR, HopAccounts, humidifi_program};
 
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext, SwapInstruction};
 
 const ARGS_LEN: usize = 25;
 
-pub struct HumidifiProcessor;
+pub struct HumidifiAdapter;
+impl DexAdapter for HumidifiAdapter {
+    fn accounts_len(&self) -> usize {
+        ACCOUNTS_LEN
+    }
 
-impl DexProcessor for HumidifiProcessor {}
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[humidifi_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
 
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct SwapParams {
@@ -101,24 +124,20 @@ impl<'info> HumidifiAccounts<'info> {
     }
 }
 
-pub fn swap<'a>(
+pub fn build_swap<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::Humidifi amount_in: {}, offset: {}", amount_in, offset);
 
-    require!(remaining_accounts.len() >= *offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
-
-    let mut swap_accounts = HumidifiAccounts::parse_accounts(remaining_accounts, *offset)?;
+    require!(remaining_accounts.len() >= offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
 
-    if swap_accounts.dex_program_id.key != &humidifi_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = HumidifiAccounts::parse_accounts(remaining_accounts, offset)?;
 
     swap_accounts.pool.key().log();
 
@@ -201,21 +220,10 @@ pub fn swap<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &HumidifiProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
@@ -1,4 +1,4 @@
-use crate::adapters::common::{before_check, invoke_process};
+use crate::adapters::common::before_check;
 use crate::error::ErrorCode;
 use crate::{HopAccounts, SWAP_SELECTOR, lifinity_v2pool_program};
 use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
@@ -6,12 +6,59 @@ use anchor_spl::token::Token;
 use anchor_spl::token_interface::{Mint, TokenAccount};
 use arrayref::array_ref;
 
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext, SwapInstruction};
 
 const ARGS_LEN: usize = 24;
 
-pub struct LifinityProcessor;
-impl DexProcessor for LifinityProcessor {}
+pub struct LifinityV1Adapter;
+impl DexAdapter for LifinityV1Adapter {
+    fn accounts_len(&self) -> usize {
+        0
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        &[]
+    }
+
+    fn swap<'a>(&self, ctx: &mut SwapContext<'a, '_>, amount_in: u64) -> Result<u64> {
+        swap_v1(
+            ctx.remaining_accounts,
+            amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
+
+pub struct LifinityV2Adapter;
+impl DexAdapter for LifinityV2Adapter {
+    fn accounts_len(&self) -> usize {
+        ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[lifinity_v2pool_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap_v2(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
 
 pub struct LifinitySwapAccountsV2<'info> {
     pub dex_program_id: &'info AccountInfo<'info>,
@@ -82,21 +129,18 @@ pub fn swap_v1<'a>(
     Ok(0)
 }
 
-pub fn swap_v2<'a>(
+pub fn build_swap_v2<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::LifinitySwapV2 amount_in: {}, offset: {}", amount_in, offset);
-    require!(remaining_accounts.len() >= *offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
-    let mut swap_accounts = LifinitySwapAccountsV2::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &lifinity_v2pool_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    require!(remaining_accounts.len() >= offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
+    let swap_accounts = LifinitySwapAccountsV2::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.amm_info.key().log();
 
@@ -116,7 +160,7 @@ pub fn swap_v2<'a>(
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.extend_from_slice(SWAP_SELECTOR);
     data.extend_from_slice(&amount_in.to_le_bytes());
-    data.extend_from_slice(&1u64.to_le_bytes());
+    data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes());
 
     let accounts = vec![
         AccountMeta::new_readonly(swap_accounts.authority.key(), false),
@@ -153,22 +197,12 @@ pub fn swap_v2<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &LifinityProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
 
 #[cfg(test)]
@@ -1,4 +1,4 @@
-use crate::adapters::common::{DexProcessor, before_check, invoke_process};
+use crate::adapters::common::{DexAdapter, SwapContext, SwapInstruction, before_check};
 use crate::error::ErrorCode;
 use crate::{HopAccounts, MANIFEST_SWAP_SELECTOR, manifest_program};
 use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
@@ -7,9 +7,39 @@ use arrayref::array_ref;
 use borsh::{BorshDeserialize, BorshSerialize};
 
 const ARGS_LEN: usize = 19; // Length after SwapParams serialization
+/// Scale of OrderBookLimit::price, quote atoms per base atom
+pub const MANIFEST_PRICE_SCALE: u128 = 1_000_000_000_000;
 
-pub struct ManifestProcessor;
-impl DexProcessor for ManifestProcessor {}
+pub struct ManifestAdapter;
+impl DexAdapter for ManifestAdapter {
+    fn accounts_len(&self) -> usize {
+        ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[manifest_program::ID] }
+    }
+
+    fn supports_order_book_limit(&self) -> bool {
+        true
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
 
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct SwapParams {
@@ -77,25 +107,34 @@ impl<'info> ManifestAccount<'info> {
     }
 }
 
-pub fn swap<'a>(
+/// Minimum output of amount_in at the worst acceptable price. Manifest swaps take no limit price,
+/// so a book worse than the limit reverts the whole swap instead of partially filling like the
+/// OpenBook V2 and Phoenix IOC orders
+fn limit_out_atoms(amount_in: u64, price: u64, is_base_in: bool) -> Result<u64> {
+    let out_atoms = if is_base_in {
+        (amount_in as u128).checked_mul(price as u128).ok_or(ErrorCode::CalculationError)?
+            / MANIFEST_PRICE_SCALE
+    } else {
+        (amount_in as u128).checked_mul(MANIFEST_PRICE_SCALE).ok_or(ErrorCode::CalculationError)?
+            / price as u128
+    };
+    Ok(u64::try_from(out_atoms).map_err(|_| ErrorCode::CalculationError)?.max(1))
+}
+
+pub fn build_swap<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::Manifest amount_in: {}, offset: {}", amount_in, offset);
 
-    require!(remaining_accounts.len() >= *offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
+    require!(remaining_accounts.len() >= offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
 
-    let mut swap_accounts = ManifestAccount::parse_accounts(remaining_accounts, *offset)?;
-
-    // Verify program ID
-    if swap_accounts.dex_program_id.key != &manifest_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = ManifestAccount::parse_accounts(remaining_accounts, offset)?;
 
     // Record market address
     swap_accounts.market.key().log();
@@ -135,10 +174,20 @@ pub fn swap<'a>(
         return Err(ErrorCode::InvalidTokenMint.into());
     }
 
+    // Manifest has no limit price nor match limit, a worst price is enforced as minimum output
+    // of the whole amount_in: no partial fill
+    let order_book_limit = hop_accounts.order_book_limit;
+    require!(order_book_limit.match_limit == 0, ErrorCode::OrderBookLimitNotSupported);
+    let out_atoms = if order_book_limit.price != 0 {
+        limit_out_atoms(amount_in, order_book_limit.price, is_base_in)?
+    } else {
+        1 // Minimum output, let the market decide actual output
+    };
+
     // Build Manifest SwapParams
     let swap_params = SwapParams {
         in_atoms: amount_in,
-        out_atoms: 1, // Minimum output, let the market decide actual output
+        out_atoms,
         is_base_in,
         is_exact_in: true, // Exact input mode
     };
@@ -205,21 +254,26 @@ pub fn swap<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &ManifestProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    Ok(amount_out)
+    #[test]
+    pub fn test_limit_out_atoms() {
+        // Selling 1_000 base atoms at 1.5 quote atoms per base atom or better
+        assert_eq!(limit_out_atoms(1_000, 1_500_000_000_000, true).unwrap(), 1_500);
+        // Buying with 1_500 quote atoms at 1.5 quote atoms per base atom or better
+        assert_eq!(limit_out_atoms(1_500, 1_500_000_000_000, false).unwrap(), 1_000);
+        // Rounded down outputs still require one atom
+        assert_eq!(limit_out_atoms(1, 1, true).unwrap(), 1);
+        assert!(limit_out_atoms(u64::MAX, u64::MAX, true).is_err());
+    }
 }
@@ -1,22 +1,279 @@
-use crate::adapters::common::{before_check, invoke_process};
+use crate::adapters::common::{before_check, invoke_process_exact_out};
 use crate::error::ErrorCode;
 use crate::{
-    DEPOSIT_SELECTOR, HopAccounts, SWAP_SELECTOR, SWAP2_SELECTOR, WITHDRAW_SELECTOR, ZERO_ADDRESS,
-    meteora_damm_v2_program, meteora_dlmm_program, meteora_dynamicpool_program,
-    meteora_vault_program,
+    DEPOSIT_SELECTOR, HopAccounts, SWAP_EXACT_OUT_SELECTOR, SWAP_EXACT_OUT2_SELECTOR,
+    SWAP_SELECTOR, SWAP2_SELECTOR, WITHDRAW_SELECTOR, ZERO_ADDRESS, meteora_damm_v2_program,
+    meteora_dlmm_program, meteora_dynamicpool_program, meteora_vault_program,
 };
 use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
 use anchor_spl::token::Token;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use arrayref::array_ref;
 
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext, SwapInstruction};
 
 const ARGS_LEN: usize = 24;
 const DLMM_SWAP2_ARGS_LEN: usize = 28;
 
-pub struct MeteoraDynamicPoolProcessor;
-impl DexProcessor for MeteoraDynamicPoolProcessor {}
+pub struct MeteoraVaultDepositAdapter;
+impl DexAdapter for MeteoraVaultDepositAdapter {
+    fn accounts_len(&self) -> usize {
+        VAULT_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[meteora_vault_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_deposit(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
+
+pub struct MeteoraVaultWithdrawAdapter;
+impl DexAdapter for MeteoraVaultWithdrawAdapter {
+    fn accounts_len(&self) -> usize {
+        VAULT_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[meteora_vault_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_withdraw(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
+
+pub struct MeteoraDynamicpoolAdapter;
+impl DexAdapter for MeteoraDynamicpoolAdapter {
+    fn accounts_len(&self) -> usize {
+        ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[meteora_dynamicpool_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
+
+pub struct MeteoraLstAdapter;
+impl DexAdapter for MeteoraLstAdapter {
+    fn accounts_len(&self) -> usize {
+        LST_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[meteora_dynamicpool_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap_lst(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
+
+pub struct MeteoraDlmmAdapter;
+impl DexAdapter for MeteoraDlmmAdapter {
+    fn accounts_len(&self) -> usize {
+        DLMM_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[meteora_dlmm_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_dlmm_swap(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+
+    fn supports_exact_out(&self) -> bool {
+        true
+    }
+
+    fn swap_exact_out<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_out: u64,
+        max_amount_in: u64,
+    ) -> Result<(u64, u64)> {
+        dlmm_swap_exact_out(
+            ctx.remaining_accounts,
+            amount_out,
+            max_amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
+
+pub struct MeteoraDlmmSwap2Adapter;
+impl DexAdapter for MeteoraDlmmSwap2Adapter {
+    fn accounts_len(&self) -> usize {
+        DLMM_SWAP2_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[meteora_dlmm_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_dlmm_swap2(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+
+    fn supports_exact_out(&self) -> bool {
+        true
+    }
+
+    fn swap_exact_out<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_out: u64,
+        max_amount_in: u64,
+    ) -> Result<(u64, u64)> {
+        dlmm_swap2_exact_out(
+            ctx.remaining_accounts,
+            amount_out,
+            max_amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
+
+pub struct MeteoraDAMMV2Adapter;
+impl DexAdapter for MeteoraDAMMV2Adapter {
+    fn accounts_len(&self) -> usize {
+        DAMMV2_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[meteora_damm_v2_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_damm_v2_swap(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
+
+pub struct MeteoraDAMMV2Swap2Adapter;
+impl DexAdapter for MeteoraDAMMV2Swap2Adapter {
+    fn accounts_len(&self) -> usize {
+        DAMMV2_SWAP2_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[meteora_damm_v2_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_damm_v2_swap2(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
 
 pub struct MeteoraDynamicPoolAccounts<'info> {
     pub dex_program_id: &'info AccountInfo<'info>,
@@ -451,25 +708,21 @@ impl<'info> MeteoraDAMMV2Swap2Accounts<'info> {
     }
 }
 
-pub fn deposit<'a>(
+pub fn build_deposit<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::MeteoraVaultDeposit amount_in: {}, offset: {}", amount_in, offset);
     require!(
-        remaining_accounts.len() >= *offset + VAULT_ACCOUNTS_LEN,
+        remaining_accounts.len() >= offset + VAULT_ACCOUNTS_LEN,
         ErrorCode::InvalidAccountsLength
     );
-    let mut swap_accounts =
-        MeteoraDynamicVaultAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &meteora_vault_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = MeteoraDynamicVaultAccounts::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.vault.key().log();
 
@@ -489,7 +742,7 @@ pub fn deposit<'a>(
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.extend_from_slice(DEPOSIT_SELECTOR);
     data.extend_from_slice(&amount_in.to_le_bytes());
-    data.extend_from_slice(&1u64.to_le_bytes());
+    data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes());
 
     let accounts = vec![
         AccountMeta::new(swap_accounts.vault.key(), false),
@@ -514,43 +767,29 @@ pub fn deposit<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &MeteoraDynamicPoolProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        VAULT_ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
 
-pub fn withdraw<'a>(
+pub fn build_withdraw<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::MeteoraVaultWithdraw amount_in: {}, offset: {}", amount_in, offset);
     require!(
-        remaining_accounts.len() >= *offset + VAULT_ACCOUNTS_LEN,
+        remaining_accounts.len() >= offset + VAULT_ACCOUNTS_LEN,
         ErrorCode::InvalidAccountsLength
     );
-    let mut swap_accounts =
-        MeteoraDynamicVaultAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &meteora_vault_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = MeteoraDynamicVaultAccounts::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.vault.key().log();
 
@@ -570,7 +809,7 @@ pub fn withdraw<'a>(
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.extend_from_slice(WITHDRAW_SELECTOR);
     data.extend_from_slice(&amount_in.to_le_bytes());
-    data.extend_from_slice(&1u64.to_le_bytes());
+    data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes());
 
     let accounts = vec![
         AccountMeta::new(swap_accounts.vault.key(), false),
@@ -595,40 +834,26 @@ pub fn withdraw<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &MeteoraDynamicPoolProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        VAULT_ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
 
-pub fn swap<'a>(
+pub fn build_swap<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::MeteoraSwap amount_in: {}, offset: {}", amount_in, offset);
-    require!(remaining_accounts.len() >= *offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
-    let mut swap_accounts =
-        MeteoraDynamicPoolAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &meteora_dynamicpool_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    require!(remaining_accounts.len() >= offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
+    let swap_accounts = MeteoraDynamicPoolAccounts::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.pool.key().log();
 
@@ -648,7 +873,7 @@ pub fn swap<'a>(
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.extend_from_slice(SWAP_SELECTOR);
     data.extend_from_slice(&amount_in.to_le_bytes());
-    data.extend_from_slice(&1u64.to_le_bytes());
+    data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes());
 
     let accounts = vec![
         AccountMeta::new(swap_accounts.pool.key(), false),
@@ -689,42 +914,29 @@ pub fn swap<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &MeteoraDynamicPoolProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
 
-pub fn swap_lst<'a>(
+pub fn build_swap_lst<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::MeteoraSwapLst amount_in: {}, offset: {}", amount_in, offset);
     require!(
-        remaining_accounts.len() >= *offset + LST_ACCOUNTS_LEN,
+        remaining_accounts.len() >= offset + LST_ACCOUNTS_LEN,
         ErrorCode::InvalidAccountsLength
     );
-    let mut swap_accounts = MeteoraLSTPoolAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &meteora_dynamicpool_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = MeteoraLSTPoolAccounts::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.pool.key().log();
 
@@ -744,7 +956,7 @@ pub fn swap_lst<'a>(
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.extend_from_slice(SWAP_SELECTOR);
     data.extend_from_slice(&amount_in.to_le_bytes());
-    data.extend_from_slice(&1u64.to_le_bytes());
+    data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes());
 
     let accounts = vec![
         AccountMeta::new(swap_accounts.pool.key(), false),
@@ -787,9 +999,77 @@ pub fn swap_lst<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &MeteoraDynamicPoolProcessor;
-    let amount_out = invoke_process(
+    Ok(SwapInstruction {
+        instruction,
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
+}
+
+pub fn build_dlmm_swap<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+    owner_seeds: Option<&[&[&[u8]]]>,
+) -> Result<SwapInstruction<'a>> {
+    msg!("Dex::MeteoraDlmm amount_in: {}, offset: {}", amount_in, offset);
+    let min_amount_out = hop_accounts.min_amount_out.max(1);
+    let (swap_accounts, instruction, account_infos) = prepare_dlmm_swap(
+        remaining_accounts,
+        offset,
+        hop_accounts,
+        hop,
+        proxy_swap,
+        owner_seeds,
+        SWAP_SELECTOR,
         amount_in,
+        min_amount_out,
+    )?;
+
+    Ok(SwapInstruction {
+        instruction,
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
+}
+
+pub fn dlmm_swap_exact_out<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_out: u64,
+    max_amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+    owner_seeds: Option<&[&[&[u8]]]>,
+) -> Result<(u64, u64)> {
+    msg!(
+        "Dex::MeteoraDlmm amount_out: {}, max_amount_in: {}, offset: {}",
+        amount_out,
+        max_amount_in,
+        offset
+    );
+    let (mut swap_accounts, instruction, account_infos) = prepare_dlmm_swap(
+        remaining_accounts,
+        *offset,
+        hop_accounts,
+        hop,
+        proxy_swap,
+        owner_seeds,
+        SWAP_EXACT_OUT_SELECTOR,
+        max_amount_in,
+        amount_out,
+    )?;
+
+    let dex_processor = &MeteoraDlmmAdapter;
+    invoke_process_exact_out(
+        amount_out,
+        max_amount_in,
         dex_processor,
         &account_infos,
         &mut swap_accounts.swap_source_token,
@@ -798,31 +1078,28 @@ pub fn swap_lst<'a>(
         instruction,
         hop,
         offset,
-        LST_ACCOUNTS_LEN,
+        DLMM_ACCOUNTS_LEN,
         proxy_swap,
         owner_seeds,
-    )?;
-    Ok(amount_out)
+    )
 }
 
-pub fn dlmm_swap<'a>(
+fn prepare_dlmm_swap<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
-    amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
-    msg!("Dex::MeteoraDlmm amount_in: {}, offset: {}", amount_in, offset);
+    selector: &[u8; 8],
+    amount_0: u64,
+    amount_1: u64,
+) -> Result<(MeteoraDlmmAccounts<'a>, Instruction, Vec<AccountInfo<'a>>)> {
     require!(
-        remaining_accounts.len() >= *offset + DLMM_ACCOUNTS_LEN,
+        remaining_accounts.len() >= offset + DLMM_ACCOUNTS_LEN,
         ErrorCode::InvalidAccountsLength
     );
-    let mut swap_accounts = MeteoraDlmmAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &meteora_dlmm_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = MeteoraDlmmAccounts::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.lb_pair.key().log();
 
@@ -840,9 +1117,9 @@ pub fn dlmm_swap<'a>(
     )?;
 
     let mut data = Vec::with_capacity(ARGS_LEN);
-    data.extend_from_slice(SWAP_SELECTOR);
-    data.extend_from_slice(&amount_in.to_le_bytes());
-    data.extend_from_slice(&1u64.to_le_bytes());
+    data.extend_from_slice(selector);
+    data.extend_from_slice(&amount_0.to_le_bytes());
+    data.extend_from_slice(&amount_1.to_le_bytes());
 
     let mut accounts = vec![
         AccountMeta::new(swap_accounts.lb_pair.key(), false),
@@ -896,9 +1173,72 @@ pub fn dlmm_swap<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &MeteoraDynamicPoolProcessor;
-    let amount_out = invoke_process(
+    Ok((swap_accounts, instruction, account_infos))
+}
+
+pub fn build_dlmm_swap2<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+    owner_seeds: Option<&[&[&[u8]]]>,
+) -> Result<SwapInstruction<'a>> {
+    msg!("Dex::MeteoraDlmmSwap2 amount_in: {}, offset: {}", amount_in, offset);
+    let min_amount_out = hop_accounts.min_amount_out.max(1);
+    let (swap_accounts, instruction, account_infos) = prepare_dlmm_swap2(
+        remaining_accounts,
+        offset,
+        hop_accounts,
+        hop,
+        proxy_swap,
+        owner_seeds,
+        SWAP2_SELECTOR,
         amount_in,
+        min_amount_out,
+    )?;
+
+    Ok(SwapInstruction {
+        instruction,
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
+}
+
+pub fn dlmm_swap2_exact_out<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_out: u64,
+    max_amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+    owner_seeds: Option<&[&[&[u8]]]>,
+) -> Result<(u64, u64)> {
+    msg!(
+        "Dex::MeteoraDlmmSwap2 amount_out: {}, max_amount_in: {}, offset: {}",
+        amount_out,
+        max_amount_in,
+        offset
+    );
+    let (mut swap_accounts, instruction, account_infos) = prepare_dlmm_swap2(
+        remaining_accounts,
+        *offset,
+        hop_accounts,
+        hop,
+        proxy_swap,
+        owner_seeds,
+        SWAP_EXACT_OUT2_SELECTOR,
+        max_amount_in,
+        amount_out,
+    )?;
+
+    let dex_processor = &MeteoraDlmmSwap2Adapter;
+    invoke_process_exact_out(
+        amount_out,
+        max_amount_in,
         dex_processor,
         &account_infos,
         &mut swap_accounts.swap_source_token,
@@ -907,31 +1247,28 @@ pub fn dlmm_swap<'a>(
         instruction,
         hop,
         offset,
-        DLMM_ACCOUNTS_LEN,
+        DLMM_SWAP2_ACCOUNTS_LEN,
         proxy_swap,
         owner_seeds,
-    )?;
-    Ok(amount_out)
+    )
 }
 
-pub fn dlmm_swap2<'a>(
+fn prepare_dlmm_swap2<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
-    amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
-    msg!("Dex::MeteoraDlmmSwap2 amount_in: {}, offset: {}", amount_in, offset);
+    selector: &[u8; 8],
+    amount_0: u64,
+    amount_1: u64,
+) -> Result<(MeteoraDlmmSwap2Accounts<'a>, Instruction, Vec<AccountInfo<'a>>)> {
     require!(
-        remaining_accounts.len() >= *offset + DLMM_SWAP2_ACCOUNTS_LEN,
+        remaining_accounts.len() >= offset + DLMM_SWAP2_ACCOUNTS_LEN,
         ErrorCode::InvalidAccountsLength
     );
-    let mut swap_accounts = MeteoraDlmmSwap2Accounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &meteora_dlmm_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = MeteoraDlmmSwap2Accounts::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.lb_pair.key().log();
 
@@ -949,9 +1286,9 @@ pub fn dlmm_swap2<'a>(
     )?;
 
     let mut data = Vec::with_capacity(DLMM_SWAP2_ARGS_LEN);
-    data.extend_from_slice(SWAP2_SELECTOR);
-    data.extend_from_slice(&amount_in.to_le_bytes());
-    data.extend_from_slice(&1u64.to_le_bytes());
+    data.extend_from_slice(selector);
+    data.extend_from_slice(&amount_0.to_le_bytes());
+    data.extend_from_slice(&amount_1.to_le_bytes());
     data.extend_from_slice(&0u32.to_le_bytes());
 
     let mut accounts = vec![
@@ -1008,43 +1345,25 @@ pub fn dlmm_swap2<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &MeteoraDynamicPoolProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
-        instruction,
-        hop,
-        offset,
-        DLMM_SWAP2_ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-    Ok(amount_out)
+    Ok((swap_accounts, instruction, account_infos))
 }
 
-pub fn damm_v2_swap<'a>(
+pub fn build_damm_v2_swap<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::MeteoraDAMMV2 amount_in: {}, offset: {}", amount_in, offset);
     require!(
-        remaining_accounts.len() >= *offset + DAMMV2_ACCOUNTS_LEN,
+        remaining_accounts.len() >= offset + DAMMV2_ACCOUNTS_LEN,
         ErrorCode::InvalidAccountsLength
     );
 
-    let mut swap_accounts = MeteoraDAMMV2SwapAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &meteora_damm_v2_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = MeteoraDAMMV2SwapAccounts::parse_accounts(remaining_accounts, offset)?;
     swap_accounts.pool.key().log();
 
     let swap_destination_token = swap_accounts.output_token_account.key();
@@ -1062,7 +1381,7 @@ pub fn damm_v2_swap<'a>(
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.extend_from_slice(SWAP_SELECTOR);
     data.extend_from_slice(&amount_in.to_le_bytes());
-    data.extend_from_slice(&1u64.to_le_bytes());
+    data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes());
 
     let accounts = vec![
         AccountMeta::new_readonly(swap_accounts.pool_authority.key(), false),
@@ -1105,45 +1424,30 @@ pub fn damm_v2_swap<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &MeteoraDynamicPoolProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        DAMMV2_ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
 
-pub fn damm_v2_swap2<'a>(
+pub fn build_damm_v2_swap2<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::MeteoraDAMMV2Swap2 amount_in: {}, offset: {}", amount_in, offset);
     require!(
-        remaining_accounts.len() >= *offset + DAMMV2_SWAP2_ACCOUNTS_LEN,
+        remaining_accounts.len() >= offset + DAMMV2_SWAP2_ACCOUNTS_LEN,
         ErrorCode::InvalidAccountsLength
     );
 
-    let mut swap_accounts =
-        MeteoraDAMMV2Swap2Accounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &meteora_damm_v2_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = MeteoraDAMMV2Swap2Accounts::parse_accounts(remaining_accounts, offset)?;
     swap_accounts.pool.key().log();
 
     let swap_destination_token = swap_accounts.swap_destination_account.key();
@@ -1161,7 +1465,7 @@ pub fn damm_v2_swap2<'a>(
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.extend_from_slice(SWAP2_SELECTOR);
     data.extend_from_slice(&amount_in.to_le_bytes()); // amount_0: When it's exact in, this will be amount_in.
-    data.extend_from_slice(&1u64.to_le_bytes()); // amount_1: When it's exact in, this will be minimum_amount_out.
+    data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes()); // amount_1: When it's exact in, this will be minimum_amount_out.
     data.extend_from_slice(&0u8.to_le_bytes()); // swap_mode: 0 - ExactIn, 1 - PartialFill, 2 - ExactOut,
 
     let mut accounts = Vec::with_capacity(DAMMV2_SWAP2_ACCOUNTS_LEN);
@@ -1207,23 +1511,12 @@ pub fn damm_v2_swap2<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &MeteoraDynamicPoolProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_account,
-        &mut swap_accounts.swap_destination_account,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        DAMMV2_SWAP2_ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_account,
+        swap_destination_token: swap_accounts.swap_destination_account,
+    })
 }
 
 #[cfg(test)]
@@ -1241,4 +1534,16 @@ mod tests {
         msg!("data.len: {}", data.len());
         assert!(data.len() == ARGS_LEN);
     }
+    #[test]
+    pub fn test_pack_dlmm_swap2_exact_out_instruction() {
+        let amount_out = 100u64;
+        let max_amount_in = 200u64;
+        let mut data = Vec::with_capacity(DLMM_SWAP2_ARGS_LEN);
+        data.extend_from_slice(SWAP_EXACT_OUT2_SELECTOR);
+        data.extend_from_slice(&max_amount_in.to_le_bytes());
+        data.extend_from_slice(&amount_out.to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes());
+
+        assert!(data.len() == DLMM_SWAP2_ARGS_LEN);
+    }
 }
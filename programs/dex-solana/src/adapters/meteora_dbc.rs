@@ -1,14 +1,65 @@
-use crate::adapters::common::{before_check, invoke_process};
+use crate::adapters::common::before_check;
 use crate::error::ErrorCode;
 use crate::{HopAccounts, SWAP2_SELECTOR, ZERO_ADDRESS, meteora_dbc_program};
 use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use arrayref::array_ref;
 
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext, SwapInstruction};
 
-pub struct MeteoraDynamicBondingCurveProcessor;
-impl DexProcessor for MeteoraDynamicBondingCurveProcessor {}
+pub struct MeteoraDbcAdapter;
+impl DexAdapter for MeteoraDbcAdapter {
+    fn accounts_len(&self) -> usize {
+        ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[meteora_dbc_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
+
+pub struct MeteoraDbc2Adapter;
+impl DexAdapter for MeteoraDbc2Adapter {
+    fn accounts_len(&self) -> usize {
+        ACCOUNTS2_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[meteora_dbc_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap2(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
 
 pub struct MeteoraDynamicBondingCurve<'info> {
     pub dex_program_id: &'info AccountInfo<'info>,
@@ -139,23 +190,19 @@ impl<'info> MeteoraDynamicBondingCurve2<'info> {
     }
 }
 
-pub fn swap<'a>(
+pub fn build_swap<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::MeteoraDbc amount_in: {}, offset: {}", amount_in, offset);
-    require!(remaining_accounts.len() >= *offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
+    require!(remaining_accounts.len() >= offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
 
-    let mut swap_accounts =
-        MeteoraDynamicBondingCurve::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &meteora_dbc_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = MeteoraDynamicBondingCurve::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.pool.key().log();
 
@@ -172,7 +219,7 @@ pub fn swap<'a>(
     let mut data = Vec::with_capacity(25);
     data.extend_from_slice(SWAP2_SELECTOR);
     data.extend_from_slice(&amount_in.to_le_bytes()); // amount0(amount_in)
-    data.extend_from_slice(&1u64.to_le_bytes()); // amount1(minimum_amount_out)
+    data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes()); // amount1(minimum_amount_out)
     data.extend_from_slice(&1u8.to_le_bytes()); // swap_mode(partial_fill)
 
     let accounts = vec![
@@ -226,40 +273,27 @@ pub fn swap<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let amount_out = invoke_process(
-        amount_in,
-        &MeteoraDynamicBondingCurveProcessor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
 
-pub fn swap2<'a>(
+pub fn build_swap2<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::MeteoraDbc2 amount_in: {}, offset: {}", amount_in, offset);
-    require!(remaining_accounts.len() >= *offset + ACCOUNTS2_LEN, ErrorCode::InvalidAccountsLength);
+    require!(remaining_accounts.len() >= offset + ACCOUNTS2_LEN, ErrorCode::InvalidAccountsLength);
 
-    let mut swap_accounts =
-        MeteoraDynamicBondingCurve2::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &meteora_dbc_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = MeteoraDynamicBondingCurve2::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.pool.key().log();
 
@@ -276,7 +310,7 @@ pub fn swap2<'a>(
     let mut data = Vec::with_capacity(25);
     data.extend_from_slice(SWAP2_SELECTOR);
     data.extend_from_slice(&amount_in.to_le_bytes()); // amount0(amount_in)
-    data.extend_from_slice(&1u64.to_le_bytes()); // amount1(minimum_amount_out)
+    data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes()); // amount1(minimum_amount_out)
     data.extend_from_slice(&1u8.to_le_bytes()); // swap_mode(partial_fill)
 
     let accounts = vec![
@@ -332,19 +366,10 @@ pub fn swap2<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let amount_out = invoke_process(
-        amount_in,
-        &MeteoraDynamicBondingCurveProcessor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        ACCOUNTS2_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
@@ -26,6 +26,7 @@ pub mod pumpfunamm;
 pub mod qualia;
 pub mod raydium;
 pub mod raydium_launchpad;
+pub mod registry;
 pub mod saber_decimal_wrapper;
 pub mod sanctum;
 pub mod sanctum_router;
@@ -1,5 +1,5 @@
-use super::common::DexProcessor;
-use crate::adapters::common::{before_check, invoke_process};
+use super::common::{DexAdapter, SwapContext, SwapInstruction};
+use crate::adapters::common::before_check;
 use crate::error::ErrorCode;
 use crate::utils::{close_token_account, sync_wsol_account, transfer_sol};
 use crate::{
@@ -75,8 +75,33 @@ impl<'info> MoonitAccounts<'info> {
     }
 }
 
-pub struct MoonitBuyProcessor;
-impl DexProcessor for MoonitBuyProcessor {
+pub struct MoonitBuyAdapter;
+impl DexAdapter for MoonitBuyAdapter {
+    fn accounts_len(&self) -> usize {
+        MOONIT_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[moonit_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_buy(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+            ctx.payer,
+        )
+    }
+
     fn before_invoke(&self, account_infos: &[AccountInfo]) -> Result<u64> {
         let payer = account_infos.get(12).unwrap();
         let source_token_account = account_infos.get(11).unwrap();
@@ -114,17 +139,44 @@ impl DexProcessor for MoonitBuyProcessor {
     }
 }
 
-pub struct MoonitSellProcessor {
-    pub sender_before_lamports: u64,
-}
+pub struct MoonitSellAdapter;
+impl DexAdapter for MoonitSellAdapter {
+    fn accounts_len(&self) -> usize {
+        MOONIT_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[moonit_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_sell(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+
+    fn before_invoke(&self, account_infos: &[AccountInfo]) -> Result<u64> {
+        let authority = account_infos.first().unwrap();
+        Ok(authority.lamports())
+    }
 
-impl DexProcessor for MoonitSellProcessor {
     fn after_invoke(
         &self,
         account_infos: &[AccountInfo],
         hop: usize,
         owner_seeds: Option<&[&[&[u8]]]>,
-        _before_sa_authority_lamports: u64,
+        sender_before_lamports: u64,
+        _expected_amount_out: u64,
     ) -> Result<u64> {
         let destination_token_account = account_infos.last().unwrap();
         let authority = account_infos.get(0).unwrap();
@@ -140,7 +192,7 @@ impl DexProcessor for MoonitSellProcessor {
         };
 
         let received_lamports = sender_after_lamports
-            .checked_sub(self.sender_before_lamports)
+            .checked_sub(sender_before_lamports)
             .ok_or(ErrorCode::CalculationError)?;
         transfer_sol(
             authority.to_account_info(),
@@ -157,26 +209,23 @@ impl DexProcessor for MoonitSellProcessor {
     }
 }
 
-pub fn buy<'a>(
+pub fn build_buy<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
     payer: Option<&AccountInfo<'a>>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::Moonit amount_in: {}, offset: {}", amount_in, offset);
     require!(
-        remaining_accounts.len() >= *offset + MOONIT_ACCOUNTS_LEN,
+        remaining_accounts.len() >= offset + MOONIT_ACCOUNTS_LEN,
         ErrorCode::InvalidAccountsLength
     );
 
-    let mut swap_accounts = MoonitAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &moonit_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = MoonitAccounts::parse_accounts(remaining_accounts, offset)?;
 
     // Record pool address
     swap_accounts.curve_account.key().log();
@@ -232,43 +281,30 @@ pub fn buy<'a>(
     let instruction =
         Instruction { program_id: moonit_program::id(), accounts: account_metas, data };
 
-    let dex_processor = &MoonitBuyProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        MOONIT_ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
 
-pub fn sell<'a>(
+pub fn build_sell<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::Moonit amount_in: {}, offset: {}", amount_in, offset);
     require!(
-        remaining_accounts.len() >= *offset + MOONIT_ACCOUNTS_LEN,
+        remaining_accounts.len() >= offset + MOONIT_ACCOUNTS_LEN,
         ErrorCode::InvalidAccountsLength
     );
 
-    let mut swap_accounts = MoonitAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &moonit_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = MoonitAccounts::parse_accounts(remaining_accounts, offset)?;
 
     // Record pool address
     swap_accounts.curve_account.key().log();
@@ -323,23 +359,12 @@ pub fn sell<'a>(
     let instruction =
         Instruction { program_id: moonit_program::id(), accounts: account_metas, data };
 
-    let dex_processor =
-        MoonitSellProcessor { sender_before_lamports: swap_accounts.sender.lamports() };
-    let amount_out = invoke_process(
-        amount_in,
-        &dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        MOONIT_ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
 
 fn moonit_before_check(
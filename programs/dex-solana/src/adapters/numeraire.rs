@@ -1,4 +1,4 @@
-use crate::adapters::common::{before_check, invoke_process};
+use crate::adapters::common::before_check;
 use crate::error::ErrorCode;
 use crate::{HopAccounts, SWAP_EXACT_IN_SELECTOR, numeraire_program, numeraire_usdstar_mint};
 use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
@@ -9,10 +9,34 @@ use anchor_spl::{
 };
 use arrayref::array_ref;
 
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext, SwapInstruction};
 
-pub struct NumeraireProcessor;
-impl DexProcessor for NumeraireProcessor {}
+pub struct NumeraireAdapter;
+impl DexAdapter for NumeraireAdapter {
+    fn accounts_len(&self) -> usize {
+        ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[numeraire_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
 
 pub struct NumeraireSwapAccounts<'info> {
     pub dex_program_id: &'info AccountInfo<'info>,
@@ -83,21 +107,18 @@ fn get_mint_index(
     }
 }
 
-pub fn swap<'a>(
+pub fn build_swap<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::Numeraire amount_in: {}, offset: {}", amount_in, offset);
-    require!(remaining_accounts.len() >= *offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
-    let mut swap_accounts = NumeraireSwapAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &numeraire_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    require!(remaining_accounts.len() >= offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
+    let swap_accounts = NumeraireSwapAccounts::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.pool.key().log();
 
@@ -132,7 +153,7 @@ pub fn swap<'a>(
     data.extend_from_slice(&in_index.to_le_bytes());
     data.extend_from_slice(&out_index.to_le_bytes());
     data.extend_from_slice(&amount_in.to_le_bytes()); // exactAmountIn
-    data.extend_from_slice(&1u64.to_le_bytes()); // min_amount_out
+    data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes()); // min_amount_out
 
     let accounts = vec![
         AccountMeta::new(swap_accounts.pool.key(), false),
@@ -177,19 +198,10 @@ pub fn swap<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let amount_out = invoke_process(
-        amount_in,
-        &NumeraireProcessor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
@@ -1,4 +1,4 @@
-use crate::adapters::common::{before_check, invoke_process};
+use crate::adapters::common::before_check;
 use crate::error::ErrorCode;
 use crate::{HopAccounts, SWAP2_SELECTOR, obric_v2_program};
 use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
@@ -6,12 +6,36 @@ use anchor_spl::token::Token;
 use anchor_spl::token_interface::TokenAccount;
 use arrayref::array_ref;
 
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext, SwapInstruction};
 
 const ARGS_LEN: usize = 25;
 
-pub struct ObricV2Processor;
-impl DexProcessor for ObricV2Processor {}
+pub struct ObricV2Adapter;
+impl DexAdapter for ObricV2Adapter {
+    fn accounts_len(&self) -> usize {
+        ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[obric_v2_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
 
 //this dex only supoort spltoken not support token_2022
 pub struct ObricV2Account<'info> {
@@ -67,21 +91,18 @@ impl<'info> ObricV2Account<'info> {
     }
 }
 
-pub fn swap<'a>(
+pub fn build_swap<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::Obric v2 amount_in: {}, offset: {}", amount_in, offset);
-    require!(remaining_accounts.len() >= *offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
-    let mut swap_accounts = ObricV2Account::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &obric_v2_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    require!(remaining_accounts.len() >= offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
+    let swap_accounts = ObricV2Account::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.trading_pair.key().log();
 
@@ -124,7 +145,7 @@ pub fn swap<'a>(
     data.extend_from_slice(SWAP2_SELECTOR);
     data.extend_from_slice(&(x_to_y as u8).to_le_bytes());
     data.extend_from_slice(&amount_in.to_le_bytes());
-    data.extend_from_slice(&1u64.to_le_bytes());
+    data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes());
 
     let accounts = vec![
         AccountMeta::new(swap_accounts.trading_pair.key(), false),
@@ -159,22 +180,12 @@ pub fn swap<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &ObricV2Processor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
 
 #[cfg(test)]
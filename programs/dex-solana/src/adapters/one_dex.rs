@@ -4,13 +4,9 @@ use arrayref::array_ref;
 
 use crate::ONE_DEX_SWAP_SELECTOR;
 use crate::error::ErrorCode;
-use crate::{
-    HopAccounts,
-    adapters::common::{before_check, invoke_process},
-    one_dex_program,
-};
+use crate::{HopAccounts, adapters::common::before_check, one_dex_program};
 
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext, SwapInstruction};
 
 const ARGS_LEN: usize = 24;
 
@@ -67,28 +63,49 @@ impl<'info> OneDexSwapAccounts<'info> {
     }
 }
 
-pub struct OneDexSwapProcessor;
-impl DexProcessor for OneDexSwapProcessor {}
+pub struct OneDexSwapAdapter;
+impl DexAdapter for OneDexSwapAdapter {
+    fn accounts_len(&self) -> usize {
+        SWAP_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[one_dex_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
 
-pub fn swap<'a>(
+pub fn build_swap<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::OneDexSwap amount_in: {}, offset: {}", amount_in, offset);
     require!(
-        remaining_accounts.len() >= *offset + SWAP_ACCOUNTS_LEN,
+        remaining_accounts.len() >= offset + SWAP_ACCOUNTS_LEN,
         ErrorCode::InvalidAccountsLength
     );
 
-    let mut swap_accounts = OneDexSwapAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &one_dex_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = OneDexSwapAccounts::parse_accounts(remaining_accounts, offset)?;
     swap_accounts.pool_auth_pubkey.key().log();
 
     before_check(
@@ -104,7 +121,7 @@ pub fn swap<'a>(
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.extend_from_slice(ONE_DEX_SWAP_SELECTOR);
     data.extend_from_slice(&amount_in.to_le_bytes());
-    data.extend_from_slice(&1u64.to_le_bytes());
+    data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes());
 
     let accounts = vec![
         AccountMeta::new_readonly(swap_accounts.metadata_state.key(), false),
@@ -136,20 +153,10 @@ pub fn swap<'a>(
     let instruction: Instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &OneDexSwapProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_info,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        SWAP_ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-    Ok(amount_out)
+        account_infos: account_info,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
@@ -1,4 +1,4 @@
-use crate::adapters::common::{before_check, invoke_process};
+use crate::adapters::common::{before_check, invoke_process, invoke_process_exact_out};
 use crate::error::ErrorCode;
 use crate::{HopAccounts, PLACE_TAKE_ORDER_SELECTOR, ZERO_ADDRESS, openbookv2_program};
 use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
@@ -7,7 +7,7 @@ use anchor_spl::token_interface::TokenAccount;
 use arrayref::array_ref;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext};
 
 const ARGS_LEN: usize = 35;
 
@@ -20,8 +20,58 @@ pub enum Side {
     Ask = 1,
 }
 
-pub struct OpenbookV2Processor;
-impl DexProcessor for OpenbookV2Processor {}
+pub struct OpenBookV2Adapter;
+impl DexAdapter for OpenBookV2Adapter {
+    fn accounts_len(&self) -> usize {
+        ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[openbookv2_program::ID] }
+    }
+
+    fn swap<'a>(&self, ctx: &mut SwapContext<'a, '_>, amount_in: u64) -> Result<u64> {
+        place_take_order(
+            ctx.remaining_accounts,
+            amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+
+    fn supports_order_book_limit(&self) -> bool {
+        true
+    }
+
+    fn leaves_lot_dust(&self) -> bool {
+        true
+    }
+
+    fn supports_exact_out(&self) -> bool {
+        true
+    }
+
+    fn swap_exact_out<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_out: u64,
+        max_amount_in: u64,
+    ) -> Result<(u64, u64)> {
+        place_take_order_exact_out(
+            ctx.remaining_accounts,
+            amount_out,
+            max_amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
 
 pub struct PlaceTakeOrderAccounts<'info> {
     pub dex_program_id: &'info AccountInfo<'info>,
@@ -112,12 +162,95 @@ pub fn place_take_order<'a>(
     owner_seeds: Option<&[&[&[u8]]]>,
 ) -> Result<u64> {
     msg!("Dex::OpenBookV2 amount_in: {}, offset: {}", amount_in, offset);
-    require!(remaining_accounts.len() >= *offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
+    let (mut swap_accounts, instruction, account_infos) = prepare_place_take_order(
+        remaining_accounts,
+        *offset,
+        hop_accounts,
+        hop,
+        proxy_swap,
+        owner_seeds,
+        amount_in,
+        None,
+    )?;
 
-    let mut swap_accounts = PlaceTakeOrderAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &openbookv2_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    // The sub-lot dust is left in the source account for the router
+    let dex_processor = &OpenBookV2Adapter;
+    let amount_out = invoke_process(
+        amount_in.checked_sub(hop_accounts.dust_amount_in).ok_or(ErrorCode::CalculationError)?,
+        dex_processor,
+        &account_infos,
+        &mut swap_accounts.swap_source_token,
+        &mut swap_accounts.swap_destination_token,
+        hop_accounts,
+        instruction,
+        hop,
+        offset,
+        ACCOUNTS_LEN,
+        proxy_swap,
+        owner_seeds,
+        0,
+    )?;
+    Ok(amount_out)
+}
+
+pub fn place_take_order_exact_out<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_out: u64,
+    max_amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+    owner_seeds: Option<&[&[&[u8]]]>,
+) -> Result<(u64, u64)> {
+    msg!(
+        "Dex::OpenBookV2 amount_out: {}, max_amount_in: {}, offset: {}",
+        amount_out,
+        max_amount_in,
+        offset
+    );
+    let (mut swap_accounts, instruction, account_infos) = prepare_place_take_order(
+        remaining_accounts,
+        *offset,
+        hop_accounts,
+        hop,
+        proxy_swap,
+        owner_seeds,
+        max_amount_in,
+        Some(amount_out),
+    )?;
+
+    let dex_processor = &OpenBookV2Adapter;
+    invoke_process_exact_out(
+        amount_out,
+        max_amount_in,
+        dex_processor,
+        &account_infos,
+        &mut swap_accounts.swap_source_token,
+        &mut swap_accounts.swap_destination_token,
+        hop_accounts,
+        instruction,
+        hop,
+        offset,
+        ACCOUNTS_LEN,
+        proxy_swap,
+        owner_seeds,
+    )
+}
+
+fn prepare_place_take_order<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    offset: usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+    owner_seeds: Option<&[&[&[u8]]]>,
+    amount_in: u64,
+    amount_out: Option<u64>,
+) -> Result<(PlaceTakeOrderAccounts<'a>, Instruction, Vec<AccountInfo<'a>>)> {
+    require!(remaining_accounts.len() >= offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
+
+    let swap_accounts = PlaceTakeOrderAccounts::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.market.key().log();
 
@@ -134,6 +267,14 @@ pub fn place_take_order<'a>(
         owner_seeds,
     )?;
 
+    // A worst price turns the take order into a limit IOC order
+    let order_book_limit = hop_accounts.order_book_limit;
+    let limit_price_lots = if order_book_limit.price != 0 {
+        Some(i64::try_from(order_book_limit.price).map_err(|_| ErrorCode::CalculationError)?)
+    } else {
+        None
+    };
+
     let (base_lot_size, quote_lot_size) = swap_accounts.get_lot_size()?;
     let side;
     let price_lots: i64;
@@ -141,24 +282,38 @@ pub fn place_take_order<'a>(
     let max_quote_lots_including_fees;
     if swap_accounts.swap_source_token.mint == swap_accounts.market_base_vault.mint {
         side = Side::Ask;
-        price_lots = 1;
+        price_lots = limit_price_lots.unwrap_or(1);
         max_base_lots = i64::try_from(amount_in)
             .unwrap()
             .checked_div(base_lot_size)
             .ok_or(ErrorCode::CalculationError)?;
-        max_quote_lots_including_fees =
-            i64::MAX.checked_div(quote_lot_size).ok_or(ErrorCode::CalculationError)?;
+        if amount_out.is_none() {
+            hop_accounts.dust_amount_in = lot_dust(amount_in, max_base_lots, base_lot_size)?;
+        }
+        // exact out: stop matching once the requested quote amount is received
+        max_quote_lots_including_fees = match amount_out {
+            Some(amount_out) => ceil_div_lots(amount_out, quote_lot_size)?,
+            None => i64::MAX.checked_div(quote_lot_size).ok_or(ErrorCode::CalculationError)?,
+        };
     } else {
         side = Side::Bid;
-        price_lots = i64::MAX;
-        max_base_lots = i64::MAX.checked_div(base_lot_size).ok_or(ErrorCode::CalculationError)?;
+        price_lots = limit_price_lots.unwrap_or(i64::MAX);
+        // exact out: stop matching once the requested base amount is received
+        max_base_lots = match amount_out {
+            Some(amount_out) => ceil_div_lots(amount_out, base_lot_size)?,
+            None => i64::MAX.checked_div(base_lot_size).ok_or(ErrorCode::CalculationError)?,
+        };
         max_quote_lots_including_fees = i64::try_from(amount_in)
             .unwrap()
             .checked_div(quote_lot_size)
             .ok_or(ErrorCode::CalculationError)?;
+        if amount_out.is_none() {
+            hop_accounts.dust_amount_in =
+                lot_dust(amount_in, max_quote_lots_including_fees, quote_lot_size)?;
+        }
     }
     let order_type = 3u8;
-    let limit = 50u8;
+    let limit = if order_book_limit.match_limit != 0 { order_book_limit.match_limit } else { 50u8 };
 
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.extend_from_slice(PLACE_TAKE_ORDER_SELECTOR);
@@ -238,22 +393,25 @@ pub fn place_take_order<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &OpenbookV2Processor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
-        instruction,
-        hop,
-        offset,
-        ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-    Ok(amount_out)
+    Ok((swap_accounts, instruction, account_infos))
+}
+
+fn ceil_div_lots(amount: u64, lot_size: i64) -> Result<i64> {
+    let lots = i64::try_from(amount)
+        .map_err(|_| ErrorCode::CalculationError)?
+        .checked_add(lot_size.checked_sub(1).ok_or(ErrorCode::CalculationError)?)
+        .ok_or(ErrorCode::CalculationError)?
+        .checked_div(lot_size)
+        .ok_or(ErrorCode::CalculationError)?;
+    Ok(lots)
+}
+
+/// Remainder of amount below one lot
+fn lot_dust(amount: u64, lots: i64, lot_size: i64) -> Result<u64> {
+    let lot_amount = lots.checked_mul(lot_size).ok_or(ErrorCode::CalculationError)?;
+    amount
+        .checked_sub(u64::try_from(lot_amount).map_err(|_| ErrorCode::CalculationError)?)
+        .ok_or(ErrorCode::CalculationError.into())
 }
 
 #[cfg(test)]
@@ -294,4 +452,11 @@ mod tests {
         let res = i64::try_from(amount_in).unwrap() / base_lot_size;
         msg!("res: {}", res);
     }
+
+    #[test]
+    pub fn test_lot_dust() {
+        assert_eq!(lot_dust(113934176, 113, 1000000).unwrap(), 934176);
+        assert_eq!(lot_dust(113000000, 113, 1000000).unwrap(), 0);
+        assert!(lot_dust(113000000, 114, 1000000).is_err());
+    }
 }
@@ -3,16 +3,75 @@ use anchor_spl::token::Token;
 use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
 use arrayref::array_ref;
 
-use crate::adapters::common::{before_check, invoke_process};
+use crate::adapters::common::before_check;
 use crate::error::ErrorCode;
 use crate::{HopAccounts, SWAP_SELECTOR, SWAP_V2_SELECTOR, ZERO_ADDRESS, pancake_swap_v3_program};
 
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext, SwapInstruction};
 
 const ARGS_LEN: usize = 41;
 
-pub struct PancakeSwapV3Processor;
-impl DexProcessor for PancakeSwapV3Processor {}
+pub struct PancakeSwapV3SwapAdapter;
+impl DexAdapter for PancakeSwapV3SwapAdapter {
+    fn accounts_len(&self) -> usize {
+        SWAP_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[pancake_swap_v3_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+
+    fn supports_sqrt_price_limit(&self) -> bool {
+        true
+    }
+}
+
+pub struct PancakeSwapV3SwapV2Adapter;
+impl DexAdapter for PancakeSwapV3SwapV2Adapter {
+    fn accounts_len(&self) -> usize {
+        SWAP_V2_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[pancake_swap_v3_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap_v2(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+
+    fn supports_sqrt_price_limit(&self) -> bool {
+        true
+    }
+}
 
 pub struct PancakeSwapV3SwapAccounts<'info> {
     pub dex_program_id: &'info AccountInfo<'info>,
@@ -142,25 +201,22 @@ impl<'info> PancakeSwapV3SwapV2Accounts<'info> {
     }
 }
 
-pub fn swap<'a>(
+pub fn build_swap<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::PancakeSwapV3Swap amount_in: {}, offset: {}", amount_in, offset);
     require!(
-        remaining_accounts.len() >= *offset + SWAP_ACCOUNTS_LEN,
+        remaining_accounts.len() >= offset + SWAP_ACCOUNTS_LEN,
         ErrorCode::InvalidAccountsLength
     );
 
-    let mut swap_accounts = PancakeSwapV3SwapAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &pancake_swap_v3_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = PancakeSwapV3SwapAccounts::parse_accounts(remaining_accounts, offset)?;
 
     // log pool address
     swap_accounts.pool_state.key().log();
@@ -179,8 +235,8 @@ pub fn swap<'a>(
     )?;
 
     let is_base_input = true;
-    let sqrt_price_limit_x64 = 0u128;
-    let other_amount_threshold = 1u64;
+    let sqrt_price_limit_x64 = hop_accounts.sqrt_price_limit;
+    let other_amount_threshold = hop_accounts.min_amount_out.max(1);
 
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.extend_from_slice(SWAP_SELECTOR);
@@ -231,45 +287,30 @@ pub fn swap<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &PancakeSwapV3Processor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        SWAP_ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
 
-pub fn swap_v2<'a>(
+pub fn build_swap_v2<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::PancakeSwapV3SwapV2 amount_in: {}, offset: {}", amount_in, offset);
     require!(
-        remaining_accounts.len() >= *offset + SWAP_V2_ACCOUNTS_LEN,
+        remaining_accounts.len() >= offset + SWAP_V2_ACCOUNTS_LEN,
         ErrorCode::InvalidAccountsLength
     );
 
-    let mut swap_accounts =
-        PancakeSwapV3SwapV2Accounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &pancake_swap_v3_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = PancakeSwapV3SwapV2Accounts::parse_accounts(remaining_accounts, offset)?;
 
     // log pool address
     swap_accounts.pool_state.key().log();
@@ -288,8 +329,8 @@ pub fn swap_v2<'a>(
     )?;
 
     let is_base_input = true;
-    let sqrt_price_limit_x64 = 0u128;
-    let other_amount_threshold = 1u64;
+    let sqrt_price_limit_x64 = hop_accounts.sqrt_price_limit;
+    let other_amount_threshold = hop_accounts.min_amount_out.max(1);
 
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.extend_from_slice(SWAP_V2_SELECTOR);
@@ -348,21 +389,10 @@ pub fn swap_v2<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &PancakeSwapV3Processor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        SWAP_V2_ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
@@ -1,6 +1,5 @@
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext};
 use crate::adapters::common::{before_check, invoke_process};
-use crate::error::ErrorCode;
 use crate::{
     HopAccounts, PERPETUALS_ADDLIQ_SELECTOR, PERPETUALS_REMOVELIQ_SELECTOR,
     PERPETUALS_SWAP_SELECTOR, perpetuals_program,
@@ -9,8 +8,54 @@ use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
 use anchor_spl::{token::Token, token_interface::TokenAccount};
 use arrayref::array_ref;
 
-pub struct PerpetualsProcessor;
-impl DexProcessor for PerpetualsProcessor {}
+pub struct PerpetualsSwapAdapter;
+impl DexAdapter for PerpetualsSwapAdapter {
+    fn accounts_len(&self) -> usize {
+        PERPETUALS_SWAP_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[perpetuals_program::ID] }
+    }
+
+    fn swap<'a>(&self, ctx: &mut SwapContext<'a, '_>, amount_in: u64) -> Result<u64> {
+        perpetuals_swap_handler(
+            ctx.remaining_accounts,
+            amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
+
+pub struct PerpetualsLiquidityAdapter {
+    pub is_add_liquidity: bool,
+}
+impl DexAdapter for PerpetualsLiquidityAdapter {
+    fn accounts_len(&self) -> usize {
+        PERPETUALS_LIQUIDITY_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[perpetuals_program::ID] }
+    }
+
+    fn swap<'a>(&self, ctx: &mut SwapContext<'a, '_>, amount_in: u64) -> Result<u64> {
+        liquidity_handler(
+            ctx.remaining_accounts,
+            amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            self.is_add_liquidity,
+            ctx.owner_seeds,
+        )
+    }
+}
 
 pub const PERPETUALS_SWAP_ACCOUNTS_LEN: usize = 17;
 
@@ -266,9 +311,6 @@ pub fn perpetuals_swap_handler<'a>(
 
     let mut swap_accounts = PerpetualsSwapAccount::parse_accounts(remaining_accounts, *offset)?;
 
-    if swap_accounts.dex_program_id.key != &perpetuals_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
     swap_accounts.perpetuals_pool.key.log();
 
     before_check(
@@ -284,7 +326,7 @@ pub fn perpetuals_swap_handler<'a>(
     let mut ix_data = Vec::with_capacity(24);
     ix_data.extend_from_slice(PERPETUALS_SWAP_SELECTOR); //discriminator
     ix_data.extend_from_slice(&amount_in.to_le_bytes()); // amount in
-    ix_data.extend_from_slice(&1u64.to_le_bytes()); // min amount out
+    ix_data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes()); // min amount out
 
     swap_accounts.dex_program_id.key().log();
 
@@ -296,7 +338,7 @@ pub fn perpetuals_swap_handler<'a>(
 
     let amount_out = invoke_process(
         amount_in,
-        &PerpetualsProcessor,
+        &PerpetualsSwapAdapter,
         &swap_accounts.get_accountinfos(),
         &mut swap_accounts.funding_account,
         &mut swap_accounts.receiving_account,
@@ -307,6 +349,7 @@ pub fn perpetuals_swap_handler<'a>(
         PERPETUALS_SWAP_ACCOUNTS_LEN,
         proxy_swap,
         owner_seeds,
+        0,
     )?;
 
     Ok(amount_out)
@@ -326,9 +369,6 @@ pub fn liquidity_handler<'a>(
     let mut handle_liquidity_accounts =
         PerpetualsAccount::parse_accounts(remaining_accounts, *offset)?;
 
-    if handle_liquidity_accounts.dex_program_id.key != &perpetuals_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
     handle_liquidity_accounts.perpetuals_pool.key.log();
 
     before_check(
@@ -359,7 +399,7 @@ pub fn liquidity_handler<'a>(
 
         invoke_process(
             amount_in,
-            &PerpetualsProcessor,
+            &PerpetualsLiquidityAdapter { is_add_liquidity },
             &handle_liquidity_accounts.get_accountinfos(),
             &mut handle_liquidity_accounts.funding_or_receiving_account,
             &mut handle_liquidity_accounts.jlp_token_account,
@@ -370,6 +410,7 @@ pub fn liquidity_handler<'a>(
             PERPETUALS_LIQUIDITY_ACCOUNTS_LEN + PEEPETUALS_REMAINING_ACCOUNTS_LEN,
             proxy_swap,
             owner_seeds,
+            0,
         )
     } else {
         ix_data = Vec::with_capacity(24);
@@ -385,7 +426,7 @@ pub fn liquidity_handler<'a>(
 
         invoke_process(
             amount_in,
-            &PerpetualsProcessor,
+            &PerpetualsLiquidityAdapter { is_add_liquidity },
             &handle_liquidity_accounts.get_accountinfos(),
             &mut handle_liquidity_accounts.jlp_token_account,
             &mut handle_liquidity_accounts.funding_or_receiving_account,
@@ -396,6 +437,7 @@ pub fn liquidity_handler<'a>(
             PERPETUALS_LIQUIDITY_ACCOUNTS_LEN + PEEPETUALS_REMAINING_ACCOUNTS_LEN,
             proxy_swap,
             owner_seeds,
+            0,
         )
     }
 }
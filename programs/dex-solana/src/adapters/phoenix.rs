@@ -1,4 +1,4 @@
-use crate::adapters::common::{before_check, invoke_process};
+use crate::adapters::common::{before_check, invoke_process, invoke_process_exact_out};
 use crate::error::ErrorCode;
 use crate::{HopAccounts, phoenix_program};
 use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
@@ -7,12 +7,62 @@ use anchor_spl::token_interface::TokenAccount;
 use arrayref::array_ref;
 use std::u64;
 
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext};
 
 const ARGS_LEN: usize = 55;
 
-pub struct PhoenixProcessor;
-impl DexProcessor for PhoenixProcessor {}
+pub struct PhoenixAdapter;
+impl DexAdapter for PhoenixAdapter {
+    fn accounts_len(&self) -> usize {
+        ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[phoenix_program::ID] }
+    }
+
+    fn swap<'a>(&self, ctx: &mut SwapContext<'a, '_>, amount_in: u64) -> Result<u64> {
+        swap(
+            ctx.remaining_accounts,
+            amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+
+    fn supports_order_book_limit(&self) -> bool {
+        true
+    }
+
+    fn leaves_lot_dust(&self) -> bool {
+        true
+    }
+
+    fn supports_exact_out(&self) -> bool {
+        true
+    }
+
+    fn swap_exact_out<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_out: u64,
+        max_amount_in: u64,
+    ) -> Result<(u64, u64)> {
+        swap_exact_out(
+            ctx.remaining_accounts,
+            amount_out,
+            max_amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
 
 pub struct SwapAccounts<'info> {
     pub dex_program_id: &'info AccountInfo<'info>,
@@ -73,12 +123,95 @@ pub fn swap<'a>(
     owner_seeds: Option<&[&[&[u8]]]>,
 ) -> Result<u64> {
     msg!("Dex::Phoenix amount_in: {}, offset: {}", amount_in, offset);
-    require!(remaining_accounts.len() >= *offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
+    let (mut swap_accounts, instruction, account_infos) = prepare_swap(
+        remaining_accounts,
+        *offset,
+        hop_accounts,
+        hop,
+        proxy_swap,
+        owner_seeds,
+        amount_in,
+        None,
+    )?;
 
-    let mut swap_accounts = SwapAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &phoenix_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    // The sub-lot dust is left in the source account for the router
+    let dex_processor = &PhoenixAdapter;
+    let amount_out = invoke_process(
+        amount_in.checked_sub(hop_accounts.dust_amount_in).ok_or(ErrorCode::CalculationError)?,
+        dex_processor,
+        &account_infos,
+        &mut swap_accounts.swap_source_token,
+        &mut swap_accounts.swap_destination_token,
+        hop_accounts,
+        instruction,
+        hop,
+        offset,
+        ACCOUNTS_LEN,
+        proxy_swap,
+        owner_seeds,
+        0,
+    )?;
+    Ok(amount_out)
+}
+
+pub fn swap_exact_out<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_out: u64,
+    max_amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+    owner_seeds: Option<&[&[&[u8]]]>,
+) -> Result<(u64, u64)> {
+    msg!(
+        "Dex::Phoenix amount_out: {}, max_amount_in: {}, offset: {}",
+        amount_out,
+        max_amount_in,
+        offset
+    );
+    let (mut swap_accounts, instruction, account_infos) = prepare_swap(
+        remaining_accounts,
+        *offset,
+        hop_accounts,
+        hop,
+        proxy_swap,
+        owner_seeds,
+        max_amount_in,
+        Some(amount_out),
+    )?;
+
+    let dex_processor = &PhoenixAdapter;
+    invoke_process_exact_out(
+        amount_out,
+        max_amount_in,
+        dex_processor,
+        &account_infos,
+        &mut swap_accounts.swap_source_token,
+        &mut swap_accounts.swap_destination_token,
+        hop_accounts,
+        instruction,
+        hop,
+        offset,
+        ACCOUNTS_LEN,
+        proxy_swap,
+        owner_seeds,
+    )
+}
+
+fn prepare_swap<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    offset: usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+    owner_seeds: Option<&[&[&[u8]]]>,
+    amount_in: u64,
+    amount_out: Option<u64>,
+) -> Result<(SwapAccounts<'a>, Instruction, Vec<AccountInfo<'a>>)> {
+    require!(remaining_accounts.len() >= offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
+
+    let swap_accounts = SwapAccounts::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.market.key().log();
 
@@ -97,28 +230,52 @@ pub fn swap<'a>(
 
     let mut data = Vec::with_capacity(ARGS_LEN);
     let (base_lot_size, quote_lot_size) = swap_accounts.get_lot_size()?;
-    let (side, num_base_lots, num_quote_lots) =
-        if swap_accounts.swap_source_token.mint == swap_accounts.base_vault.mint {
-            (1u8, amount_in.checked_div(base_lot_size).ok_or(ErrorCode::CalculationError)?, 0u64)
+    let is_ask = swap_accounts.swap_source_token.mint == swap_accounts.base_vault.mint;
+    let (side, num_base_lots, num_quote_lots) = match (is_ask, amount_out) {
         // 'ask' side
-        } else {
+        (true, None) => {
+            (1u8, amount_in.checked_div(base_lot_size).ok_or(ErrorCode::CalculationError)?, 0u64)
+        }
+        // 'bid' side
+        (false, None) => {
             (0u8, 0u64, amount_in.checked_div(quote_lot_size).ok_or(ErrorCode::CalculationError)?)
-            // 'bid' side
-        };
+        }
+        // exact out 'ask' side: sell base until the requested quote lots are received
+        (true, Some(amount_out)) => (1u8, 0u64, ceil_div_lots(amount_out, quote_lot_size)?),
+        // exact out 'bid' side: buy the requested base lots
+        (false, Some(amount_out)) => (0u8, ceil_div_lots(amount_out, base_lot_size)?, 0u64),
+    };
+    if amount_out.is_none() {
+        hop_accounts.dust_amount_in =
+            if is_ask { amount_in % base_lot_size } else { amount_in % quote_lot_size };
+    }
 
     let order_type = 2u8; // 'immediateOrCancel'
     let self_trade_behavior = 1u8; // 'cancelProvide'
 
+    // A worst price turns the market order into a limit IOC order
+    let order_book_limit = hop_accounts.order_book_limit;
+
     data.push(0); //discriminator
     data.push(order_type);
     data.push(side);
-    data.push(0); // Indicates absence of price_in_ticks (market order)
+    if order_book_limit.price != 0 {
+        data.push(1);
+        data.extend_from_slice(&order_book_limit.price.to_le_bytes()); // price_in_ticks
+    } else {
+        data.push(0); // Indicates absence of price_in_ticks (market order)
+    }
     data.extend_from_slice(&num_base_lots.to_le_bytes());
     data.extend_from_slice(&num_quote_lots.to_le_bytes());
     data.extend_from_slice(&0u64.to_le_bytes()); // min_base_lots_to_fill
     data.extend_from_slice(&0u64.to_le_bytes()); // min_quote_lots_to_fill
     data.push(self_trade_behavior);
-    data.push(0); // Indicates absence of match_limit
+    if order_book_limit.match_limit != 0 {
+        data.push(1);
+        data.extend_from_slice(&(order_book_limit.match_limit as u64).to_le_bytes()); // match_limit
+    } else {
+        data.push(0); // Indicates absence of match_limit
+    }
     data.extend_from_slice(&0u128.to_le_bytes()); // client_order_id
     data.push(0u8); // use_only_deposited_funds as false
 
@@ -162,22 +319,16 @@ pub fn swap<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &PhoenixProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
-        instruction,
-        hop,
-        offset,
-        ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-    Ok(amount_out)
+    Ok((swap_accounts, instruction, account_infos))
+}
+
+fn ceil_div_lots(amount: u64, lot_size: u64) -> Result<u64> {
+    let lots = amount
+        .checked_add(lot_size.checked_sub(1).ok_or(ErrorCode::CalculationError)?)
+        .ok_or(ErrorCode::CalculationError)?
+        .checked_div(lot_size)
+        .ok_or(ErrorCode::CalculationError)?;
+    Ok(lots)
 }
 
 #[cfg(test)]
@@ -210,4 +361,11 @@ mod tests {
         assert!(data.len() == ARGS_LEN);
         println!("constructed_data {:?}", data);
     }
+    #[test]
+    pub fn test_ceil_div_lots() {
+        assert_eq!(ceil_div_lots(1000, 100).unwrap(), 10);
+        assert_eq!(ceil_div_lots(1001, 100).unwrap(), 11);
+        assert_eq!(ceil_div_lots(0, 100).unwrap(), 0);
+        assert!(ceil_div_lots(1000, 0).is_err());
+    }
 }
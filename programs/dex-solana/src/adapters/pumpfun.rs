@@ -1,4 +1,4 @@
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext};
 use crate::adapters::common::{before_check, invoke_process};
 use crate::error::ErrorCode;
 use crate::utils::{close_token_account, log_sa_lamports_info, sync_wsol_account, transfer_sol};
@@ -99,8 +99,98 @@ pub struct PumpfunBuyAccounts3<'info> {
 }
 const BUY_ACCOUNTS_LEN3: usize = 17;
 
-pub struct PumpfunBuyProcessor3;
-impl DexProcessor for PumpfunBuyProcessor3 {
+pub struct PumpfunBuyAdapter;
+impl DexAdapter for PumpfunBuyAdapter {
+    fn accounts_len(&self) -> usize {
+        0
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        &[]
+    }
+
+    fn swap<'a>(&self, ctx: &mut SwapContext<'a, '_>, amount_in: u64) -> Result<u64> {
+        buy(
+            ctx.remaining_accounts,
+            amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
+
+pub struct PumpfunBuy2Adapter;
+impl DexAdapter for PumpfunBuy2Adapter {
+    fn accounts_len(&self) -> usize {
+        0
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        &[]
+    }
+
+    fn swap<'a>(&self, ctx: &mut SwapContext<'a, '_>, amount_in: u64) -> Result<u64> {
+        buy2(
+            ctx.remaining_accounts,
+            amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
+
+pub struct PumpfunSellAdapter;
+impl DexAdapter for PumpfunSellAdapter {
+    fn accounts_len(&self) -> usize {
+        0
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        &[]
+    }
+
+    fn swap<'a>(&self, ctx: &mut SwapContext<'a, '_>, amount_in: u64) -> Result<u64> {
+        sell(
+            ctx.remaining_accounts,
+            amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
+
+pub struct PumpfunBuy3Adapter;
+impl DexAdapter for PumpfunBuy3Adapter {
+    fn accounts_len(&self) -> usize {
+        BUY_ACCOUNTS_LEN3
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[pumpfun_program::ID] }
+    }
+
+    fn swap<'a>(&self, ctx: &mut SwapContext<'a, '_>, amount_in: u64) -> Result<u64> {
+        buy3(
+            ctx.remaining_accounts,
+            amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+            ctx.payer,
+        )
+    }
+
     fn before_invoke(&self, account_infos: &[AccountInfo]) -> Result<u64> {
         let source_token_account = account_infos.last().unwrap();
         let token_program = account_infos.get(8).unwrap();
@@ -137,6 +227,7 @@ impl DexProcessor for PumpfunBuyProcessor3 {
         _hop: usize,
         _owner_seeds: Option<&[&[&[u8]]]>,
         before_sa_authority_lamports: u64,
+        _expected_amount_out: u64,
     ) -> Result<u64> {
         if before_sa_authority_lamports > 0 {
             let payer = account_infos.get(16).unwrap();
@@ -245,9 +336,6 @@ pub fn buy3<'a>(
         ErrorCode::InvalidAccountsLength
     );
     let mut swap_accounts = PumpfunBuyAccounts3::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &pumpfun_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
     // log pool address
     swap_accounts.bonding_curve.key().log();
 
@@ -336,7 +424,7 @@ pub fn buy3<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &PumpfunBuyProcessor3;
+    let dex_processor = &PumpfunBuy3Adapter;
     let amount_out = invoke_process(
         amount_in,
         dex_processor,
@@ -350,14 +438,34 @@ pub fn buy3<'a>(
         BUY_ACCOUNTS_LEN3,
         proxy_swap,
         None,
+        0,
     )?;
     Ok(amount_out)
 }
-pub struct PumpfunSellProcessor3 {
-    pub amount: u64,
-}
+pub struct PumpfunSell3Adapter;
+
+impl DexAdapter for PumpfunSell3Adapter {
+    fn accounts_len(&self) -> usize {
+        SELL_ACCOUNTS_LEN3
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[pumpfun_program::ID] }
+    }
+
+    fn swap<'a>(&self, ctx: &mut SwapContext<'a, '_>, amount_in: u64) -> Result<u64> {
+        sell3(
+            ctx.remaining_accounts,
+            amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+            ctx.payer,
+        )
+    }
 
-impl DexProcessor for PumpfunSellProcessor3 {
     fn before_invoke(&self, account_infos: &[AccountInfo]) -> Result<u64> {
         let authority = account_infos.get(6).unwrap();
         if authority.key() == authority_pda::ID {
@@ -374,6 +482,7 @@ impl DexProcessor for PumpfunSellProcessor3 {
         hop: usize,
         owner_seeds: Option<&[&[&[u8]]]>,
         before_sa_authority_lamports: u64,
+        expected_amount_out: u64,
     ) -> Result<u64> {
         let destination_token_account = account_infos.get(14).unwrap();
         let authority = account_infos.get(6).unwrap();
@@ -390,7 +499,7 @@ impl DexProcessor for PumpfunSellProcessor3 {
         transfer_sol(
             authority.to_account_info(),
             destination_token_account.to_account_info(),
-            self.amount,
+            expected_amount_out,
             signer_seeds,
         )?;
         sync_wsol_account(
@@ -419,7 +528,7 @@ impl DexProcessor for PumpfunSellProcessor3 {
                 );
             }
         }
-        Ok(self.amount)
+        Ok(expected_amount_out)
     }
 }
 
@@ -516,9 +625,6 @@ pub fn sell3<'a>(
     );
 
     let mut swap_accounts = PumpfunSellAccounts3::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &pumpfun_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
     // log pool address
     swap_accounts.bonding_curve.key().log();
 
@@ -556,7 +662,7 @@ pub fn sell3<'a>(
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.extend_from_slice(PUMPFUN_SELL_SELECTOR);
     data.extend_from_slice(&amount_in.to_le_bytes()); // token_amount_in
-    data.extend_from_slice(&1u64.to_le_bytes()); // min_sol_amount_out
+    data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes()); // min_sol_amount_out
 
     let accounts = vec![
         AccountMeta::new_readonly(swap_accounts.global.key(), false),
@@ -596,12 +702,10 @@ pub fn sell3<'a>(
 
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
-    let dex_processor: &PumpfunSellProcessor3 =
-        &PumpfunSellProcessor3 { amount: min_sol_amount_out };
 
     let amount_out = invoke_process(
         amount_in,
-        dex_processor,
+        &PumpfunSell3Adapter,
         &account_infos,
         &mut swap_accounts.swap_source_token,
         &mut swap_accounts.swap_destination_token,
@@ -612,6 +716,7 @@ pub fn sell3<'a>(
         SELL_ACCOUNTS_LEN3,
         proxy_swap,
         owner_seeds,
+        min_sol_amount_out,
     )?;
     Ok(amount_out)
 }
@@ -1,5 +1,5 @@
-use super::common::DexProcessor;
-use crate::adapters::common::{before_check, invoke_process};
+use super::common::{DexAdapter, SwapContext, SwapInstruction};
+use crate::adapters::common::before_check;
 use crate::adapters::pumpfun::{FeeConfig, FeeTier, parse_fee_config};
 use crate::error::ErrorCode;
 use crate::utils::transfer_sol;
@@ -97,8 +97,33 @@ impl<'info> PumpfunammSellAccounts3<'info> {
         })
     }
 }
-pub struct PumpfunammSellProcessor;
-impl DexProcessor for PumpfunammSellProcessor {
+pub struct PumpfunammSell3Adapter;
+impl DexAdapter for PumpfunammSell3Adapter {
+    fn accounts_len(&self) -> usize {
+        SELL_ACCOUNTS_LEN3
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[pumpfunamm_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_sell3(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+            ctx.payer,
+        )
+    }
+
     fn before_invoke(&self, account_infos: &[AccountInfo]) -> Result<u64> {
         let authority = account_infos.get(1).unwrap();
 
@@ -116,6 +141,7 @@ impl DexProcessor for PumpfunammSellProcessor {
         _hop: usize,
         _owner_seeds: Option<&[&[&[u8]]]>,
         before_sa_authority_lamports: u64,
+        _expected_amount_out: u64,
     ) -> Result<u64> {
         let authority = account_infos.get(1).unwrap();
         let payer = account_infos.last().unwrap();
@@ -145,27 +171,24 @@ impl DexProcessor for PumpfunammSellProcessor {
     }
 }
 
-pub fn sell3<'a>(
+pub fn build_sell3<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
     payer: Option<&AccountInfo<'a>>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::Pumpfunamm amount_in: {}, offset: {}", amount_in, offset);
     require!(
-        remaining_accounts.len() >= *offset + SELL_ACCOUNTS_LEN3,
+        remaining_accounts.len() >= offset + SELL_ACCOUNTS_LEN3,
         ErrorCode::InvalidAccountsLength
     );
 
-    let mut swap_accounts: PumpfunammSellAccounts3<'_> =
-        PumpfunammSellAccounts3::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &pumpfunamm_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts: PumpfunammSellAccounts3<'_> =
+        PumpfunammSellAccounts3::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.pool.key().log();
 
@@ -184,7 +207,7 @@ pub fn sell3<'a>(
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.extend_from_slice(PUMPFUN_SELL_SELECTOR);
     data.extend_from_slice(&amount_in.to_le_bytes()); // base_amount_in
-    data.extend_from_slice(&1u64.to_le_bytes()); // min_quote_amount_out
+    data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes()); // min_quote_amount_out
 
     let accounts = vec![
         AccountMeta::new_readonly(swap_accounts.pool.key(), false),
@@ -238,23 +261,12 @@ pub fn sell3<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &PumpfunammSellProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        SELL_ACCOUNTS_LEN3,
-        proxy_swap,
-        owner_seeds,
-    )?;
-
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
 pub struct PumpfunammBuyAccounts3<'info> {
     pub dex_program_id: &'info AccountInfo<'info>,
@@ -380,28 +392,118 @@ impl<'info> PumpfunammBuyAccounts3<'info> {
         Ok(base_amount_out as u128)
     }
 }
-pub struct PumpfunammBuyProcessor;
-impl DexProcessor for PumpfunammBuyProcessor {}
+pub struct PumpfunammBuyAdapter;
+impl DexAdapter for PumpfunammBuyAdapter {
+    fn accounts_len(&self) -> usize {
+        0
+    }
 
-pub fn buy3<'a>(
+    fn program_ids(&self) -> &[Pubkey] {
+        &[]
+    }
+
+    fn swap<'a>(&self, ctx: &mut SwapContext<'a, '_>, amount_in: u64) -> Result<u64> {
+        buy(
+            ctx.remaining_accounts,
+            amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
+
+pub struct PumpfunammBuy2Adapter;
+impl DexAdapter for PumpfunammBuy2Adapter {
+    fn accounts_len(&self) -> usize {
+        0
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        &[]
+    }
+
+    fn swap<'a>(&self, ctx: &mut SwapContext<'a, '_>, amount_in: u64) -> Result<u64> {
+        buy2(
+            ctx.remaining_accounts,
+            amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
+
+pub struct PumpfunammSellAdapter;
+impl DexAdapter for PumpfunammSellAdapter {
+    fn accounts_len(&self) -> usize {
+        0
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        &[]
+    }
+
+    fn swap<'a>(&self, ctx: &mut SwapContext<'a, '_>, amount_in: u64) -> Result<u64> {
+        sell(
+            ctx.remaining_accounts,
+            amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
+
+pub struct PumpfunammBuy3Adapter;
+impl DexAdapter for PumpfunammBuy3Adapter {
+    fn accounts_len(&self) -> usize {
+        BUY_ACCOUNTS_LEN3
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[pumpfunamm_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_buy3(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
+
+pub fn build_buy3<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::Pumpfunamm amount_in: {}, offset: {}", amount_in, offset);
     require!(
-        remaining_accounts.len() >= *offset + BUY_ACCOUNTS_LEN3,
+        remaining_accounts.len() >= offset + BUY_ACCOUNTS_LEN3,
         ErrorCode::InvalidAccountsLength
     );
 
-    let mut swap_accounts = PumpfunammBuyAccounts3::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &pumpfunamm_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = PumpfunammBuyAccounts3::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.pool.key().log();
 
@@ -477,23 +579,12 @@ pub fn buy3<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &PumpfunammBuyProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        BUY_ACCOUNTS_LEN3,
-        proxy_swap,
-        owner_seeds,
-    )?;
-
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
 
 pub fn compute_fees_bps(
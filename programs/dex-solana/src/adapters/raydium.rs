@@ -1,4 +1,4 @@
-use crate::adapters::common::{before_check, invoke_process};
+use crate::adapters::common::{before_check, invoke_process_exact_out};
 use crate::error::ErrorCode;
 use crate::{
     CPSWAP_SELECTOR, HopAccounts, SWAP_SELECTOR, SWAP_V2_SELECTOR, ZERO_ADDRESS,
@@ -9,14 +9,225 @@ use anchor_spl::token::Token;
 use anchor_spl::token_interface::{Mint, Token2022, TokenAccount, TokenInterface};
 use arrayref::array_ref;
 
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext, SwapInstruction};
 
 const ARGS_LEN: usize = 17;
 const ARGS_CLMM_LEN: usize = 41;
 const ARGS_CPMM_LEN: usize = 24;
 
-pub struct RaydiumSwapProcessor;
-impl DexProcessor for RaydiumSwapProcessor {}
+pub struct RaydiumSwapAdapter;
+impl DexAdapter for RaydiumSwapAdapter {
+    fn accounts_len(&self) -> usize {
+        ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[raydium_swap_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
+
+pub struct RaydiumSwapV2Adapter;
+impl DexAdapter for RaydiumSwapV2Adapter {
+    fn accounts_len(&self) -> usize {
+        ACCOUNTS_V2_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[raydium_swap_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap_v2(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
+
+pub struct RaydiumStableSwapAdapter;
+impl DexAdapter for RaydiumStableSwapAdapter {
+    fn accounts_len(&self) -> usize {
+        STABLE_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[raydium_stable_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap_stable(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
+
+pub struct RaydiumClmmSwapAdapter;
+impl DexAdapter for RaydiumClmmSwapAdapter {
+    fn accounts_len(&self) -> usize {
+        CLMM_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[raydium_clmm_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap_clmm(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+
+    fn supports_exact_out(&self) -> bool {
+        true
+    }
+
+    fn swap_exact_out<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_out: u64,
+        max_amount_in: u64,
+    ) -> Result<(u64, u64)> {
+        swap_clmm_exact_out(
+            ctx.remaining_accounts,
+            amount_out,
+            max_amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+
+    fn supports_sqrt_price_limit(&self) -> bool {
+        true
+    }
+}
+
+pub struct RaydiumClmmSwapV2Adapter;
+impl DexAdapter for RaydiumClmmSwapV2Adapter {
+    fn accounts_len(&self) -> usize {
+        CLMM_V2_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[raydium_clmm_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap_clmm_v2(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+
+    fn supports_exact_out(&self) -> bool {
+        true
+    }
+
+    fn swap_exact_out<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_out: u64,
+        max_amount_in: u64,
+    ) -> Result<(u64, u64)> {
+        swap_clmm_v2_exact_out(
+            ctx.remaining_accounts,
+            amount_out,
+            max_amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+
+    fn supports_sqrt_price_limit(&self) -> bool {
+        true
+    }
+}
+
+pub struct RaydiumCpmmSwapAdapter;
+impl DexAdapter for RaydiumCpmmSwapAdapter {
+    fn accounts_len(&self) -> usize {
+        CPMM_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[raydium_cpmm_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap_cpmm(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
 
 pub struct RaydiumSwapAccounts<'info> {
     pub dex_program_id: &'info AccountInfo<'info>,
@@ -395,23 +606,20 @@ impl<'info> RaydiumCpmmAccounts<'info> {
     }
 }
 
-pub fn swap<'a>(
+pub fn build_swap<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     //Dex::RaydiumSwap amount_in: 100000000, offset: 0
     msg!("Dex::RaydiumSwap amount_in: {}, offset: {}", amount_in, offset);
-    require!(remaining_accounts.len() >= *offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
+    require!(remaining_accounts.len() >= offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
 
-    let mut swap_accounts = RaydiumSwapAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &raydium_swap_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = RaydiumSwapAccounts::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.amm_id.key().log();
 
@@ -431,7 +639,7 @@ pub fn swap<'a>(
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.push(9);
     data.extend_from_slice(&amount_in.to_le_bytes());
-    data.extend_from_slice(&1u64.to_le_bytes());
+    data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes());
 
     let accounts = vec![
         // spl token
@@ -482,43 +690,30 @@ pub fn swap<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &RaydiumSwapProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
 
-pub fn swap_v2<'a>(
+pub fn build_swap_v2<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::RaydiumSwapV2 amount_in: {}, offset: {}", amount_in, offset);
     require!(
-        remaining_accounts.len() >= *offset + ACCOUNTS_V2_LEN,
+        remaining_accounts.len() >= offset + ACCOUNTS_V2_LEN,
         ErrorCode::InvalidAccountsLength
     );
 
-    let mut swap_accounts = RaydiumSwapV2Accounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &raydium_swap_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = RaydiumSwapV2Accounts::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.amm_id.key().log();
 
@@ -538,7 +733,7 @@ pub fn swap_v2<'a>(
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.push(16);
     data.extend_from_slice(&amount_in.to_le_bytes());
-    data.extend_from_slice(&1u64.to_le_bytes());
+    data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes());
 
     let accounts = vec![
         AccountMeta::new_readonly(swap_accounts.token_program.key(), false),
@@ -565,43 +760,30 @@ pub fn swap_v2<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &RaydiumSwapProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        ACCOUNTS_V2_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
 
-pub fn swap_stable<'a>(
+pub fn build_swap_stable<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::RaydiumStable amount_in: {}, offset: {}", amount_in, offset);
     require!(
-        remaining_accounts.len() >= *offset + STABLE_ACCOUNTS_LEN,
+        remaining_accounts.len() >= offset + STABLE_ACCOUNTS_LEN,
         ErrorCode::InvalidAccountsLength
     );
 
-    let mut swap_accounts = RaydiumStableAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &raydium_stable_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = RaydiumStableAccounts::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.amm_id.key().log();
 
@@ -621,7 +803,7 @@ pub fn swap_stable<'a>(
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.push(9);
     data.extend_from_slice(&amount_in.to_le_bytes());
-    data.extend_from_slice(&1u64.to_le_bytes());
+    data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes());
 
     let accounts = vec![
         // spl token
@@ -672,9 +854,77 @@ pub fn swap_stable<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &RaydiumSwapProcessor;
-    let amount_out = invoke_process(
+    Ok(SwapInstruction {
+        instruction,
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
+}
+
+pub fn build_swap_clmm<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+    owner_seeds: Option<&[&[&[u8]]]>,
+) -> Result<SwapInstruction<'a>> {
+    msg!("Dex::RaydiumClmmSwap amount_in: {}, offset: {}", amount_in, offset);
+    let min_amount_out = hop_accounts.min_amount_out.max(1);
+    let (swap_accounts, instruction, account_infos) = prepare_swap_clmm(
+        remaining_accounts,
+        offset,
+        hop_accounts,
+        hop,
+        proxy_swap,
+        owner_seeds,
         amount_in,
+        min_amount_out,
+        true,
+    )?;
+
+    Ok(SwapInstruction {
+        instruction,
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
+}
+
+pub fn swap_clmm_exact_out<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_out: u64,
+    max_amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+    owner_seeds: Option<&[&[&[u8]]]>,
+) -> Result<(u64, u64)> {
+    msg!(
+        "Dex::RaydiumClmmSwap amount_out: {}, max_amount_in: {}, offset: {}",
+        amount_out,
+        max_amount_in,
+        offset
+    );
+    let (mut swap_accounts, instruction, account_infos) = prepare_swap_clmm(
+        remaining_accounts,
+        *offset,
+        hop_accounts,
+        hop,
+        proxy_swap,
+        owner_seeds,
+        amount_out,
+        max_amount_in,
+        false,
+    )?;
+
+    let dex_processor = &RaydiumClmmSwapAdapter;
+    invoke_process_exact_out(
+        amount_out,
+        max_amount_in,
         dex_processor,
         &account_infos,
         &mut swap_accounts.swap_source_token,
@@ -683,32 +933,29 @@ pub fn swap_stable<'a>(
         instruction,
         hop,
         offset,
-        STABLE_ACCOUNTS_LEN,
+        CLMM_ACCOUNTS_LEN,
         proxy_swap,
         owner_seeds,
-    )?;
-    Ok(amount_out)
+    )
 }
 
-pub fn swap_clmm<'a>(
+fn prepare_swap_clmm<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
-    amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
-    msg!("Dex::RaydiumClmmSwap amount_in: {}, offset: {}", amount_in, offset);
+    amount: u64,
+    other_amount_threshold: u64,
+    is_base_input: bool,
+) -> Result<(RaydiumClmmAccounts<'a>, Instruction, Vec<AccountInfo<'a>>)> {
     require!(
-        remaining_accounts.len() >= *offset + CLMM_ACCOUNTS_LEN,
+        remaining_accounts.len() >= offset + CLMM_ACCOUNTS_LEN,
         ErrorCode::InvalidAccountsLength
     );
 
-    let mut swap_accounts = RaydiumClmmAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &raydium_clmm_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = RaydiumClmmAccounts::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.pool_id.key().log();
 
@@ -725,13 +972,11 @@ pub fn swap_clmm<'a>(
         owner_seeds,
     )?;
 
-    let is_base_input = true;
-    let sqrt_price_limit_x64 = 0u128;
-    let other_amount_threshold = 1u64;
+    let sqrt_price_limit_x64 = hop_accounts.sqrt_price_limit;
 
     let mut data = Vec::with_capacity(ARGS_CLMM_LEN);
     data.extend_from_slice(SWAP_SELECTOR);
-    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&amount.to_le_bytes());
     data.extend_from_slice(&other_amount_threshold.to_le_bytes());
     data.extend_from_slice(&sqrt_price_limit_x64.to_le_bytes());
     data.extend_from_slice(&(is_base_input as u8).to_le_bytes());
@@ -778,9 +1023,72 @@ pub fn swap_clmm<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &RaydiumSwapProcessor;
-    let amount_out = invoke_process(
+    Ok((swap_accounts, instruction, account_infos))
+}
+
+pub fn build_swap_clmm_v2<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+    owner_seeds: Option<&[&[&[u8]]]>,
+) -> Result<SwapInstruction<'a>> {
+    msg!("Dex::RaydiumClmmSwapV2 amount_in: {}, offset: {}", amount_in, offset);
+    let min_amount_out = hop_accounts.min_amount_out.max(1);
+    let (swap_accounts, instruction, account_infos) = prepare_swap_clmm_v2(
+        remaining_accounts,
+        offset,
+        hop_accounts,
+        hop,
+        proxy_swap,
+        owner_seeds,
         amount_in,
+        min_amount_out,
+        true,
+    )?;
+
+    Ok(SwapInstruction {
+        instruction,
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
+}
+
+pub fn swap_clmm_v2_exact_out<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_out: u64,
+    max_amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+    owner_seeds: Option<&[&[&[u8]]]>,
+) -> Result<(u64, u64)> {
+    msg!(
+        "Dex::RaydiumClmmSwapV2 amount_out: {}, max_amount_in: {}, offset: {}",
+        amount_out,
+        max_amount_in,
+        offset
+    );
+    let (mut swap_accounts, instruction, account_infos) = prepare_swap_clmm_v2(
+        remaining_accounts,
+        *offset,
+        hop_accounts,
+        hop,
+        proxy_swap,
+        owner_seeds,
+        amount_out,
+        max_amount_in,
+        false,
+    )?;
+
+    let dex_processor = &RaydiumClmmSwapV2Adapter;
+    invoke_process_exact_out(
+        amount_out,
+        max_amount_in,
         dex_processor,
         &account_infos,
         &mut swap_accounts.swap_source_token,
@@ -789,32 +1097,29 @@ pub fn swap_clmm<'a>(
         instruction,
         hop,
         offset,
-        CLMM_ACCOUNTS_LEN,
+        CLMM_V2_ACCOUNTS_LEN,
         proxy_swap,
         owner_seeds,
-    )?;
-    Ok(amount_out)
+    )
 }
 
-pub fn swap_clmm_v2<'a>(
+fn prepare_swap_clmm_v2<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
-    amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
-    msg!("Dex::RaydiumClmmSwapV2 amount_in: {}, offset: {}", amount_in, offset);
+    amount: u64,
+    other_amount_threshold: u64,
+    is_base_input: bool,
+) -> Result<(RaydiumClmmV2Accounts<'a>, Instruction, Vec<AccountInfo<'a>>)> {
     require!(
-        remaining_accounts.len() >= *offset + CLMM_V2_ACCOUNTS_LEN,
+        remaining_accounts.len() >= offset + CLMM_V2_ACCOUNTS_LEN,
         ErrorCode::InvalidAccountsLength
     );
 
-    let mut swap_accounts = RaydiumClmmV2Accounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &raydium_clmm_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = RaydiumClmmV2Accounts::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.pool_id.key().log();
 
@@ -831,13 +1136,11 @@ pub fn swap_clmm_v2<'a>(
         owner_seeds,
     )?;
 
-    let is_base_input = true;
-    let sqrt_price_limit_x64 = 0u128;
-    let other_amount_threshold = 1u64;
+    let sqrt_price_limit_x64 = hop_accounts.sqrt_price_limit;
 
     let mut data = Vec::with_capacity(ARGS_CLMM_LEN);
     data.extend_from_slice(SWAP_V2_SELECTOR);
-    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&amount.to_le_bytes());
     data.extend_from_slice(&other_amount_threshold.to_le_bytes());
     data.extend_from_slice(&sqrt_price_limit_x64.to_le_bytes());
     data.extend_from_slice(&(is_base_input as u8).to_le_bytes());
@@ -892,44 +1195,26 @@ pub fn swap_clmm_v2<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &RaydiumSwapProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
-        instruction,
-        hop,
-        offset,
-        CLMM_V2_ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-    Ok(amount_out)
+    Ok((swap_accounts, instruction, account_infos))
 }
 
-pub fn swap_cpmm<'a>(
+pub fn build_swap_cpmm<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::RaydiumCpmmSwap amount_in: {}, offset: {}", amount_in, offset);
 
     require!(
-        remaining_accounts.len() >= *offset + CPMM_ACCOUNTS_LEN,
+        remaining_accounts.len() >= offset + CPMM_ACCOUNTS_LEN,
         ErrorCode::InvalidAccountsLength
     );
 
-    let mut swap_accounts = RaydiumCpmmAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &raydium_cpmm_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = RaydiumCpmmAccounts::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.pool_state.key().log();
 
@@ -946,7 +1231,7 @@ pub fn swap_cpmm<'a>(
         owner_seeds,
     )?;
 
-    let minimum_amount_out = 0u64;
+    let minimum_amount_out = hop_accounts.min_amount_out;
     let mut data = Vec::with_capacity(ARGS_CPMM_LEN);
     data.extend_from_slice(CPSWAP_SELECTOR);
     data.extend_from_slice(&amount_in.to_le_bytes());
@@ -987,22 +1272,12 @@ pub fn swap_cpmm<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &RaydiumSwapProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        CPMM_ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
 
 #[cfg(test)]
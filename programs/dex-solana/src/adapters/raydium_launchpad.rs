@@ -1,4 +1,4 @@
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext};
 use crate::adapters::common::{before_check, invoke_process};
 use crate::error::ErrorCode;
 use crate::{
@@ -9,8 +9,31 @@ use anchor_lang::solana_program::instruction::Instruction;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use arrayref::array_ref;
 
-pub struct LaunchpadProcessor;
-impl DexProcessor for LaunchpadProcessor {}
+pub struct LaunchpadAdapter {
+    pub platform_name: &'static str,
+}
+impl DexAdapter for LaunchpadAdapter {
+    fn accounts_len(&self) -> usize {
+        LAUNCHPAD_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[raydium_launchpad_program::ID] }
+    }
+
+    fn swap<'a>(&self, ctx: &mut SwapContext<'a, '_>, amount_in: u64) -> Result<u64> {
+        launchpad_handler(
+            ctx.remaining_accounts,
+            amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+            self.platform_name,
+        )
+    }
+}
 
 const LAUNCHPAD_ACCOUNTS_LEN: usize = 18;
 pub struct LaunchpadAccounts<'info> {
@@ -90,17 +113,13 @@ pub fn launchpad_handler<'a>(
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-    platform_name: &str,
+    platform_name: &'static str,
 ) -> Result<u64> {
     require!(
         remaining_accounts.len() >= *offset + LAUNCHPAD_ACCOUNTS_LEN,
         ErrorCode::InvalidAccountsLength
     );
     let mut swap_accounts = LaunchpadAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &raydium_launchpad_program::id() {
-        swap_accounts.dex_program_id.key().log();
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
 
     msg!("Dex::{} amount_in: {}, offset: {}", platform_name, amount_in, offset);
     swap_accounts.pool_state.key().log();
@@ -130,7 +149,7 @@ pub fn launchpad_handler<'a>(
         data.extend_from_slice(SELL_EXACT_IN_SELECTOR);
     }
     data.extend_from_slice(&amount_in.to_le_bytes());
-    data.extend_from_slice(&1u64.to_le_bytes()); //minimum_amount_out
+    data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes()); //minimum_amount_out
     data.extend_from_slice(&0u64.to_le_bytes()); //share_fee_rate
 
     let mut accounts = Vec::with_capacity(LAUNCHPAD_ACCOUNTS_LEN);
@@ -176,7 +195,7 @@ pub fn launchpad_handler<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &LaunchpadProcessor;
+    let dex_processor = &LaunchpadAdapter { platform_name };
     let amount_out = invoke_process(
         amount_in,
         dex_processor,
@@ -190,6 +209,7 @@ pub fn launchpad_handler<'a>(
         LAUNCHPAD_ACCOUNTS_LEN,
         proxy_swap,
         owner_seeds,
+        0,
     )?;
     Ok(amount_out)
 }
@@ -0,0 +1,180 @@
+use super::common::DexAdapter;
+use super::*;
+use crate::Dex;
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+
+/// Look up the adapter registered for a dex
+pub fn get_adapter(dex: &Dex) -> &'static dyn DexAdapter {
+    match dex {
+        Dex::SplTokenSwap => &spl_token_swap::SplTokenSwapAdapter,
+        Dex::StableSwap => &stable_swap::StableSwapAdapter,
+        Dex::Whirlpool => &whirlpool::WhirlpoolAdapter,
+        Dex::MeteoraDynamicpool => &meteora::MeteoraDynamicpoolAdapter,
+        Dex::RaydiumSwap => &raydium::RaydiumSwapAdapter,
+        Dex::RaydiumStableSwap => &raydium::RaydiumStableSwapAdapter,
+        Dex::RaydiumClmmSwap => &raydium::RaydiumClmmSwapAdapter,
+        Dex::AldrinExchangeV1 => &aldrin::AldrinExchangeV1Adapter,
+        Dex::AldrinExchangeV2 => &aldrin::AldrinExchangeV2Adapter,
+        Dex::LifinityV1 => &lifinity::LifinityV1Adapter,
+        Dex::LifinityV2 => &lifinity::LifinityV2Adapter,
+        Dex::RaydiumClmmSwapV2 => &raydium::RaydiumClmmSwapV2Adapter,
+        Dex::FluxBeam => &fluxbeam::FluxBeamAdapter,
+        Dex::MeteoraDlmm => &meteora::MeteoraDlmmAdapter,
+        Dex::RaydiumCpmmSwap => &raydium::RaydiumCpmmSwapAdapter,
+        Dex::OpenBookV2 => &openbookv2::OpenBookV2Adapter,
+        Dex::WhirlpoolV2 => &whirlpool::WhirlpoolV2Adapter,
+        Dex::Phoenix => &phoenix::PhoenixAdapter,
+        Dex::ObricV2 => &obric_v2::ObricV2Adapter,
+        Dex::SanctumAddLiq => &sanctum::SanctumAddLiqAdapter,
+        Dex::SanctumRemoveLiq => &sanctum::SanctumRemoveLiqAdapter,
+        Dex::SanctumNonWsolSwap => &sanctum::SanctumNonWsolSwapAdapter,
+        Dex::SanctumWsolSwap => &sanctum::SanctumWsolSwapAdapter,
+        Dex::PumpfunBuy => &pumpfun::PumpfunBuyAdapter,
+        Dex::PumpfunSell => &pumpfun::PumpfunSellAdapter,
+        Dex::StabbleSwap => &stabble::StabbleSwapAdapter,
+        Dex::SanctumRouter => &sanctum_router::SanctumRouterAdapter,
+        Dex::MeteoraVaultDeposit => &meteora::MeteoraVaultDepositAdapter,
+        Dex::MeteoraVaultWithdraw => &meteora::MeteoraVaultWithdrawAdapter,
+        Dex::Saros => &saros::SarosAdapter,
+        Dex::MeteoraLst => &meteora::MeteoraLstAdapter,
+        Dex::Solfi => &solfi::SolfiAdapter,
+        Dex::QualiaSwap => &qualia::QualiaSwapAdapter,
+        Dex::Zerofi => &zerofi::ZerofiAdapter,
+        Dex::PumpfunammBuy => &pumpfunamm::PumpfunammBuyAdapter,
+        Dex::PumpfunammSell => &pumpfunamm::PumpfunammSellAdapter,
+        Dex::Virtuals => &virtuals::VirtualsAdapter,
+        Dex::VertigoBuy => &vertigo::VertigoBuyAdapter,
+        Dex::VertigoSell => &vertigo::VertigoSellAdapter,
+        Dex::PerpetualsAddLiq => &perpetuals::PerpetualsLiquidityAdapter { is_add_liquidity: true },
+        Dex::PerpetualsRemoveLiq => {
+            &perpetuals::PerpetualsLiquidityAdapter { is_add_liquidity: false }
+        }
+        Dex::PerpetualsSwap => &perpetuals::PerpetualsSwapAdapter,
+        Dex::RaydiumLaunchpad => {
+            &raydium_launchpad::LaunchpadAdapter { platform_name: "RaydiumLaunchpad" }
+        }
+        Dex::LetsBonkFun => &raydium_launchpad::LaunchpadAdapter { platform_name: "LetsBonkFun" },
+        Dex::Woofi => &woofi::WoofiAdapter,
+        Dex::MeteoraDbc => &meteora_dbc::MeteoraDbcAdapter,
+        Dex::MeteoraDlmmSwap2 => &meteora::MeteoraDlmmSwap2Adapter,
+        Dex::MeteoraDAMMV2 => &meteora::MeteoraDAMMV2Adapter,
+        Dex::Gavel => &gavel::GavelAdapter,
+        Dex::BoopfunBuy => &boopfun::BoopfunBuyAdapter,
+        Dex::BoopfunSell => &boopfun::BoopfunSellAdapter,
+        Dex::MeteoraDbc2 => &meteora_dbc::MeteoraDbc2Adapter,
+        Dex::GooseFX => &goosefx::GooseFXAdapter,
+        Dex::Dooar => &dooar::DooarAdapter,
+        Dex::Numeraire => &numeraire::NumeraireAdapter,
+        Dex::SaberDecimalWrapperDeposit => {
+            &saber_decimal_wrapper::SaberDecimalWrapperDepositAdapter
+        }
+        Dex::SaberDecimalWrapperWithdraw => {
+            &saber_decimal_wrapper::SaberDecimalWrapperWithdrawAdapter
+        }
+        Dex::SarosDlmm => &saros::SarosDlmmAdapter,
+        Dex::OneDexSwap => &one_dex::OneDexSwapAdapter,
+        Dex::Manifest => &manifest::ManifestAdapter,
+        Dex::ByrealClmm => &byreal_clmm::ByrealClmmAdapter,
+        Dex::PancakeSwapV3Swap => &pancake_swap_v3::PancakeSwapV3SwapAdapter,
+        Dex::PancakeSwapV3SwapV2 => &pancake_swap_v3::PancakeSwapV3SwapV2Adapter,
+        Dex::Tessera => &tessera::TesseraAdapter,
+        Dex::SolRfq { .. } => &sol_rfq::SolRfqAdapter,
+        Dex::PumpfunBuy2 => &pumpfun::PumpfunBuy2Adapter,
+        Dex::PumpfunammBuy2 => &pumpfunamm::PumpfunammBuy2Adapter,
+        Dex::Humidifi => &humidifi::HumidifiAdapter,
+        Dex::HeavenBuy => &heaven::HeavenAdapter { is_buy: true },
+        Dex::HeavenSell => &heaven::HeavenAdapter { is_buy: false },
+        Dex::SolfiV2 => &solfi::SolfiV2Adapter,
+        Dex::PumpfunBuy3 => &pumpfun::PumpfunBuy3Adapter,
+        Dex::PumpfunSell3 => &pumpfun::PumpfunSell3Adapter,
+        Dex::PumpfunammBuy3 => &pumpfunamm::PumpfunammBuy3Adapter,
+        Dex::PumpfunammSell3 => &pumpfunamm::PumpfunammSell3Adapter,
+        Dex::Goonfi => &goonfi::GoonfiAdapter,
+        Dex::MoonitBuy => &moonit::MoonitBuyAdapter,
+        Dex::MoonitSell => &moonit::MoonitSellAdapter,
+        Dex::RaydiumSwapV2 => &raydium::RaydiumSwapV2Adapter,
+        Dex::Swaap => &swaap::SwaapAdapter,
+        Dex::SugarMoneyBuy { .. } => &sugar_money::SugarMoneyBuyAdapter,
+        Dex::SugarMoneySell { .. } => &sugar_money::SugarMoneySellAdapter,
+        Dex::MeteoraDAMMV2Swap2 => &meteora::MeteoraDAMMV2Swap2Adapter,
+    }
+}
+
+/// Check that the remaining accounts hold the adapter's accounts at `offset`, starting with one
+/// of its programs. Adapters rely on this and do not check the program id themselves.
+pub fn check_accounts(
+    adapter: &dyn DexAdapter,
+    remaining_accounts: &[AccountInfo],
+    offset: usize,
+) -> Result<()> {
+    let accounts_len = adapter.accounts_len();
+    require!(remaining_accounts.len() >= offset + accounts_len, ErrorCode::InvalidAccountsLength);
+    let program_ids = adapter.program_ids();
+    if accounts_len > 0 && !program_ids.is_empty() {
+        require!(program_ids.contains(remaining_accounts[offset].key), ErrorCode::InvalidProgramId);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{raydium_clmm_program, whirlpool_program};
+
+    #[test]
+    pub fn test_get_adapter() {
+        let whirlpool = get_adapter(&Dex::Whirlpool);
+        assert!(whirlpool.supports_exact_out());
+        assert!(whirlpool.supports_sqrt_price_limit());
+        assert_eq!(whirlpool.program_ids(), &[whirlpool_program::ID]);
+
+        let raydium_clmm = get_adapter(&Dex::RaydiumClmmSwapV2);
+        assert!(raydium_clmm.supports_exact_out());
+        assert_eq!(raydium_clmm.program_ids(), &[raydium_clmm_program::ID]);
+
+        let phoenix = get_adapter(&Dex::Phoenix);
+        assert!(phoenix.supports_exact_out());
+        assert!(!phoenix.supports_sqrt_price_limit());
+
+        let raydium = get_adapter(&Dex::RaydiumSwap);
+        assert!(!raydium.supports_exact_out());
+        assert!(!raydium.supports_sqrt_price_limit());
+        assert_eq!(raydium.accounts_len(), 19);
+
+        let sugar_money = get_adapter(&Dex::SugarMoneyBuy {
+            bonding_curve_bump: 255,
+            bonding_curve_sol_associated_account_bump: 254,
+        });
+        assert_eq!(sugar_money.accounts_len(), 15);
+    }
+
+    #[test]
+    pub fn test_check_accounts() {
+        let adapter = get_adapter(&Dex::Whirlpool);
+        let len = adapter.accounts_len();
+        let mut keys: Vec<Pubkey> = (0..=len).map(|_| Pubkey::new_unique()).collect();
+        keys[1] = whirlpool_program::ID;
+        let owner = Pubkey::default();
+        let mut lamports = vec![0u64; keys.len()];
+        let mut data = vec![[0u8; 0]; keys.len()];
+        let accounts: Vec<AccountInfo> = keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|((key, lamports), data)| {
+                AccountInfo::new(key, false, false, lamports, data, &owner, false, 0)
+            })
+            .collect();
+
+        assert!(check_accounts(adapter, &accounts, 1).is_ok());
+        assert_eq!(
+            check_accounts(adapter, &accounts, 0).unwrap_err(),
+            ErrorCode::InvalidProgramId.into()
+        );
+        assert_eq!(
+            check_accounts(adapter, &accounts, 2).unwrap_err(),
+            ErrorCode::InvalidAccountsLength.into()
+        );
+    }
+}
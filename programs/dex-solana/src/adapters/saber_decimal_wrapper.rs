@@ -8,11 +8,58 @@ use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
 use anchor_spl::token_interface::TokenAccount;
 use arrayref::array_ref;
 
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext, SwapInstruction};
 
 const ARGS_LEN: usize = 16;
-pub struct SaberDecimalProcessor;
-impl DexProcessor for SaberDecimalProcessor {}
+pub struct SaberDecimalWrapperDepositAdapter;
+impl DexAdapter for SaberDecimalWrapperDepositAdapter {
+    fn accounts_len(&self) -> usize {
+        ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[saber_decimal_wrapper_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_deposit(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
+
+pub struct SaberDecimalWrapperWithdrawAdapter;
+impl DexAdapter for SaberDecimalWrapperWithdrawAdapter {
+    fn accounts_len(&self) -> usize {
+        ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[saber_decimal_wrapper_program::ID] }
+    }
+
+    fn swap<'a>(&self, ctx: &mut SwapContext<'a, '_>, amount_in: u64) -> Result<u64> {
+        withdraw(
+            ctx.remaining_accounts,
+            amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
 pub struct SaberDecimalWrapperAccounts<'info> {
     pub dex_program_id: &'info AccountInfo<'info>,
     pub swap_authority_pubkey: &'info AccountInfo<'info>,
@@ -62,22 +109,18 @@ impl<'info> SaberDecimalWrapperAccounts<'info> {
     }
 }
 
-pub fn deposit<'a>(
+pub fn build_deposit<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::SaberDecimalWrapperDeposit amount_in: {}, offset: {}", amount_in, offset);
-    require!(remaining_accounts.len() >= *offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
-    let mut swap_accounts =
-        SaberDecimalWrapperAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &saber_decimal_wrapper_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    require!(remaining_accounts.len() >= offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
+    let swap_accounts = SaberDecimalWrapperAccounts::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.wrapper.key().log();
 
@@ -104,7 +147,7 @@ pub fn deposit<'a>(
         AccountMeta::new_readonly(swap_accounts.token_program.key(), false),
     ];
 
-    let account_info = [
+    let account_info = vec![
         swap_accounts.wrapper.to_account_info(),
         swap_accounts.wrapper_mint.to_account_info(),
         swap_accounts.wrapper_underlying_tokens.to_account_info(),
@@ -121,23 +164,12 @@ pub fn deposit<'a>(
     let instruction =
         Instruction { program_id: *swap_accounts.dex_program_id.key, accounts: account_meta, data };
 
-    let dex_processor = SaberDecimalProcessor {};
-    let amount_out = invoke_process(
-        amount_in,
-        &dex_processor,
-        &account_info,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-
-    Ok(amount_out)
+        account_infos: account_info,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
 
 pub fn withdraw<'a>(
@@ -153,9 +185,6 @@ pub fn withdraw<'a>(
     require!(remaining_accounts.len() >= *offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
     let mut swap_accounts =
         SaberDecimalWrapperAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &saber_decimal_wrapper_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
     // log pool address
     swap_accounts.wrapper.key().log();
 
@@ -199,7 +228,7 @@ pub fn withdraw<'a>(
     let instruction =
         Instruction { program_id: *swap_accounts.dex_program_id.key, accounts: account_meta, data };
 
-    let dex_processor = SaberDecimalProcessor {};
+    let dex_processor = SaberDecimalWrapperWithdrawAdapter;
     invoke_process(
         amount_in,
         &dex_processor,
@@ -213,6 +242,7 @@ pub fn withdraw<'a>(
         ACCOUNTS_LEN,
         proxy_swap,
         owner_seeds,
+        0,
     )?;
 
     Ok(amount_in)
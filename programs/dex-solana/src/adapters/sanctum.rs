@@ -7,10 +7,99 @@ use anchor_spl::token_interface::{Mint, TokenAccount};
 use arrayref::array_ref;
 use bytemuck::{AnyBitPattern, Pod, Zeroable};
 
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext};
 
-pub struct SanctumProcessor;
-impl DexProcessor for SanctumProcessor {}
+pub struct SanctumAddLiqAdapter;
+impl DexAdapter for SanctumAddLiqAdapter {
+    fn accounts_len(&self) -> usize {
+        ADD_WSOL_LIQ_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[sanctum_program::ID] }
+    }
+
+    fn swap<'a>(&self, ctx: &mut SwapContext<'a, '_>, amount_in: u64) -> Result<u64> {
+        add_liquidity_handler(
+            ctx.remaining_accounts,
+            amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
+
+pub struct SanctumRemoveLiqAdapter;
+impl DexAdapter for SanctumRemoveLiqAdapter {
+    fn accounts_len(&self) -> usize {
+        REMOVE_WSOL_LIQ_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[sanctum_program::ID] }
+    }
+
+    fn swap<'a>(&self, ctx: &mut SwapContext<'a, '_>, amount_in: u64) -> Result<u64> {
+        remove_liquidity_handler(
+            ctx.remaining_accounts,
+            amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
+
+pub struct SanctumWsolSwapAdapter;
+impl DexAdapter for SanctumWsolSwapAdapter {
+    fn accounts_len(&self) -> usize {
+        SWAP_LST_SOL_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[sanctum_program::ID] }
+    }
+
+    fn swap<'a>(&self, ctx: &mut SwapContext<'a, '_>, amount_in: u64) -> Result<u64> {
+        swap_with_wsol_handler(
+            ctx.remaining_accounts,
+            amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
+
+pub struct SanctumNonWsolSwapAdapter;
+impl DexAdapter for SanctumNonWsolSwapAdapter {
+    fn accounts_len(&self) -> usize {
+        SWAP_LST_LST_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[sanctum_program::ID] }
+    }
+
+    fn swap<'a>(&self, ctx: &mut SwapContext<'a, '_>, amount_in: u64) -> Result<u64> {
+        swap_without_wsol_handler(
+            ctx.remaining_accounts,
+            amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
 
 const ADD_LST_LIQ_ACCOUNTS_LEN: usize = 18;
 const ADD_WSOL_LIQ_ACCOUNTS_LEN: usize = 14;
@@ -639,7 +728,7 @@ pub fn add_liquidity_handler<'a>(
             let account_infos = swap_accounts.get_account_infos();
             let (source_token_account, dst_token_account) = swap_accounts.get_token_accounts_mut();
 
-            let dex_processor = &SanctumProcessor;
+            let dex_processor = &SanctumAddLiqAdapter;
             invoke_process(
                 amount_in,
                 dex_processor,
@@ -653,6 +742,7 @@ pub fn add_liquidity_handler<'a>(
                 ADD_WSOL_LIQ_ACCOUNTS_LEN,
                 proxy_swap,
                 owner_seeds,
+                0,
             )?
         }
         SanctumAddLiqAccounts::LST(mut swap_accounts) => {
@@ -673,7 +763,7 @@ pub fn add_liquidity_handler<'a>(
             let account_infos = swap_accounts.get_account_infos();
             let (source_token_account, dst_token_account) = swap_accounts.get_token_accounts_mut();
 
-            let dex_processor = &SanctumProcessor;
+            let dex_processor = &SanctumAddLiqAdapter;
             invoke_process(
                 amount_in,
                 dex_processor,
@@ -687,6 +777,7 @@ pub fn add_liquidity_handler<'a>(
                 ADD_LST_LIQ_ACCOUNTS_LEN,
                 proxy_swap,
                 owner_seeds,
+                0,
             )?
         }
     };
@@ -727,7 +818,7 @@ pub fn remove_liquidity_handler<'a>(
             let account_infos = swap_accounts.get_account_infos();
             let (source_token_account, dst_token_account) = swap_accounts.get_token_accounts_mut();
 
-            let dex_processor = &SanctumProcessor;
+            let dex_processor = &SanctumRemoveLiqAdapter;
             invoke_process(
                 amount_in,
                 dex_processor,
@@ -741,6 +832,7 @@ pub fn remove_liquidity_handler<'a>(
                 REMOVE_WSOL_LIQ_ACCOUNTS_LEN,
                 proxy_swap,
                 owner_seeds,
+                0,
             )?
         }
         SanctumRemoveLiqAccounts::LST(mut swap_accounts) => {
@@ -761,7 +853,7 @@ pub fn remove_liquidity_handler<'a>(
             let accounts = swap_accounts.get_accountmetas();
             let account_infos = swap_accounts.get_account_infos();
             let (source_token_account, dst_token_account) = swap_accounts.get_token_accounts_mut();
-            let dex_processor = &SanctumProcessor;
+            let dex_processor = &SanctumRemoveLiqAdapter;
             invoke_process(
                 amount_in,
                 dex_processor,
@@ -775,6 +867,7 @@ pub fn remove_liquidity_handler<'a>(
                 REMOVE_LST_LIQ_ACCOUNTS_LEN,
                 proxy_swap,
                 owner_seeds,
+                0,
             )?
         }
     };
@@ -902,10 +995,6 @@ fn handle_prechecks<'info>(
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
 ) -> Result<()> {
-    if swap_accounts.dex_program_id().key != &sanctum_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
-
     before_check(
         swap_accounts.swap_authority_pubkey(),
         &swap_accounts.source_token_account(),
@@ -920,7 +1009,6 @@ fn handle_prechecks<'info>(
 }
 
 trait CommonAccountInfo<'info> {
-    fn dex_program_id(&self) -> &AccountInfo<'info>;
     fn swap_authority_pubkey(&self) -> &AccountInfo<'info>;
     fn source_token_account(&self) -> &Box<InterfaceAccount<'info, TokenAccount>>;
     fn dst_token_account(&self) -> &Box<InterfaceAccount<'info, TokenAccount>>;
@@ -935,9 +1023,6 @@ trait CommonAccountInfo<'info> {
 }
 
 impl<'info> CommonAccountInfo<'info> for SanctumAddLstLiqAccounts<'info> {
-    fn dex_program_id(&self) -> &AccountInfo<'info> {
-        &self.dex_program_id
-    }
     fn swap_authority_pubkey(&self) -> &AccountInfo<'info> {
         &self.swap_authority_pubkey
     }
@@ -1000,9 +1085,6 @@ impl<'info> CommonAccountInfo<'info> for SanctumAddLstLiqAccounts<'info> {
 }
 
 impl<'info> CommonAccountInfo<'info> for SanctumAddWsolLiqAccounts<'info> {
-    fn dex_program_id(&self) -> &AccountInfo<'info> {
-        &self.dex_program_id
-    }
     fn swap_authority_pubkey(&self) -> &AccountInfo<'info> {
         &self.swap_authority_pubkey
     }
@@ -1057,9 +1139,6 @@ impl<'info> CommonAccountInfo<'info> for SanctumAddWsolLiqAccounts<'info> {
 }
 
 impl<'info> CommonAccountInfo<'info> for SanctumRemoveWsolLiqAccounts<'info> {
-    fn dex_program_id(&self) -> &AccountInfo<'info> {
-        &self.dex_program_id
-    }
     fn swap_authority_pubkey(&self) -> &AccountInfo<'info> {
         &self.swap_authority_pubkey
     }
@@ -1116,9 +1195,6 @@ impl<'info> CommonAccountInfo<'info> for SanctumRemoveWsolLiqAccounts<'info> {
 }
 
 impl<'info> CommonAccountInfo<'info> for SanctumRemoveLstLiqAccounts<'info> {
-    fn dex_program_id(&self) -> &AccountInfo<'info> {
-        &self.dex_program_id
-    }
     fn swap_authority_pubkey(&self) -> &AccountInfo<'info> {
         &self.swap_authority_pubkey
     }
@@ -1183,9 +1259,6 @@ impl<'info> CommonAccountInfo<'info> for SanctumRemoveLstLiqAccounts<'info> {
 }
 
 impl<'info> CommonAccountInfo<'info> for SanctumWsolLstSwapAccounts<'info> {
-    fn dex_program_id(&self) -> &AccountInfo<'info> {
-        &self.dex_program_id
-    }
     fn swap_authority_pubkey(&self) -> &AccountInfo<'info> {
         &self.swap_authority_pubkey
     }
@@ -1256,9 +1329,6 @@ impl<'info> CommonAccountInfo<'info> for SanctumWsolLstSwapAccounts<'info> {
 }
 
 impl<'info> CommonAccountInfo<'info> for SanctumLstWsolSwapAccounts<'info> {
-    fn dex_program_id(&self) -> &AccountInfo<'info> {
-        &self.dex_program_id
-    }
     fn swap_authority_pubkey(&self) -> &AccountInfo<'info> {
         &self.swap_authority_pubkey
     }
@@ -1329,9 +1399,6 @@ impl<'info> CommonAccountInfo<'info> for SanctumLstWsolSwapAccounts<'info> {
 }
 
 impl<'info> CommonAccountInfo<'info> for SanctumLstLstSwapAccounts<'info> {
-    fn dex_program_id(&self) -> &AccountInfo<'info> {
-        &self.dex_program_id
-    }
     fn swap_authority_pubkey(&self) -> &AccountInfo<'info> {
         &self.swap_authority_pubkey
     }
@@ -1435,14 +1502,14 @@ fn handle_lst_lst_swap<'info>(
     ix_data.extend_from_slice(&5u8.to_le_bytes()); //dst_lst_value_calc_accs
     ix_data.extend_from_slice(&src_lst_index.to_le_bytes()); //src_lst_index
     ix_data.extend_from_slice(&dst_lst_index.to_le_bytes()); //dst_lst_index
-    ix_data.extend_from_slice(&1u64.to_le_bytes()); //min_amount_out
+    ix_data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes()); //min_amount_out
     ix_data.extend_from_slice(&amount_in.to_le_bytes()); //lst_amount
 
     let accounts = swap_accounts.get_accountmetas();
     let account_infos = swap_accounts.get_account_infos();
     let (source_token_account, dst_token_account) = swap_accounts.get_token_accounts_mut();
 
-    let dex_processor = &SanctumProcessor;
+    let dex_processor = &SanctumNonWsolSwapAdapter;
     invoke_process(
         amount_in,
         dex_processor,
@@ -1456,6 +1523,7 @@ fn handle_lst_lst_swap<'info>(
         SWAP_LST_LST_ACCOUNTS_LEN,
         proxy_swap,
         owner_seeds,
+        0,
     )
 }
 
@@ -1482,14 +1550,14 @@ fn handle_wsol_lst_swap<'info>(
     ix_data.extend_from_slice(&5u8.to_le_bytes()); //dst_lst_value_calc_accs
     ix_data.extend_from_slice(&1u32.to_le_bytes()); //src_lst_index
     ix_data.extend_from_slice(&lst_index.to_le_bytes()); //dst_lst_index
-    ix_data.extend_from_slice(&1u64.to_le_bytes()); //min_amount_out
+    ix_data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes()); //min_amount_out
     ix_data.extend_from_slice(&amount_in.to_le_bytes()); //lst_amount
 
     let accounts = swap_accounts.get_accountmetas();
     let account_infos = swap_accounts.get_account_infos();
     let (source_token_account, dst_token_account) = swap_accounts.get_token_accounts_mut();
 
-    let dex_processor = &SanctumProcessor;
+    let dex_processor = &SanctumWsolSwapAdapter;
     invoke_process(
         amount_in,
         dex_processor,
@@ -1503,6 +1571,7 @@ fn handle_wsol_lst_swap<'info>(
         SWAP_LST_SOL_ACCOUNTS_LEN,
         proxy_swap,
         owner_seeds,
+        0,
     )
 }
 
@@ -1529,14 +1598,14 @@ fn handle_lst_wsol_swap<'info>(
     ix_data.extend_from_slice(&1u8.to_le_bytes()); //dst_lst_value_calc_accs
     ix_data.extend_from_slice(&lst_index.to_le_bytes()); //src_lst_index
     ix_data.extend_from_slice(&1u32.to_le_bytes()); //dst_lst_index
-    ix_data.extend_from_slice(&1u64.to_le_bytes()); //min_amount_out
+    ix_data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes()); //min_amount_out
     ix_data.extend_from_slice(&amount_in.to_le_bytes()); //lst_amount
 
     let accounts = swap_accounts.get_accountmetas();
     let account_infos = swap_accounts.get_account_infos();
     let (source_token_account, dst_token_account) = swap_accounts.get_token_accounts_mut();
 
-    let dex_processor = &SanctumProcessor;
+    let dex_processor = &SanctumWsolSwapAdapter;
     invoke_process(
         amount_in,
         dex_processor,
@@ -1550,5 +1619,6 @@ fn handle_lst_wsol_swap<'info>(
         SWAP_LST_SOL_ACCOUNTS_LEN,
         proxy_swap,
         owner_seeds,
+        0,
     )
 }
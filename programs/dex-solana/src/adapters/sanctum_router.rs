@@ -5,9 +5,30 @@ use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use arrayref::array_ref;
 
-use super::common::DexProcessor;
-pub struct SanctumRouterProcessor;
-impl DexProcessor for SanctumRouterProcessor {}
+use super::common::{DexAdapter, SwapContext};
+pub struct SanctumRouterAdapter;
+impl DexAdapter for SanctumRouterAdapter {
+    fn accounts_len(&self) -> usize {
+        WITHDRAW_WRAPPED_SOL_IX_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        &[]
+    }
+
+    fn swap<'a>(&self, ctx: &mut SwapContext<'a, '_>, amount_in: u64) -> Result<u64> {
+        sanctum_router_handler(
+            ctx.remaining_accounts,
+            amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.order_id,
+            ctx.owner_seeds,
+        )
+    }
+}
 
 pub const PREFUND_WITHDRAW_STAKE_IX_ACCOUNTS_LEN: usize = 17;
 pub const SPL_STAKEPOOL_WITHDRAW_STAKE_ACCOUNTS_LEN: usize = 10;
@@ -1223,7 +1244,7 @@ pub fn stake_wsol_handler<'a>(
 
     let (src_token_account_mut, dst_token_account_mut) =
         stake_wsol_accounts.get_token_accounts_mut();
-    let dex_processor = &SanctumRouterProcessor;
+    let dex_processor = &SanctumRouterAdapter;
     invoke_process(
         amount_in,
         dex_processor,
@@ -1241,6 +1262,7 @@ pub fn stake_wsol_handler<'a>(
         accounts_len,
         proxy_swap,
         owner_seeds,
+        0,
     )
 }
 
@@ -1349,7 +1371,7 @@ pub fn prefund_swap_via_stake_handler<'a>(
         .chain(pool_deposit_accounts.get_accountinfos())
         .collect();
 
-    let dex_processor = &SanctumRouterProcessor;
+    let dex_processor = &SanctumRouterAdapter;
 
     invoke_processes(
         amount_in,
@@ -1434,7 +1456,7 @@ pub fn withdraw_wsol_handler<'a>(
     let (src_token_account, dst_token_account) = withdraw_wsol_accounts.get_token_accounts_mut();
     invoke_process(
         amount_in,
-        &SanctumRouterProcessor,
+        &SanctumRouterAdapter,
         &withdraw_accout_infos,
         src_token_account,
         dst_token_account,
@@ -1449,6 +1471,7 @@ pub fn withdraw_wsol_handler<'a>(
         accounts_len,
         proxy_swap,
         owner_seeds,
+        0,
     )
 }
 
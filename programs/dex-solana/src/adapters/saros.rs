@@ -1,4 +1,4 @@
-use crate::adapters::common::{before_check, invoke_process};
+use crate::adapters::common::before_check;
 use crate::error::ErrorCode;
 use crate::{HopAccounts, SWAP_SELECTOR, saros_dlmm_program, saros_program};
 use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
@@ -7,10 +7,61 @@ use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use arrayref::array_ref;
 use std::u64;
 
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext, SwapInstruction};
 
-pub struct SarosProcessor;
-impl DexProcessor for SarosProcessor {}
+pub struct SarosAdapter;
+impl DexAdapter for SarosAdapter {
+    fn accounts_len(&self) -> usize {
+        ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[saros_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
+
+pub struct SarosDlmmAdapter;
+impl DexAdapter for SarosDlmmAdapter {
+    fn accounts_len(&self) -> usize {
+        DLMM_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[saros_dlmm_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_dlmm_swap(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
 
 const ARGS_LEN: usize = 17;
 
@@ -61,21 +112,18 @@ impl<'info> SarosAccounts<'info> {
     }
 }
 
-pub fn swap<'a>(
+pub fn build_swap<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::Saros amount_in: {}, offset: {}", amount_in, offset);
-    require!(remaining_accounts.len() >= *offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
-    let mut swap_accounts = SarosAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &saros_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    require!(remaining_accounts.len() >= offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
+    let swap_accounts = SarosAccounts::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.pool.key().log();
 
@@ -93,7 +141,7 @@ pub fn swap<'a>(
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.extend_from_slice(&[1u8]); // instruction: 1 = swap
     data.extend_from_slice(&amount_in.to_le_bytes()); // amountIn
-    data.extend_from_slice(&1u64.to_le_bytes()); // minimumAmountOut = 1
+    data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes()); // minimumAmountOut
 
     // Accounts for Instruction
     let accounts = vec![
@@ -126,28 +174,15 @@ pub fn swap<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &SarosProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
 
 // DLMM
-pub struct SarosDlmmProcessor;
-impl DexProcessor for SarosDlmmProcessor {}
 
 const ARGS_LEN_DLMM: usize = 26;
 
@@ -224,24 +259,21 @@ impl<'info> SarosDlmmAccounts<'info> {
     }
 }
 
-pub fn dlmm_swap<'a>(
+pub fn build_dlmm_swap<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::Saros DLMM amount_in: {}, offset: {}", amount_in, offset);
     require!(
-        remaining_accounts.len() >= *offset + DLMM_ACCOUNTS_LEN,
+        remaining_accounts.len() >= offset + DLMM_ACCOUNTS_LEN,
         ErrorCode::InvalidAccountsLength
     );
-    let mut swap_accounts = SarosDlmmAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &saros_dlmm_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = SarosDlmmAccounts::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.pair.key().log();
 
@@ -266,7 +298,7 @@ pub fn dlmm_swap<'a>(
     let mut data = Vec::with_capacity(ARGS_LEN_DLMM);
     data.extend_from_slice(SWAP_SELECTOR);
     data.extend_from_slice(&amount_in.to_le_bytes());
-    data.extend_from_slice(&1u64.to_le_bytes()); // minimumAmountOut = 1
+    data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes()); // minimumAmountOut
     data.extend_from_slice(&(direction as u8).to_le_bytes()); // swap for y
     data.extend_from_slice(&0u8.to_le_bytes()); // EXACT IN
 
@@ -324,21 +356,10 @@ pub fn dlmm_swap<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &SarosDlmmProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        DLMM_ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
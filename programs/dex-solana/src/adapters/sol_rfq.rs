@@ -1,16 +1,57 @@
 use crate::adapters::common::{before_check, invoke_process};
 use crate::error::ErrorCode;
-use crate::{HopAccounts, SOL_RFQ_FILL_ORDER_SELECTOR, sol_rfq_program};
+use crate::{Dex, HopAccounts, SOL_RFQ_FILL_ORDER_SELECTOR, sol_rfq_program};
 use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
 use anchor_spl::token_interface::TokenAccount;
 use arrayref::array_ref;
 
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext};
 
 const ARGS_LEN: usize = 58;
 
-pub struct SolRfqProcessor;
-impl DexProcessor for SolRfqProcessor {}
+pub struct SolRfqAdapter;
+impl DexAdapter for SolRfqAdapter {
+    fn accounts_len(&self) -> usize {
+        ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[sol_rfq_program::ID] }
+    }
+
+    fn swap<'a>(&self, ctx: &mut SwapContext<'a, '_>, amount_in: u64) -> Result<u64> {
+        let Dex::SolRfq {
+            rfq_id,
+            expected_maker_amount,
+            expected_taker_amount,
+            maker_send_amount,
+            taker_send_amount,
+            expiry,
+            maker_use_native_sol,
+            taker_use_native_sol,
+        } = *ctx.dex
+        else {
+            return Err(ErrorCode::DexAdapterMismatch.into());
+        };
+        fill_order(
+            ctx.remaining_accounts,
+            amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+            rfq_id,
+            expected_maker_amount,
+            expected_taker_amount,
+            maker_send_amount,
+            taker_send_amount,
+            expiry,
+            maker_use_native_sol,
+            taker_use_native_sol,
+        )
+    }
+}
 
 pub struct SolRfqAccount<'info> {
     pub dex_program_id: &'info AccountInfo<'info>,
@@ -90,9 +131,6 @@ pub fn fill_order<'a>(
     require!(taker_send_amount == amount_in, ErrorCode::InvalidRfqParameters);
 
     let mut swap_accounts = SolRfqAccount::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &sol_rfq_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
     // log dex_program_id as pool address
     swap_accounts.dex_program_id.key().log();
 
@@ -153,7 +191,7 @@ pub fn fill_order<'a>(
         data,
     };
 
-    let dex_processor = &SolRfqProcessor;
+    let dex_processor = &SolRfqAdapter;
     let _ = invoke_process(
         amount_in,
         dex_processor,
@@ -167,6 +205,7 @@ pub fn fill_order<'a>(
         ACCOUNTS_LEN,
         proxy_swap,
         owner_seeds,
+        0,
     )?;
 
     Ok(maker_send_amount) // maker_send_amount is the amount_out
@@ -1,4 +1,4 @@
-use crate::adapters::common::{before_check, invoke_process};
+use crate::adapters::common::before_check;
 use crate::error::ErrorCode;
 use crate::{HopAccounts, solfi_program, solfi_v2_program};
 use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
@@ -6,12 +6,55 @@ use anchor_spl::token::Token;
 use anchor_spl::token_interface::{TokenAccount, TokenInterface};
 use arrayref::array_ref;
 
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext, SwapInstruction};
 
 const ARGS_LEN: usize = 18;
 
-pub struct SolfiProcessor;
-impl DexProcessor for SolfiProcessor {}
+pub struct SolfiAdapter;
+impl DexAdapter for SolfiAdapter {
+    fn accounts_len(&self) -> usize {
+        ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[solfi_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
+
+pub struct SolfiV2Adapter;
+impl DexAdapter for SolfiV2Adapter {
+    fn accounts_len(&self) -> usize {
+        V2_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[solfi_v2_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap_v2(ctx.remaining_accounts, amount_in, *ctx.offset, ctx.hop_accounts)
+    }
+}
 
 pub struct SolfiAccount<'info> {
     pub dex_program_id: &'info AccountInfo<'info>,
@@ -54,22 +97,19 @@ impl<'info> SolfiAccount<'info> {
     }
 }
 
-pub fn swap<'a>(
+pub fn build_swap<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::Solfi amount_in: {}, offset: {}", amount_in, offset);
-    require!(remaining_accounts.len() >= *offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
+    require!(remaining_accounts.len() >= offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
 
-    let mut swap_accounts = SolfiAccount::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &solfi_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = SolfiAccount::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.pair.key().log();
 
@@ -103,7 +143,7 @@ pub fn swap<'a>(
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.push(7u8); //discriminator
     data.extend_from_slice(&amount_in.to_le_bytes()); //amount_in
-    data.extend_from_slice(&0u64.to_le_bytes());
+    data.extend_from_slice(&hop_accounts.min_amount_out.to_le_bytes());
     data.extend_from_slice(&direction.to_le_bytes()); //swap direction
 
     let accounts = vec![
@@ -131,22 +171,12 @@ pub fn swap<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &SolfiProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
 
 pub struct SolfiAccountV2<'info> {
@@ -206,24 +236,18 @@ impl<'info> SolfiAccountV2<'info> {
     }
 }
 
-pub fn swap_v2<'a>(
+pub fn build_swap_v2<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
-    hop_accounts: &mut HopAccounts,
-    hop: usize,
-    proxy_swap: bool,
-    owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+    offset: usize,
+    hop_accounts: &HopAccounts,
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::SolfiV2 amount_in: {}, offset: {}", amount_in, offset);
     require!(
-        remaining_accounts.len() >= *offset + V2_ACCOUNTS_LEN,
+        remaining_accounts.len() >= offset + V2_ACCOUNTS_LEN,
         ErrorCode::InvalidAccountsLength
     );
-    let mut swap_accounts = SolfiAccountV2::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &solfi_v2_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = SolfiAccountV2::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.market.key().log();
 
@@ -245,7 +269,7 @@ pub fn swap_v2<'a>(
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.push(7u8); //discriminator
     data.extend_from_slice(&amount_in.to_le_bytes()); //amount_in
-    data.extend_from_slice(&1u64.to_le_bytes());
+    data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes());
     data.extend_from_slice(&direction.to_le_bytes()); //swap direction
 
     let accounts = vec![
@@ -283,20 +307,10 @@ pub fn swap_v2<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &SolfiProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        V2_ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
@@ -1,5 +1,5 @@
 use crate::HopAccounts;
-use crate::adapters::common::{before_check, invoke_process};
+use crate::adapters::common::before_check;
 use crate::error::ErrorCode;
 use crate::{one_moon_swap_program, orca_swap_program, saros_program, spl_token_swap_program};
 use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
@@ -7,12 +7,60 @@ use anchor_spl::token::Token;
 use anchor_spl::token_interface::TokenAccount;
 use arrayref::array_ref;
 
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext, SwapInstruction};
 
 const ARGS_LEN: usize = 17;
 
-pub struct SplTokenSwapProcessor;
-impl DexProcessor for SplTokenSwapProcessor {}
+// Versioned `SwapV1` state of spl-token-swap
+const SWAP_INFO_LEN: usize = 324;
+const SWAP_VERSION: u8 = 1;
+const TRADE_FEE_OFFSET: usize = 227;
+const OWNER_TRADE_FEE_OFFSET: usize = 243;
+const CURVE_TYPE_OFFSET: usize = 291;
+const CONSTANT_PRODUCT_CURVE: u8 = 0;
+
+pub struct SplTokenSwapAdapter;
+impl DexAdapter for SplTokenSwapAdapter {
+    fn accounts_len(&self) -> usize {
+        ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const {
+            &[
+                spl_token_swap_program::ID,
+                orca_swap_program::ID,
+                saros_program::ID,
+                one_moon_swap_program::ID,
+            ]
+        }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+
+    fn quote<'a>(
+        &self,
+        remaining_accounts: &'a [AccountInfo<'a>],
+        offset: usize,
+        amount_in: u64,
+    ) -> Result<Option<u64>> {
+        quote(remaining_accounts, offset, amount_in)
+    }
+}
 
 pub struct SplTokenSwapAccounts<'info> {
     pub dex_program_id: &'info AccountInfo<'info>,
@@ -61,29 +109,18 @@ impl<'info> SplTokenSwapAccounts<'info> {
     }
 }
 
-pub fn swap<'a>(
+pub fn build_swap<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::SplTokenSwap amount_in: {}, offset: {}", amount_in, offset);
-    require!(remaining_accounts.len() >= *offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
-    let mut swap_accounts = SplTokenSwapAccounts::parse_accounts(remaining_accounts, *offset)?;
-    let valid_program_ids = [
-        spl_token_swap_program::id(),
-        orca_swap_program::id(),
-        saros_program::id(),
-        one_moon_swap_program::id(),
-        // step_swap_program::id(), // Adapter aborted
-    ];
-    require!(
-        valid_program_ids.contains(swap_accounts.dex_program_id.key),
-        ErrorCode::InvalidProgramId
-    );
+    require!(remaining_accounts.len() >= offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
+    let swap_accounts = SplTokenSwapAccounts::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.swap_info.key().log();
 
@@ -119,7 +156,7 @@ pub fn swap<'a>(
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.push(1);
     data.extend_from_slice(&amount_in.to_le_bytes());
-    data.extend_from_slice(&1u64.to_le_bytes());
+    data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes());
 
     let accounts = vec![
         AccountMeta::new_readonly(swap_accounts.swap_info.key(), false),
@@ -150,22 +187,81 @@ pub fn swap<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &SplTokenSwapProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
+}
+
+/// `calculate_fee` of spl-token-swap: any non-zero fee rate charges at least 1
+fn trading_fee(amount_in: u128, data: &[u8], offset: usize) -> Result<u128> {
+    let numerator = u128::from(u64::from_le_bytes(*array_ref![data, offset, 8]));
+    let denominator = u128::from(u64::from_le_bytes(*array_ref![data, offset + 8, 8]));
+    if numerator == 0 || amount_in == 0 {
+        return Ok(0);
+    }
+    let fee = amount_in
+        .checked_mul(numerator)
+        .ok_or(ErrorCode::CalculationError)?
+        .checked_div(denominator)
+        .ok_or(ErrorCode::CalculationError)?;
+    Ok(fee.max(1))
+}
+
+/// Constant product output of spl-token-swap, with its fees and `checked_ceil_div` rounding.
+/// Other curves are not quoted.
+pub fn quote<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    offset: usize,
+    amount_in: u64,
+) -> Result<Option<u64>> {
+    require!(remaining_accounts.len() >= offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
+    let swap_accounts = SplTokenSwapAccounts::parse_accounts(remaining_accounts, offset)?;
+    require!(
+        swap_accounts.swap_info.owner == swap_accounts.dex_program_id.key,
+        ErrorCode::InvalidProgramId
+    );
+    let data = swap_accounts.swap_info.try_borrow_data()?;
+    if data.len() < SWAP_INFO_LEN
+        || data[0] != SWAP_VERSION
+        || data[CURVE_TYPE_OFFSET] != CONSTANT_PRODUCT_CURVE
+    {
+        return Ok(None);
+    }
+
+    let (reserve_in, reserve_out) = if (swap_accounts.swap_source_token.mint
+        == swap_accounts.token_a_account.mint)
+        && (swap_accounts.swap_destination_token.mint == swap_accounts.token_b_account.mint)
+    {
+        (swap_accounts.token_a_account.amount, swap_accounts.token_b_account.amount)
+    } else if (swap_accounts.swap_source_token.mint == swap_accounts.token_b_account.mint)
+        && (swap_accounts.swap_destination_token.mint == swap_accounts.token_a_account.mint)
+    {
+        (swap_accounts.token_b_account.amount, swap_accounts.token_a_account.amount)
+    } else {
+        return Err(ErrorCode::InvalidPool.into());
+    };
+
+    let amount_in = u128::from(amount_in);
+    let total_fees = trading_fee(amount_in, &data, TRADE_FEE_OFFSET)?
+        .checked_add(trading_fee(amount_in, &data, OWNER_TRADE_FEE_OFFSET)?)
+        .ok_or(ErrorCode::CalculationError)?;
+    let amount_in = amount_in.checked_sub(total_fees).ok_or(ErrorCode::CalculationError)?;
+    let (reserve_in, reserve_out) = (u128::from(reserve_in), u128::from(reserve_out));
+    let invariant = reserve_in.checked_mul(reserve_out).ok_or(ErrorCode::CalculationError)?;
+    let new_reserve_in = reserve_in.checked_add(amount_in).ok_or(ErrorCode::CalculationError)?;
+    let mut new_reserve_out =
+        invariant.checked_div(new_reserve_in).ok_or(ErrorCode::CalculationError)?;
+    if new_reserve_out == 0 {
+        return Ok(Some(0));
+    }
+    if invariant.checked_rem(new_reserve_in).ok_or(ErrorCode::CalculationError)? > 0 {
+        new_reserve_out = new_reserve_out.checked_add(1).ok_or(ErrorCode::CalculationError)?;
+    }
+    let amount_out = reserve_out.checked_sub(new_reserve_out).ok_or(ErrorCode::CalculationError)?;
+    Ok(Some(u64::try_from(amount_out).map_err(|_| ErrorCode::CalculationError)?))
 }
 
 #[cfg(test)]
@@ -8,12 +8,11 @@ use arrayref::array_ref;
 
 use crate::error::ErrorCode;
 use crate::{
-    HopAccounts, STABBLE_SWAP_SELECTOR,
-    adapters::common::{before_check, invoke_process},
-    stabble_stable_program, stabble_weighted_program,
+    HopAccounts, STABBLE_SWAP_SELECTOR, adapters::common::before_check, stabble_stable_program,
+    stabble_weighted_program,
 };
 
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext, SwapInstruction};
 
 const ARGS_LEN: usize = 25;
 
@@ -82,30 +81,49 @@ impl<'info> StabbleSwapAccounts<'info> {
     }
 }
 
-pub struct StabbleSwapProcessor;
-impl DexProcessor for StabbleSwapProcessor {}
+pub struct StabbleSwapAdapter;
+impl DexAdapter for StabbleSwapAdapter {
+    fn accounts_len(&self) -> usize {
+        SWAP_ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[stabble_stable_program::ID, stabble_weighted_program::ID] }
+    }
 
-pub fn swap<'a>(
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
+
+pub fn build_swap<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::StabbleSwap amount_in: {}, offset: {}", amount_in, offset);
     require!(
-        remaining_accounts.len() >= *offset + SWAP_ACCOUNTS_LEN,
+        remaining_accounts.len() >= offset + SWAP_ACCOUNTS_LEN,
         ErrorCode::InvalidAccountsLength
     );
 
-    let mut swap_accounts = StabbleSwapAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &stabble_stable_program::id()
-        && swap_accounts.dex_program_id.key != &stabble_weighted_program::id()
-    {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = StabbleSwapAccounts::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.pool_token_in.key().log();
 
@@ -122,7 +140,7 @@ pub fn swap<'a>(
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.extend_from_slice(STABBLE_SWAP_SELECTOR);
     data.extend_from_slice(&(Some(amount_in)).try_to_vec().unwrap());
-    data.extend_from_slice(&1u64.to_le_bytes());
+    data.extend_from_slice(&hop_accounts.min_amount_out.max(1).to_le_bytes());
 
     let accounts = vec![
         AccountMeta::new(swap_accounts.swap_authority_pubkey.key(), true),
@@ -163,21 +181,10 @@ pub fn swap<'a>(
     let instruction: Instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &StabbleSwapProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_info,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        SWAP_ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-
-    Ok(amount_out)
+        account_infos: account_info,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
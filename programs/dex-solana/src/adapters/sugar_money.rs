@@ -1,9 +1,9 @@
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext};
 use crate::adapters::common::{before_check, invoke_process};
 use crate::error::ErrorCode;
 use crate::utils::{close_token_account, log_sa_lamports_info, sync_wsol_account, transfer_sol};
 use crate::{
-    BUY_EXACT_IN_SELECTOR, HopAccounts, MIN_SOL_ACCOUNT_RENT, SA_AUTHORITY_SEED,
+    BUY_EXACT_IN_SELECTOR, Dex, HopAccounts, MIN_SOL_ACCOUNT_RENT, SA_AUTHORITY_SEED,
     SELL_EXACT_IN_SELECTOR, SOL_DIFF_LIMIT, ZERO_ADDRESS, authority_pda, sugar_money_program,
     wsol_sa,
 };
@@ -51,8 +51,36 @@ pub struct SugarMoneyAccounts<'info> {
 }
 const ACCOUNTS_LEN: usize = 15;
 
-pub struct SugarMoneyBuyProcessor;
-impl DexProcessor for SugarMoneyBuyProcessor {
+pub struct SugarMoneyBuyAdapter;
+impl DexAdapter for SugarMoneyBuyAdapter {
+    fn accounts_len(&self) -> usize {
+        ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[sugar_money_program::ID] }
+    }
+
+    fn swap<'a>(&self, ctx: &mut SwapContext<'a, '_>, amount_in: u64) -> Result<u64> {
+        let Dex::SugarMoneyBuy { bonding_curve_bump, bonding_curve_sol_associated_account_bump } =
+            *ctx.dex
+        else {
+            return Err(ErrorCode::DexAdapterMismatch.into());
+        };
+        buy(
+            ctx.remaining_accounts,
+            amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+            ctx.payer,
+            bonding_curve_bump,
+            bonding_curve_sol_associated_account_bump,
+        )
+    }
+
     fn before_invoke(&self, account_infos: &[AccountInfo]) -> Result<u64> {
         let source_token_account = account_infos.last().unwrap();
         let token_program = account_infos.get(9).unwrap();
@@ -89,6 +117,7 @@ impl DexProcessor for SugarMoneyBuyProcessor {
         _hop: usize,
         _owner_seeds: Option<&[&[&[u8]]]>,
         before_sa_authority_lamports: u64,
+        _expected_amount_out: u64,
     ) -> Result<u64> {
         if before_sa_authority_lamports > 0 {
             let payer = account_infos.get(15).unwrap();
@@ -122,17 +151,47 @@ impl DexProcessor for SugarMoneyBuyProcessor {
     }
 }
 
-pub struct SugarMoneySellProcessor {
-    pub sender_before_lamports: u64,
-}
+pub struct SugarMoneySellAdapter;
+impl DexAdapter for SugarMoneySellAdapter {
+    fn accounts_len(&self) -> usize {
+        ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[sugar_money_program::ID] }
+    }
+
+    fn swap<'a>(&self, ctx: &mut SwapContext<'a, '_>, amount_in: u64) -> Result<u64> {
+        let Dex::SugarMoneySell { bonding_curve_bump, bonding_curve_sol_associated_account_bump } =
+            *ctx.dex
+        else {
+            return Err(ErrorCode::DexAdapterMismatch.into());
+        };
+        sell(
+            ctx.remaining_accounts,
+            amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+            bonding_curve_bump,
+            bonding_curve_sol_associated_account_bump,
+        )
+    }
+
+    fn before_invoke(&self, account_infos: &[AccountInfo]) -> Result<u64> {
+        let authority = account_infos.get(6).unwrap();
+        Ok(authority.lamports())
+    }
 
-impl DexProcessor for SugarMoneySellProcessor {
     fn after_invoke(
         &self,
         account_infos: &[AccountInfo],
         hop: usize,
         owner_seeds: Option<&[&[&[u8]]]>,
-        _before_sa_authority_lamports: u64,
+        sender_before_lamports: u64,
+        _expected_amount_out: u64,
     ) -> Result<u64> {
         let destination_token_account = account_infos.last().unwrap();
         let authority = account_infos.get(6).unwrap();
@@ -148,7 +207,7 @@ impl DexProcessor for SugarMoneySellProcessor {
         };
 
         let received_lamports = sender_after_lamports
-            .checked_sub(self.sender_before_lamports)
+            .checked_sub(sender_before_lamports)
             .ok_or(ErrorCode::CalculationError)?;
         transfer_sol(
             authority.to_account_info(),
@@ -221,9 +280,6 @@ pub fn buy<'a>(
     require!(remaining_accounts.len() >= *offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
 
     let mut swap_accounts = SugarMoneyAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &sugar_money_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
 
     // Record pool address
     swap_accounts.bonding_curve.key().log();
@@ -244,7 +300,7 @@ pub fn buy<'a>(
             bonding_curve_sol_associated_account: bonding_curve_sol_associated_account_bump,
         },
         amount_in,
-        min_amount_out: 1,
+        min_amount_out: hop_accounts.min_amount_out.max(1),
     };
 
     let mut data = Vec::with_capacity(ARGS_LEN);
@@ -291,7 +347,7 @@ pub fn buy<'a>(
 
     let instruction = Instruction { program_id: sugar_money_program::id(), accounts, data };
 
-    let dex_processor = &SugarMoneyBuyProcessor;
+    let dex_processor = &SugarMoneyBuyAdapter;
     let amount_out = invoke_process(
         amount_in,
         dex_processor,
@@ -305,6 +361,7 @@ pub fn buy<'a>(
         ACCOUNTS_LEN,
         proxy_swap,
         owner_seeds,
+        0,
     )?;
 
     Ok(amount_out)
@@ -325,9 +382,6 @@ pub fn sell<'a>(
     require!(remaining_accounts.len() >= *offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
 
     let mut swap_accounts = SugarMoneyAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &sugar_money_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
 
     // Record pool address
     swap_accounts.bonding_curve.key().log();
@@ -349,7 +403,7 @@ pub fn sell<'a>(
             bonding_curve_sol_associated_account: bonding_curve_sol_associated_account_bump,
         },
         amount_in,
-        min_amount_out: 1,
+        min_amount_out: hop_accounts.min_amount_out.max(1),
     };
 
     let mut data = Vec::with_capacity(ARGS_LEN);
@@ -396,9 +450,7 @@ pub fn sell<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &SugarMoneySellProcessor {
-        sender_before_lamports: swap_accounts.swap_authority.lamports(),
-    };
+    let dex_processor = &SugarMoneySellAdapter;
     let amount_out = invoke_process(
         amount_in,
         dex_processor,
@@ -412,6 +464,7 @@ pub fn sell<'a>(
         ACCOUNTS_LEN,
         proxy_swap,
         owner_seeds,
+        0,
     )?;
 
     Ok(amount_out)
@@ -1,4 +1,4 @@
-use crate::adapters::common::{before_check, invoke_process};
+use crate::adapters::common::{before_check, invoke_process_exact_out};
 use crate::error::ErrorCode;
 use crate::{HopAccounts, SWAP_SELECTOR, SWAP_V2_SELECTOR, whirlpool_program};
 use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
@@ -6,13 +6,116 @@ use anchor_spl::token::Token;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use arrayref::array_ref;
 
-use super::common::DexProcessor;
+use super::common::{DexAdapter, SwapContext, SwapInstruction};
 
 const ARGS_LEN: usize = 42;
 const ARGS_V2_LEN: usize = 43;
 
-pub struct WhirlpoolProcessor;
-impl DexProcessor for WhirlpoolProcessor {}
+pub struct WhirlpoolAdapter;
+impl DexAdapter for WhirlpoolAdapter {
+    fn accounts_len(&self) -> usize {
+        ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[whirlpool_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+
+    fn supports_exact_out(&self) -> bool {
+        true
+    }
+
+    fn swap_exact_out<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_out: u64,
+        max_amount_in: u64,
+    ) -> Result<(u64, u64)> {
+        swap_exact_out(
+            ctx.remaining_accounts,
+            amount_out,
+            max_amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+
+    fn supports_sqrt_price_limit(&self) -> bool {
+        true
+    }
+}
+
+pub struct WhirlpoolV2Adapter;
+impl DexAdapter for WhirlpoolV2Adapter {
+    fn accounts_len(&self) -> usize {
+        ACCOUNTS_V2_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[whirlpool_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap_v2(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+
+    fn supports_exact_out(&self) -> bool {
+        true
+    }
+
+    fn swap_exact_out<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_out: u64,
+        max_amount_in: u64,
+    ) -> Result<(u64, u64)> {
+        swap_v2_exact_out(
+            ctx.remaining_accounts,
+            amount_out,
+            max_amount_in,
+            ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+
+    fn supports_sqrt_price_limit(&self) -> bool {
+        true
+    }
+}
 
 pub struct WhirlpoolAccounts<'info> {
     pub dex_program_id: &'info AccountInfo<'info>,
@@ -126,21 +229,96 @@ impl<'info> WhirlpoolV2Accounts<'info> {
     }
 }
 
-pub fn swap<'a>(
+pub fn build_swap<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::Whirlpool amount_in: {}, offset: {}", amount_in, offset);
-    require!(remaining_accounts.len() >= *offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
-    let mut swap_accounts = WhirlpoolAccounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &whirlpool_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let min_amount_out = hop_accounts.min_amount_out.max(1);
+    let (swap_accounts, instruction, account_infos) = prepare_swap(
+        remaining_accounts,
+        offset,
+        hop_accounts,
+        hop,
+        proxy_swap,
+        owner_seeds,
+        amount_in,
+        min_amount_out,
+        true,
+    )?;
+
+    Ok(SwapInstruction {
+        instruction,
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
+}
+
+pub fn swap_exact_out<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_out: u64,
+    max_amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+    owner_seeds: Option<&[&[&[u8]]]>,
+) -> Result<(u64, u64)> {
+    msg!(
+        "Dex::Whirlpool amount_out: {}, max_amount_in: {}, offset: {}",
+        amount_out,
+        max_amount_in,
+        offset
+    );
+    let (mut swap_accounts, instruction, account_infos) = prepare_swap(
+        remaining_accounts,
+        *offset,
+        hop_accounts,
+        hop,
+        proxy_swap,
+        owner_seeds,
+        amount_out,
+        max_amount_in,
+        false,
+    )?;
+
+    let dex_processor = &WhirlpoolAdapter;
+    invoke_process_exact_out(
+        amount_out,
+        max_amount_in,
+        dex_processor,
+        &account_infos,
+        &mut swap_accounts.swap_source_token,
+        &mut swap_accounts.swap_destination_token,
+        hop_accounts,
+        instruction,
+        hop,
+        offset,
+        ACCOUNTS_LEN,
+        proxy_swap,
+        owner_seeds,
+    )
+}
+
+fn prepare_swap<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    offset: usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+    owner_seeds: Option<&[&[&[u8]]]>,
+    amount: u64,
+    other_amount_threshold: u64,
+    amount_specified_is_input: bool,
+) -> Result<(WhirlpoolAccounts<'a>, Instruction, Vec<AccountInfo<'a>>)> {
+    require!(remaining_accounts.len() >= offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
+    let swap_accounts = WhirlpoolAccounts::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.whirlpool.key().log();
 
@@ -156,8 +334,6 @@ pub fn swap<'a>(
         owner_seeds,
     )?;
 
-    let amount_specified_is_input = true;
-    let other_amount_threshold = 1u64;
     let a_to_b: bool;
     let sqrt_price_limit: i128;
     if swap_accounts.swap_source_token.mint == swap_accounts.token_vault_a.mint
@@ -173,6 +349,12 @@ pub fn swap<'a>(
     } else {
         return Err(ErrorCode::InvalidTokenMint.into());
     }
+    // fork sqrt price limit, if provided
+    let sqrt_price_limit = if hop_accounts.sqrt_price_limit != 0 {
+        i128::try_from(hop_accounts.sqrt_price_limit).map_err(|_| ErrorCode::CalculationError)?
+    } else {
+        sqrt_price_limit
+    };
     let (token_owner_account_a, token_owner_account_b) = if a_to_b {
         (swap_accounts.swap_source_token.clone(), swap_accounts.swap_destination_token.clone())
     } else {
@@ -181,7 +363,7 @@ pub fn swap<'a>(
 
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.extend_from_slice(SWAP_SELECTOR);
-    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&amount.to_le_bytes());
     data.extend_from_slice(&other_amount_threshold.to_le_bytes());
     data.extend_from_slice(&sqrt_price_limit.to_le_bytes());
     data.extend_from_slice(&(amount_specified_is_input as u8).to_le_bytes());
@@ -218,9 +400,72 @@ pub fn swap<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &WhirlpoolProcessor;
-    let amount_out = invoke_process(
+    Ok((swap_accounts, instruction, account_infos))
+}
+
+pub fn build_swap_v2<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_in: u64,
+    offset: usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+    owner_seeds: Option<&[&[&[u8]]]>,
+) -> Result<SwapInstruction<'a>> {
+    msg!("Dex::WhirlpoolV2 amount_in: {}, offset: {}", amount_in, offset);
+    let min_amount_out = hop_accounts.min_amount_out.max(1);
+    let (swap_accounts, instruction, account_infos) = prepare_swap_v2(
+        remaining_accounts,
+        offset,
+        hop_accounts,
+        hop,
+        proxy_swap,
+        owner_seeds,
         amount_in,
+        min_amount_out,
+        true,
+    )?;
+
+    Ok(SwapInstruction {
+        instruction,
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
+}
+
+pub fn swap_v2_exact_out<'a>(
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_out: u64,
+    max_amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_swap: bool,
+    owner_seeds: Option<&[&[&[u8]]]>,
+) -> Result<(u64, u64)> {
+    msg!(
+        "Dex::WhirlpoolV2 amount_out: {}, max_amount_in: {}, offset: {}",
+        amount_out,
+        max_amount_in,
+        offset
+    );
+    let (mut swap_accounts, instruction, account_infos) = prepare_swap_v2(
+        remaining_accounts,
+        *offset,
+        hop_accounts,
+        hop,
+        proxy_swap,
+        owner_seeds,
+        amount_out,
+        max_amount_in,
+        false,
+    )?;
+
+    let dex_processor = &WhirlpoolV2Adapter;
+    invoke_process_exact_out(
+        amount_out,
+        max_amount_in,
         dex_processor,
         &account_infos,
         &mut swap_accounts.swap_source_token,
@@ -229,31 +474,28 @@ pub fn swap<'a>(
         instruction,
         hop,
         offset,
-        ACCOUNTS_LEN,
+        ACCOUNTS_V2_LEN,
         proxy_swap,
         owner_seeds,
-    )?;
-    Ok(amount_out)
+    )
 }
 
-pub fn swap_v2<'a>(
+fn prepare_swap_v2<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
-    amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
-    msg!("Dex::WhirlpoolV2 amount_in: {}, offset: {}", amount_in, offset);
+    amount: u64,
+    other_amount_threshold: u64,
+    amount_specified_is_input: bool,
+) -> Result<(WhirlpoolV2Accounts<'a>, Instruction, Vec<AccountInfo<'a>>)> {
     require!(
-        remaining_accounts.len() >= *offset + ACCOUNTS_V2_LEN,
+        remaining_accounts.len() >= offset + ACCOUNTS_V2_LEN,
         ErrorCode::InvalidAccountsLength
     );
-    let mut swap_accounts = WhirlpoolV2Accounts::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &whirlpool_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = WhirlpoolV2Accounts::parse_accounts(remaining_accounts, offset)?;
     // log pool address
     swap_accounts.whirlpool.key().log();
 
@@ -269,8 +511,6 @@ pub fn swap_v2<'a>(
         owner_seeds,
     )?;
 
-    let amount_specified_is_input = true;
-    let other_amount_threshold = 1u64;
     let a_to_b: bool;
     let sqrt_price_limit: i128;
     if swap_accounts.swap_source_token.mint == swap_accounts.token_vault_a.mint
@@ -286,6 +526,12 @@ pub fn swap_v2<'a>(
     } else {
         return Err(ErrorCode::InvalidTokenMint.into());
     }
+    // fork sqrt price limit, if provided
+    let sqrt_price_limit = if hop_accounts.sqrt_price_limit != 0 {
+        i128::try_from(hop_accounts.sqrt_price_limit).map_err(|_| ErrorCode::CalculationError)?
+    } else {
+        sqrt_price_limit
+    };
     let (token_owner_account_a, token_owner_account_b) = if a_to_b {
         (swap_accounts.swap_source_token.clone(), swap_accounts.swap_destination_token.clone())
     } else {
@@ -294,7 +540,7 @@ pub fn swap_v2<'a>(
 
     let mut data = Vec::with_capacity(ARGS_V2_LEN);
     data.extend_from_slice(SWAP_V2_SELECTOR);
-    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&amount.to_le_bytes());
     data.extend_from_slice(&other_amount_threshold.to_le_bytes());
     data.extend_from_slice(&sqrt_price_limit.to_le_bytes());
     data.extend_from_slice(&(amount_specified_is_input as u8).to_le_bytes());
@@ -340,22 +586,7 @@ pub fn swap_v2<'a>(
     let instruction =
         Instruction { program_id: swap_accounts.dex_program_id.key(), accounts, data };
 
-    let dex_processor = &WhirlpoolProcessor;
-    let amount_out = invoke_process(
-        amount_in,
-        dex_processor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
-        instruction,
-        hop,
-        offset,
-        ACCOUNTS_V2_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-    Ok(amount_out)
+    Ok((swap_accounts, instruction, account_infos))
 }
 
 #[cfg(test)]
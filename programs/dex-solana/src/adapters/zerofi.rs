@@ -1,5 +1,5 @@
-use super::common::DexProcessor;
-use crate::adapters::common::{before_check, invoke_process};
+use super::common::{DexAdapter, SwapContext, SwapInstruction};
+use crate::adapters::common::before_check;
 use crate::error::ErrorCode;
 use crate::{HopAccounts, zerofi_program};
 use anchor_lang::{prelude::*, solana_program::instruction::Instruction};
@@ -8,8 +8,32 @@ use arrayref::array_ref;
 
 const ARGS_LEN: usize = 17;
 
-pub struct ZeroFiProcessor;
-impl DexProcessor for ZeroFiProcessor {}
+pub struct ZerofiAdapter;
+impl DexAdapter for ZerofiAdapter {
+    fn accounts_len(&self) -> usize {
+        ACCOUNTS_LEN
+    }
+
+    fn program_ids(&self) -> &[Pubkey] {
+        const { &[zerofi_program::ID] }
+    }
+
+    fn build_swap<'a>(
+        &self,
+        ctx: &mut SwapContext<'a, '_>,
+        amount_in: u64,
+    ) -> Result<SwapInstruction<'a>> {
+        build_swap(
+            ctx.remaining_accounts,
+            amount_in,
+            *ctx.offset,
+            ctx.hop_accounts,
+            ctx.hop,
+            ctx.proxy_swap,
+            ctx.owner_seeds,
+        )
+    }
+}
 
 pub struct ZeroFiAccount<'info> {
     pub dex_program_id: &'info AccountInfo<'info>,
@@ -58,22 +82,19 @@ impl<'info> ZeroFiAccount<'info> {
     }
 }
 
-pub fn swap<'a>(
+pub fn build_swap<'a>(
     remaining_accounts: &'a [AccountInfo<'a>],
     amount_in: u64,
-    offset: &mut usize,
+    offset: usize,
     hop_accounts: &mut HopAccounts,
     hop: usize,
     proxy_swap: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
-) -> Result<u64> {
+) -> Result<SwapInstruction<'a>> {
     msg!("Dex::ZeroFi amount_in: {}, offset: {}", amount_in, offset);
-    require!(remaining_accounts.len() >= *offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
+    require!(remaining_accounts.len() >= offset + ACCOUNTS_LEN, ErrorCode::InvalidAccountsLength);
 
-    let mut swap_accounts = ZeroFiAccount::parse_accounts(remaining_accounts, *offset)?;
-    if swap_accounts.dex_program_id.key != &zerofi_program::id() {
-        return Err(ErrorCode::InvalidProgramId.into());
-    }
+    let swap_accounts = ZeroFiAccount::parse_accounts(remaining_accounts, offset)?;
     swap_accounts.pair.key().log();
 
     // check hop accounts & swap authority
@@ -115,7 +136,7 @@ pub fn swap<'a>(
     let mut data = Vec::with_capacity(ARGS_LEN);
     data.push(6u8); //discriminator
     data.extend_from_slice(&amount_in.to_le_bytes()); //amount_in
-    data.extend_from_slice(&0u64.to_le_bytes()); //desired ouput token amount
+    data.extend_from_slice(&hop_accounts.min_amount_out.to_le_bytes()); //desired ouput token amount
 
     let accounts = vec![
         AccountMeta::new(swap_accounts.pair.key(), false),
@@ -145,20 +166,10 @@ pub fn swap<'a>(
 
     let instruction = Instruction { program_id: zerofi_program::id(), accounts, data };
 
-    let amount_out = invoke_process(
-        amount_in,
-        &ZeroFiProcessor,
-        &account_infos,
-        &mut swap_accounts.swap_source_token,
-        &mut swap_accounts.swap_destination_token,
-        hop_accounts,
+    Ok(SwapInstruction {
         instruction,
-        hop,
-        offset,
-        ACCOUNTS_LEN,
-        proxy_swap,
-        owner_seeds,
-    )?;
-    require!(amount_out > 0, ErrorCode::AmountOutMustBeGreaterThanZero);
-    Ok(amount_out)
+        account_infos,
+        swap_source_token: swap_accounts.swap_source_token,
+        swap_destination_token: swap_accounts.swap_destination_token,
+    })
 }
@@ -3,7 +3,10 @@ use anchor_lang::prelude::*;
 #[constant]
 pub const SEED_SA: &[u8] = b"okx_sa";
 pub const SEED_TEMP_WSOL: &[u8] = b"temp_wsol";
+#[cfg(not(feature = "local-sa"))]
 pub const BUMP_SA: u8 = 251;
+#[cfg(feature = "local-sa")]
+pub const BUMP_SA: u8 = 255; //SA bump under declare_id, for local tests
 
 pub const COMMISSION_RATE_LIMIT: u16 = 1_000; // 10%
 pub const COMMISSION_DENOMINATOR: u64 = 10_000;
@@ -36,6 +39,8 @@ pub const SWAP2_SELECTOR: &[u8; 8] = &[65, 75, 63, 76, 235, 91, 91, 136];
 pub const CPSWAP_SELECTOR: &[u8; 8] = &[143, 190, 90, 218, 196, 30, 51, 222];
 pub const SWAP_V2_SELECTOR: &[u8; 8] = &[43, 4, 237, 11, 26, 201, 30, 98];
 pub const SWAP_EXACT_IN_SELECTOR: &[u8; 8] = &[104, 104, 131, 86, 161, 189, 180, 216];
+pub const SWAP_EXACT_OUT_SELECTOR: &[u8; 8] = &[250, 73, 101, 33, 38, 207, 75, 184];
+pub const SWAP_EXACT_OUT2_SELECTOR: &[u8; 8] = &[43, 215, 247, 132, 137, 60, 243, 81];
 pub const PLACE_TAKE_ORDER_SELECTOR: &[u8; 8] = &[3, 44, 71, 3, 26, 199, 203, 85];
 pub const BRIDGE_TO_LOG_SELECTOR: &[u8; 8] = &[212, 189, 176, 218, 196, 135, 64, 122];
 pub const ZERO_ADDRESS: Pubkey = Pubkey::new_from_array([0u8; 32]);
@@ -90,28 +95,49 @@ pub const HUMIDIFI_IX_DATA_KEY: u64 = u64::from_le_bytes([
     HUMIDIFI_IX_DATA_KEY_SEED[7],
 ]);
 
+// ******************** Claim ******************** //
+pub const CLAIM_CONFIG_SEED: &str = "claim_config";
+
 // ******************** Limit Order ******************** //
 pub const GLOBAL_CONFIG_SEED: &str = "global_config";
 pub const ORDER_V1_SEED: &str = "order_v1";
 pub const ESCROW_TOKEN_SEED: &str = "escrow_token";
+pub const RESOLVER_SEED: &str = "resolver";
+pub const RECURRING_ORDER_SEED: &str = "recurring_order";
+pub const INTENT_NONCES_SEED: &str = "intent_nonces";
+pub const INTENT_DELEGATE_SEED: &str = "intent_delegate";
+pub const ORDER_INTENT_DOMAIN: &[u8] = b"dex_solana_order_intent";
+pub const INTENT_NONCES_PER_PAGE: u64 = 1024; //nonces tracked by one bitmap account
+pub const ORACLE_CONFIDENCE_DENOMINATOR: u64 = 10_000;
 pub const MIN_DEADLINE: u64 = 300; //min order deadline: 5 minutes
+pub const MIN_RECURRING_INTERVAL: u64 = 60; //min interval between two slices: 1 minute
 pub const SIGNATURE_FEE: u64 = 5000;
+pub const MAX_ORDER_FILLS: u64 = 10; //a partial fill takes at least 1/10 of the making amount
 pub const ORDER_MIN_RENT: u64 = 3563520; //needs to be changed when order account size is changed
 pub const DEFAULT_COMPUTE_UNIT_LIMIT: u32 = 200_000;
 pub const FEE_MULTIPLIER_DENOMINATOR: u64 = 10;
+pub const MAX_BRIDGE_RECIPIENT_LEN: usize = 64; //longest recipient address on a target chain
+pub const MAX_COMMISSION_SPLITS: usize = 5;
+pub const COMMISSION_SPLIT_DENOMINATOR: u64 = 10_000;
 
-#[cfg(feature = "staging")]
+#[cfg(all(feature = "staging", not(feature = "local-sa")))]
 pub mod authority_pda {
     use anchor_lang::declare_id;
     declare_id!("4DwLmWvMyWPPKa8jhmW6AZKGctUMe7GxAWrb2Wcw8ZUa"); //pre_deploy
 }
 
-#[cfg(not(feature = "staging"))]
+#[cfg(not(any(feature = "staging", feature = "local-sa")))]
 pub mod authority_pda {
     use anchor_lang::declare_id;
     declare_id!("HV1KXxWFaSeriyFvXyx48FqG9BoFbfinB8njCJonqP7K");
 }
 
+#[cfg(feature = "local-sa")]
+pub mod authority_pda {
+    use anchor_lang::declare_id;
+    declare_id!("sqiYge5zD9imJv4A23t3n1tHQkUXmuLCPe1FMM3N7g1"); //okx_sa PDA of declare_id
+}
+
 #[cfg(feature = "staging")]
 pub mod okx_bridge_program {
     use anchor_lang::declare_id;
@@ -372,6 +398,16 @@ pub mod price_update_solusd {
     declare_id!("7UVimffxr9ow1uXYxsr4LHAcV58mLzhmwaeKvJ1pjLiE");
 }
 
+pub mod pyth_receiver_program {
+    use anchor_lang::declare_id;
+    declare_id!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+}
+
+pub mod switchboard_on_demand_program {
+    use anchor_lang::declare_id;
+    declare_id!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv");
+}
+
 pub mod vertigo_program {
     use anchor_lang::declare_id;
     declare_id!("vrTGoBuy5rYSxAfV3jaRJWHH6nN9WK4NRExGxsk1bCJ"); // mainnet
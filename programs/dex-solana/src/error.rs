@@ -226,6 +226,111 @@ pub enum ErrorCode {
 
     #[msg("Invalid trim amount")]
     InvalidTrimAmount,
+
+    #[msg("Max amount in exceeded")]
+    MaxAmountInExceeded,
+
+    #[msg("Exact amount out not reached")]
+    ExactAmountOutNotReached,
+
+    #[msg("Dex does not support exact out")]
+    ExactOutNotSupported,
+
+    #[msg("Exact out route must be single hop")]
+    ExactOutRouteMustBeSingleHop,
+
+    #[msg("Exact out requires commission from output")]
+    ExactOutRequiresOutputCommission,
+
+    #[msg("total_amounts must be equal to amount_out")]
+    TotalAmountsMustBeEqualToAmountOut,
+
+    #[msg("Dexes and min_outs must have the same length")]
+    DexesAndMinOutsMustHaveTheSameLength,
+
+    #[msg("Dex min amount out not reached")]
+    DexMinAmountOutNotReached,
+
+    #[msg("Dexes and sqrt_price_limits must have the same length")]
+    DexesAndSqrtPriceLimitsMustHaveTheSameLength,
+
+    #[msg("Sqrt price limit is only supported by clmm dexes")]
+    SqrtPriceLimitNotSupported,
+
+    #[msg("Sqrt price limits are only allowed on the first hop")]
+    SqrtPriceLimitsOnlyAllowedOnFirstHop,
+
+    #[msg("Dex does not match the adapter")]
+    DexAdapterMismatch,
+
+    #[msg("Quote is simulation only, read the result from return data")]
+    QuoteOnly,
+
+    #[msg("Dexes and order_book_limits must have the same length")]
+    DexesAndOrderBookLimitsMustHaveTheSameLength,
+
+    #[msg("Order book limit is only supported by order book dexes")]
+    OrderBookLimitNotSupported,
+
+    #[msg("Order book limits are only allowed on the first hop")]
+    OrderBookLimitsOnlyAllowedOnFirstHop,
+
+    #[msg("Invalid order book price")]
+    InvalidOrderBookPrice,
+
+    #[msg("Invalid claim config account")]
+    InvalidClaimConfigAccount,
+
+    #[msg("Claim receiver is exist")]
+    ClaimReceiverIsExist,
+
+    #[msg("Claim receiver is not exist")]
+    ClaimReceiverIsNotExist,
+
+    #[msg("Exceed claim receiver limit")]
+    ExceedClaimReceiverLimit,
+
+    #[msg("Claim receiver is not allowed")]
+    InvalidClaimReceiver,
+
+    #[msg("Claim is paused")]
+    ClaimPaused,
+
+    #[msg("Unsupported bridge adaptor")]
+    UnsupportedBridgeAdaptor,
+
+    #[msg("Invalid bridge args")]
+    InvalidBridgeArgs,
+
+    #[msg("Caller is not the bridge program")]
+    InvalidBridgeCaller,
+
+    #[msg("Invalid commission split")]
+    InvalidCommissionSplit,
+
+    #[msg("Invalid pending admin")]
+    InvalidPendingAdmin,
+
+    #[msg("No pending admin")]
+    NoPendingAdmin,
+
+    #[msg("Invalid dust refund account")]
+    InvalidDustRefundAccount,
+
+    #[msg("Batch claim does not support transfer hook mints, use claim")]
+    TransferHookMintNotSupported,
+
+    #[msg("Route can not be quoted on chain")]
+    RouteNotQuotable,
+
+    #[msg("Routes with price limits require commission on the output side")]
+    PriceLimitsRequireOutputCommission,
+
+    #[msg("Adapter does not build a single swap instruction")]
+    SwapInstructionNotSupported,
+
+    #[msg("Per-dex limits require RouteV2 and a with_limits instruction")]
+    RouteLimitsRequireV2,
 }
 
 #[error_code]
@@ -295,4 +400,67 @@ pub enum LimitOrderError {
 
     #[msg("Invalid input token account")]
     InvalidInputTokenAccount,
+
+    #[msg("Invalid fill amount")]
+    InvalidFillAmount,
+
+    #[msg("Invalid pending admin")]
+    InvalidPendingAdmin,
+
+    #[msg("No pending admin")]
+    NoPendingAdmin,
+
+    #[msg("Resolver suspended")]
+    ResolverSuspended,
+
+    #[msg("Resolver mint not allowed")]
+    ResolverMintNotAllowed,
+
+    #[msg("Exceed resolver slot limit")]
+    ExceedResolverSlotLimit,
+
+    #[msg("Exceed resolver mint limit")]
+    ExceedResolverMintLimit,
+
+    #[msg("Invalid order trigger")]
+    InvalidOrderTrigger,
+
+    #[msg("Invalid oracle")]
+    InvalidOracle,
+
+    #[msg("Oracle price is stale")]
+    StaleOraclePrice,
+
+    #[msg("Oracle confidence too wide")]
+    OracleConfidenceTooWide,
+
+    #[msg("Trigger price not reached")]
+    TriggerPriceNotReached,
+
+    #[msg("Invalid slice count")]
+    InvalidSliceCount,
+
+    #[msg("Invalid interval")]
+    InvalidInterval,
+
+    #[msg("Slice not ready")]
+    SliceNotReady,
+
+    #[msg("Invalid order intent")]
+    InvalidIntent,
+
+    #[msg("Invalid intent signature")]
+    InvalidIntentSignature,
+
+    #[msg("Intent nonce already used")]
+    IntentNonceUsed,
+
+    #[msg("Invalid intent delegate")]
+    InvalidIntentDelegate,
+
+    #[msg("Invalid receiver")]
+    InvalidReceiver,
+
+    #[msg("Fill amount is below the minimum partial fill")]
+    FillAmountTooSmall,
 }
@@ -0,0 +1,141 @@
+use crate::constants::*;
+use crate::program::DexSolana;
+use crate::state::{config::*, event::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct InitClaimConfig<'info> {
+    /// Address to be set as claim config admin.
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Initialize config state account to store the claim authority and receivers.
+    #[account(
+        init,
+        seeds = [
+            CLAIM_CONFIG_SEED.as_bytes(),
+        ],
+        bump,
+        payer = admin,
+        space = ClaimConfig::LEN
+    )]
+    pub claim_config: AccountLoader<'info, ClaimConfig>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, DexSolana>,
+
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()))]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateClaimConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            CLAIM_CONFIG_SEED.as_bytes(),
+        ],
+        has_one = admin,
+        bump = claim_config.load()?.bump,
+    )]
+    pub claim_config: AccountLoader<'info, ClaimConfig>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptClaimConfigAdmin<'info> {
+    pub pending_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            CLAIM_CONFIG_SEED.as_bytes(),
+        ],
+        bump = claim_config.load()?.bump,
+    )]
+    pub claim_config: AccountLoader<'info, ClaimConfig>,
+}
+
+/// The claim authority starts as the `claim_authority` constant
+pub fn init_claim_config_handler(ctx: Context<InitClaimConfig>) -> Result<()> {
+    let claim_config = &mut ctx.accounts.claim_config.load_init()?;
+    let admin = *ctx.accounts.admin.key;
+    let claim_authority = claim_authority::id();
+    claim_config.bump = ctx.bumps.claim_config;
+    claim_config.admin = admin;
+    claim_config.claim_authority = claim_authority;
+    claim_config.pending_admin = Pubkey::default();
+    claim_config.padding = [0u8; 95];
+    emit!(InitClaimConfigEvent { admin, claim_authority });
+    Ok(())
+}
+
+/// The proposed admin takes over once it accepts
+pub fn propose_claim_config_admin_handler(
+    ctx: Context<UpdateClaimConfig>,
+    pending_admin: Pubkey,
+) -> Result<()> {
+    let claim_config = &mut ctx.accounts.claim_config.load_mut()?;
+    claim_config.propose_admin(pending_admin)?;
+    emit!(ProposeClaimConfigAdminEvent { admin: claim_config.admin, pending_admin });
+    Ok(())
+}
+
+pub fn accept_claim_config_admin_handler(ctx: Context<AcceptClaimConfigAdmin>) -> Result<()> {
+    let claim_config = &mut ctx.accounts.claim_config.load_mut()?;
+    let admin = ctx.accounts.pending_admin.key();
+    claim_config.accept_admin(admin)?;
+    emit!(SetClaimConfigAdminEvent { admin });
+    Ok(())
+}
+
+pub fn cancel_claim_config_admin_transfer_handler(ctx: Context<UpdateClaimConfig>) -> Result<()> {
+    let claim_config = &mut ctx.accounts.claim_config.load_mut()?;
+    let pending_admin = claim_config.cancel_admin_transfer()?;
+    emit!(CancelClaimConfigAdminTransferEvent { admin: claim_config.admin, pending_admin });
+    Ok(())
+}
+
+pub fn set_claim_authority_handler(
+    ctx: Context<UpdateClaimConfig>,
+    claim_authority: Pubkey,
+) -> Result<()> {
+    let claim_config = &mut ctx.accounts.claim_config.load_mut()?;
+    claim_config.set_claim_authority(claim_authority)?;
+    emit!(SetClaimAuthorityEvent { claim_authority });
+    Ok(())
+}
+
+pub fn add_claim_receiver_handler(ctx: Context<UpdateClaimConfig>, receiver: Pubkey) -> Result<()> {
+    let claim_config = &mut ctx.accounts.claim_config.load_mut()?;
+    claim_config.add_receiver(receiver)?;
+    emit!(AddClaimReceiverEvent { receiver });
+    Ok(())
+}
+
+pub fn remove_claim_receiver_handler(
+    ctx: Context<UpdateClaimConfig>,
+    receiver: Pubkey,
+) -> Result<()> {
+    let claim_config = &mut ctx.accounts.claim_config.load_mut()?;
+    claim_config.remove_receiver(receiver)?;
+    emit!(RemoveClaimReceiverEvent { receiver });
+    Ok(())
+}
+
+pub fn pause_claim_handler(ctx: Context<UpdateClaimConfig>) -> Result<()> {
+    let claim_config = &mut ctx.accounts.claim_config.load_mut()?;
+    claim_config.set_paused(true)?;
+    emit!(PauseClaimEvent { paused: true });
+    Ok(())
+}
+
+pub fn unpause_claim_handler(ctx: Context<UpdateClaimConfig>) -> Result<()> {
+    let claim_config = &mut ctx.accounts.claim_config.load_mut()?;
+    claim_config.set_paused(false)?;
+    emit!(PauseClaimEvent { paused: false });
+    Ok(())
+}
@@ -30,6 +30,7 @@ pub struct InitGlobalConfig<'info> {
     pub system_program: Program<'info, System>,
 }
 
+/// The admin only signs, so it can be a multisig PDA executing the instruction through CPI
 #[derive(Accounts)]
 pub struct UpdateGlobalConfig<'info> {
     pub admin: Signer<'info>,
@@ -45,36 +46,56 @@ pub struct UpdateGlobalConfig<'info> {
     pub global_config: AccountLoader<'info, GlobalConfig>,
 }
 
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub pending_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            GLOBAL_CONFIG_SEED.as_bytes(),
+        ],
+        bump = global_config.load()?.bump,
+    )]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+}
+
 pub fn init_global_config_handler(ctx: Context<InitGlobalConfig>, trade_fee: u64) -> Result<()> {
     let global_config = &mut ctx.accounts.global_config.load_init()?;
     let admin = *ctx.accounts.admin.key;
     global_config.bump = ctx.bumps.global_config;
     global_config.admin = admin;
     global_config.fee_multiplier = 10;
-    global_config.padding = [0u8; 127];
+    global_config.pending_admin = Pubkey::default();
+    global_config.padding = [0u8; 95];
     global_config.set_trade_fee(trade_fee)?;
     emit!(InitGlobalConfigEvent { admin, trade_fee });
     Ok(())
 }
 
-pub fn set_admin_handler(ctx: Context<UpdateGlobalConfig>, admin: Pubkey) -> Result<()> {
+/// The proposed admin takes over once it accepts
+pub fn propose_admin_handler(
+    ctx: Context<UpdateGlobalConfig>,
+    pending_admin: Pubkey,
+) -> Result<()> {
     let global_config = &mut ctx.accounts.global_config.load_mut()?;
-    global_config.set_admin(admin)?;
-    emit!(SetAdminEvent { admin });
+    global_config.propose_admin(pending_admin)?;
+    emit!(ProposeAdminEvent { admin: global_config.admin, pending_admin });
     Ok(())
 }
 
-pub fn add_resolver_handler(ctx: Context<UpdateGlobalConfig>, resolver: Pubkey) -> Result<()> {
+pub fn accept_admin_handler(ctx: Context<AcceptAdmin>) -> Result<()> {
     let global_config = &mut ctx.accounts.global_config.load_mut()?;
-    global_config.add_resolver(resolver)?;
-    emit!(AddResolverEvent { resolver });
+    let admin = ctx.accounts.pending_admin.key();
+    global_config.accept_admin(admin)?;
+    emit!(SetAdminEvent { admin });
     Ok(())
 }
 
-pub fn remove_resolver_handler(ctx: Context<UpdateGlobalConfig>, resolver: Pubkey) -> Result<()> {
+pub fn cancel_admin_transfer_handler(ctx: Context<UpdateGlobalConfig>) -> Result<()> {
     let global_config = &mut ctx.accounts.global_config.load_mut()?;
-    global_config.remove_resolver(resolver)?;
-    emit!(RemoveResolverEvent { resolver });
+    let pending_admin = global_config.cancel_admin_transfer()?;
+    emit!(CancelAdminTransferEvent { admin: global_config.admin, pending_admin });
     Ok(())
 }
 
@@ -1,2 +1,6 @@
+pub mod claim_config;
 pub mod global_config;
+pub mod resolver;
+pub use claim_config::*;
 pub use global_config::*;
+pub use resolver::*;
@@ -0,0 +1,182 @@
+use crate::constants::*;
+use crate::state::{config::*, event::*, resolver::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(resolver: Pubkey)]
+pub struct AddResolver<'info> {
+    /// Pays the rent of the resolver PDA, so a multisig admin only signs
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [
+            GLOBAL_CONFIG_SEED.as_bytes(),
+        ],
+        bump = global_config.load()?.bump,
+        has_one = admin,
+    )]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(
+        init,
+        seeds = [
+            RESOLVER_SEED.as_bytes(),
+            resolver.as_ref(),
+        ],
+        bump,
+        payer = payer,
+        space = Resolver::LEN
+    )]
+    pub resolver_pda: AccountLoader<'info, Resolver>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(resolver: Pubkey)]
+pub struct MigrateResolver<'info> {
+    /// Pays the rent of the resolver PDA, so a multisig admin only signs
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            GLOBAL_CONFIG_SEED.as_bytes(),
+        ],
+        bump = global_config.load()?.bump,
+        has_one = admin,
+    )]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(
+        init,
+        seeds = [
+            RESOLVER_SEED.as_bytes(),
+            resolver.as_ref(),
+        ],
+        bump,
+        payer = payer,
+        space = Resolver::LEN
+    )]
+    pub resolver_pda: AccountLoader<'info, Resolver>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(resolver: Pubkey)]
+pub struct UpdateResolver<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [
+            GLOBAL_CONFIG_SEED.as_bytes(),
+        ],
+        bump = global_config.load()?.bump,
+        has_one = admin,
+    )]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            RESOLVER_SEED.as_bytes(),
+            resolver.as_ref(),
+        ],
+        bump = resolver_pda.load()?.bump,
+    )]
+    pub resolver_pda: AccountLoader<'info, Resolver>,
+}
+
+#[derive(Accounts)]
+#[instruction(resolver: Pubkey)]
+pub struct RemoveResolver<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [
+            GLOBAL_CONFIG_SEED.as_bytes(),
+        ],
+        bump = global_config.load()?.bump,
+        has_one = admin,
+    )]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        close = rent_receiver,
+        seeds = [
+            RESOLVER_SEED.as_bytes(),
+            resolver.as_ref(),
+        ],
+        bump = resolver_pda.load()?.bump,
+    )]
+    pub resolver_pda: AccountLoader<'info, Resolver>,
+
+    /// CHECK: receives the rent of the resolver PDA
+    #[account(mut)]
+    pub rent_receiver: UncheckedAccount<'info>,
+}
+
+pub fn add_resolver_handler(
+    ctx: Context<AddResolver>,
+    resolver: Pubkey,
+    max_orders_per_slot: u16,
+    allowed_mints: Vec<Pubkey>,
+) -> Result<()> {
+    let resolver_pda = &mut ctx.accounts.resolver_pda.load_init()?;
+    resolver_pda.bump = ctx.bumps.resolver_pda;
+    resolver_pda.resolver = resolver;
+    resolver_pda.set_limits(max_orders_per_slot, &allowed_mints)?;
+    emit!(AddResolverEvent { resolver });
+    emit!(SetResolverLimitsEvent { resolver, max_orders_per_slot, allowed_mints });
+    Ok(())
+}
+
+/// Moves a resolver of the previous registry to its resolver PDA, without limits
+pub fn migrate_resolver_handler(ctx: Context<MigrateResolver>, resolver: Pubkey) -> Result<()> {
+    ctx.accounts.global_config.load_mut()?.take_deprecated_resolver(resolver)?;
+    let resolver_pda = &mut ctx.accounts.resolver_pda.load_init()?;
+    resolver_pda.bump = ctx.bumps.resolver_pda;
+    resolver_pda.resolver = resolver;
+    emit!(AddResolverEvent { resolver });
+    Ok(())
+}
+
+/// Replaces the limits of the resolver, an empty mint list allows every mint
+pub fn set_resolver_limits_handler(
+    ctx: Context<UpdateResolver>,
+    resolver: Pubkey,
+    max_orders_per_slot: u16,
+    allowed_mints: Vec<Pubkey>,
+) -> Result<()> {
+    let resolver_pda = &mut ctx.accounts.resolver_pda.load_mut()?;
+    resolver_pda.set_limits(max_orders_per_slot, &allowed_mints)?;
+    emit!(SetResolverLimitsEvent { resolver, max_orders_per_slot, allowed_mints });
+    Ok(())
+}
+
+pub fn suspend_resolver_handler(ctx: Context<UpdateResolver>, resolver: Pubkey) -> Result<()> {
+    let resolver_pda = &mut ctx.accounts.resolver_pda.load_mut()?;
+    resolver_pda.set_suspended(true)?;
+    emit!(SuspendResolverEvent { resolver });
+    Ok(())
+}
+
+pub fn resume_resolver_handler(ctx: Context<UpdateResolver>, resolver: Pubkey) -> Result<()> {
+    let resolver_pda = &mut ctx.accounts.resolver_pda.load_mut()?;
+    resolver_pda.set_suspended(false)?;
+    emit!(ResumeResolverEvent { resolver });
+    Ok(())
+}
+
+pub fn remove_resolver_handler(_ctx: Context<RemoveResolver>, resolver: Pubkey) -> Result<()> {
+    emit!(RemoveResolverEvent { resolver });
+    Ok(())
+}
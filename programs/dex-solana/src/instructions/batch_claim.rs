@@ -0,0 +1,163 @@
+use crate::error::ErrorCode;
+use crate::state::config::ClaimConfig;
+use crate::state::event::{BatchClaimEvent, ClaimTransfer};
+use crate::utils::{
+    associate_convert_token_account, close_token_account, create_ata_if_needed,
+    get_transfer_hook_program_id, is_token_account_initialized, transfer_token,
+};
+use crate::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenInterface};
+
+/// Remaining accounts of one claim: source SA token account, destination account, mint, token program
+pub const BATCH_CLAIM_ACCOUNTS_LEN: usize = 4;
+
+#[derive(Accounts)]
+pub struct BatchClaimAccounts<'info> {
+    /// Claim authority of the claim config
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK: receiver
+    #[account(mut)]
+    pub receiver: AccountInfo<'info>,
+
+    /// CHECK: sa authority
+    #[account(
+        mut,
+        address = authority_pda::id() @ ErrorCode::InvalidAuthorityPda
+    )]
+    pub sa_authority: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// CHECK: claim config PDA, the `claim_authority` constant claims until it is initialized
+    #[account(
+        seeds = [
+            CLAIM_CONFIG_SEED.as_bytes(),
+        ],
+        bump,
+    )]
+    pub claim_config: UncheckedAccount<'info>,
+}
+
+/// Sweeps every SA token account in the remaining accounts to the receiver's token accounts,
+/// creating missing ATAs and optionally closing the emptied SA accounts to the receiver.
+pub fn batch_claim_handler<'a>(
+    ctx: Context<'_, '_, 'a, 'a, BatchClaimAccounts<'a>>,
+    close_empty: bool,
+) -> Result<()> {
+    ClaimConfig::check_claim_account(
+        &ctx.accounts.claim_config,
+        ctx.accounts.signer.key(),
+        ctx.accounts.receiver.key(),
+    )?;
+
+    let remaining_accounts = ctx.remaining_accounts;
+    require!(
+        !remaining_accounts.is_empty()
+            && remaining_accounts.len().is_multiple_of(BATCH_CLAIM_ACCOUNTS_LEN),
+        ErrorCode::InvalidAccountsLength
+    );
+
+    let mut claims = Vec::with_capacity(remaining_accounts.len() / BATCH_CLAIM_ACCOUNTS_LEN);
+    for accounts in remaining_accounts.chunks(BATCH_CLAIM_ACCOUNTS_LEN) {
+        claims.push(claim_token(&ctx, accounts, close_empty)?);
+    }
+
+    emit!(BatchClaimEvent { receiver: ctx.accounts.receiver.key(), claims });
+    Ok(())
+}
+
+fn claim_token<'a>(
+    ctx: &Context<'_, '_, 'a, 'a, BatchClaimAccounts<'a>>,
+    accounts: &'a [AccountInfo<'a>],
+    close_empty: bool,
+) -> Result<ClaimTransfer> {
+    let [source_token_account, destination_token_account, token_mint, token_program] = accounts
+    else {
+        return Err(ErrorCode::InvalidAccountsLength.into());
+    };
+    let token_program = Interface::<TokenInterface>::try_from(token_program)
+        .map_err(|_| ErrorCode::InvalidTokenProgram)?;
+    let token_mint =
+        InterfaceAccount::<Mint>::try_from(token_mint).map_err(|_| ErrorCode::InvalidTokenMint)?;
+    require_keys_eq!(
+        *token_mint.to_account_info().owner,
+        token_program.key(),
+        ErrorCode::InvalidTokenProgram
+    );
+    // A claim tuple has no room for the extra accounts of a transfer hook
+    require!(
+        get_transfer_hook_program_id(&token_mint.to_account_info())?.is_none(),
+        ErrorCode::TransferHookMintNotSupported
+    );
+
+    let source_token_account = associate_convert_token_account(source_token_account)?;
+    require_keys_eq!(
+        source_token_account.owner,
+        authority_pda::ID,
+        ErrorCode::InvalidSourceTokenSa
+    );
+    require_keys_eq!(
+        source_token_account.mint,
+        token_mint.key(),
+        ErrorCode::InvalidSourceTokenSaMint
+    );
+
+    if !is_token_account_initialized(destination_token_account) {
+        create_ata_if_needed(
+            &ctx.accounts.receiver,
+            &ctx.accounts.signer,
+            destination_token_account,
+            &token_mint.to_account_info(),
+            &token_program.to_account_info(),
+            &ctx.accounts.associated_token_program.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+    }
+    let destination = associate_convert_token_account(destination_token_account)?;
+    require_keys_eq!(
+        destination.owner,
+        ctx.accounts.receiver.key(),
+        ErrorCode::InvalidDestinationTokenAccount
+    );
+    require_keys_eq!(destination.mint, token_mint.key(), ErrorCode::InvalidTokenMint);
+
+    let amount = source_token_account.amount;
+    if amount > 0 {
+        transfer_token(
+            ctx.accounts.sa_authority.to_account_info(),
+            source_token_account.to_account_info(),
+            destination_token_account.to_account_info(),
+            token_mint.to_account_info(),
+            token_program.to_account_info(),
+            amount,
+            token_mint.decimals,
+            Some(SA_AUTHORITY_SEED),
+            &[],
+        )?;
+    }
+
+    // The rent of the emptied SA account goes to the receiver
+    if close_empty {
+        close_token_account(
+            source_token_account.to_account_info(),
+            ctx.accounts.receiver.to_account_info(),
+            ctx.accounts.sa_authority.to_account_info(),
+            token_program.to_account_info(),
+            Some(SA_AUTHORITY_SEED),
+        )?;
+    }
+
+    msg!("claim mint: {}, amount: {}, closed: {}", token_mint.key(), amount, close_empty);
+    Ok(ClaimTransfer {
+        mint: token_mint.key(),
+        source_token_account: source_token_account.key(),
+        destination_token_account: destination_token_account.key(),
+        amount,
+        closed: close_empty,
+    })
+}
@@ -1,4 +1,5 @@
 use crate::error::ErrorCode;
+use crate::state::config::ClaimConfig;
 use crate::utils::{
     associate_convert_token_account, create_ata_if_needed, is_ata, is_token_account_initialized,
     log_claim_info_after, log_claim_info_before, transfer_sol, transfer_token,
@@ -9,10 +10,8 @@ use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 #[derive(Accounts)]
 pub struct ClaimAccounts<'info> {
-    #[account(
-        mut,
-        address = claim_authority::id() @ ErrorCode::InvalidSigner
-    )]
+    /// Claim authority of the claim config
+    #[account(mut)]
     pub signer: Signer<'info>,
 
     /// CHECK: receiver
@@ -46,9 +45,24 @@ pub struct ClaimAccounts<'info> {
     pub system_program: Program<'info, System>,
 
     pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+
+    /// CHECK: claim config PDA, the `claim_authority` constant claims until it is initialized
+    #[account(
+        seeds = [
+            CLAIM_CONFIG_SEED.as_bytes(),
+        ],
+        bump,
+    )]
+    pub claim_config: UncheckedAccount<'info>,
 }
 
 pub fn claim_handler<'a>(ctx: Context<'_, '_, 'a, 'a, ClaimAccounts<'a>>) -> Result<()> {
+    ClaimConfig::check_claim_account(
+        &ctx.accounts.claim_config,
+        ctx.accounts.signer.key(),
+        ctx.accounts.receiver.key(),
+    )?;
+
     let destination_account = ctx.accounts.destination_token_account.clone();
 
     if destination_account.is_some()
@@ -174,6 +188,7 @@ fn handle_token_claim<'a>(ctx: &Context<'_, '_, 'a, 'a, ClaimAccounts<'a>>) -> R
         amount,
         token_mint.decimals,
         Some(SA_AUTHORITY_SEED),
+        ctx.remaining_accounts,
     )?;
 
     source_token_account.reload()?;
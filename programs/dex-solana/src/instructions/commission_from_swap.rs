@@ -1,4 +1,4 @@
-use crate::instructions::from_swap::cpi_bridge_to_log;
+use crate::instructions::from_swap::{cpi_bridge_to_log, split_bridge_accounts_at};
 use crate::processor::swap_processor::SwapProcessor;
 use crate::{
     BridgeToArgs, CommissionProcessor, SwapArgs, common_commission_sol_swap,
@@ -56,6 +56,8 @@ pub fn commission_sol_from_swap_handler<'a>(
     offset: u8,
     len: u8,
 ) -> Result<()> {
+    let (swap_accounts, bridge_accounts) =
+        split_bridge_accounts_at(&bridge_to_args, ctx.remaining_accounts, offset, len)?;
     // 1. Commission swap
     let swap_processor = &SwapProcessor;
     let commission_processor = &CommissionProcessor;
@@ -76,7 +78,7 @@ pub fn commission_sol_from_swap_handler<'a>(
         &None,
         &None,
         &None,
-        ctx.remaining_accounts,
+        swap_accounts,
         args,
         bridge_to_args.order_id,
         commission_rate,
@@ -84,14 +86,13 @@ pub fn commission_sol_from_swap_handler<'a>(
         &ctx.accounts.commission_account,
         None, // trim_account
         None, // platform_fee_rate
-    )?;
+    )?
+    .amount_out;
 
     // 3. CPI bridge_to_log
     cpi_bridge_to_log(
         bridge_to_args,
         amount_out,
-        offset,
-        len,
         &ctx.accounts.bridge_program,
         &ctx.accounts.payer,
         &ctx.accounts.destination_token_account,
@@ -100,7 +101,7 @@ pub fn commission_sol_from_swap_handler<'a>(
         &ctx.accounts.token_program,
         &ctx.accounts.token_2022_program,
         &ctx.accounts.system_program,
-        &ctx.remaining_accounts,
+        bridge_accounts,
     )?;
     Ok(())
 }
@@ -152,6 +153,8 @@ pub fn commission_spl_from_swap_handler<'a>(
     offset: u8,
     len: u8,
 ) -> Result<()> {
+    let (swap_accounts, bridge_accounts) =
+        split_bridge_accounts_at(&bridge_to_args, ctx.remaining_accounts, offset, len)?;
     let commission_token_program =
         if *ctx.accounts.source_mint.to_account_info().owner == Token2022::id() {
             ctx.accounts.token_2022_program.to_account_info()
@@ -179,7 +182,7 @@ pub fn commission_spl_from_swap_handler<'a>(
         &None,
         &None,
         &None,
-        ctx.remaining_accounts,
+        swap_accounts,
         args,
         bridge_to_args.order_id,
         commission_rate,
@@ -189,14 +192,13 @@ pub fn commission_spl_from_swap_handler<'a>(
         commission_token_program,
         None, // trim_token_program
         None, // platform_fee_rate
-    )?;
+    )?
+    .amount_out;
 
     // 2. CPI bridge_to_log
     cpi_bridge_to_log(
         bridge_to_args,
         amount_out,
-        offset,
-        len,
         &ctx.accounts.bridge_program,
         &ctx.accounts.payer,
         &ctx.accounts.destination_token_account,
@@ -205,7 +207,7 @@ pub fn commission_spl_from_swap_handler<'a>(
         &ctx.accounts.token_program,
         &ctx.accounts.token_2022_program,
         &ctx.accounts.system_program,
-        &ctx.remaining_accounts,
+        bridge_accounts,
     )?;
     Ok(())
 }
@@ -1,7 +1,8 @@
 use crate::constants::*;
 use crate::processor::proxy_swap_processor::ProxySwapProcessor;
 use crate::{
-    CommissionProcessor, SwapArgs, common_commission_sol_swap, common_commission_token_swap,
+    CommissionProcessor, SwapArgs, SwapResult, common_commission_sol_swap,
+    common_commission_token_swap,
 };
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
@@ -60,11 +61,11 @@ pub fn commission_sol_proxy_swap_handler<'a>(
     commission_rate: u16,
     commission_direction: bool,
     order_id: u64,
-) -> Result<()> {
+) -> Result<SwapResult> {
     let swap_processor = &ProxySwapProcessor;
     let commission_processor = &CommissionProcessor;
 
-    common_commission_sol_swap(
+    let result = common_commission_sol_swap(
         swap_processor,
         commission_processor,
         &ctx.accounts.payer,
@@ -90,7 +91,7 @@ pub fn commission_sol_proxy_swap_handler<'a>(
         None, // trim_account
         None, // platform_fee_rate
     )?;
-    Ok(())
+    Ok(SwapResult::V1(result))
 }
 
 #[derive(Accounts)]
@@ -145,7 +146,7 @@ pub fn commission_spl_proxy_swap_handler<'a>(
     commission_rate: u16,
     commission_direction: bool,
     order_id: u64,
-) -> Result<()> {
+) -> Result<SwapResult> {
     let commission_token_program = if commission_direction {
         ctx.accounts.source_token_program.as_ref().unwrap().to_account_info()
     } else {
@@ -154,7 +155,7 @@ pub fn commission_spl_proxy_swap_handler<'a>(
     let swap_processor = &ProxySwapProcessor;
     let commission_processor = &CommissionProcessor;
 
-    common_commission_token_swap(
+    let result = common_commission_token_swap(
         swap_processor,
         commission_processor,
         &ctx.accounts.payer,
@@ -182,5 +183,5 @@ pub fn commission_spl_proxy_swap_handler<'a>(
         None, // trim_token_program
         None, // platform_fee_rate
     )?;
-    Ok(())
+    Ok(SwapResult::V1(result))
 }
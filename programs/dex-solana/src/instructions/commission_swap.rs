@@ -4,7 +4,7 @@ use crate::processor::swap_processor::SwapProcessor;
 use crate::utils::token::{close_token_account, transfer_sol, transfer_token};
 use crate::{
     COMMISSION_DENOMINATOR, COMMISSION_RATE_LIMIT, CommissionSwapArgs, CommonCommissionProcessor,
-    SwapArgs, common_commission_sol_swap,
+    SwapArgs, SwapResult, common_commission_sol_swap,
 };
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
@@ -31,7 +31,7 @@ impl<'info> CommonCommissionProcessor<'info> for CommissionProcessor {
         _source_token_sa: &mut Option<UncheckedAccount<'info>>, // is not required
         _destination_token_sa: &mut Option<UncheckedAccount<'info>>, // is not required
         _platform_fee_rate: Option<u16>,                        // is not required
-    ) -> Result<()> {
+    ) -> Result<(u64, u64, u64)> {
         require!(
             commission_rate > 0 && commission_rate <= COMMISSION_RATE_LIMIT,
             ErrorCode::InvalidCommissionRate
@@ -80,7 +80,7 @@ impl<'info> CommonCommissionProcessor<'info> for CommissionProcessor {
             None,
         )?;
         log_commission_info(commission_direction, commission_amount);
-        Ok(())
+        Ok((commission_amount, 0, 0))
     }
 
     fn commission_token_process(
@@ -102,7 +102,8 @@ impl<'info> CommonCommissionProcessor<'info> for CommissionProcessor {
         _source_token_sa: &mut Option<UncheckedAccount<'info>>, // is not required
         _destination_token_sa: &mut Option<UncheckedAccount<'info>>, // is not required
         _platform_fee_rate: Option<u16>,
-    ) -> Result<()> {
+        hook_accounts: &[AccountInfo<'info>],
+    ) -> Result<(u64, u64, u64)> {
         require!(
             commission_rate > 0 && commission_rate <= COMMISSION_RATE_LIMIT,
             ErrorCode::InvalidCommissionRate
@@ -128,6 +129,7 @@ impl<'info> CommonCommissionProcessor<'info> for CommissionProcessor {
                 commission_amount,
                 source_mint.decimals,
                 None,
+                hook_accounts,
             )?;
             commission_amount
         } else {
@@ -151,11 +153,12 @@ impl<'info> CommonCommissionProcessor<'info> for CommissionProcessor {
                 commission_amount,
                 destination_mint.decimals,
                 None,
+                hook_accounts,
             )?;
             commission_amount
         };
         log_commission_info(commission_direction, commission_amount);
-        Ok(())
+        Ok((commission_amount, 0, 0))
     }
 }
 
@@ -191,7 +194,7 @@ pub fn commission_sol_swap_handler<'a>(
     ctx: Context<'_, '_, 'a, 'a, CommissionSOLAccounts<'a>>,
     args: CommissionSwapArgs,
     order_id: u64,
-) -> Result<()> {
+) -> Result<SwapResult> {
     let swap_args = SwapArgs {
         amount_in: args.amount_in,
         expect_amount_out: args.expect_amount_out,
@@ -202,7 +205,7 @@ pub fn commission_sol_swap_handler<'a>(
     let swap_processor = &SwapProcessor;
     let commission_processor = &CommissionProcessor;
 
-    common_commission_sol_swap(
+    let result = common_commission_sol_swap(
         swap_processor,
         commission_processor,
         &ctx.accounts.payer,
@@ -228,7 +231,7 @@ pub fn commission_sol_swap_handler<'a>(
         None, // trim_account
         None, // platform_fee_rate
     )?;
-    Ok(())
+    Ok(SwapResult::V1(result))
 }
 
 #[derive(Accounts)]
@@ -265,7 +268,7 @@ pub fn commission_spl_swap_handler<'a>(
     ctx: Context<'_, '_, 'a, 'a, CommissionSPLAccounts<'a>>,
     args: CommissionSwapArgs,
     order_id: u64,
-) -> Result<()> {
+) -> Result<SwapResult> {
     let swap_args = SwapArgs {
         amount_in: args.amount_in,
         expect_amount_out: args.expect_amount_out,
@@ -276,7 +279,7 @@ pub fn commission_spl_swap_handler<'a>(
     let swap_processor = &SwapProcessor;
     let commission_processor = &CommissionProcessor;
 
-    common_commission_token_swap(
+    let result = common_commission_token_swap(
         swap_processor,
         commission_processor,
         &ctx.accounts.payer,
@@ -304,5 +307,5 @@ pub fn commission_spl_swap_handler<'a>(
         None, // trim_token_program
         None, // platform_fee_rate
     )?;
-    Ok(())
+    Ok(SwapResult::V1(result))
 }
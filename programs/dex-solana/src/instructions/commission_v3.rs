@@ -101,7 +101,8 @@ pub fn common_commission_token_swap_v3<
         None,
         None,
         None,
-    )?;
+    )?
+    .amount_out;
 
     // 2. Commission token
     commission_processor.commission_process_v3(
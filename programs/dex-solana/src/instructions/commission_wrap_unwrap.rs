@@ -4,7 +4,8 @@ use crate::utils::logging::{
 };
 use crate::utils::token::{close_token_account, sync_wsol_account, transfer_sol, transfer_token};
 use crate::{
-    COMMISSION_DENOMINATOR, COMMISSION_RATE_LIMIT, SEED_TEMP_WSOL, system_program, wsol_program,
+    COMMISSION_DENOMINATOR, COMMISSION_RATE_LIMIT, SEED_TEMP_WSOL, SwapResult, SwapResultV1,
+    system_program, wsol_program,
 };
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
@@ -63,7 +64,7 @@ pub fn commission_wrap_unwrap_handler<'a>(
     ctx: Context<'_, '_, 'a, 'a, CommissionWrapUnwrapAccounts<'a>>,
     args: CommissionWrapUnwrapArgs,
     order_id: u64,
-) -> Result<()> {
+) -> Result<SwapResult> {
     // CHECK: CommissionSwapArgs
     require!(args.commission_rate <= COMMISSION_RATE_LIMIT, ErrorCode::InvalidCommissionRate);
 
@@ -152,12 +153,19 @@ pub fn commission_wrap_unwrap_handler<'a>(
             commission_amount,
             ctx.accounts.wsol_mint.decimals,
             None,
+            &[],
         )?;
     }
 
     log_commission_info(args.commission_direction, commission_amount);
 
-    Ok(())
+    Ok(SwapResult::V1(SwapResultV1::new(args.amount_in, amount_out).with_fees(
+        args.commission_direction,
+        commission_amount,
+        0,
+        0,
+        0,
+    )?))
 }
 
 pub fn wrap_process<'info>(
@@ -190,6 +198,7 @@ pub fn unwrap_process<'info>(
         amount,
         wsol_mint.decimals,
         None,
+        &[],
     )?;
 
     close_token_account(
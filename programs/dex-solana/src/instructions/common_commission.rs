@@ -1,6 +1,6 @@
 use crate::instructions::common_swap::Route;
 use crate::processor::common_processor::CommonSwapProcessor;
-use crate::{SwapArgs, common_swap};
+use crate::{SwapArgs, SwapResultV1, common_swap};
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
@@ -16,6 +16,7 @@ pub struct CommissionSwapArgs {
     pub commission_direction: bool, // Commission direction: true-fromToken, false-toToken
 }
 
+/// Processes return the (commission_amount, platform_fee_amount, trim_amount) charged
 pub trait CommonCommissionProcessor<'info> {
     fn commission_sol_process(
         &self,
@@ -34,8 +35,8 @@ pub trait CommonCommissionProcessor<'info> {
         _source_token_sa: &mut Option<UncheckedAccount<'info>>,
         _destination_token_sa: &mut Option<UncheckedAccount<'info>>,
         _platform_fee_rate: Option<u16>,
-    ) -> Result<()> {
-        Ok(())
+    ) -> Result<(u64, u64, u64)> {
+        Ok((0, 0, 0))
     }
 
     fn commission_token_process(
@@ -57,8 +58,9 @@ pub trait CommonCommissionProcessor<'info> {
         _source_token_sa: &mut Option<UncheckedAccount<'info>>,
         _destination_token_sa: &mut Option<UncheckedAccount<'info>>,
         _platform_fee_rate: Option<u16>,
-    ) -> Result<()> {
-        Ok(())
+        _hook_accounts: &[AccountInfo<'info>],
+    ) -> Result<(u64, u64, u64)> {
+        Ok((0, 0, 0))
     }
 }
 
@@ -91,12 +93,12 @@ pub fn common_commission_sol_swap<
     commission_account: &AccountInfo<'info>,
     trim_account: Option<&AccountInfo<'info>>,
     platform_fee_rate: Option<u16>,
-) -> Result<u64> {
+) -> Result<SwapResultV1> {
     let amount_in = args.amount_in;
     let expect_amount_out = args.expect_amount_out;
 
     // 1. Swap
-    let amount_out = common_swap(
+    let swap_result = common_swap(
         swap_processor,
         payer,
         owner,
@@ -121,24 +123,31 @@ pub fn common_commission_sol_swap<
     )?;
 
     // 2. Commission SOL
-    commission_processor.commission_sol_process(
-        amount_in,
-        amount_out,
-        expect_amount_out,
-        commission_rate,
+    let (commission_amount, platform_fee_amount, trim_amount) = commission_processor
+        .commission_sol_process(
+            amount_in,
+            swap_result.amount_out,
+            expect_amount_out,
+            commission_rate,
+            commission_direction,
+            payer,
+            commission_account,
+            trim_account,
+            source_mint,
+            destination_mint,
+            destination_token_account,
+            destination_token_program,
+            source_token_sa,
+            destination_token_sa,
+            platform_fee_rate,
+        )?;
+    swap_result.with_fees(
         commission_direction,
-        payer,
-        commission_account,
-        trim_account,
-        source_mint,
-        destination_mint,
-        destination_token_account,
-        destination_token_program,
-        source_token_sa,
-        destination_token_sa,
-        platform_fee_rate,
-    )?;
-    Ok(amount_out)
+        commission_amount,
+        platform_fee_amount,
+        trim_amount,
+        0,
+    )
 }
 
 pub fn common_commission_token_swap<
@@ -172,12 +181,12 @@ pub fn common_commission_token_swap<
     commission_token_program: AccountInfo<'info>,
     trim_token_program: Option<AccountInfo<'info>>,
     platform_fee_rate: Option<u16>,
-) -> Result<u64> {
+) -> Result<SwapResultV1> {
     let amount_in = args.amount_in;
     let expect_amount_out = args.expect_amount_out;
 
     // 1. Swap
-    let amount_out = common_swap(
+    let swap_result = common_swap(
         swap_processor,
         payer,
         owner,
@@ -202,24 +211,32 @@ pub fn common_commission_token_swap<
     )?;
 
     // 2. Commission token
-    commission_processor.commission_token_process(
-        amount_in,
-        amount_out,
-        expect_amount_out,
-        commission_rate,
+    let (commission_amount, platform_fee_amount, trim_amount) = commission_processor
+        .commission_token_process(
+            amount_in,
+            swap_result.amount_out,
+            expect_amount_out,
+            commission_rate,
+            commission_direction,
+            payer,
+            commission_token_account,
+            trim_token_account,
+            source_token_account,
+            destination_token_account,
+            source_mint,
+            destination_mint,
+            commission_token_program,
+            trim_token_program,
+            source_token_sa,
+            destination_token_sa,
+            platform_fee_rate,
+            remaining_accounts,
+        )?;
+    swap_result.with_fees(
         commission_direction,
-        payer,
-        commission_token_account,
-        trim_token_account,
-        source_token_account,
-        destination_token_account,
-        source_mint,
-        destination_mint,
-        commission_token_program,
-        trim_token_program,
-        source_token_sa,
-        destination_token_sa,
-        platform_fee_rate,
-    )?;
-    Ok(amount_out)
+        commission_amount,
+        platform_fee_amount,
+        trim_amount,
+        0,
+    )
 }
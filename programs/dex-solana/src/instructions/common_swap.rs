@@ -1,13 +1,14 @@
+use crate::adapters::common::SwapContext;
 use crate::adapters::*;
 use crate::constants::*;
 use crate::error::ErrorCode;
+use crate::instructions::{ForkQuote, SwapResultV1};
 use crate::processor::*;
 use crate::utils::*;
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-
 #[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, Debug, strum::Display)]
 pub enum Dex {
     SplTokenSwap,
@@ -113,25 +114,38 @@ pub enum Dex {
     MeteoraDAMMV2Swap2,
 }
 
-
 //EXAMPLe FLow
 //SWAP : USDC -> sol -> bonk (2-hops)
 //HOP1: (USDC -> SOL)
 // last_to_account: 0x000.... (no previous hop)
 // from_account: User's USDC account
-// to_account: Intermediate sol account 
+// to_account: Intermediate sol account
 
-//HOP2： (SOL -> BONK) 
+//HOP2： (SOL -> BONK)
 // last_to_account: Intermediate SOL account
 // from_account: Intermediate SOL account
 // to_account: User's BONK account
 
-
 #[derive(Debug)]
 pub struct HopAccounts {
     pub last_to_account: Pubkey,
     pub from_account: Pubkey,
     pub to_account: Pubkey,
+    pub min_amount_out: u64,    // min out of the current dex, 0 if not set
+    pub sqrt_price_limit: u128, // sqrt price limit of the current clmm dex, 0 if not set
+    pub order_book_limit: OrderBookLimit, // limit of the current order book dex, default if not set
+    pub dust_amount_in: u64,    // sub-lot remainder left unswapped by the current order book dex
+    pub transfer_fee: u64,      // Token-2022 transfer fee withheld from the current dex output
+}
+
+/// Worst acceptable price and match limit of an order book fork, in the venue's native units:
+/// - OpenBook V2: price in quote lots per base lot
+/// - Phoenix: price in ticks
+/// - Manifest: quote atoms per base atom scaled by MANIFEST_PRICE_SCALE, no match limit
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct OrderBookLimit {
+    pub price: u64,      // worst acceptable price, 0 if not set
+    pub match_limit: u8, // max number of orders matched, 0 for the adapter default
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
@@ -145,7 +159,7 @@ pub struct HopAccounts {
 //2. split route (2 dexes)
 // Route {
 // dexes: vec![Dex::raydiumSwap, Dex:meteror]
-// weights: vec![60,40] 60% Raydium 40% meteora 
+// weights: vec![60,40] 60% Raydium 40% meteora
 // }
 
 pub struct Route {
@@ -162,6 +176,78 @@ pub struct SwapArgs {
     pub routes: Vec<Vec<Route>>, // 2nd level split route
 }
 
+/// Route with optional per-dex limits, taken by the `*_with_limits` instructions
+// RouteV2 {
+// dexes: vec![Dex::RaydiumSwap, Dex::Whirlpool]
+// weights: vec![60, 40]
+// min_outs: Some(vec![590, 390]) // optional min out of each dex
+// sqrt_price_limits: Some(vec![0, 0]) // optional sqrt price limit of each clmm dex, 0 if not set
+// order_book_limits: None // optional worst price and match limit of each order book dex
+// }
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct RouteV2 {
+    pub dexes: Vec<Dex>,
+    pub weights: Vec<u8>,
+    pub min_outs: Option<Vec<u64>>,
+    pub sqrt_price_limits: Option<Vec<u128>>,
+    pub order_book_limits: Option<Vec<OrderBookLimit>>,
+}
+
+impl From<Route> for RouteV2 {
+    fn from(route: Route) -> Self {
+        Self {
+            dexes: route.dexes,
+            weights: route.weights,
+            min_outs: None,
+            sqrt_price_limits: None,
+            order_book_limits: None,
+        }
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct SwapArgsV2 {
+    pub amount_in: u64,
+    pub expect_amount_out: u64,
+    pub min_return: u64,
+    pub amounts: Vec<u64>,         // 1st level split amount
+    pub routes: Vec<Vec<RouteV2>>, // 2nd level split route
+}
+
+impl From<SwapArgs> for SwapArgsV2 {
+    fn from(args: SwapArgs) -> Self {
+        Self {
+            amount_in: args.amount_in,
+            expect_amount_out: args.expect_amount_out,
+            min_return: args.min_return,
+            amounts: args.amounts,
+            routes: args
+                .routes
+                .into_iter()
+                .map(|hops| hops.into_iter().map(RouteV2::from).collect())
+                .collect(),
+        }
+    }
+}
+
+impl SwapArgsV2 {
+    /// Whether a fork may stop at its sqrt price or order book limit and leave input unfilled
+    pub(crate) fn has_price_limits(&self) -> bool {
+        self.routes.iter().any(|hops| {
+            hops.iter()
+                .any(|route| route.sqrt_price_limits.is_some() || route.order_book_limits.is_some())
+        })
+    }
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct SwapExactOutArgs {
+    pub amount_out: u64,         // Exact amount received by the user
+    pub max_amount_in: u64,      // Maximum amount spent by the user
+    pub amounts: Vec<u64>,       // 1st level split of amount_out
+    pub routes: Vec<Vec<Route>>, // 2nd level split route, single hop only
+}
+
 #[event]
 pub struct SwapEvent {
     pub dex: Dex,
@@ -201,7 +287,7 @@ pub fn common_swap<'info, T: CommonSwapProcessor<'info>>(
     fee_rate: Option<u32>,
     fee_direction: Option<bool>,
     fee_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
-) -> Result<u64> {
+) -> Result<SwapResultV1> {
     log_swap_basic_info(
         order_id,
         &source_mint.key(),
@@ -246,7 +332,8 @@ pub fn common_swap<'info, T: CommonSwapProcessor<'info>>(
         system_program,
     )?;
 
-    // before swap hook
+    // before swap hook, the source SA may withhold a Token-2022 transfer fee
+    let before_source_withheld = get_withheld_amount(&source_account.to_account_info())?;
     let real_amount_in = swap_processor.before_swap(
         owner,
         source_token_account,
@@ -258,21 +345,42 @@ pub fn common_swap<'info, T: CommonSwapProcessor<'info>>(
         fee_rate,
         fee_direction,
         fee_token_account,
+        remaining_accounts,
     )?;
+    let transfer_fee_in = get_withheld_amount(&source_account.to_account_info())?
+        .checked_sub(before_source_withheld)
+        .ok_or(ErrorCode::CalculationError)?;
+    log_transfer_fee_info(true, transfer_fee_in);
 
     // Common swap
-    let amount_out = execute_swap(
+    let (amount_out, unfilled_amount_in) = execute_swap(
         &mut source_account,
         &mut destination_account,
         remaining_accounts,
-        args,
+        args.into(),
         real_amount_in,
+        transfer_fee_in,
         order_id,
         source_token_sa.is_some(),
         owner_seeds,
         Some(payer),
+        None,
     )?;
 
+    // Refund the unfilled amount of partially filled forks and order book dust held by the source SA
+    if unfilled_amount_in > 0 {
+        ProxySwapProcessor.proxy_handle_refund(
+            sa_authority,
+            source_token_account,
+            source_mint,
+            source_token_sa,
+            source_token_program,
+            unfilled_amount_in,
+            Some(SA_AUTHORITY_SEED),
+            remaining_accounts,
+        )?;
+    }
+
     // after swap hook
     swap_processor.after_swap(
         sa_authority,
@@ -285,6 +393,7 @@ pub fn common_swap<'info, T: CommonSwapProcessor<'info>>(
         fee_rate,
         fee_direction,
         fee_token_account,
+        remaining_accounts,
     )?;
 
     // source token account has been closed in pumpfun buy
@@ -313,7 +422,7 @@ pub fn common_swap<'info, T: CommonSwapProcessor<'info>>(
 
     // Check min return
     require!(destination_token_change >= min_return, ErrorCode::MinReturnNotReached);
-    Ok(destination_token_change)
+    Ok(SwapResultV1::new(source_token_change, destination_token_change))
 }
 
 pub fn common_swap_v3<'info, T: PlatformFeeV3Processor<'info>>(
@@ -331,12 +440,13 @@ pub fn common_swap_v3<'info, T: PlatformFeeV3Processor<'info>>(
     associated_token_program: &Option<Program<'info, AssociatedToken>>,
     system_program: &Option<Program<'info, System>>,
     remaining_accounts: &'info [AccountInfo<'info>],
-    args: SwapArgs,
+    args: SwapArgsV2,
     order_id: u64,
     // COMMISSION
     commission_rate: u32,
     commission_direction: bool,
     commission_account: &Option<AccountInfo<'info>>,
+    commission_split: Option<&CommissionSplit<'_, 'info>>,
     // PLATFORM FEE
     platform_fee_rate: Option<u16>,
     platform_fee_account: &Option<AccountInfo<'info>>,
@@ -346,7 +456,7 @@ pub fn common_swap_v3<'info, T: PlatformFeeV3Processor<'info>>(
     trim_account: Option<&AccountInfo<'info>>,
     charge_account: Option<&AccountInfo<'info>>,
     acc_close_flag: bool,
-) -> Result<u64> {
+) -> Result<SwapResultV1> {
     log_swap_basic_info(
         order_id,
         &source_mint.key(),
@@ -367,6 +477,12 @@ pub fn common_swap_v3<'info, T: PlatformFeeV3Processor<'info>>(
         min_return,
     );
 
+    // Commission on the input side would be charged on the unfilled amount refunded after the swap
+    require!(
+        commission_rate == 0 || !commission_direction || !args.has_price_limits(),
+        ErrorCode::PriceLimitsRequireOutputCommission
+    );
+
     // Verify sa_authority is valid
     if sa_authority.is_some() {
         require!(
@@ -391,8 +507,9 @@ pub fn common_swap_v3<'info, T: PlatformFeeV3Processor<'info>>(
         system_program,
     )?;
 
-    // before swap hook
-    let real_amount_in = swap_processor.before_swap(
+    // before swap hook, the source SA may withhold a Token-2022 transfer fee
+    let before_source_withheld = get_withheld_amount(&source_account.to_account_info())?;
+    let (real_amount_in, before_fees) = swap_processor.before_swap(
         payer,
         sa_authority,
         source_token_account,
@@ -403,26 +520,48 @@ pub fn common_swap_v3<'info, T: PlatformFeeV3Processor<'info>>(
         commission_rate,
         commission_direction,
         commission_account,
+        commission_split,
         platform_fee_rate,
         platform_fee_account,
+        remaining_accounts,
     )?;
+    let transfer_fee_in = get_withheld_amount(&source_account.to_account_info())?
+        .checked_sub(before_source_withheld)
+        .ok_or(ErrorCode::CalculationError)?;
+    log_transfer_fee_info(true, transfer_fee_in);
 
     // Common swap
     let expected_amount_out = args.expect_amount_out;
-    let amount_out = execute_swap(
+    let (amount_out, unfilled_amount_in) = execute_swap(
         &mut source_account,
         &mut destination_account,
         remaining_accounts,
         args,
         real_amount_in,
+        transfer_fee_in,
         order_id,
         source_token_sa.is_some(),
         None,
         Some(payer),
+        None,
     )?;
 
+    // Refund the unfilled amount of partially filled forks and order book dust held by the source SA
+    if unfilled_amount_in > 0 {
+        ProxySwapProcessor.proxy_handle_refund(
+            sa_authority,
+            source_token_account,
+            source_mint,
+            source_token_sa,
+            source_token_program,
+            unfilled_amount_in,
+            Some(SA_AUTHORITY_SEED),
+            remaining_accounts,
+        )?;
+    }
+
     // after swap hook
-    let actual_amount_out = swap_processor.after_swap(
+    let (actual_amount_out, after_fees) = swap_processor.after_swap(
         payer,
         sa_authority,
         destination_token_account,
@@ -434,6 +573,7 @@ pub fn common_swap_v3<'info, T: PlatformFeeV3Processor<'info>>(
         commission_rate,
         commission_direction,
         commission_account,
+        commission_split,
         platform_fee_rate,
         platform_fee_account,
         trim_rate,
@@ -441,6 +581,7 @@ pub fn common_swap_v3<'info, T: PlatformFeeV3Processor<'info>>(
         trim_account,
         charge_account,
         acc_close_flag,
+        remaining_accounts,
     )?;
 
     // source token account has been closed in pumpfun buy
@@ -478,26 +619,40 @@ pub fn common_swap_v3<'info, T: PlatformFeeV3Processor<'info>>(
 
     // Check min return
     require!(destination_token_change >= min_return, ErrorCode::MinReturnNotReached);
-    Ok(destination_token_change)
+    let amount_in_consumed =
+        real_amount_in.checked_sub(unfilled_amount_in).ok_or(ErrorCode::CalculationError)?;
+    Ok(SwapResultV1::new(amount_in_consumed, amount_out)
+        .with_charged_fees(actual_amount_out, before_fees.checked_add(after_fees)?))
 }
 
-fn execute_swap<'info>(
+pub(crate) fn execute_swap<'info>(
     source_account: &mut InterfaceAccount<'info, TokenAccount>,
     destination_account: &mut InterfaceAccount<'info, TokenAccount>,
     remaining_accounts: &'info [AccountInfo<'info>],
-    args: SwapArgs,
+    args: SwapArgsV2,
     real_amount_in: u64,
+    transfer_fee_in: u64,
     order_id: u64,
     proxy_from: bool,
     owner_seeds: Option<&[&[&[u8]]]>,
     payer: Option<&AccountInfo<'info>>,
-) -> Result<u64> {
+    mut fork_quotes: Option<&mut Vec<ForkQuote>>,
+) -> Result<(u64, u64)> {
     //The reload() function refreshes the account data from the blockchain to ensure you have the most current state.
     destination_account.reload()?;
     let before_destination_balance = destination_account.amount;
 
+    // Forks with a sqrt price or order book limit may be partially filled
+    let partial_fill = args.has_price_limits();
+    let before_source_balance = if partial_fill {
+        source_account.reload()?;
+        source_account.amount
+    } else {
+        0
+    };
+
     // Check SwapArgs
-    let SwapArgs { amount_in: _, min_return, expect_amount_out, amounts, routes } = &args;
+    let SwapArgsV2 { amount_in: _, min_return, expect_amount_out, amounts, routes } = &args;
     require!(real_amount_in > 0, ErrorCode::AmountInMustBeGreaterThanZero);
     require!(*min_return > 0, ErrorCode::MinReturnMustBeGreaterThanZero);
     require!(*expect_amount_out >= *min_return, ErrorCode::InvalidExpectAmountOut);
@@ -511,12 +666,17 @@ fn execute_swap<'info>(
         .try_fold(0u64, |acc, &x| acc.checked_add(x).ok_or(ErrorCode::CalculationError))?;
     require!(total_amounts == real_amount_in, ErrorCode::TotalAmountsMustBeEqualToAmountIn);
 
+    // Amounts are split before the Token-2022 transfer fee withheld by the source account
+    let net_amount_in =
+        real_amount_in.checked_sub(transfer_fee_in).ok_or(ErrorCode::CalculationError)?;
+    require!(net_amount_in > 0, ErrorCode::AmountInMustBeGreaterThanZero);
+    let amounts = deduct_transfer_fee(amounts, real_amount_in, net_amount_in)?;
 
     //output routes
-    //1. Total routes count =1 
+    //1. Total routes count =1
     msg!("Total routes count: {}", routes.len());
     for (i, hops) in routes.iter().enumerate() {
-        //Route 0: 1 hops 
+        //Route 0: 1 hops
         msg!("Route {}: {} hops", i, hops.len());
         for (j, route) in hops.iter().enumerate() {
             // Hop 0: dexes=[RaydiumSwap], weights = [100]
@@ -526,6 +686,8 @@ fn execute_swap<'info>(
 
     // Swap by Routes
     let mut offset: usize = 0;
+    // Sub-lot dust of the first hop left in the source account, refunded as unfilled
+    let mut dust_amount_in: u64 = 0;
     // Level 1 split handling
     for (i, hops) in routes.iter().enumerate() {
         require!(hops.len() <= MAX_HOPS, ErrorCode::TooManyHops);
@@ -544,16 +706,60 @@ fn execute_swap<'info>(
                 .iter()
                 .try_fold(0u8, |acc, &x| acc.checked_add(x).ok_or(ErrorCode::CalculationError))?;
             require!(total_weight == TOTAL_WEIGHT, ErrorCode::WeightsMustSumTo100);
-            msg!("total_weight:{}",total_weight);
+            msg!("total_weight:{}", total_weight);
+
+            if let Some(min_outs) = &route.min_outs {
+                require!(
+                    dexes.len() == min_outs.len(),
+                    ErrorCode::DexesAndMinOutsMustHaveTheSameLength
+                );
+            }
+            if let Some(sqrt_price_limits) = &route.sqrt_price_limits {
+                // Partially filled forks are refunded from the source account, so only the first hop
+                require!(hop == 0, ErrorCode::SqrtPriceLimitsOnlyAllowedOnFirstHop);
+                require!(
+                    dexes.len() == sqrt_price_limits.len(),
+                    ErrorCode::DexesAndSqrtPriceLimitsMustHaveTheSameLength
+                );
+                for (dex, sqrt_price_limit) in dexes.iter().zip(sqrt_price_limits.iter()) {
+                    require!(
+                        *sqrt_price_limit == 0
+                            || registry::get_adapter(dex).supports_sqrt_price_limit(),
+                        ErrorCode::SqrtPriceLimitNotSupported
+                    );
+                }
+            }
+            if let Some(order_book_limits) = &route.order_book_limits {
+                require!(hop == 0, ErrorCode::OrderBookLimitsOnlyAllowedOnFirstHop);
+                require!(
+                    dexes.len() == order_book_limits.len(),
+                    ErrorCode::DexesAndOrderBookLimitsMustHaveTheSameLength
+                );
+                for (dex, order_book_limit) in dexes.iter().zip(order_book_limits.iter()) {
+                    require!(
+                        *order_book_limit == OrderBookLimit::default()
+                            || registry::get_adapter(dex).supports_order_book_limit(),
+                        ErrorCode::OrderBookLimitNotSupported
+                    );
+                }
+            }
 
             // Level 2 split handling
             let mut hop_accounts = HopAccounts {
                 last_to_account,
                 from_account: ZERO_ADDRESS,
                 to_account: ZERO_ADDRESS,
+                min_amount_out: 0,
+                sqrt_price_limit: 0,
+                order_book_limit: OrderBookLimit::default(),
+                dust_amount_in: 0,
+                transfer_fee: 0,
             };
             let mut amount_out: u64 = 0;
             let mut acc_fork_in: u64 = 0;
+            // Dust of the previous fork rolled into the next one
+            let mut carry_amount_in: u64 = 0;
+            let hop_offset = offset;
             for (index, dex) in dexes.iter().enumerate() {
                 // Calculate 2 level split amount
                 let fork_amount_in = if index == dexes.len() - 1 {
@@ -571,7 +777,24 @@ fn execute_swap<'info>(
                         acc_fork_in.checked_add(temp_amount).ok_or(ErrorCode::CalculationError)?;
                     temp_amount
                 };
-                msg!("fork amount in:{}",fork_amount_in);
+                let fork_amount_in = fork_amount_in
+                    .checked_add(carry_amount_in)
+                    .ok_or(ErrorCode::CalculationError)?;
+                msg!("fork amount in:{}", fork_amount_in);
+                hop_accounts.min_amount_out =
+                    route.min_outs.as_ref().map_or(0, |min_outs| min_outs[index]);
+                hop_accounts.sqrt_price_limit = route
+                    .sqrt_price_limits
+                    .as_ref()
+                    .map_or(0, |sqrt_price_limits| sqrt_price_limits[index]);
+                hop_accounts.order_book_limit = route
+                    .order_book_limits
+                    .as_ref()
+                    .map_or(OrderBookLimit::default(), |order_book_limits| {
+                        order_book_limits[index]
+                    });
+                hop_accounts.dust_amount_in = 0;
+                hop_accounts.transfer_fee = 0;
 
                 // Execute swap
                 let fork_amount_out = distribute_swap(
@@ -589,6 +812,15 @@ fn execute_swap<'info>(
 
                 //fork amount out is: 18414775
                 msg!("fork amount out is: {}", fork_amount_out);
+                if hop_accounts.transfer_fee > 0 {
+                    msg!("fork transfer fee is: {}", hop_accounts.transfer_fee);
+                }
+
+                // Order book adapters swap whole lots only
+                carry_amount_in = hop_accounts.dust_amount_in;
+                let fork_amount_in = fork_amount_in
+                    .checked_sub(carry_amount_in)
+                    .ok_or(ErrorCode::CalculationError)?;
 
                 // Emit SwapEvent
                 let event =
@@ -598,10 +830,42 @@ fn execute_swap<'info>(
                 hop_accounts.from_account.log();
                 hop_accounts.to_account.log();
 
+                // Collect the fork result for quotes
+                if let Some(fork_quotes) = fork_quotes.as_mut() {
+                    fork_quotes.push(ForkQuote {
+                        route: i as u8,
+                        hop: hop as u8,
+                        dex: *dex,
+                        amount_in: fork_amount_in,
+                        amount_out: fork_amount_out,
+                        transfer_fee: hop_accounts.transfer_fee,
+                    });
+                }
+
                 amount_out =
                     amount_out.checked_add(fork_amount_out).ok_or(ErrorCode::CalculationError)?;
             }
 
+            // The dust of the last fork stays in the hop source account, a later hop refunds it
+            // from the SA in its input mint
+            if carry_amount_in > 0 {
+                msg!("hop {} dust amount in: {}", hop, carry_amount_in);
+                if hop == 0 {
+                    dust_amount_in = dust_amount_in
+                        .checked_add(carry_amount_in)
+                        .ok_or(ErrorCode::CalculationError)?;
+                }
+            }
+            if hop > 0 && dexes.iter().any(|dex| registry::get_adapter(dex).leaves_lot_dust()) {
+                refund_hop_dust(
+                    remaining_accounts,
+                    hop_offset,
+                    &mut offset,
+                    hop_accounts.from_account,
+                    carry_amount_in,
+                )?;
+            }
+
             if hop == 0 {
                 // CHECK: Verify the first hop's from_token must be consistent with ctx.accounts.source_token_account
                 require!(
@@ -626,7 +890,154 @@ fn execute_swap<'info>(
     let amount_out = after_destination_balance
         .checked_sub(before_destination_balance)
         .ok_or(ErrorCode::CalculationError)?;
-    Ok(amount_out)
+
+    let unfilled_amount_in = if partial_fill {
+        source_account.reload()?;
+        let consumed_amount_in = before_source_balance
+            .checked_sub(source_account.amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        net_amount_in.checked_sub(consumed_amount_in).ok_or(ErrorCode::CalculationError)?
+    } else {
+        dust_amount_in
+    };
+    Ok((amount_out, unfilled_amount_in))
+}
+
+/// Refunds the lot dust a later hop leaves in its SA source account. The hop accounts of such a
+/// hop are followed by the dust refund account, a token account of the hop's input mint, and that
+/// mint.
+fn refund_hop_dust<'info>(
+    remaining_accounts: &'info [AccountInfo<'info>],
+    hop_offset: usize,
+    offset: &mut usize,
+    from_account: Pubkey,
+    dust_amount_in: u64,
+) -> Result<()> {
+    require!(remaining_accounts.len() >= *offset + 2, ErrorCode::InvalidAccountsLength);
+    let refund_account = &remaining_accounts[*offset];
+    let mint_account = &remaining_accounts[*offset + 1];
+    let hop_account_infos = &remaining_accounts[hop_offset..*offset];
+    *offset += 2;
+    if dust_amount_in == 0 {
+        return Ok(());
+    }
+
+    let find_account = |key: &Pubkey| {
+        hop_account_infos
+            .iter()
+            .find(|account| account.key == key)
+            .ok_or(ErrorCode::InvalidHopAccounts)
+    };
+    let from_account = find_account(&from_account)?;
+    let sa_authority = find_account(&authority_pda::id())?;
+    let token_program = find_account(from_account.owner)?;
+    let from = InterfaceAccount::<TokenAccount>::try_from(from_account)?;
+    let refund = InterfaceAccount::<TokenAccount>::try_from(refund_account)?;
+    let mint = InterfaceAccount::<Mint>::try_from(mint_account)?;
+    require_keys_eq!(from.owner, authority_pda::id(), ErrorCode::InvalidHopFromAccount);
+    require_keys_eq!(refund.mint, from.mint, ErrorCode::InvalidDustRefundAccount);
+    require_keys_eq!(mint.key(), from.mint, ErrorCode::InvalidDustRefundAccount);
+    require_keys_neq!(refund.owner, authority_pda::id(), ErrorCode::InvalidDustRefundAccount);
+
+    msg!("refund dust amount in: {}", dust_amount_in);
+    transfer_token(
+        sa_authority.clone(),
+        from_account.clone(),
+        refund_account.clone(),
+        mint_account.clone(),
+        token_program.clone(),
+        dust_amount_in,
+        mint.decimals,
+        Some(SA_AUTHORITY_SEED),
+        remaining_accounts,
+    )
+}
+
+/// Route output quoted from pool state, split like `execute_swap` but without moving tokens.
+/// `None` if a dex of the route can not quote on chain or a fork may be partially filled.
+pub(crate) fn quote_swap<'info>(
+    remaining_accounts: &'info [AccountInfo<'info>],
+    args: &SwapArgsV2,
+) -> Result<Option<u64>> {
+    require!(
+        args.amounts.len() == args.routes.len(),
+        ErrorCode::AmountsAndRoutesMustHaveTheSameLength
+    );
+    let mut offset: usize = 0;
+    let mut total_amount_out: u64 = 0;
+    for (i, hops) in args.routes.iter().enumerate() {
+        require!(hops.len() <= MAX_HOPS, ErrorCode::TooManyHops);
+        let mut amount_in = args.amounts[i];
+        for (hop, route) in hops.iter().enumerate() {
+            if route.sqrt_price_limits.is_some() || route.order_book_limits.is_some() {
+                return Ok(None);
+            }
+            let dexes = &route.dexes;
+            let weights = &route.weights;
+            require!(dexes.len() == weights.len(), ErrorCode::DexesAndWeightsMustHaveTheSameLength);
+            let mut amount_out: u64 = 0;
+            let mut acc_fork_in: u64 = 0;
+            for (index, dex) in dexes.iter().enumerate() {
+                let fork_amount_in = if index == dexes.len() - 1 {
+                    amount_in.checked_sub(acc_fork_in).ok_or(ErrorCode::CalculationError)?
+                } else {
+                    let temp_amount = amount_in
+                        .checked_mul(weights[index] as u64)
+                        .ok_or(ErrorCode::CalculationError)?
+                        .checked_div(TOTAL_WEIGHT as u64)
+                        .ok_or(ErrorCode::CalculationError)?;
+                    acc_fork_in =
+                        acc_fork_in.checked_add(temp_amount).ok_or(ErrorCode::CalculationError)?;
+                    temp_amount
+                };
+                let adapter = registry::get_adapter(dex);
+                registry::check_accounts(adapter, remaining_accounts, offset)?;
+                let Some(fork_amount_out) =
+                    adapter.quote(remaining_accounts, offset, fork_amount_in)?
+                else {
+                    return Ok(None);
+                };
+                offset += adapter.accounts_len();
+                amount_out =
+                    amount_out.checked_add(fork_amount_out).ok_or(ErrorCode::CalculationError)?;
+            }
+            // Skip the dust refund account and mint `execute_swap` reads after such a hop
+            if hop > 0 && dexes.iter().any(|dex| registry::get_adapter(dex).leaves_lot_dust()) {
+                offset += 2;
+            }
+            amount_in = amount_out;
+        }
+        total_amount_out =
+            total_amount_out.checked_add(amount_in).ok_or(ErrorCode::CalculationError)?;
+    }
+    Ok(Some(total_amount_out))
+}
+
+/// Scales the level 1 amounts down to the amount left after the transfer fee, the last route
+/// takes the rounding remainder
+fn deduct_transfer_fee(amounts: &[u64], amount_in: u64, net_amount_in: u64) -> Result<Vec<u64>> {
+    if net_amount_in == amount_in {
+        return Ok(amounts.to_vec());
+    }
+    let mut net_amounts = Vec::with_capacity(amounts.len());
+    let mut acc_amount: u64 = 0;
+    for (i, amount) in amounts.iter().enumerate() {
+        let net_amount = if i == amounts.len() - 1 {
+            net_amount_in.checked_sub(acc_amount).ok_or(ErrorCode::CalculationError)?
+        } else {
+            u64::try_from(
+                u128::from(*amount)
+                    .checked_mul(u128::from(net_amount_in))
+                    .and_then(|v| v.checked_div(u128::from(amount_in)))
+                    .ok_or(ErrorCode::CalculationError)?,
+            )
+            .map_err(|_| ErrorCode::CalculationError)?
+        };
+        acc_amount = acc_amount.checked_add(net_amount).ok_or(ErrorCode::CalculationError)?;
+        net_amounts.push(net_amount);
+    }
+    msg!("net amounts after transfer fee: {:?}", net_amounts);
+    Ok(net_amounts)
 }
 
 fn distribute_swap<'a>(
@@ -641,252 +1052,405 @@ fn distribute_swap<'a>(
     owner_seeds: Option<&[&[&[u8]]]>,
     payer: Option<&AccountInfo<'a>>,
 ) -> Result<u64> {
-    let swap_function = match dex {
-        Dex::SplTokenSwap => spl_token_swap::swap,
-        Dex::StableSwap => stable_swap::swap,
-        Dex::Whirlpool => whirlpool::swap,
-        Dex::MeteoraDynamicpool => meteora::swap,
-        Dex::RaydiumSwap => raydium::swap,
-        Dex::RaydiumStableSwap => raydium::swap_stable,
-        Dex::RaydiumClmmSwap => raydium::swap_clmm,
-        Dex::RaydiumClmmSwapV2 => raydium::swap_clmm_v2,
-        Dex::AldrinExchangeV1 => aldrin::swap_v1,
-        Dex::AldrinExchangeV2 => aldrin::swap_v2,
-        Dex::LifinityV1 => lifinity::swap_v1,
-        Dex::LifinityV2 => lifinity::swap_v2,
-        Dex::FluxBeam => fluxbeam::swap,
-        Dex::MeteoraDlmm => meteora::dlmm_swap,
-        Dex::RaydiumCpmmSwap => raydium::swap_cpmm,
-        Dex::OpenBookV2 => openbookv2::place_take_order,
-        Dex::WhirlpoolV2 => whirlpool::swap_v2,
-        Dex::Phoenix => phoenix::swap,
-        Dex::ObricV2 => obric_v2::swap,
-        Dex::SanctumAddLiq => sanctum::add_liquidity_handler,
-        Dex::SanctumRemoveLiq => sanctum::remove_liquidity_handler,
-        Dex::SanctumNonWsolSwap => sanctum::swap_without_wsol_handler,
-        Dex::SanctumWsolSwap => sanctum::swap_with_wsol_handler,
-        Dex::PumpfunBuy => pumpfun::buy,
-        Dex::PumpfunSell => pumpfun::sell,
-        Dex::Saros => saros::swap,
-        Dex::StabbleSwap => stabble::swap,
-        Dex::SanctumRouter => {
-            return sanctum_router::sanctum_router_handler(
-                remaining_accounts,
-                amount_in,
-                offset,
-                hop_accounts,
-                hop,
-                proxy_from,
-                order_id,
-                owner_seeds,
-            );
-        }
-        Dex::MeteoraVaultDeposit => meteora::deposit,
-        Dex::MeteoraVaultWithdraw => meteora::withdraw,
-        Dex::MeteoraLst => meteora::swap_lst,
-        Dex::Solfi => solfi::swap,
-        Dex::QualiaSwap => qualia::swap,
-        Dex::Zerofi => zerofi::swap,
-        Dex::PumpfunammBuy => pumpfunamm::buy,
-        Dex::PumpfunammSell => pumpfunamm::sell,
-        Dex::Virtuals => virtuals::swap,
-        Dex::VertigoBuy => vertigo::buy,
-        Dex::VertigoSell => vertigo::sell,
-        Dex::PerpetualsAddLiq => {
-            return perpetuals::liquidity_handler(
-                remaining_accounts,
-                amount_in,
-                offset,
-                hop_accounts,
-                hop,
-                proxy_from,
-                true,
-                owner_seeds,
-            );
-        }
-        Dex::PerpetualsRemoveLiq => {
-            return perpetuals::liquidity_handler(
-                remaining_accounts,
-                amount_in,
-                offset,
-                hop_accounts,
-                hop,
-                proxy_from,
-                false,
-                owner_seeds,
-            );
-        }
-        Dex::PerpetualsSwap => perpetuals::perpetuals_swap_handler,
-        Dex::RaydiumLaunchpad => {
-            return raydium_launchpad::launchpad_handler(
-                remaining_accounts,
-                amount_in,
-                offset,
-                hop_accounts,
-                hop,
-                proxy_from,
-                owner_seeds,
-                "RaydiumLaunchpad",
-            );
-        }
-        Dex::LetsBonkFun => {
-            return raydium_launchpad::launchpad_handler(
-                remaining_accounts,
-                amount_in,
-                offset,
-                hop_accounts,
-                hop,
-                proxy_from,
-                owner_seeds,
-                "LetsBonkFun",
-            );
-        }
-        Dex::Woofi => woofi::swap,
-        Dex::MeteoraDbc => meteora_dbc::swap,
-        Dex::MeteoraDlmmSwap2 => meteora::dlmm_swap2,
-        Dex::MeteoraDAMMV2 => meteora::damm_v2_swap,
-        Dex::Gavel => gavel::swap,
-        Dex::BoopfunBuy => {
-            return boopfun::buy(
-                remaining_accounts,
-                amount_in,
-                offset,
-                hop_accounts,
-                hop,
-                proxy_from,
-                owner_seeds,
-                payer,
-            );
-        }
-        Dex::BoopfunSell => boopfun::sell,
-        Dex::MeteoraDbc2 => meteora_dbc::swap2,
-        Dex::GooseFX => goosefx::swap,
-        Dex::Dooar => dooar::swap,
-        Dex::Numeraire => numeraire::swap,
-        Dex::SaberDecimalWrapperDeposit => saber_decimal_wrapper::deposit,
-        Dex::SaberDecimalWrapperWithdraw => saber_decimal_wrapper::withdraw,
-        Dex::SarosDlmm => saros::dlmm_swap,
-        Dex::OneDexSwap => one_dex::swap,
-        Dex::Manifest => manifest::swap,
-        Dex::ByrealClmm => byreal_clmm::swap_v2,
-        Dex::PancakeSwapV3Swap => pancake_swap_v3::swap,
-        Dex::PancakeSwapV3SwapV2 => pancake_swap_v3::swap_v2,
-        Dex::Tessera => tessera::swap,
-        Dex::SolRfq {
-            rfq_id,
-            expected_maker_amount,
-            expected_taker_amount,
-            maker_send_amount,
-            taker_send_amount,
-            expiry,
-            maker_use_native_sol,
-            taker_use_native_sol,
-        } => {
-            return sol_rfq::fill_order(
-                remaining_accounts,
-                amount_in,
-                offset,
-                hop_accounts,
-                hop,
-                proxy_from,
-                owner_seeds,
-                *rfq_id,
-                *expected_maker_amount,
-                *expected_taker_amount,
-                *maker_send_amount,
-                *taker_send_amount,
-                *expiry,
-                *maker_use_native_sol,
-                *taker_use_native_sol,
-            );
-        }
-        Dex::PumpfunBuy2 => pumpfun::buy2,
-        Dex::PumpfunammBuy2 => pumpfunamm::buy2,
-        Dex::Humidifi => humidifi::swap,
-        Dex::HeavenBuy => heaven::buy,
-        Dex::HeavenSell => heaven::sell,
-        Dex::SolfiV2 => solfi::swap_v2,
-        Dex::PumpfunBuy3 => {
-            return pumpfun::buy3(
-                remaining_accounts,
-                amount_in,
-                offset,
-                hop_accounts,
-                hop,
-                proxy_from,
-                owner_seeds,
-                payer,
-            );
-        }
-        Dex::PumpfunSell3 => {
-            return pumpfun::sell3(
-                remaining_accounts,
-                amount_in,
-                offset,
-                hop_accounts,
-                hop,
-                proxy_from,
-                owner_seeds,
-                payer,
-            );
-        }
-        Dex::PumpfunammBuy3 => pumpfunamm::buy3,
-        Dex::PumpfunammSell3 => {
-            return pumpfunamm::sell3(
-                remaining_accounts,
-                amount_in,
-                offset,
-                hop_accounts,
-                hop,
-                proxy_from,
-                owner_seeds,
-                payer,
-            );
-        }
-        Dex::Goonfi => goonfi::swap,
-        Dex::MoonitBuy => {
-            return moonit::buy(
-                remaining_accounts,
-                amount_in,
-                offset,
-                hop_accounts,
-                hop,
-                proxy_from,
-                owner_seeds,
-                payer,
-            );
-        }
-        Dex::MoonitSell => moonit::sell,
-        Dex::RaydiumSwapV2 => raydium::swap_v2,
-        Dex::Swaap => swaap::swap,
-        Dex::SugarMoneyBuy { bonding_curve_bump, bonding_curve_sol_associated_account_bump } => {
-            return sugar_money::buy(
-                remaining_accounts,
-                amount_in,
-                offset,
-                hop_accounts,
-                hop,
-                proxy_from,
-                owner_seeds,
-                payer,
-                *bonding_curve_bump,
-                *bonding_curve_sol_associated_account_bump,
+    let adapter = registry::get_adapter(dex);
+    registry::check_accounts(adapter, remaining_accounts, *offset)?;
+    let mut ctx = SwapContext {
+        dex,
+        remaining_accounts,
+        offset,
+        hop_accounts,
+        hop,
+        proxy_swap: proxy_from,
+        order_id,
+        owner_seeds,
+        payer,
+    };
+    adapter.swap(&mut ctx, amount_in)
+}
+
+pub fn common_swap_v3_exact_out<'info, T: PlatformFeeV3Processor<'info>>(
+    swap_processor: &T,
+    payer: &AccountInfo<'info>,
+    source_token_account: &mut InterfaceAccount<'info, TokenAccount>,
+    destination_token_account: &mut InterfaceAccount<'info, TokenAccount>,
+    source_mint: &InterfaceAccount<'info, Mint>,
+    destination_mint: &InterfaceAccount<'info, Mint>,
+    sa_authority: &Option<UncheckedAccount<'info>>,
+    source_token_sa: &mut Option<UncheckedAccount<'info>>,
+    destination_token_sa: &mut Option<UncheckedAccount<'info>>,
+    source_token_program: &Option<Interface<'info, TokenInterface>>,
+    destination_token_program: &Option<Interface<'info, TokenInterface>>,
+    associated_token_program: &Option<Program<'info, AssociatedToken>>,
+    system_program: &Option<Program<'info, System>>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+    args: SwapExactOutArgs,
+    order_id: u64,
+    // COMMISSION
+    commission_rate: u32,
+    commission_direction: bool,
+    commission_account: &Option<AccountInfo<'info>>,
+    commission_split: Option<&CommissionSplit<'_, 'info>>,
+    // PLATFORM FEE
+    platform_fee_rate: Option<u16>,
+    platform_fee_account: &Option<AccountInfo<'info>>,
+    // TRIM
+    trim_rate: Option<u8>,
+    trim_account: Option<&AccountInfo<'info>>,
+    acc_close_flag: bool,
+) -> Result<SwapResultV1> {
+    log_swap_basic_info(
+        order_id,
+        &source_mint.key(),
+        &destination_mint.key(),
+        &source_token_account.owner,
+        &destination_token_account.owner,
+    );
+
+    let before_source_balance = source_token_account.amount;
+    let before_destination_balance = destination_token_account.amount;
+    let amount_out = args.amount_out;
+    let max_amount_in = args.max_amount_in;
+
+    log_swap_balance_before(
+        before_source_balance,
+        before_destination_balance,
+        max_amount_in,
+        amount_out,
+        amount_out,
+    );
+
+    // Commission on the input side would be charged on max_amount_in rather than the amount spent
+    require!(
+        commission_rate == 0 || !commission_direction,
+        ErrorCode::ExactOutRequiresOutputCommission
+    );
+
+    // Verify sa_authority is valid
+    if sa_authority.is_some() {
+        require!(
+            sa_authority.as_ref().unwrap().key() == authority_pda::ID,
+            ErrorCode::InvalidSaAuthority
+        );
+    }
+
+    // Gross up amount_out so that the user still receives amount_out after the output fees,
+    // including the Token-2022 transfer fee of the destination SA payout
+    let user_amount_out = if destination_token_sa.is_some() {
+        amount_out
+            .checked_add(get_transfer_inverse_fee(&destination_mint.to_account_info(), amount_out)?)
+            .ok_or(ErrorCode::CalculationError)?
+    } else {
+        amount_out
+    };
+    let (commission_amount, platform_fee_amount) =
+        calculate_fee_amounts(user_amount_out, commission_rate, true, platform_fee_rate)?;
+    let gross_amount_out = user_amount_out
+        .checked_add(commission_amount)
+        .ok_or(ErrorCode::CalculationError)?
+        .checked_add(platform_fee_amount)
+        .ok_or(ErrorCode::CalculationError)?;
+
+    // get swap accounts
+    let (mut source_account, mut destination_account) = swap_processor.get_swap_accounts(
+        payer,
+        source_token_account,
+        destination_token_account,
+        source_mint,
+        destination_mint,
+        sa_authority,
+        source_token_sa,
+        destination_token_sa,
+        source_token_program,
+        destination_token_program,
+        associated_token_program,
+        system_program,
+    )?;
+
+    // before swap hook, the source SA may withhold a Token-2022 transfer fee
+    let before_source_withheld = get_withheld_amount(&source_account.to_account_info())?;
+    let (real_max_amount_in, before_fees) = swap_processor.before_swap(
+        payer,
+        sa_authority,
+        source_token_account,
+        source_mint,
+        source_token_sa,
+        source_token_program,
+        max_amount_in,
+        commission_rate,
+        commission_direction,
+        commission_account,
+        commission_split,
+        platform_fee_rate,
+        platform_fee_account,
+        remaining_accounts,
+    )?;
+    let transfer_fee_in = get_withheld_amount(&source_account.to_account_info())?
+        .checked_sub(before_source_withheld)
+        .ok_or(ErrorCode::CalculationError)?;
+    log_transfer_fee_info(true, transfer_fee_in);
+    let net_max_amount_in =
+        real_max_amount_in.checked_sub(transfer_fee_in).ok_or(ErrorCode::CalculationError)?;
+
+    // Exact out swap
+    let (consumed_amount_in, swap_amount_out) = execute_swap_exact_out(
+        &mut source_account,
+        &mut destination_account,
+        remaining_accounts,
+        args,
+        gross_amount_out,
+        net_max_amount_in,
+        source_token_sa.is_some(),
+        None,
+    )?;
+
+    // Refund the unspent input held by the source SA
+    let refund_amount =
+        net_max_amount_in.checked_sub(consumed_amount_in).ok_or(ErrorCode::CalculationError)?;
+    if refund_amount > 0 {
+        ProxySwapProcessor.proxy_handle_refund(
+            sa_authority,
+            source_token_account,
+            source_mint,
+            source_token_sa,
+            source_token_program,
+            refund_amount,
+            Some(SA_AUTHORITY_SEED),
+            remaining_accounts,
+        )?;
+    }
+
+    // after swap hook
+    let (actual_amount_out, after_fees) = swap_processor.after_swap(
+        payer,
+        sa_authority,
+        destination_token_account,
+        destination_mint,
+        destination_token_sa,
+        destination_token_program,
+        gross_amount_out,
+        swap_amount_out,
+        commission_rate,
+        commission_direction,
+        commission_account,
+        commission_split,
+        platform_fee_rate,
+        platform_fee_account,
+        trim_rate,
+        None,
+        trim_account,
+        None,
+        acc_close_flag,
+        remaining_accounts,
+    )?;
+
+    source_token_account.reload()?;
+    let after_source_balance = source_token_account.amount;
+    let source_token_change = before_source_balance
+        .checked_sub(after_source_balance)
+        .ok_or(ErrorCode::CalculationError)?;
+
+    // destination token account has been closed in swap_tob_processor
+    let (after_destination_balance, destination_token_change) =
+        if destination_token_account.get_lamports() != 0 {
+            destination_token_account.reload()?;
+            let after_destination_balance = destination_token_account.amount;
+            (
+                after_destination_balance,
+                after_destination_balance
+                    .checked_sub(before_destination_balance)
+                    .ok_or(ErrorCode::CalculationError)?,
+            )
+        } else {
+            (actual_amount_out, actual_amount_out)
+        };
+
+    log_swap_end(
+        after_source_balance,
+        after_destination_balance,
+        source_token_change,
+        destination_token_change,
+    );
+
+    // Check max amount in and exact amount out
+    require!(source_token_change <= max_amount_in, ErrorCode::MaxAmountInExceeded);
+    require!(destination_token_change >= amount_out, ErrorCode::ExactAmountOutNotReached);
+    // The transfer fee withheld by the source SA is spent along with the route input
+    let consumed_amount_in =
+        consumed_amount_in.checked_add(transfer_fee_in).ok_or(ErrorCode::CalculationError)?;
+    Ok(SwapResultV1::new(consumed_amount_in, swap_amount_out)
+        .with_charged_fees(actual_amount_out, before_fees.checked_add(after_fees)?))
+}
+
+fn execute_swap_exact_out<'info>(
+    source_account: &mut InterfaceAccount<'info, TokenAccount>,
+    destination_account: &mut InterfaceAccount<'info, TokenAccount>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+    args: SwapExactOutArgs,
+    real_amount_out: u64,
+    real_max_amount_in: u64,
+    proxy_from: bool,
+    owner_seeds: Option<&[&[&[u8]]]>,
+) -> Result<(u64, u64)> {
+    destination_account.reload()?;
+    let before_destination_balance = destination_account.amount;
+
+    // Check SwapExactOutArgs
+    let SwapExactOutArgs { amount_out, max_amount_in: _, amounts, routes } = &args;
+    require!(*amount_out > 0, ErrorCode::AmountOutMustBeGreaterThanZero);
+    require!(real_max_amount_in > 0, ErrorCode::AmountInMustBeGreaterThanZero);
+    require!(amounts.len() == routes.len(), ErrorCode::AmountsAndRoutesMustHaveTheSameLength);
+
+    let total_amounts: u64 = amounts
+        .iter()
+        .try_fold(0u64, |acc, &x| acc.checked_add(x).ok_or(ErrorCode::CalculationError))?;
+    require!(total_amounts == *amount_out, ErrorCode::TotalAmountsMustBeEqualToAmountOut);
+    // The output fees are grossed up on the last route
+    let fee_amount_out =
+        real_amount_out.checked_sub(total_amounts).ok_or(ErrorCode::CalculationError)?;
+
+    // Swap by Routes
+    let mut offset: usize = 0;
+    let mut consumed_amount_in: u64 = 0;
+    // Level 1 split handling
+    for (i, hops) in routes.iter().enumerate() {
+        require!(hops.len() == 1, ErrorCode::ExactOutRouteMustBeSingleHop);
+        let route_amount_out = if i == routes.len() - 1 {
+            amounts[i].checked_add(fee_amount_out).ok_or(ErrorCode::CalculationError)?
+        } else {
+            amounts[i]
+        };
+        let route = &hops[0];
+        let dexes = &route.dexes;
+        let weights = &route.weights;
+        require!(dexes.len() == weights.len(), ErrorCode::DexesAndWeightsMustHaveTheSameLength);
+        let total_weight: u8 = weights
+            .iter()
+            .try_fold(0u8, |acc, &x| acc.checked_add(x).ok_or(ErrorCode::CalculationError))?;
+        require!(total_weight == TOTAL_WEIGHT, ErrorCode::WeightsMustSumTo100);
+
+        // Level 2 split handling
+        let mut hop_accounts = HopAccounts {
+            last_to_account: ZERO_ADDRESS,
+            from_account: ZERO_ADDRESS,
+            to_account: ZERO_ADDRESS,
+            min_amount_out: 0,
+            sqrt_price_limit: 0,
+            order_book_limit: OrderBookLimit::default(),
+            dust_amount_in: 0,
+            transfer_fee: 0,
+        };
+        let mut acc_fork_out: u64 = 0;
+        for (index, dex) in dexes.iter().enumerate() {
+            require!(
+                registry::get_adapter(dex).supports_exact_out(),
+                ErrorCode::ExactOutNotSupported
             );
-        }
-        Dex::SugarMoneySell { bonding_curve_bump, bonding_curve_sol_associated_account_bump } => {
-            return sugar_money::sell(
+
+            // Calculate 2 level split amount
+            let fork_amount_out = if index == dexes.len() - 1 {
+                // The last dex, use the remaining amount_out to prevent accumulation
+                route_amount_out.checked_sub(acc_fork_out).ok_or(ErrorCode::CalculationError)?
+            } else {
+                let temp_amount = route_amount_out
+                    .checked_mul(weights[index] as u64)
+                    .ok_or(ErrorCode::CalculationError)?
+                    .checked_div(TOTAL_WEIGHT as u64)
+                    .ok_or(ErrorCode::CalculationError)?;
+                acc_fork_out =
+                    acc_fork_out.checked_add(temp_amount).ok_or(ErrorCode::CalculationError)?;
+                temp_amount
+            };
+
+            // Each fork may spend whatever input budget is left
+            let fork_max_amount_in = real_max_amount_in
+                .checked_sub(consumed_amount_in)
+                .ok_or(ErrorCode::CalculationError)?;
+
+            // Execute swap
+            let (fork_amount_in, fork_amount_out) = distribute_swap_exact_out(
+                dex,
                 remaining_accounts,
-                amount_in,
-                offset,
-                hop_accounts,
-                hop,
+                fork_amount_out,
+                fork_max_amount_in,
+                &mut offset,
+                &mut hop_accounts,
+                0,
                 proxy_from,
                 owner_seeds,
-                *bonding_curve_bump,
-                *bonding_curve_sol_associated_account_bump,
-            );
+            )?;
+
+            // Emit SwapEvent
+            let event =
+                SwapEvent { dex: *dex, amount_in: fork_amount_in, amount_out: fork_amount_out };
+            emit!(event);
+            msg!("{:?}", event);
+            hop_accounts.from_account.log();
+            hop_accounts.to_account.log();
+
+            consumed_amount_in = consumed_amount_in
+                .checked_add(fork_amount_in)
+                .ok_or(ErrorCode::CalculationError)?;
         }
-        Dex::MeteoraDAMMV2Swap2 => meteora::damm_v2_swap2,
+
+        // CHECK: Verify the from_token must be consistent with ctx.accounts.source_token_account
+        require!(
+            source_account.key() == hop_accounts.from_account,
+            ErrorCode::InvalidSourceTokenAccount
+        );
+        // CHECK: Verify the to_account must be consistent with ctx.accounts.destination_token_account
+        require!(
+            destination_account.key() == hop_accounts.to_account,
+            ErrorCode::InvalidDestinationTokenAccount
+        );
+    }
+
+    destination_account.reload()?;
+    let after_destination_balance = destination_account.amount;
+    let amount_out = after_destination_balance
+        .checked_sub(before_destination_balance)
+        .ok_or(ErrorCode::CalculationError)?;
+    require!(amount_out >= real_amount_out, ErrorCode::ExactAmountOutNotReached);
+    Ok((consumed_amount_in, amount_out))
+}
+
+fn distribute_swap_exact_out<'a>(
+    dex: &Dex,
+    remaining_accounts: &'a [AccountInfo<'a>],
+    amount_out: u64,
+    max_amount_in: u64,
+    offset: &mut usize,
+    hop_accounts: &mut HopAccounts,
+    hop: usize,
+    proxy_from: bool,
+    owner_seeds: Option<&[&[&[u8]]]>,
+) -> Result<(u64, u64)> {
+    let adapter = registry::get_adapter(dex);
+    require!(adapter.supports_exact_out(), ErrorCode::ExactOutNotSupported);
+    registry::check_accounts(adapter, remaining_accounts, *offset)?;
+    let mut ctx = SwapContext {
+        dex,
+        remaining_accounts,
+        offset,
+        hop_accounts,
+        hop,
+        proxy_swap: proxy_from,
+        order_id: 0,
+        owner_seeds,
+        payer: None,
     };
-    swap_function(remaining_accounts, amount_in, offset, hop_accounts, hop, proxy_from, owner_seeds)
+    adapter.swap_exact_out(&mut ctx, amount_out, max_amount_in)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_deduct_transfer_fee() {
+        assert_eq!(deduct_transfer_fee(&[600, 400], 1_000, 1_000).unwrap(), vec![600, 400]);
+        // 1% fee withheld, the last route takes the rounding remainder
+        assert_eq!(deduct_transfer_fee(&[600, 400], 1_000, 990).unwrap(), vec![594, 396]);
+        assert_eq!(deduct_transfer_fee(&[333, 333, 334], 1_000, 997).unwrap(), vec![332, 332, 333]);
+        assert_eq!(deduct_transfer_fee(&[1_000], 1_000, 1).unwrap(), vec![1]);
+    }
 }
@@ -1,6 +1,8 @@
-use crate::BRIDGE_TO_LOG_SELECTOR;
-use crate::processor::swap_processor::SwapProcessor;
-use crate::{SwapArgs, common_swap, error::ErrorCode};
+use crate::processor::SwapToCProcessor;
+use crate::state::event::SwapAndBridgeEvent;
+use crate::utils::log_rate_info_v3;
+use crate::{BRIDGE_TO_LOG_SELECTOR, MAX_BRIDGE_RECIPIENT_LEN};
+use crate::{SwapArgs, SwapResult, common_swap_v3, error::ErrorCode};
 use anchor_lang::{
     prelude::*,
     solana_program::{instruction::Instruction, program::invoke},
@@ -9,7 +11,7 @@ use anchor_spl::{
     associated_token::AssociatedToken,
     token::Token,
     token_2022::Token2022,
-    token_interface::{Mint, TokenAccount},
+    token_interface::{Mint, TokenAccount, TokenInterface},
 };
 
 #[derive(Accounts)]
@@ -22,18 +24,41 @@ pub struct FromSwapAccounts<'info> {
         token::mint = source_mint,
         token::authority = payer,
     )]
-    pub source_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub source_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    // The bridge program pulls the swap output from the payer
     #[account(
         mut,
         token::mint = destination_mint,
+        token::authority = payer,
     )]
-    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub destination_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub source_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub destination_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: commission account
+    #[account(mut)]
+    pub commission_account: Option<AccountInfo<'info>>,
+
+    /// CHECK: platform fee account
+    #[account(mut)]
+    pub platform_fee_account: Option<AccountInfo<'info>>,
+
+    /// CHECK: sa_authority
+    #[account(mut)]
+    pub sa_authority: Option<UncheckedAccount<'info>>,
 
-    pub source_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub source_token_sa: Option<UncheckedAccount<'info>>,
 
     #[account(mut)]
-    pub destination_mint: InterfaceAccount<'info, Mint>,
+    pub destination_token_sa: Option<UncheckedAccount<'info>>,
+
+    pub source_token_program: Option<Interface<'info, TokenInterface>>,
+    pub destination_token_program: Option<Interface<'info, TokenInterface>>,
 
     /// CHECK: bridge_program
     #[account(address = crate::okx_bridge_program::id())]
@@ -45,7 +70,7 @@ pub struct FromSwapAccounts<'info> {
     pub system_program: Program<'info, System>,
 }
 
-#[derive(AnchorDeserialize, AnchorSerialize)]
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
 pub struct BridgeToArgs {
     pub adaptor_id: AdaptorID, // bridge adaptor id
     pub to: Vec<u8>,           // recipient address on target chain
@@ -57,13 +82,13 @@ pub struct BridgeToArgs {
     pub ext_data: Vec<u8>,     // ext data for extension feature
 }
 
-#[derive(AnchorDeserialize, AnchorSerialize)]
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum SwapType {
     BRIDGE,
     SWAPANDBRIDGE,
 }
 
-#[derive(AnchorDeserialize, AnchorSerialize)]
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum AdaptorID {
     /* 00 */ Bridge0,
     /* 01 */ Bridge1,
@@ -117,46 +142,133 @@ pub enum AdaptorID {
     /* 49 */ Bridge49,
 }
 
+impl AdaptorID {
+    /// Number of adaptor accounts the bridge program reads after its fixed accounts, None for
+    /// adaptors the router does not bridge through. These mirror the per-adaptor account lists
+    /// of the bridge program's `bridge_to_log`, which the router cannot read on chain: a layout
+    /// change to an adaptor on the bridge must land here in the same release, and
+    /// `test_swap_and_bridge_passes_adaptor_accounts` pins each count.
+    pub fn bridge_accounts_len(&self) -> Option<usize> {
+        match self {
+            AdaptorID::Cctp => Some(16),
+            AdaptorID::Wormhole => Some(14),
+            AdaptorID::Meson => Some(4),
+            AdaptorID::Debridgedln => Some(12),
+            AdaptorID::Allbridge => Some(11),
+            AdaptorID::MayanSwift => Some(9),
+            _ => None,
+        }
+    }
+}
+
+/// Splits the remaining accounts into the swap accounts and the trailing bridge accounts,
+/// sized by the adaptor rather than by the caller
+pub fn split_bridge_accounts<'a, 'info>(
+    bridge_to_args: &BridgeToArgs,
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> Result<(&'a [AccountInfo<'info>], &'a [AccountInfo<'info>])> {
+    let len = bridge_to_args
+        .adaptor_id
+        .bridge_accounts_len()
+        .ok_or(ErrorCode::UnsupportedBridgeAdaptor)?;
+    require!(remaining_accounts.len() > len, ErrorCode::InvalidAccountsLength);
+    Ok(remaining_accounts.split_at(remaining_accounts.len() - len))
+}
+
+/// `split_bridge_accounts` for callers that also name the bridge slice: `offset` and `len` must
+/// be exactly the trailing accounts the adaptor reads
+pub fn split_bridge_accounts_at<'a, 'info>(
+    bridge_to_args: &BridgeToArgs,
+    remaining_accounts: &'a [AccountInfo<'info>],
+    offset: u8,
+    len: u8,
+) -> Result<(&'a [AccountInfo<'info>], &'a [AccountInfo<'info>])> {
+    let (swap_accounts, bridge_accounts) =
+        split_bridge_accounts(bridge_to_args, remaining_accounts)?;
+    require!(
+        offset as usize == swap_accounts.len() && len as usize == bridge_accounts.len(),
+        ErrorCode::InvalidAccountsLength
+    );
+    Ok((swap_accounts, bridge_accounts))
+}
+
 pub fn from_swap_log_handler<'a>(
     ctx: Context<'_, '_, 'a, 'a, FromSwapAccounts<'a>>,
     args: SwapArgs,
+    commission_info: u32,
+    platform_fee_rate: u16,
     bridge_to_args: BridgeToArgs,
-    offset: u8,
-    len: u8,
-) -> Result<()> {
-    // 1.Smart swap
-    let amount_out = common_swap(
-        &SwapProcessor,
-        &ctx.accounts.payer,
+) -> Result<SwapResult> {
+    let commission_direction = commission_info >> 31 == 1;
+    let commission_rate = commission_info & ((1 << 30) - 1);
+    log_rate_info_v3(commission_rate, Some(platform_fee_rate), None, commission_direction, false);
+
+    require!(bridge_to_args.swap_type == SwapType::SWAPANDBRIDGE, ErrorCode::InvalidBridgeArgs);
+    require!(
+        !bridge_to_args.to.is_empty() && bridge_to_args.to.len() <= MAX_BRIDGE_RECIPIENT_LEN,
+        ErrorCode::InvalidBridgeArgs
+    );
+    let (swap_accounts, bridge_accounts) =
+        split_bridge_accounts(&bridge_to_args, ctx.remaining_accounts)?;
+
+    // 1. Swap v3, fees are taken before the output reaches the payer
+    let before_destination_balance = ctx.accounts.destination_token_account.amount;
+    let result = common_swap_v3(
+        &SwapToCProcessor,
         &ctx.accounts.payer,
-        None,
         &mut ctx.accounts.source_token_account,
         &mut ctx.accounts.destination_token_account,
         &ctx.accounts.source_mint,
         &ctx.accounts.destination_mint,
-        &None,
-        &mut None,
-        &mut None,
-        &None,
-        &None,
-        &None,
-        &None,
-        ctx.remaining_accounts,
-        args,
-        0,
+        &ctx.accounts.sa_authority,
+        &mut ctx.accounts.source_token_sa,
+        &mut ctx.accounts.destination_token_sa,
+        &ctx.accounts.source_token_program,
+        &ctx.accounts.destination_token_program,
+        &Some(ctx.accounts.associated_token_program.clone()),
+        &Some(ctx.accounts.system_program.clone()),
+        swap_accounts,
+        args.into(),
+        bridge_to_args.order_id,
+        commission_rate,
+        commission_direction,
+        &ctx.accounts.commission_account,
         None,
+        Some(platform_fee_rate),
+        &ctx.accounts.platform_fee_account,
         None,
         None,
+        None,
+        None,
+        false,
     )?;
 
-    msg!("Swap amount_out: {}", amount_out);
+    // 2. Bridge exactly what the payer received
+    ctx.accounts.destination_token_account.reload()?;
+    let bridge_amount = ctx
+        .accounts
+        .destination_token_account
+        .amount
+        .checked_sub(before_destination_balance)
+        .ok_or(ErrorCode::CalculationError)?;
+    require!(bridge_amount > 0, ErrorCode::InvalidBridgeArgs);
+    msg!("Bridge amount: {}", bridge_amount);
+
+    emit!(SwapAndBridgeEvent {
+        order_id: bridge_to_args.order_id,
+        payer: ctx.accounts.payer.key(),
+        source_mint: ctx.accounts.source_mint.key(),
+        destination_mint: ctx.accounts.destination_mint.key(),
+        amount_in: result.amount_in,
+        bridge_amount,
+        adaptor_id: bridge_to_args.adaptor_id,
+        to_chain_id: bridge_to_args.to_chain_id,
+        to: bridge_to_args.to.clone(),
+    });
 
-    // 2. CPI bridge_to_log
     cpi_bridge_to_log(
         bridge_to_args,
-        amount_out,
-        offset,
-        len,
+        bridge_amount,
         &ctx.accounts.bridge_program,
         &ctx.accounts.payer,
         &ctx.accounts.destination_token_account,
@@ -165,17 +277,14 @@ pub fn from_swap_log_handler<'a>(
         &ctx.accounts.token_program,
         &ctx.accounts.token_2022_program,
         &ctx.accounts.system_program,
-        &ctx.remaining_accounts,
+        bridge_accounts,
     )?;
 
-    Ok(())
+    Ok(SwapResult::V1(result))
 }
-
 pub fn cpi_bridge_to_log<'info>(
     bridge_to_args: BridgeToArgs,
     amount_out: u64,
-    offset: u8,
-    len: u8,
     bridge_program: &AccountInfo<'info>,
     payer: &Signer<'info>,
     destination_token_account: &InterfaceAccount<'info, TokenAccount>,
@@ -184,14 +293,8 @@ pub fn cpi_bridge_to_log<'info>(
     token_program: &AccountInfo<'info>,
     token_2022_program: &AccountInfo<'info>,
     system_program: &AccountInfo<'info>,
-    remaining_accounts: &[AccountInfo<'info>],
+    bridge_accounts: &[AccountInfo<'info>],
 ) -> Result<()> {
-    let offset = offset as usize;
-    let len = len as usize;
-    require!(remaining_accounts.len() >= offset + len, ErrorCode::InvalidAccountsLength);
-    // get bridgeTo remaining accounts
-    let bridge_remaining_accounts = Vec::from(&remaining_accounts[offset..offset + len]);
-
     // reset amount
     let mut bridge_to_args = bridge_to_args;
     bridge_to_args.amount = amount_out;
@@ -210,7 +313,7 @@ pub fn cpi_bridge_to_log<'info>(
         AccountMeta::new_readonly(token_2022_program.key(), false),
         AccountMeta::new_readonly(system_program.key(), false),
     ];
-    accounts.extend(bridge_remaining_accounts.to_account_metas(None));
+    accounts.extend(bridge_accounts.iter().flat_map(|account| account.to_account_metas(None)));
 
     let mut accounts_infos = vec![
         payer.to_account_info(),
@@ -221,7 +324,7 @@ pub fn cpi_bridge_to_log<'info>(
         token_2022_program.to_account_info(),
         system_program.to_account_info(),
     ];
-    accounts_infos.extend(bridge_remaining_accounts.to_account_infos());
+    accounts_infos.extend_from_slice(bridge_accounts);
 
     let ix = Instruction { program_id: bridge_program.key(), accounts, data };
     invoke(&ix, &accounts_infos)?;
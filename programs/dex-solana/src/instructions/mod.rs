@@ -1,3 +1,4 @@
+pub mod batch_claim;
 pub mod claim;
 pub mod commission_from_swap;
 pub mod commission_proxy_swap;
@@ -13,10 +14,15 @@ pub mod from_swap;
 pub mod platform_fee_proxy_swap_v2;
 pub mod platform_fee_wrap_unwrap_v2;
 pub mod proxy_swap;
+pub mod quote;
 pub mod swap;
+pub mod swap_result;
 pub mod swap_v3;
+pub mod swap_v3_exact_out;
+pub mod to_swap;
 pub mod wrap_unwrap_v3;
 
+pub use batch_claim::*;
 pub use claim::*;
 pub use commission_from_swap::*;
 pub use commission_proxy_swap::*;
@@ -32,6 +38,10 @@ pub use from_swap::*;
 pub use platform_fee_proxy_swap_v2::*;
 pub use platform_fee_wrap_unwrap_v2::*;
 pub use proxy_swap::*;
+pub use quote::*;
 pub use swap::*;
+pub use swap_result::*;
 pub use swap_v3::*;
+pub use swap_v3_exact_out::*;
+pub use to_swap::*;
 pub use wrap_unwrap_v3::*;
@@ -2,11 +2,11 @@ use super::{
     CommissionSOLProxySwapAccounts, CommissionSPLProxySwapAccounts, CommonCommissionProcessorV2,
     common_commission_sol_swap_v2, common_commission_token_swap_v2,
 };
-use crate::SwapArgs;
 use crate::constants::*;
 use crate::error::ErrorCode;
 use crate::processor::proxy_swap_processor::ProxySwapProcessor;
 use crate::utils::*;
+use crate::{SwapArgs, SwapResult};
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
@@ -30,7 +30,8 @@ impl<'info> CommonCommissionProcessorV2<'info> for PlatformFeeProcessorV2 {
         destination_token_sa: &mut Option<UncheckedAccount<'info>>,
         platform_fee_rate: Option<u32>,
         trim_rate: Option<u8>,
-    ) -> Result<()> {
+        hook_accounts: &[AccountInfo<'info>],
+    ) -> Result<(u64, u64, u64)> {
         if platform_fee_rate.is_some() && platform_fee_rate.unwrap() > 0 {
             require!(
                 source_token_sa.is_some() || destination_token_sa.is_some(),
@@ -141,6 +142,7 @@ impl<'info> CommonCommissionProcessorV2<'info> for PlatformFeeProcessorV2 {
         commission_account.key().log();
 
         // Trim destionation token
+        let mut trim_amount: u64 = 0;
         if trim_account.is_some()
             && trim_rate.is_some()
             && trim_account.unwrap().key() != crate::ID
@@ -154,7 +156,7 @@ impl<'info> CommonCommissionProcessorV2<'info> for PlatformFeeProcessorV2 {
             )
             .unwrap();
 
-            let trim_amount = if commission_direction {
+            trim_amount = if commission_direction {
                 (amount_out.saturating_sub(expected_amount_out)).min(trim_limit)
             } else {
                 (amount_out.saturating_sub(commission_amount).saturating_sub(expected_amount_out))
@@ -183,13 +185,18 @@ impl<'info> CommonCommissionProcessorV2<'info> for PlatformFeeProcessorV2 {
                         trim_amount,
                         destination_mint.decimals,
                         None,
+                        hook_accounts,
                     )?;
                 }
                 msg!("trim_amount: {:?}", trim_amount);
                 trim_account.unwrap().key().log();
             }
         }
-        Ok(())
+        Ok((
+            commission_amount.checked_sub(platform_fee_amount).unwrap(),
+            platform_fee_amount,
+            trim_amount,
+        ))
     }
 
     fn commission_token_process_v2(
@@ -212,7 +219,8 @@ impl<'info> CommonCommissionProcessorV2<'info> for PlatformFeeProcessorV2 {
         destination_token_sa: &mut Option<UncheckedAccount<'info>>,
         platform_fee_rate: Option<u32>,
         trim_rate: Option<u8>,
-    ) -> Result<()> {
+        hook_accounts: &[AccountInfo<'info>],
+    ) -> Result<(u64, u64, u64)> {
         if platform_fee_rate.is_some() && platform_fee_rate.unwrap() > 0 {
             require!(
                 source_token_sa.is_some() || destination_token_sa.is_some(),
@@ -260,6 +268,7 @@ impl<'info> CommonCommissionProcessorV2<'info> for PlatformFeeProcessorV2 {
                     platform_fee_amount,
                     source_mint.decimals,
                     None,
+                    hook_accounts,
                 )?;
                 let sa_account_key = if source_token_sa.as_ref().unwrap().owner
                     == &crate::token_program::ID
@@ -282,6 +291,7 @@ impl<'info> CommonCommissionProcessorV2<'info> for PlatformFeeProcessorV2 {
                 commission_amount.checked_sub(platform_fee_amount).unwrap(),
                 source_mint.decimals,
                 None,
+                hook_accounts,
             )?;
             (commission_amount, platform_fee_amount)
         } else {
@@ -318,6 +328,7 @@ impl<'info> CommonCommissionProcessorV2<'info> for PlatformFeeProcessorV2 {
                     platform_fee_amount,
                     destination_mint.decimals,
                     None,
+                    hook_accounts,
                 )?;
                 let sa_account_key =
                     if destination_token_sa.as_ref().unwrap().owner == &crate::token_program::ID {
@@ -340,6 +351,7 @@ impl<'info> CommonCommissionProcessorV2<'info> for PlatformFeeProcessorV2 {
                 commission_amount.checked_sub(platform_fee_amount).unwrap(),
                 destination_mint.decimals,
                 None,
+                hook_accounts,
             )?;
 
             (commission_amount, platform_fee_amount)
@@ -352,6 +364,7 @@ impl<'info> CommonCommissionProcessorV2<'info> for PlatformFeeProcessorV2 {
         commission_token_account.key().log();
 
         // Trim token
+        let mut trim_amount: u64 = 0;
         if trim_token_account.is_some()
             && trim_rate.is_some()
             && trim_token_account.unwrap().key() != crate::ID
@@ -365,7 +378,7 @@ impl<'info> CommonCommissionProcessorV2<'info> for PlatformFeeProcessorV2 {
             )
             .unwrap();
 
-            let trim_amount = if commission_direction {
+            trim_amount = if commission_direction {
                 (amount_out.saturating_sub(expected_amount_out)).min(trim_limit)
             } else {
                 (amount_out.saturating_sub(commission_amount).saturating_sub(expected_amount_out))
@@ -382,12 +395,17 @@ impl<'info> CommonCommissionProcessorV2<'info> for PlatformFeeProcessorV2 {
                     trim_amount,
                     destination_mint.decimals,
                     None,
+                    hook_accounts,
                 )?;
                 msg!("trim_amount: {:?}", trim_amount);
                 trim_token_account.unwrap().to_account_info().key().log();
             }
         }
-        Ok(())
+        Ok((
+            commission_amount.checked_sub(platform_fee_amount).unwrap(),
+            platform_fee_amount,
+            trim_amount,
+        ))
     }
 }
 
@@ -398,7 +416,7 @@ pub fn platform_fee_sol_proxy_swap_handler_v2<'a>(
     order_id: u64,
     platform_fee_rate: u32,
     trim_rate: u8,
-) -> Result<()> {
+) -> Result<SwapResult> {
     let swap_processor = &ProxySwapProcessor;
     let platform_fee_processor = &PlatformFeeProcessorV2;
 
@@ -413,7 +431,7 @@ pub fn platform_fee_sol_proxy_swap_handler_v2<'a>(
         None
     };
 
-    common_commission_sol_swap_v2(
+    let result = common_commission_sol_swap_v2(
         swap_processor,
         platform_fee_processor,
         &ctx.accounts.payer,
@@ -440,7 +458,7 @@ pub fn platform_fee_sol_proxy_swap_handler_v2<'a>(
         Some(platform_fee_rate), // platform_fee_rate
         Some(trim_rate),         // trim_rate
     )?;
-    Ok(())
+    Ok(SwapResult::V1(result))
 }
 
 pub fn platform_fee_spl_proxy_swap_handler_v2<'a>(
@@ -450,7 +468,7 @@ pub fn platform_fee_spl_proxy_swap_handler_v2<'a>(
     order_id: u64,
     platform_fee_rate: u32,
     trim_rate: u8,
-) -> Result<()> {
+) -> Result<SwapResult> {
     let commission_direction = commission_info >> 31 == 1;
     let commission_rate = commission_info & ((1 << 30) - 1);
 
@@ -473,7 +491,7 @@ pub fn platform_fee_spl_proxy_swap_handler_v2<'a>(
     let swap_processor = &ProxySwapProcessor;
     let commission_processor = &PlatformFeeProcessorV2;
 
-    common_commission_token_swap_v2(
+    let result = common_commission_token_swap_v2(
         swap_processor,
         commission_processor,
         &ctx.accounts.payer,
@@ -502,5 +520,5 @@ pub fn platform_fee_spl_proxy_swap_handler_v2<'a>(
         Some(platform_fee_rate),
         Some(trim_rate),
     )?;
-    Ok(())
+    Ok(SwapResult::V1(result))
 }
@@ -7,8 +7,8 @@ use crate::utils::logging::{log_commission_info, log_platform_fee_info};
 use crate::utils::token::{sync_wsol_account, transfer_sol, transfer_token};
 use crate::{
     COMMISSION_DENOMINATOR_V2, COMMISSION_RATE_LIMIT_V2, PLATFORM_FEE_DENOMINATOR_V2,
-    PLATFORM_FEE_RATE_LIMIT_V2, SA_AUTHORITY_SEED, SEED_TEMP_WSOL, unwrap_process, wrap_process,
-    wsol_program,
+    PLATFORM_FEE_RATE_LIMIT_V2, SA_AUTHORITY_SEED, SEED_TEMP_WSOL, SwapResult, SwapResultV1,
+    unwrap_process, wrap_process, wsol_program,
 };
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
@@ -72,7 +72,7 @@ pub fn platform_fee_wrap_unwrap_handler_v2<'a>(
     ctx: Context<'_, '_, 'a, 'a, PlatformFeeWrapUnwrapAccountsV2<'a>>,
     args: PlatformFeeWrapUnwrapArgsV2,
     order_id: u64,
-) -> Result<()> {
+) -> Result<SwapResult> {
     let commission_direction = args.commission_info >> 31 == 1;
     let wrap_direction = ((args.commission_info & (1 << 30)) >> 30) == 1;
     let commission_rate = args.commission_info & ((1 << 30) - 1);
@@ -233,6 +233,7 @@ pub fn platform_fee_wrap_unwrap_handler_v2<'a>(
                 platform_fee_amount,
                 ctx.accounts.wsol_mint.decimals,
                 None,
+                &[],
             )?;
 
             log_platform_fee_info(platform_fee_amount, &sa_account_key);
@@ -247,6 +248,7 @@ pub fn platform_fee_wrap_unwrap_handler_v2<'a>(
             commission_amount.checked_sub(platform_fee_amount).unwrap(),
             ctx.accounts.wsol_mint.decimals,
             None,
+            &[],
         )?;
     }
 
@@ -256,5 +258,11 @@ pub fn platform_fee_wrap_unwrap_handler_v2<'a>(
     );
     commission_account_info.key().log();
 
-    Ok(())
+    Ok(SwapResult::V1(SwapResultV1::new(args.amount_in, args.amount_in).with_fees(
+        commission_direction,
+        commission_amount.checked_sub(platform_fee_amount).unwrap(),
+        platform_fee_amount,
+        0,
+        0,
+    )?))
 }
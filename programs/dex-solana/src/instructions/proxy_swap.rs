@@ -1,6 +1,6 @@
 use crate::constants::*;
 use crate::processor::proxy_swap_processor::ProxySwapProcessor;
-use crate::{SwapArgs, common_swap};
+use crate::{SwapArgs, SwapResult, common_swap};
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
@@ -53,8 +53,8 @@ pub fn proxy_swap_handler<'a>(
     ctx: Context<'_, '_, 'a, 'a, ProxySwapAccounts<'a>>,
     args: SwapArgs,
     order_id: u64,
-) -> Result<()> {
-    common_swap(
+) -> Result<SwapResult> {
+    let result = common_swap(
         &ProxySwapProcessor,
         &ctx.accounts.payer,
         &ctx.accounts.payer,
@@ -77,5 +77,5 @@ pub fn proxy_swap_handler<'a>(
         None,
         None,
     )?;
-    Ok(())
+    Ok(SwapResult::V1(result))
 }
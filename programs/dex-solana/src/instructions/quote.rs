@@ -0,0 +1,116 @@
+use super::{Dex, SwapArgs, SwapResultV1, execute_swap};
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    compute_units::sol_remaining_compute_units, program::set_return_data,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+#[derive(Accounts)]
+pub struct QuoteAccounts<'info> {
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = source_mint,
+        token::authority = payer,
+    )]
+    pub source_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = destination_mint,
+    )]
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub source_mint: InterfaceAccount<'info, Mint>,
+
+    pub destination_mint: InterfaceAccount<'info, Mint>,
+}
+
+/// Result of a single fork of the route, in execution order.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ForkQuote {
+    /// Index of the level 1 route.
+    pub route: u8,
+    /// Index of the hop within the route.
+    pub hop: u8,
+    pub dex: Dex,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// Token-2022 transfer fee withheld from the fork output.
+    pub transfer_fee: u64,
+}
+
+/// Return data of the `quote` instruction.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct QuoteResult {
+    /// Source amount consumed by the route, excluding commission charged on top.
+    pub amount_in: u64,
+    /// Destination amount received from the route, before fees.
+    pub amount_out: u64,
+    /// Destination amount left to the user after commission, platform fee and trim.
+    pub actual_amount_out: u64,
+    pub commission_amount: u64,
+    pub platform_fee_amount: u64,
+    pub trim_amount: u64,
+    pub charge_amount: u64,
+    /// Compute units used by the route and fee calculation.
+    pub compute_units: u64,
+    pub forks: Vec<ForkQuote>,
+}
+
+/// Executes the route like `swap_tob_v3` and returns a `QuoteResult` through return data.
+/// The instruction always fails with `QuoteOnly`, so its effects never outlive a simulation.
+pub fn quote_handler<'a>(
+    ctx: Context<'_, '_, 'a, 'a, QuoteAccounts<'a>>,
+    args: SwapArgs,
+    commission_info: u32,
+    platform_fee_rate: u16,
+    trim_rate: u8,
+    charge_rate: u16,
+) -> Result<()> {
+    let start_compute_units = sol_remaining_compute_units();
+    let real_amount_in = args.amount_in;
+    let expected_amount_out = args.expect_amount_out;
+    let mut forks = Vec::new();
+    let (amount_out, unfilled_amount_in) = execute_swap(
+        &mut ctx.accounts.source_token_account,
+        &mut ctx.accounts.destination_token_account,
+        ctx.remaining_accounts,
+        args.into(),
+        real_amount_in,
+        0,
+        0,
+        false,
+        None,
+        Some(&ctx.accounts.payer),
+        Some(&mut forks),
+    )?;
+
+    let amount_in =
+        real_amount_in.checked_sub(unfilled_amount_in).ok_or(ErrorCode::CalculationError)?;
+    let swap_result = SwapResultV1::new(amount_in, amount_out).with_v3_fees(
+        real_amount_in,
+        expected_amount_out,
+        commission_info,
+        Some(platform_fee_rate),
+        Some(trim_rate),
+        Some(charge_rate),
+    )?;
+
+    let result = QuoteResult {
+        amount_in,
+        amount_out,
+        actual_amount_out: swap_result.actual_amount_out,
+        commission_amount: swap_result.commission_amount,
+        platform_fee_amount: swap_result.platform_fee_amount,
+        trim_amount: swap_result.trim_amount,
+        charge_amount: swap_result.charge_amount,
+        compute_units: start_compute_units.saturating_sub(sol_remaining_compute_units()),
+        forks,
+    };
+    msg!("{:?}", result);
+    set_return_data(&result.try_to_vec()?);
+    Err(ErrorCode::QuoteOnly.into())
+}
@@ -1,6 +1,6 @@
-use crate::SwapArgs;
 use crate::common_swap;
 use crate::processor::swap_processor::SwapProcessor;
+use crate::{SwapArgs, SwapResult};
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount};
 
@@ -30,8 +30,8 @@ pub fn swap_handler<'a>(
     ctx: Context<'_, '_, 'a, 'a, SwapAccounts<'a>>,
     args: SwapArgs,
     order_id: u64,
-) -> Result<()> {
-    common_swap(
+) -> Result<SwapResult> {
+    let result = common_swap(
         &SwapProcessor,
         &ctx.accounts.payer,
         &ctx.accounts.payer,
@@ -54,5 +54,5 @@ pub fn swap_handler<'a>(
         None,
         None,
     )?;
-    Ok(())
+    Ok(SwapResult::V1(result))
 }
@@ -0,0 +1,141 @@
+use crate::error::ErrorCode;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+/// Return data of every swap instruction, Borsh encoded.
+/// Fields are never changed in place; a new layout is added as a new variant.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwapResult {
+    V1(SwapResultV1),
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SwapResultV1 {
+    /// Source amount consumed by the route, excluding commission charged on top.
+    pub amount_in: u64,
+    /// Destination amount received from the route, before fees.
+    pub amount_out: u64,
+    /// Destination amount left to the user after commission, platform fee and trim.
+    pub actual_amount_out: u64,
+    pub commission_amount: u64,
+    pub platform_fee_amount: u64,
+    pub trim_amount: u64,
+    pub charge_amount: u64,
+}
+
+impl SwapResultV1 {
+    /// Result of a route without fees
+    pub fn new(amount_in: u64, amount_out: u64) -> Self {
+        Self { amount_in, amount_out, actual_amount_out: amount_out, ..Default::default() }
+    }
+
+    /// Adds commission, platform fee and trim amounts,
+    /// only output side fees are deducted from actual_amount_out.
+    pub fn with_fees(
+        mut self,
+        commission_direction: bool,
+        commission_amount: u64,
+        platform_fee_amount: u64,
+        trim_amount: u64,
+        charge_amount: u64,
+    ) -> Result<Self> {
+        self.commission_amount = commission_amount;
+        self.platform_fee_amount = platform_fee_amount;
+        self.trim_amount = trim_amount;
+        self.charge_amount = charge_amount;
+        let (to_commission_amount, to_platform_fee_amount) =
+            if commission_direction { (0, 0) } else { (commission_amount, platform_fee_amount) };
+        self.actual_amount_out = self
+            .amount_out
+            .checked_sub(to_commission_amount)
+            .ok_or(ErrorCode::CalculationError)?
+            .checked_sub(to_platform_fee_amount)
+            .ok_or(ErrorCode::CalculationError)?
+            .checked_sub(trim_amount)
+            .ok_or(ErrorCode::CalculationError)?
+            .checked_sub(charge_amount)
+            .ok_or(ErrorCode::CalculationError)?;
+        Ok(self)
+    }
+
+    /// Adds the fees charged by the v3 processors,
+    /// actual_amount_out is the destination amount after_swap left to the user.
+    pub fn with_charged_fees(mut self, actual_amount_out: u64, fees: ChargedFees) -> Self {
+        self.actual_amount_out = actual_amount_out;
+        self.commission_amount = fees.commission_amount;
+        self.platform_fee_amount = fees.platform_fee_amount;
+        self.trim_amount = fees.trim_amount;
+        self.charge_amount = fees.charge_amount;
+        self
+    }
+
+    /// Estimates the fees the v3 processors would charge, see SwapToBProcessor.
+    /// Commission on the source side is charged on the requested amount_in.
+    pub fn with_v3_fees(
+        self,
+        amount_in: u64,
+        expected_amount_out: u64,
+        commission_info: u32,
+        platform_fee_rate: Option<u16>,
+        trim_rate: Option<u8>,
+        charge_rate: Option<u16>,
+    ) -> Result<Self> {
+        let commission_direction = commission_info >> 31 == 1;
+        let commission_rate = commission_info & ((1 << 30) - 1);
+        let (commission_amount, platform_fee_amount) = calculate_fee_amounts(
+            if commission_direction { amount_in } else { self.amount_out },
+            commission_rate,
+            commission_direction,
+            platform_fee_rate,
+        )?;
+        let (trim_amount, charge_amount) = calculate_trim_amount(
+            self.amount_out,
+            expected_amount_out,
+            if commission_direction { 0 } else { commission_amount },
+            if commission_direction { 0 } else { platform_fee_amount },
+            commission_direction,
+            trim_rate,
+            charge_rate,
+        )?;
+        self.with_fees(
+            commission_direction,
+            commission_amount,
+            platform_fee_amount,
+            trim_amount,
+            charge_amount,
+        )
+    }
+}
+
+/// Fee amounts charged by a PlatformFeeV3Processor hook.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ChargedFees {
+    pub commission_amount: u64,
+    pub platform_fee_amount: u64,
+    pub trim_amount: u64,
+    pub charge_amount: u64,
+}
+
+impl ChargedFees {
+    /// Sums the fees charged before and after the swap
+    pub fn checked_add(self, other: Self) -> Result<Self> {
+        Ok(Self {
+            commission_amount: self
+                .commission_amount
+                .checked_add(other.commission_amount)
+                .ok_or(ErrorCode::CalculationError)?,
+            platform_fee_amount: self
+                .platform_fee_amount
+                .checked_add(other.platform_fee_amount)
+                .ok_or(ErrorCode::CalculationError)?,
+            trim_amount: self
+                .trim_amount
+                .checked_add(other.trim_amount)
+                .ok_or(ErrorCode::CalculationError)?,
+            charge_amount: self
+                .charge_amount
+                .checked_add(other.charge_amount)
+                .ok_or(ErrorCode::CalculationError)?,
+        })
+    }
+}
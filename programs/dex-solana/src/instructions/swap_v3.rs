@@ -1,4 +1,4 @@
-use super::{SwapArgs, common_swap_v3};
+use super::{SwapArgs, SwapArgsV2, SwapResult, common_swap_v3};
 use crate::error::ErrorCode;
 use crate::processor::*;
 use crate::utils::transfer_sol_with_rent_exemption;
@@ -58,12 +58,12 @@ pub struct CommissionProxySwapAccountsV3<'info> {
 
 pub fn swap_tob_handler<'a>(
     ctx: Context<'_, '_, 'a, 'a, CommissionProxySwapAccountsV3<'a>>,
-    args: SwapArgs,
+    args: SwapArgsV2,
     commission_info: u32,
     order_id: u64,
     trim_rate: Option<u8>,
     platform_fee_rate: Option<u16>,
-) -> Result<()> {
+) -> Result<SwapResult> {
     let commission_direction = commission_info >> 31 == 1;
     let acc_close_flag = ((commission_info & (1 << 30)) >> 30) == 1;
     let commission_rate = commission_info & ((1 << 30) - 1);
@@ -80,7 +80,7 @@ pub fn swap_tob_handler<'a>(
     } else {
         None
     };
-    common_swap_v3(
+    let result = common_swap_v3(
         &SwapToBProcessor,
         &ctx.accounts.payer,
         &mut ctx.accounts.source_token_account,
@@ -100,6 +100,7 @@ pub fn swap_tob_handler<'a>(
         commission_rate,
         commission_direction,
         &ctx.accounts.commission_account,
+        None,
         platform_fee_rate,
         &ctx.accounts.platform_fee_account,
         trim_rate,
@@ -108,21 +109,21 @@ pub fn swap_tob_handler<'a>(
         None,
         acc_close_flag,
     )?;
-    Ok(())
+    Ok(SwapResult::V1(result))
 }
 
 pub fn swap_toc_handler<'a>(
     ctx: Context<'_, '_, 'a, 'a, CommissionProxySwapAccountsV3<'a>>,
-    args: SwapArgs,
+    args: SwapArgsV2,
     commission_info: u32,
     order_id: u64,
     platform_fee_rate: Option<u16>,
-) -> Result<()> {
+) -> Result<SwapResult> {
     let commission_direction = commission_info >> 31 == 1;
     let commission_rate = commission_info & ((1 << 30) - 1);
     log_rate_info_v3(commission_rate, platform_fee_rate, None, commission_direction, false);
 
-    common_swap_v3(
+    let result = common_swap_v3(
         &SwapToCProcessor,
         &ctx.accounts.payer,
         &mut ctx.accounts.source_token_account,
@@ -142,6 +143,7 @@ pub fn swap_toc_handler<'a>(
         commission_rate,
         commission_direction,
         &ctx.accounts.commission_account,
+        None,
         platform_fee_rate,
         &ctx.accounts.platform_fee_account,
         None,
@@ -150,7 +152,127 @@ pub fn swap_toc_handler<'a>(
         None,
         false,
     )?;
-    Ok(())
+    Ok(SwapResult::V1(result))
+}
+
+/// Splits the trailing commission recipient accounts off the route accounts
+pub fn split_commission_accounts<'a, 'info>(
+    commission_shares: &[CommissionShare],
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> Result<(&'a [AccountInfo<'info>], &'a [AccountInfo<'info>])> {
+    require!(remaining_accounts.len() > commission_shares.len(), ErrorCode::InvalidAccountsLength);
+    Ok(remaining_accounts.split_at(remaining_accounts.len() - commission_shares.len()))
+}
+
+fn swap_split_commission<'a, T: PlatformFeeV3Processor<'a>>(
+    processor: &T,
+    ctx: Context<'_, '_, 'a, 'a, CommissionProxySwapAccountsV3<'a>>,
+    args: SwapArgs,
+    commission_rate: u32,
+    commission_direction: bool,
+    commission_shares: Vec<CommissionShare>,
+    order_id: u64,
+    platform_fee_rate: Option<u16>,
+    acc_close_flag: bool,
+) -> Result<SwapResult> {
+    // Recipients take the place of the single commission account
+    require!(ctx.accounts.commission_account.is_none(), ErrorCode::InvalidCommissionSplit);
+    let (remaining_accounts, recipient_accounts) =
+        split_commission_accounts(&commission_shares, ctx.remaining_accounts)?;
+    let commission_split = CommissionSplit::new(&commission_shares, recipient_accounts)?;
+    let commission_account = Some(recipient_accounts[0].clone());
+
+    let result = common_swap_v3(
+        processor,
+        &ctx.accounts.payer,
+        &mut ctx.accounts.source_token_account,
+        &mut ctx.accounts.destination_token_account,
+        &ctx.accounts.source_mint,
+        &ctx.accounts.destination_mint,
+        &ctx.accounts.sa_authority,
+        &mut ctx.accounts.source_token_sa,
+        &mut ctx.accounts.destination_token_sa,
+        &ctx.accounts.source_token_program,
+        &ctx.accounts.destination_token_program,
+        &ctx.accounts.associated_token_program,
+        &ctx.accounts.system_program,
+        remaining_accounts,
+        args.into(),
+        order_id,
+        commission_rate,
+        commission_direction,
+        &commission_account,
+        Some(&commission_split),
+        platform_fee_rate,
+        &ctx.accounts.platform_fee_account,
+        None,
+        None,
+        None,
+        None,
+        acc_close_flag,
+    )?;
+    Ok(SwapResult::V1(result))
+}
+
+/// ToC swap paying the commission across up to `MAX_COMMISSION_SPLITS` recipients, passed as
+/// the trailing remaining accounts in the order of `commission_shares`
+pub fn swap_toc_split_commission_handler<'a>(
+    ctx: Context<'_, '_, 'a, 'a, CommissionProxySwapAccountsV3<'a>>,
+    args: SwapArgs,
+    commission_info: u32,
+    commission_shares: Vec<CommissionShare>,
+    order_id: u64,
+    platform_fee_rate: Option<u16>,
+) -> Result<SwapResult> {
+    let commission_direction = commission_info >> 31 == 1;
+    let commission_rate = commission_info & ((1 << 30) - 1);
+    log_rate_info_v3(commission_rate, platform_fee_rate, None, commission_direction, false);
+
+    swap_split_commission(
+        &SwapToCProcessor,
+        ctx,
+        args,
+        commission_rate,
+        commission_direction,
+        commission_shares,
+        order_id,
+        platform_fee_rate,
+        false,
+    )
+}
+
+/// ToB swap paying the commission across up to `MAX_COMMISSION_SPLITS` recipients, passed as
+/// the trailing remaining accounts in the order of `commission_shares`
+pub fn swap_tob_split_commission_handler<'a>(
+    ctx: Context<'_, '_, 'a, 'a, CommissionProxySwapAccountsV3<'a>>,
+    args: SwapArgs,
+    commission_info: u32,
+    commission_shares: Vec<CommissionShare>,
+    order_id: u64,
+    platform_fee_rate: Option<u16>,
+) -> Result<SwapResult> {
+    let commission_direction = commission_info >> 31 == 1;
+    let acc_close_flag = ((commission_info & (1 << 30)) >> 30) == 1;
+    let commission_rate = commission_info & ((1 << 30) - 1);
+    log_rate_info_v3(
+        commission_rate,
+        platform_fee_rate,
+        None,
+        commission_direction,
+        acc_close_flag,
+    );
+
+    swap_split_commission(
+        &SwapToBProcessor,
+        ctx,
+        args,
+        commission_rate,
+        commission_direction,
+        commission_shares,
+        order_id,
+        platform_fee_rate,
+        acc_close_flag,
+    )
 }
 
 /// Account structure for swap with optional specified receiver
@@ -246,7 +368,7 @@ pub fn swap_tob_specified_receiver_handler<'a>(
     order_id: u64,
     trim_rate: Option<u8>,
     platform_fee_rate: Option<u16>,
-) -> Result<()> {
+) -> Result<SwapResult> {
     // Parse commission info
     let commission_direction = commission_info >> 31 == 1;
     let acc_close_flag = ((commission_info & (1 << 30)) >> 30) == 1;
@@ -274,7 +396,7 @@ pub fn swap_tob_specified_receiver_handler<'a>(
     };
 
     // Execute swap and get actual amount out
-    let actual_amount_out = common_swap_v3(
+    let result = common_swap_v3(
         &SwapToBProcessor,
         &ctx.accounts.payer,
         &mut ctx.accounts.source_token_account,
@@ -289,11 +411,12 @@ pub fn swap_tob_specified_receiver_handler<'a>(
         &ctx.accounts.associated_token_program,
         &ctx.accounts.system_program,
         ctx.remaining_accounts,
-        args,
+        args.into(),
         order_id,
         commission_rate,
         commission_direction,
         &ctx.accounts.commission_account,
+        None,
         platform_fee_rate,
         &ctx.accounts.platform_fee_account,
         trim_rate,
@@ -307,17 +430,17 @@ pub fn swap_tob_specified_receiver_handler<'a>(
     if let Some(sol_receiver) = &ctx.accounts.sol_receiver {
         // Only transfer if acc_close_flag is true (already validated)
         // and destination was wSOL (already validated)
-        if acc_close_flag && actual_amount_out > 0 {
+        if acc_close_flag && result.actual_amount_out > 0 {
             transfer_sol_with_rent_exemption(
                 &ctx.accounts.payer,
                 sol_receiver,
-                actual_amount_out,
+                result.actual_amount_out,
                 None, // No seeds needed for payer
             )?;
         }
     }
 
-    Ok(())
+    Ok(SwapResult::V1(result))
 }
 
 pub fn swap_tob_enhanced_handler<'a>(
@@ -328,7 +451,7 @@ pub fn swap_tob_enhanced_handler<'a>(
     trim_rate: u8,
     charge_rate: u16,
     platform_fee_rate: Option<u16>,
-) -> Result<()> {
+) -> Result<SwapResult> {
     let commission_direction = commission_info >> 31 == 1;
     let acc_close_flag = ((commission_info & (1 << 30)) >> 30) == 1;
     let commission_rate = commission_info & ((1 << 30) - 1);
@@ -345,7 +468,7 @@ pub fn swap_tob_enhanced_handler<'a>(
     let trim_account = &ctx.remaining_accounts[ctx.remaining_accounts.len() - 2];
     let charge_account = &ctx.remaining_accounts[ctx.remaining_accounts.len() - 1];
 
-    common_swap_v3(
+    let result = common_swap_v3(
         &SwapToBProcessor,
         &ctx.accounts.payer,
         &mut ctx.accounts.source_token_account,
@@ -360,11 +483,12 @@ pub fn swap_tob_enhanced_handler<'a>(
         &ctx.accounts.associated_token_program,
         &ctx.accounts.system_program,
         ctx.remaining_accounts,
-        args,
+        args.into(),
         order_id,
         commission_rate,
         commission_direction,
         &ctx.accounts.commission_account,
+        None,
         platform_fee_rate,
         &ctx.accounts.platform_fee_account,
         Some(trim_rate),
@@ -373,5 +497,5 @@ pub fn swap_tob_enhanced_handler<'a>(
         Some(charge_account),
         acc_close_flag,
     )?;
-    Ok(())
+    Ok(SwapResult::V1(result))
 }
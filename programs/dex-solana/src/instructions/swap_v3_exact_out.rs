@@ -0,0 +1,101 @@
+use super::{
+    CommissionProxySwapAccountsV3, SwapExactOutArgs, SwapResult, common_swap_v3_exact_out,
+};
+use crate::processor::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+pub fn swap_tob_exact_out_handler<'a>(
+    ctx: Context<'_, '_, 'a, 'a, CommissionProxySwapAccountsV3<'a>>,
+    args: SwapExactOutArgs,
+    commission_info: u32,
+    order_id: u64,
+    trim_rate: Option<u8>,
+    platform_fee_rate: Option<u16>,
+) -> Result<SwapResult> {
+    let commission_direction = commission_info >> 31 == 1;
+    let acc_close_flag = ((commission_info & (1 << 30)) >> 30) == 1;
+    let commission_rate = commission_info & ((1 << 30) - 1);
+    log_rate_info_v3(
+        commission_rate,
+        platform_fee_rate,
+        trim_rate,
+        commission_direction,
+        acc_close_flag,
+    );
+
+    let trim_account = if trim_rate.is_some() && trim_rate.unwrap() > 0 {
+        Some(&ctx.remaining_accounts[ctx.remaining_accounts.len() - 1])
+    } else {
+        None
+    };
+    let result = common_swap_v3_exact_out(
+        &SwapToBProcessor,
+        &ctx.accounts.payer,
+        &mut ctx.accounts.source_token_account,
+        &mut ctx.accounts.destination_token_account,
+        &ctx.accounts.source_mint,
+        &ctx.accounts.destination_mint,
+        &ctx.accounts.sa_authority,
+        &mut ctx.accounts.source_token_sa,
+        &mut ctx.accounts.destination_token_sa,
+        &ctx.accounts.source_token_program,
+        &ctx.accounts.destination_token_program,
+        &ctx.accounts.associated_token_program,
+        &ctx.accounts.system_program,
+        ctx.remaining_accounts,
+        args,
+        order_id,
+        commission_rate,
+        commission_direction,
+        &ctx.accounts.commission_account,
+        None,
+        platform_fee_rate,
+        &ctx.accounts.platform_fee_account,
+        trim_rate,
+        trim_account,
+        acc_close_flag,
+    )?;
+    Ok(SwapResult::V1(result))
+}
+
+pub fn swap_toc_exact_out_handler<'a>(
+    ctx: Context<'_, '_, 'a, 'a, CommissionProxySwapAccountsV3<'a>>,
+    args: SwapExactOutArgs,
+    commission_info: u32,
+    order_id: u64,
+    platform_fee_rate: Option<u16>,
+) -> Result<SwapResult> {
+    let commission_direction = commission_info >> 31 == 1;
+    let commission_rate = commission_info & ((1 << 30) - 1);
+    log_rate_info_v3(commission_rate, platform_fee_rate, None, commission_direction, false);
+
+    let result = common_swap_v3_exact_out(
+        &SwapToCProcessor,
+        &ctx.accounts.payer,
+        &mut ctx.accounts.source_token_account,
+        &mut ctx.accounts.destination_token_account,
+        &ctx.accounts.source_mint,
+        &ctx.accounts.destination_mint,
+        &ctx.accounts.sa_authority,
+        &mut ctx.accounts.source_token_sa,
+        &mut ctx.accounts.destination_token_sa,
+        &ctx.accounts.source_token_program,
+        &ctx.accounts.destination_token_program,
+        &ctx.accounts.associated_token_program,
+        &ctx.accounts.system_program,
+        ctx.remaining_accounts,
+        args,
+        order_id,
+        commission_rate,
+        commission_direction,
+        &ctx.accounts.commission_account,
+        None,
+        platform_fee_rate,
+        &ctx.accounts.platform_fee_account,
+        None,
+        None,
+        false,
+    )?;
+    Ok(SwapResult::V1(result))
+}
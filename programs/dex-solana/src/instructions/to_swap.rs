@@ -0,0 +1,191 @@
+use crate::error::ErrorCode;
+use crate::processor::SwapToCProcessor;
+use crate::state::event::ReceiveAndSwapEvent;
+use crate::utils::{log_rate_info_v3, transfer_token};
+use crate::{
+    SwapArgs, SwapArgsV2, SwapResult, SwapResultV1, common_swap_v3, okx_bridge_program, quote_swap,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{TRANSACTION_LEVEL_STACK_HEIGHT, get_stack_height};
+use anchor_lang::solana_program::sysvar::{
+    self,
+    instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+#[derive(Accounts)]
+pub struct ToSwapAccounts<'info> {
+    /// Bridge PDA holding the bridged tokens, signed by the bridge program
+    #[account(mut)]
+    pub bridge_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        token::mint = source_mint,
+        token::authority = bridge_authority,
+        token::token_program = source_token_program,
+    )]
+    pub bridge_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: owner of the recipient token accounts
+    pub recipient: UncheckedAccount<'info>,
+
+    // Receives the bridged tokens untouched when the route falls back
+    #[account(
+        mut,
+        token::mint = source_mint,
+        token::authority = recipient,
+        token::token_program = source_token_program,
+    )]
+    pub recipient_source_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = destination_mint,
+        token::authority = recipient,
+    )]
+    pub destination_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub source_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub destination_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: commission account
+    #[account(mut)]
+    pub commission_account: Option<AccountInfo<'info>>,
+
+    /// CHECK: platform fee account
+    #[account(mut)]
+    pub platform_fee_account: Option<AccountInfo<'info>>,
+
+    /// CHECK: sa_authority
+    #[account(mut)]
+    pub sa_authority: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub source_token_sa: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub destination_token_sa: Option<UncheckedAccount<'info>>,
+
+    pub source_token_program: Interface<'info, TokenInterface>,
+    pub destination_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+    pub system_program: Option<Program<'info, System>>,
+
+    /// CHECK: instructions sysvar, the top-level instruction must be the bridge program's
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+}
+
+/// Checks this instruction is a direct CPI of a top-level bridge program instruction
+fn verify_bridge_caller(instructions_sysvar: &AccountInfo) -> Result<()> {
+    require!(
+        get_stack_height() == TRANSACTION_LEVEL_STACK_HEIGHT + 1,
+        ErrorCode::InvalidBridgeCaller
+    );
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    let instruction = load_instruction_at_checked(current_index as usize, instructions_sysvar)?;
+    require_keys_eq!(
+        instruction.program_id,
+        okx_bridge_program::ID,
+        ErrorCode::InvalidBridgeCaller
+    );
+    Ok(())
+}
+
+/// Swaps freshly bridged tokens into the recipient's destination token. Swapped tokens can not
+/// be restored once a route ran, so the fallback is decided up front from the route quoted on
+/// pool state, after fees: a route short of `min_return` hands the bridged tokens to the recipient
+/// untouched instead of reverting. Only dexes with an on-chain quote (SPL token-swap pools) can be
+/// routed; other routes are rejected.
+pub fn to_swap_handler<'a>(
+    ctx: Context<'_, '_, 'a, 'a, ToSwapAccounts<'a>>,
+    args: SwapArgs,
+    commission_info: u32,
+    platform_fee_rate: u16,
+    order_id: u64,
+) -> Result<SwapResult> {
+    verify_bridge_caller(&ctx.accounts.instructions_sysvar)?;
+
+    let commission_direction = commission_info >> 31 == 1;
+    let commission_rate = commission_info & ((1 << 30) - 1);
+    log_rate_info_v3(commission_rate, Some(platform_fee_rate), None, commission_direction, false);
+
+    let args = SwapArgsV2::from(args);
+    let amount_in = args.amount_in;
+    require!(amount_in > 0, ErrorCode::AmountInMustBeGreaterThanZero);
+    let amount_out =
+        quote_swap(ctx.remaining_accounts, &args)?.ok_or(ErrorCode::RouteNotQuotable)?;
+    let quoted_amount_out = SwapResultV1::new(amount_in, amount_out)
+        .with_v3_fees(
+            amount_in,
+            args.expect_amount_out,
+            commission_info,
+            Some(platform_fee_rate),
+            None,
+            None,
+        )?
+        .actual_amount_out;
+    msg!("quoted amount out: {}", quoted_amount_out);
+    let fallback = args.expect_amount_out < args.min_return || quoted_amount_out < args.min_return;
+    let result = if fallback {
+        msg!("Route quoted below min_return, delivering the bridged tokens");
+        transfer_token(
+            ctx.accounts.bridge_authority.to_account_info(),
+            ctx.accounts.bridge_token_account.to_account_info(),
+            ctx.accounts.recipient_source_token_account.to_account_info(),
+            ctx.accounts.source_mint.to_account_info(),
+            ctx.accounts.source_token_program.to_account_info(),
+            amount_in,
+            ctx.accounts.source_mint.decimals,
+            None,
+            ctx.remaining_accounts,
+        )?;
+        SwapResultV1::new(0, 0)
+    } else {
+        common_swap_v3(
+            &SwapToCProcessor,
+            &ctx.accounts.bridge_authority,
+            &mut ctx.accounts.bridge_token_account,
+            &mut ctx.accounts.destination_token_account,
+            &ctx.accounts.source_mint,
+            &ctx.accounts.destination_mint,
+            &ctx.accounts.sa_authority,
+            &mut ctx.accounts.source_token_sa,
+            &mut ctx.accounts.destination_token_sa,
+            &Some(ctx.accounts.source_token_program.clone()),
+            &Some(ctx.accounts.destination_token_program.clone()),
+            &ctx.accounts.associated_token_program,
+            &ctx.accounts.system_program,
+            ctx.remaining_accounts,
+            args,
+            order_id,
+            commission_rate,
+            commission_direction,
+            &ctx.accounts.commission_account,
+            None,
+            Some(platform_fee_rate),
+            &ctx.accounts.platform_fee_account,
+            None,
+            None,
+            None,
+            None,
+            false,
+        )?
+    };
+
+    emit!(ReceiveAndSwapEvent {
+        order_id,
+        recipient: ctx.accounts.recipient.key(),
+        source_mint: ctx.accounts.source_mint.key(),
+        destination_mint: ctx.accounts.destination_mint.key(),
+        amount_in,
+        amount_out: result.actual_amount_out,
+        fallback,
+    });
+    Ok(SwapResult::V1(result))
+}
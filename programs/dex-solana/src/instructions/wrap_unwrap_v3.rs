@@ -1,3 +1,4 @@
+use super::{SwapResult, SwapResultV1};
 use crate::constants::*;
 use crate::error::ErrorCode;
 use crate::utils::*;
@@ -92,7 +93,7 @@ impl CommissionInfo {
 pub fn platform_fee_wrap_unwrap_handler_v3<'a>(
     ctx: Context<'_, '_, 'a, 'a, PlatformFeeWrapUnwrapAccounts<'a>>,
     args: PlatformFeeWrapUnwrapArgs,
-) -> Result<()> {
+) -> Result<SwapResult> {
     let PlatformFeeWrapUnwrapArgs { order_id, amount_in, commission_info, platform_fee_rate, tob } =
         args;
 
@@ -167,7 +168,13 @@ pub fn platform_fee_wrap_unwrap_handler_v3<'a>(
         platform_fee_amount,
     )?;
 
-    Ok(())
+    Ok(SwapResult::V1(SwapResultV1::new(amount_in, amount_in).with_fees(
+        commission_info.commission_direction,
+        commission_amount,
+        platform_fee_amount,
+        0,
+        0,
+    )?))
 }
 
 /// Execute wrap or unwrap process based on direction
@@ -235,6 +242,7 @@ pub fn unwrap_process_v3<'info>(
         amount,
         wsol_mint.decimals,
         None,
+        &[],
     )?;
 
     // Close temporary account to unwrap WSOL to SOL
@@ -465,7 +473,7 @@ fn transfer_sol_fees<'info>(
     Ok(())
 }
 
-/// Transfer token fees
+/// Transfer token fees, WSOL has no transfer hook so no hook accounts are passed
 fn transfer_token_fees<'info>(
     payer: &AccountInfo<'info>,
     payer_wsol_account: &InterfaceAccount<'info, TokenAccount>,
@@ -501,6 +509,7 @@ fn transfer_token_fees<'info>(
             total_amount,
             wsol_mint.decimals,
             None, // User signs
+            &[],
         )?;
 
         // Step 2: wsol_sa → commission/platform accounts (actual fee payment)
@@ -513,7 +522,9 @@ fn transfer_token_fees<'info>(
                 token_program,
                 commission_account,
                 commission_amount,
+                false,
                 Some(SA_AUTHORITY_SEED),
+                &[],
             )?;
             log_commission_info(commission_direction, commission_amount);
             commission_account.key().log();
@@ -528,7 +539,9 @@ fn transfer_token_fees<'info>(
                 token_program,
                 platform_fee_account,
                 platform_fee_amount,
+                false,
                 Some(SA_AUTHORITY_SEED),
+                &[],
             )?;
             log_platform_fee_info(platform_fee_amount, &platform_fee_account.key());
         }
@@ -543,7 +556,9 @@ fn transfer_token_fees<'info>(
                 token_program,
                 commission_account,
                 commission_amount,
+                false,
                 None,
+                &[],
             )?;
             log_commission_info(commission_direction, commission_amount);
             commission_account.key().log();
@@ -558,7 +573,9 @@ fn transfer_token_fees<'info>(
                 token_program,
                 platform_fee_account,
                 platform_fee_amount,
+                false,
                 None,
+                &[],
             )?;
             log_platform_fee_info(platform_fee_amount, &platform_fee_account.key());
         }
@@ -17,6 +17,9 @@ pub use instructions::*;
 pub use limitorder::instructions as limitorder_instructions;
 pub use limitorder::instructions::*;
 pub use processor::*;
+pub use state::intent::OrderIntent;
+pub use state::order::{OrderTrigger, TriggerCondition};
+pub use utils::fee::CommissionShare;
 
 #[cfg(feature = "staging")]
 declare_id!("Bv3yUnYUUp9HX6Wzum4632t4PGs2S5rBXEoG4jwsYTxM");
@@ -32,7 +35,7 @@ pub mod dex_solana {
         ctx: Context<'_, '_, 'a, 'a, SwapAccounts<'a>>,
         data: SwapArgs,
         order_id: u64,
-    ) -> Result<()> {
+    ) -> Result<SwapResult> {
         instructions::swap_handler(ctx, data, order_id)
     }
 
@@ -41,7 +44,7 @@ pub mod dex_solana {
         ctx: Context<'_, '_, 'a, 'a, CommissionSPLAccounts<'a>>,
         data: CommissionSwapArgs,
         order_id: u64,
-    ) -> Result<()> {
+    ) -> Result<SwapResult> {
         instructions::commission_spl_swap_handler(ctx, data, order_id)
     }
 
@@ -49,7 +52,7 @@ pub mod dex_solana {
         ctx: Context<'_, '_, 'a, 'a, CommissionSOLAccounts<'a>>,
         data: CommissionSwapArgs,
         order_id: u64,
-    ) -> Result<()> {
+    ) -> Result<SwapResult> {
         instructions::commission_sol_swap_handler(ctx, data, order_id)
     }
 
@@ -57,7 +60,7 @@ pub mod dex_solana {
         ctx: Context<'_, '_, 'a, 'a, CommissionWrapUnwrapAccounts<'a>>,
         data: CommissionWrapUnwrapArgs,
         order_id: u64,
-    ) -> Result<()> {
+    ) -> Result<SwapResult> {
         instructions::commission_wrap_unwrap_handler(ctx, data, order_id)
     }
 
@@ -66,7 +69,7 @@ pub mod dex_solana {
         ctx: Context<'_, '_, 'a, 'a, ProxySwapAccounts<'a>>,
         data: SwapArgs,
         order_id: u64,
-    ) -> Result<()> {
+    ) -> Result<SwapResult> {
         instructions::proxy_swap_handler(ctx, data, order_id)
     }
 
@@ -76,7 +79,7 @@ pub mod dex_solana {
         commission_rate: u16,
         commission_direction: bool,
         order_id: u64,
-    ) -> Result<()> {
+    ) -> Result<SwapResult> {
         instructions::commission_sol_proxy_swap_handler(
             ctx,
             data,
@@ -92,7 +95,7 @@ pub mod dex_solana {
         commission_rate: u16,
         commission_direction: bool,
         order_id: u64,
-    ) -> Result<()> {
+    ) -> Result<SwapResult> {
         instructions::commission_spl_proxy_swap_handler(
             ctx,
             data,
@@ -110,7 +113,7 @@ pub mod dex_solana {
         platform_fee_rate: u32,
         trim_rate: u8,
         order_id: u64,
-    ) -> Result<()> {
+    ) -> Result<SwapResult> {
         instructions::platform_fee_sol_proxy_swap_handler_v2(
             ctx,
             args,
@@ -128,7 +131,7 @@ pub mod dex_solana {
         platform_fee_rate: u32,
         trim_rate: u8,
         order_id: u64,
-    ) -> Result<()> {
+    ) -> Result<SwapResult> {
         instructions::platform_fee_spl_proxy_swap_handler_v2(
             ctx,
             args,
@@ -143,7 +146,7 @@ pub mod dex_solana {
         ctx: Context<'_, '_, 'a, 'a, PlatformFeeWrapUnwrapAccountsV2<'a>>,
         args: PlatformFeeWrapUnwrapArgsV2,
         order_id: u64,
-    ) -> Result<()> {
+    ) -> Result<SwapResult> {
         instructions::platform_fee_wrap_unwrap_handler_v2(ctx, args, order_id)
     }
 
@@ -154,10 +157,10 @@ pub mod dex_solana {
         commission_info: u32,
         platform_fee_rate: u16,
         order_id: u64,
-    ) -> Result<()> {
+    ) -> Result<SwapResult> {
         instructions::swap_toc_handler(
             ctx,
-            args,
+            args.into(),
             commission_info,
             order_id,
             Some(platform_fee_rate),
@@ -171,7 +174,43 @@ pub mod dex_solana {
         trim_rate: u8,
         platform_fee_rate: u16,
         order_id: u64,
-    ) -> Result<()> {
+    ) -> Result<SwapResult> {
+        instructions::swap_tob_handler(
+            ctx,
+            args.into(),
+            commission_info,
+            order_id,
+            Some(trim_rate),
+            Some(platform_fee_rate),
+        )
+    }
+
+    /// `swap_v3` with per-dex min outs, sqrt price limits and order book limits
+    pub fn swap_v3_with_limits<'a>(
+        ctx: Context<'_, '_, 'a, 'a, CommissionProxySwapAccountsV3<'a>>,
+        args: SwapArgsV2,
+        commission_info: u32,
+        platform_fee_rate: u16,
+        order_id: u64,
+    ) -> Result<SwapResult> {
+        instructions::swap_toc_handler(
+            ctx,
+            args,
+            commission_info,
+            order_id,
+            Some(platform_fee_rate),
+        )
+    }
+
+    /// `swap_tob_v3` with per-dex min outs, sqrt price limits and order book limits
+    pub fn swap_tob_v3_with_limits<'a>(
+        ctx: Context<'_, '_, 'a, 'a, CommissionProxySwapAccountsV3<'a>>,
+        args: SwapArgsV2,
+        commission_info: u32,
+        trim_rate: u8,
+        platform_fee_rate: u16,
+        order_id: u64,
+    ) -> Result<SwapResult> {
         instructions::swap_tob_handler(
             ctx,
             args,
@@ -182,6 +221,76 @@ pub mod dex_solana {
         )
     }
 
+    pub fn swap_v3_split_commission<'a>(
+        ctx: Context<'_, '_, 'a, 'a, CommissionProxySwapAccountsV3<'a>>,
+        args: SwapArgs,
+        commission_info: u32,
+        commission_shares: Vec<CommissionShare>,
+        platform_fee_rate: u16,
+        order_id: u64,
+    ) -> Result<SwapResult> {
+        instructions::swap_toc_split_commission_handler(
+            ctx,
+            args,
+            commission_info,
+            commission_shares,
+            order_id,
+            Some(platform_fee_rate),
+        )
+    }
+
+    pub fn swap_tob_v3_split_commission<'a>(
+        ctx: Context<'_, '_, 'a, 'a, CommissionProxySwapAccountsV3<'a>>,
+        args: SwapArgs,
+        commission_info: u32,
+        commission_shares: Vec<CommissionShare>,
+        platform_fee_rate: u16,
+        order_id: u64,
+    ) -> Result<SwapResult> {
+        instructions::swap_tob_split_commission_handler(
+            ctx,
+            args,
+            commission_info,
+            commission_shares,
+            order_id,
+            Some(platform_fee_rate),
+        )
+    }
+
+    pub fn swap_v3_exact_out<'a>(
+        ctx: Context<'_, '_, 'a, 'a, CommissionProxySwapAccountsV3<'a>>,
+        args: SwapExactOutArgs,
+        commission_info: u32,
+        platform_fee_rate: u16,
+        order_id: u64,
+    ) -> Result<SwapResult> {
+        instructions::swap_toc_exact_out_handler(
+            ctx,
+            args,
+            commission_info,
+            order_id,
+            Some(platform_fee_rate),
+        )
+    }
+
+    pub fn swap_tob_v3_exact_out<'a>(
+        ctx: Context<'_, '_, 'a, 'a, CommissionProxySwapAccountsV3<'a>>,
+        args: SwapExactOutArgs,
+        commission_info: u32,
+        trim_rate: u8,
+        platform_fee_rate: u16,
+        order_id: u64,
+    ) -> Result<SwapResult> {
+        instructions::swap_tob_exact_out_handler(
+            ctx,
+            args,
+            commission_info,
+            order_id,
+            Some(trim_rate),
+            Some(platform_fee_rate),
+        )
+    }
+
     /// Swap ToB with optional specified receiver
     /// - For normal token swaps: sol_receiver should be None
     /// - For swap to SOL with custom receiver: sol_receiver should be Some and acc_close_flag must be true
@@ -192,7 +301,7 @@ pub mod dex_solana {
         trim_rate: u8,
         platform_fee_rate: u16,
         order_id: u64,
-    ) -> Result<()> {
+    ) -> Result<SwapResult> {
         instructions::swap_tob_specified_receiver_handler(
             ctx,
             args,
@@ -211,7 +320,7 @@ pub mod dex_solana {
         charge_rate: u16,
         platform_fee_rate: u16,
         order_id: u64,
-    ) -> Result<()> {
+    ) -> Result<SwapResult> {
         instructions::swap_tob_enhanced_handler(
             ctx,
             args,
@@ -226,7 +335,7 @@ pub mod dex_solana {
     pub fn wrap_unwrap_v3<'a>(
         ctx: Context<'_, '_, 'a, 'a, PlatformFeeWrapUnwrapAccounts<'a>>,
         args: PlatformFeeWrapUnwrapArgs,
-    ) -> Result<()> {
+    ) -> Result<SwapResult> {
         instructions::platform_fee_wrap_unwrap_handler_v3(ctx, args)
     }
 
@@ -245,8 +354,305 @@ pub mod dex_solana {
         instructions::create_token_account_with_seed_handler(ctx, bump, seed)
     }
 
+    // ******************** Swap And Bridge ******************** //
+    pub fn swap_and_bridge<'a>(
+        ctx: Context<'_, '_, 'a, 'a, FromSwapAccounts<'a>>,
+        args: SwapArgs,
+        commission_info: u32,
+        platform_fee_rate: u16,
+        bridge_to_args: BridgeToArgs,
+    ) -> Result<SwapResult> {
+        instructions::from_swap_log_handler(
+            ctx,
+            args,
+            commission_info,
+            platform_fee_rate,
+            bridge_to_args,
+        )
+    }
+
+    pub fn receive_and_swap<'a>(
+        ctx: Context<'_, '_, 'a, 'a, ToSwapAccounts<'a>>,
+        args: SwapArgs,
+        commission_info: u32,
+        platform_fee_rate: u16,
+        order_id: u64,
+    ) -> Result<SwapResult> {
+        instructions::to_swap_handler(ctx, args, commission_info, platform_fee_rate, order_id)
+    }
+
+    // ******************** Quote ******************** //
+    pub fn quote<'a>(
+        ctx: Context<'_, '_, 'a, 'a, QuoteAccounts<'a>>,
+        args: SwapArgs,
+        commission_info: u32,
+        platform_fee_rate: u16,
+        trim_rate: u8,
+        charge_rate: u16,
+    ) -> Result<()> {
+        instructions::quote_handler(
+            ctx,
+            args,
+            commission_info,
+            platform_fee_rate,
+            trim_rate,
+            charge_rate,
+        )
+    }
+
     // ******************** Claim ******************** //
     pub fn claim<'a>(ctx: Context<'_, '_, 'a, 'a, ClaimAccounts<'a>>) -> Result<()> {
         instructions::claim_handler(ctx)
     }
+
+    pub fn batch_claim<'a>(
+        ctx: Context<'_, '_, 'a, 'a, BatchClaimAccounts<'a>>,
+        close_empty: bool,
+    ) -> Result<()> {
+        instructions::batch_claim_handler(ctx, close_empty)
+    }
+
+    // ******************** Claim Config ******************** //
+    pub fn init_claim_config(ctx: Context<InitClaimConfig>) -> Result<()> {
+        global_config_instructions::init_claim_config_handler(ctx)
+    }
+
+    pub fn propose_claim_config_admin(
+        ctx: Context<UpdateClaimConfig>,
+        pending_admin: Pubkey,
+    ) -> Result<()> {
+        global_config_instructions::propose_claim_config_admin_handler(ctx, pending_admin)
+    }
+
+    pub fn accept_claim_config_admin(ctx: Context<AcceptClaimConfigAdmin>) -> Result<()> {
+        global_config_instructions::accept_claim_config_admin_handler(ctx)
+    }
+
+    pub fn cancel_claim_config_admin_transfer(ctx: Context<UpdateClaimConfig>) -> Result<()> {
+        global_config_instructions::cancel_claim_config_admin_transfer_handler(ctx)
+    }
+
+    pub fn set_claim_authority(
+        ctx: Context<UpdateClaimConfig>,
+        claim_authority: Pubkey,
+    ) -> Result<()> {
+        global_config_instructions::set_claim_authority_handler(ctx, claim_authority)
+    }
+
+    pub fn add_claim_receiver(ctx: Context<UpdateClaimConfig>, receiver: Pubkey) -> Result<()> {
+        global_config_instructions::add_claim_receiver_handler(ctx, receiver)
+    }
+
+    pub fn remove_claim_receiver(ctx: Context<UpdateClaimConfig>, receiver: Pubkey) -> Result<()> {
+        global_config_instructions::remove_claim_receiver_handler(ctx, receiver)
+    }
+
+    pub fn pause_claim(ctx: Context<UpdateClaimConfig>) -> Result<()> {
+        global_config_instructions::pause_claim_handler(ctx)
+    }
+
+    pub fn unpause_claim(ctx: Context<UpdateClaimConfig>) -> Result<()> {
+        global_config_instructions::unpause_claim_handler(ctx)
+    }
+
+    // ******************** Global Config ******************** //
+    pub fn init_global_config(ctx: Context<InitGlobalConfig>, trade_fee: u64) -> Result<()> {
+        global_config_instructions::init_global_config_handler(ctx, trade_fee)
+    }
+
+    pub fn propose_admin(ctx: Context<UpdateGlobalConfig>, pending_admin: Pubkey) -> Result<()> {
+        global_config_instructions::propose_admin_handler(ctx, pending_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        global_config_instructions::accept_admin_handler(ctx)
+    }
+
+    pub fn cancel_admin_transfer(ctx: Context<UpdateGlobalConfig>) -> Result<()> {
+        global_config_instructions::cancel_admin_transfer_handler(ctx)
+    }
+
+    pub fn add_resolver(
+        ctx: Context<AddResolver>,
+        resolver: Pubkey,
+        max_orders_per_slot: u16,
+        allowed_mints: Vec<Pubkey>,
+    ) -> Result<()> {
+        global_config_instructions::add_resolver_handler(
+            ctx,
+            resolver,
+            max_orders_per_slot,
+            allowed_mints,
+        )
+    }
+
+    pub fn set_resolver_limits(
+        ctx: Context<UpdateResolver>,
+        resolver: Pubkey,
+        max_orders_per_slot: u16,
+        allowed_mints: Vec<Pubkey>,
+    ) -> Result<()> {
+        global_config_instructions::set_resolver_limits_handler(
+            ctx,
+            resolver,
+            max_orders_per_slot,
+            allowed_mints,
+        )
+    }
+
+    pub fn suspend_resolver(ctx: Context<UpdateResolver>, resolver: Pubkey) -> Result<()> {
+        global_config_instructions::suspend_resolver_handler(ctx, resolver)
+    }
+
+    pub fn resume_resolver(ctx: Context<UpdateResolver>, resolver: Pubkey) -> Result<()> {
+        global_config_instructions::resume_resolver_handler(ctx, resolver)
+    }
+
+    pub fn migrate_resolver(ctx: Context<MigrateResolver>, resolver: Pubkey) -> Result<()> {
+        global_config_instructions::migrate_resolver_handler(ctx, resolver)
+    }
+
+    pub fn remove_resolver(ctx: Context<RemoveResolver>, resolver: Pubkey) -> Result<()> {
+        global_config_instructions::remove_resolver_handler(ctx, resolver)
+    }
+
+    pub fn set_trade_fee(ctx: Context<UpdateGlobalConfig>, trade_fee: u64) -> Result<()> {
+        global_config_instructions::set_trade_fee_handler(ctx, trade_fee)
+    }
+
+    pub fn pause_trading(ctx: Context<UpdateGlobalConfig>) -> Result<()> {
+        global_config_instructions::pause_trading_handler(ctx)
+    }
+
+    pub fn unpause_trading(ctx: Context<UpdateGlobalConfig>) -> Result<()> {
+        global_config_instructions::unpause_trading_handler(ctx)
+    }
+
+    pub fn set_fee_multiplier(ctx: Context<UpdateGlobalConfig>, fee_multiplier: u8) -> Result<()> {
+        global_config_instructions::set_fee_multiplier_handler(ctx, fee_multiplier)
+    }
+
+    // ******************** Limit Order ******************** //
+    pub fn place_order<'a>(
+        ctx: Context<'_, '_, 'a, 'a, PlaceOrder<'a>>,
+        order_id: u64,
+        making_amount: u64,
+        expect_taking_amount: u64,
+        min_return_amount: u64,
+        deadline: u64,
+        trade_fee: u64,
+    ) -> Result<()> {
+        limitorder_instructions::place_order_handler(
+            ctx,
+            order_id,
+            making_amount,
+            expect_taking_amount,
+            min_return_amount,
+            deadline,
+            trade_fee,
+        )
+    }
+
+    pub fn place_conditional_order<'a>(
+        ctx: Context<'_, '_, 'a, 'a, PlaceOrder<'a>>,
+        order_id: u64,
+        args: ConditionalOrderArgs,
+    ) -> Result<()> {
+        limitorder_instructions::place_conditional_order_handler(ctx, order_id, args)
+    }
+
+    pub fn update_order(
+        ctx: Context<UpdateOrder>,
+        order_id: u64,
+        expect_taking_amount: u64,
+        min_return_amount: u64,
+        deadline: u64,
+        increase_fee: u64,
+    ) -> Result<()> {
+        limitorder_instructions::update_order_handler(
+            ctx,
+            order_id,
+            expect_taking_amount,
+            min_return_amount,
+            deadline,
+            increase_fee,
+        )
+    }
+
+    pub fn cancel_order<'a>(
+        ctx: Context<'_, '_, 'a, 'a, CancelOrder<'a>>,
+        order_id: u64,
+        tips: u64,
+    ) -> Result<()> {
+        limitorder_instructions::cancel_order_handler(ctx, order_id, tips)
+    }
+
+    pub fn fill_order_by_resolver<'a>(
+        ctx: Context<'_, '_, 'a, 'a, FillOrder<'a>>,
+        order_id: u64,
+        tips: u64,
+        args: SwapArgs,
+    ) -> Result<()> {
+        limitorder_instructions::fill_order_by_resolver_handler(ctx, order_id, tips, args)
+    }
+
+    pub fn place_recurring_order<'a>(
+        ctx: Context<'_, '_, 'a, 'a, PlaceRecurringOrder<'a>>,
+        order_id: u64,
+        making_amount: u64,
+        slice_count: u16,
+        slice_min_return_amount: u64,
+        interval: u64,
+        trade_fee: u64,
+    ) -> Result<()> {
+        limitorder_instructions::place_recurring_order_handler(
+            ctx,
+            order_id,
+            making_amount,
+            slice_count,
+            slice_min_return_amount,
+            interval,
+            trade_fee,
+        )
+    }
+
+    pub fn fill_recurring_order<'a>(
+        ctx: Context<'_, '_, 'a, 'a, FillRecurringOrder<'a>>,
+        order_id: u64,
+        tips: u64,
+        args: SwapArgs,
+    ) -> Result<()> {
+        limitorder_instructions::fill_recurring_order_handler(ctx, order_id, tips, args)
+    }
+
+    pub fn cancel_recurring_order<'a>(
+        ctx: Context<'_, '_, 'a, 'a, CancelRecurringOrder<'a>>,
+        order_id: u64,
+    ) -> Result<()> {
+        limitorder_instructions::cancel_recurring_order_handler(ctx, order_id)
+    }
+
+    pub fn fill_order_intent<'a>(
+        ctx: Context<'_, '_, 'a, 'a, FillOrderIntent<'a>>,
+        intent: OrderIntent,
+        args: SwapArgs,
+    ) -> Result<()> {
+        limitorder_instructions::fill_order_intent_handler(ctx, intent, args)
+    }
+
+    pub fn commission_fill_order<'a>(
+        ctx: Context<'_, '_, 'a, 'a, CommissionFillOrder<'a>>,
+        order_id: u64,
+        tips: u64,
+        args: SwapArgs,
+        commission_info: u32,
+    ) -> Result<()> {
+        limitorder_instructions::commission_fill_order_handler(
+            ctx,
+            order_id,
+            tips,
+            args,
+            commission_info,
+        )
+    }
 }
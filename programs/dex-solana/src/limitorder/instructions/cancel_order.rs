@@ -1,4 +1,4 @@
-use crate::state::{config::*, event::*, order::*};
+use crate::state::{config::*, event::*, order::*, resolver::*};
 use crate::utils::*;
 use crate::wsol_program;
 use crate::{constants::*, error::LimitOrderError};
@@ -73,9 +73,24 @@ pub struct CancelOrder<'info> {
     /// CHECK: Solana Instructions Sysvar
     #[account(address = sysvar::instructions::ID)]
     pub instructions_sysvar: UncheckedAccount<'info>,
+
+    /// The resolver PDA of the payer, required unless the payer is the maker
+    #[account(
+        mut,
+        seeds = [
+            RESOLVER_SEED.as_bytes(),
+            payer.key().as_ref(),
+        ],
+        bump = resolver_pda.load()?.bump,
+    )]
+    pub resolver_pda: Option<AccountLoader<'info, Resolver>>,
 }
 
-pub fn cancel_order_handler(ctx: Context<CancelOrder>, order_id: u64, tips: u64) -> Result<()> {
+pub fn cancel_order_handler<'a>(
+    ctx: Context<'_, '_, 'a, 'a, CancelOrder<'a>>,
+    order_id: u64,
+    tips: u64,
+) -> Result<()> {
     msg!("CancelOrder orderId: {}", order_id);
     let global_config = ctx.accounts.global_config.load()?;
 
@@ -88,8 +103,10 @@ pub fn cancel_order_handler(ctx: Context<CancelOrder>, order_id: u64, tips: u64)
     if !is_maker {
         msg!("CancelOrder by Resolver");
 
-        // Check if the resolver is the payer
-        require!(global_config.is_resolver(payer), LimitOrderError::OnlyResolver);
+        // Check the payer is a registered resolver
+        let resolver_pda =
+            ctx.accounts.resolver_pda.as_ref().ok_or(LimitOrderError::OnlyResolver)?;
+        resolver_pda.load_mut()?.record_cancel()?;
 
         // Check if the order has expired
         #[cfg(feature = "check-deadline")]
@@ -132,6 +149,7 @@ pub fn cancel_order_handler(ctx: Context<CancelOrder>, order_id: u64, tips: u64)
         amount,
         ctx.accounts.input_token_mint.decimals,
         Some(order_pda_seeds),
+        ctx.remaining_accounts,
     )?;
 
     // Harvest the transfer fee if it exists
@@ -162,6 +180,7 @@ pub fn cancel_order_handler(ctx: Context<CancelOrder>, order_id: u64, tips: u64)
             ctx.accounts.payer.to_account_info(),
             &ctx.accounts.instructions_sysvar.to_account_info(),
             ORDER_MIN_RENT,
+            None,
         )?;
     }
 
@@ -0,0 +1,121 @@
+use crate::state::{config::*, event::*, recurring_order::*};
+use crate::utils::*;
+use crate::{constants::*, error::LimitOrderError};
+use anchor_lang::{prelude::*, solana_program::clock};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(order_id: u64)]
+pub struct CancelRecurringOrder<'info> {
+    /// The maker of the order
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    /// The global config account
+    #[account(
+        seeds = [
+            GLOBAL_CONFIG_SEED.as_bytes(),
+        ],
+        bump = global_config.load()?.bump,
+        constraint = !global_config.load()?.paused @ LimitOrderError::TradingPaused,
+    )]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    /// The recurring order PDA account
+    #[account(
+        mut,
+        close = maker,
+        seeds = [
+            RECURRING_ORDER_SEED.as_bytes(),
+            &order_id.to_le_bytes(),
+            maker.key().as_ref(),
+        ],
+        bump = order_pda.bump,
+    )]
+    pub order_pda: Account<'info, RecurringOrder>,
+
+    /// The escrow token account for the order
+    #[account(
+        mut,
+        token::mint = input_token_mint,
+        token::authority = order_pda,
+        token::token_program = input_token_program,
+        seeds = [
+            ESCROW_TOKEN_SEED.as_bytes(),
+            order_pda.key().as_ref(),
+            input_token_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The user token account for input token
+    #[account(
+        mut,
+        token::mint = input_token_mint,
+        token::authority = maker,
+        token::token_program = input_token_program,
+    )]
+    pub input_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of input token
+    #[account(mut)]
+    pub input_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// SPL program for input token transfers
+    pub input_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Refunds the unfilled slices and the unused trade fee to the maker
+pub fn cancel_recurring_order_handler<'a>(
+    ctx: Context<'_, '_, 'a, 'a, CancelRecurringOrder<'a>>,
+    order_id: u64,
+) -> Result<()> {
+    msg!("CancelRecurringOrder orderId: {}", order_id);
+    let update_ts = clock::Clock::get()?.unix_timestamp as u64;
+    let order = &ctx.accounts.order_pda;
+    let maker = ctx.accounts.maker.key();
+    let refund_amount = ctx.accounts.escrow_token_account.amount;
+    let order_pda_seeds: &[&[&[u8]]] = &[&[
+        RECURRING_ORDER_SEED.as_bytes(),
+        &order_id.to_le_bytes(),
+        maker.as_ref(),
+        &[order.bump],
+    ]];
+
+    // Transfer the escrow token from the escrow account to the maker
+    transfer_token(
+        ctx.accounts.order_pda.to_account_info(),
+        ctx.accounts.escrow_token_account.to_account_info(),
+        ctx.accounts.input_token_account.to_account_info(),
+        ctx.accounts.input_token_mint.to_account_info(),
+        ctx.accounts.input_token_program.to_account_info(),
+        refund_amount,
+        ctx.accounts.input_token_mint.decimals,
+        Some(order_pda_seeds),
+        ctx.remaining_accounts,
+    )?;
+
+    // Harvest the transfer fee if it exists
+    if get_transfer_fee(&ctx.accounts.input_token_mint.to_account_info(), refund_amount)? > 0 {
+        harvest_withheld_tokens_to_mint(
+            ctx.accounts.input_token_program.to_account_info(),
+            ctx.accounts.input_token_mint.to_account_info(),
+            ctx.accounts.escrow_token_account.to_account_info(),
+            Some(order_pda_seeds),
+        )?;
+    }
+
+    // Close the escrow token account
+    close_token_account(
+        ctx.accounts.escrow_token_account.to_account_info(),
+        ctx.accounts.maker.to_account_info(),
+        ctx.accounts.order_pda.to_account_info(),
+        ctx.accounts.input_token_program.to_account_info(),
+        Some(order_pda_seeds),
+    )?;
+
+    emit_cpi!(CancelRecurringOrderEvent { order_id, maker, refund_amount, update_ts });
+    Ok(())
+}
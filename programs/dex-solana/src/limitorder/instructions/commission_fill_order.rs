@@ -1,6 +1,7 @@
 use crate::constants::*;
 use crate::error::LimitOrderError;
-use crate::state::{config::*, event::*, order::*};
+use crate::limitorder_instructions::sol_receiver_account;
+use crate::state::{config::*, event::*, order::*, resolver::*};
 use crate::utils::*;
 use crate::{SwapArgs, common_swap};
 use anchor_lang::{prelude::*, solana_program::clock::Clock, solana_program::sysvar};
@@ -26,7 +27,6 @@ pub struct CommissionFillOrder<'info> {
         ],
         bump = global_config.load()?.bump,
         constraint = !global_config.load()?.paused @ LimitOrderError::TradingPaused,
-        constraint = global_config.load()?.is_resolver(payer.key()) @ LimitOrderError::OnlyResolver,
     )]
     pub global_config: AccountLoader<'info, GlobalConfig>,
 
@@ -45,10 +45,9 @@ pub struct CommissionFillOrder<'info> {
     #[account(mut)]
     pub output_token_sa: Option<UncheckedAccount<'info>>,
 
-    /// The order PDA account
+    /// The order PDA account, closed to the maker once fully filled
     #[account(
         mut,
-        close = maker,
         seeds = [
             ORDER_V1_SEED.as_bytes(),
             &order_id.to_le_bytes(),
@@ -58,7 +57,7 @@ pub struct CommissionFillOrder<'info> {
     )]
     pub order_pda: Box<Account<'info, OrderV1>>,
 
-    /// The escrow token account for the order
+    /// The escrow token account for the order, closed once fully filled
     #[account(
         mut,
         token::mint = input_token_mint,
@@ -107,6 +106,24 @@ pub struct CommissionFillOrder<'info> {
     /// CHECK: commission token account
     #[account(mut)]
     pub commission_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The resolver PDA of the payer
+    #[account(
+        mut,
+        seeds = [
+            RESOLVER_SEED.as_bytes(),
+            payer.key().as_ref(),
+        ],
+        bump = resolver_pda.load()?.bump,
+    )]
+    pub resolver_pda: AccountLoader<'info, Resolver>,
+
+    /// CHECK: price account of a conditional order, checked against the order trigger
+    pub oracle: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: The designated receiver of the order, receives native sol output
+    #[account(mut)]
+    pub receiver: Option<UncheckedAccount<'info>>,
 }
 
 pub fn commission_fill_order_handler<'a>(
@@ -123,6 +140,16 @@ pub fn commission_fill_order_handler<'a>(
     let current_ts = Clock::get()?.unix_timestamp as u64;
     require_gte!(order.deadline, current_ts, LimitOrderError::OrderExpired);
 
+    // Check and count the fill against the resolver limits
+    ctx.accounts.resolver_pda.load_mut()?.record_fill(
+        order.input_token_mint,
+        order.output_token_mint,
+        Clock::get()?.slot,
+    )?;
+
+    // Conditional orders fill only once the oracle price crosses the trigger price
+    order.trigger.check(ctx.accounts.oracle.as_deref(), current_ts, Clock::get()?.slot)?;
+
     // update on 2025-05-23: fix tax token issue start
     // Check amount_in
     // require!(
@@ -137,12 +164,22 @@ pub fn commission_fill_order_handler<'a>(
     );
     // update on 2025-05-23: fix tax token issue end
 
+    // Fill a chunk of the order, a fill of the whole escrow balance settles the order
+    let fill_amount = args.amount_in;
+    order.check_fill_amount(fill_amount, escrow_token_amount)?;
+    let is_final_fill = fill_amount == escrow_token_amount;
+    let (expect_taking_amount, min_return_amount) =
+        order.fill_taking_amounts(fill_amount, is_final_fill)?;
+    let escrow_fee = order.to_account_info().lamports().saturating_sub(ORDER_MIN_RENT);
+    let max_fees = order.fill_fee_cap(escrow_fee, fill_amount, is_final_fill)?;
+
     // Parse fee info
     let fee_direction = commission_info >> 31 == 1;
     let fee_rate = commission_info & ((1 << 31) - 1);
 
     let payer = ctx.accounts.payer.key();
     let maker = ctx.accounts.maker.key();
+    let receiver = order.output_receiver();
     let input_token_mint = ctx.accounts.input_token_mint.key();
     let output_token_mint = ctx.accounts.output_token_mint.key();
     let order_pda_seeds: &[&[&[u8]]] = &[&[
@@ -157,7 +194,7 @@ pub fn commission_fill_order_handler<'a>(
 
     // Set source token account
     let mut source_token_account = &mut ctx.accounts.escrow_token_account;
-    let mut real_amount_in = source_token_account.amount;
+    let mut real_amount_in = fill_amount;
     if is_wsol_input {
         // The pump.fun adapter can only close a temporary wsol account of a system account
         if let Some(temp_input_token_account) = &mut ctx.accounts.temp_input_token_account {
@@ -189,6 +226,7 @@ pub fn commission_fill_order_handler<'a>(
                 fee_amount,
                 ctx.accounts.input_token_mint.decimals,
                 Some(order_pda_seeds),
+                ctx.remaining_accounts,
             )?;
 
             real_amount_in =
@@ -204,6 +242,7 @@ pub fn commission_fill_order_handler<'a>(
                 real_amount_in,
                 ctx.accounts.input_token_mint.decimals,
                 Some(order_pda_seeds),
+                ctx.remaining_accounts,
             )?;
             source_token_account = temp_input_token_account;
         }
@@ -213,21 +252,21 @@ pub fn commission_fill_order_handler<'a>(
     let output_token_account = &mut ctx.accounts.output_token_account;
     if is_wsol_output {
         // Owner is payer, support toToken is sol, The following instruction will close output_token_account for user and recover the rent through tips.
-        // Owner is maker, support toToken is wsol
+        // Owner is receiver, support toToken is wsol
         require!(
-            output_token_account.owner == payer || output_token_account.owner == maker,
+            output_token_account.owner == payer || output_token_account.owner == receiver,
             LimitOrderError::InvalidOutputTokenOwner
         );
     } else {
-        // Owner is maker, support other toToken
-        require!(output_token_account.owner == maker, LimitOrderError::InvalidOutputTokenOwner);
+        // Owner is receiver, support other toToken
+        require!(output_token_account.owner == receiver, LimitOrderError::InvalidOutputTokenOwner);
     }
 
     // Reset swap args
     let mut _args = args.clone();
     _args.amount_in = real_amount_in;
-    _args.expect_amount_out = order.expect_taking_amount;
-    _args.min_return = order.min_return_amount;
+    _args.expect_amount_out = expect_taking_amount;
+    _args.min_return = min_return_amount;
 
     // Swap
     let actual_taking_amount = common_swap(
@@ -252,14 +291,17 @@ pub fn commission_fill_order_handler<'a>(
         Some(fee_rate),
         Some(fee_direction),
         Some(&ctx.accounts.commission_token_account),
-    )?;
+    )?
+    .amount_out;
 
     if is_wsol_output && output_token_account.owner == payer {
-        // Output token is sol, close the output_token_account and transfer sol to maker
+        // Output token is sol, close the output_token_account and transfer sol to the receiver
+        let receiver_account =
+            sol_receiver_account(receiver, &ctx.accounts.maker, &ctx.accounts.receiver)?;
         handle_sol_output(
             output_token_account,
             &ctx.accounts.payer.to_account_info(),
-            &ctx.accounts.maker.to_account_info(),
+            &receiver_account,
             &ctx.accounts.output_token_mint,
             &ctx.accounts.output_token_program,
             actual_taking_amount,
@@ -270,25 +312,35 @@ pub fn commission_fill_order_handler<'a>(
         )?;
     }
 
-    // Harvest the transfer fee if it exists
-    if get_transfer_fee(&ctx.accounts.input_token_mint.to_account_info(), real_amount_in)? > 0 {
-        harvest_withheld_tokens_to_mint(
-            ctx.accounts.input_token_program.to_account_info(),
-            ctx.accounts.input_token_mint.to_account_info(),
+    // Record the fill with the amount actually taken from the escrow
+    ctx.accounts.escrow_token_account.reload()?;
+    let making_amount = escrow_token_amount
+        .checked_sub(ctx.accounts.escrow_token_account.amount)
+        .ok_or(LimitOrderError::MathOverflow)?;
+    let is_fully_filled = ctx.accounts.escrow_token_account.amount == 0;
+    ctx.accounts.order_pda.record_fill(making_amount, actual_taking_amount, is_fully_filled)?;
+
+    if is_fully_filled {
+        // Harvest the transfer fee if it exists
+        if get_transfer_fee(&ctx.accounts.input_token_mint.to_account_info(), real_amount_in)? > 0 {
+            harvest_withheld_tokens_to_mint(
+                ctx.accounts.input_token_program.to_account_info(),
+                ctx.accounts.input_token_mint.to_account_info(),
+                ctx.accounts.escrow_token_account.to_account_info(),
+                Some(order_pda_seeds),
+            )?;
+        }
+
+        // Close the escrow token account
+        close_token_account(
             ctx.accounts.escrow_token_account.to_account_info(),
+            ctx.accounts.maker.to_account_info(),
+            ctx.accounts.order_pda.to_account_info(),
+            ctx.accounts.input_token_program.to_account_info(),
             Some(order_pda_seeds),
         )?;
     }
 
-    // Close the escrow token account
-    close_token_account(
-        ctx.accounts.escrow_token_account.to_account_info(),
-        ctx.accounts.maker.to_account_info(),
-        ctx.accounts.order_pda.to_account_info(),
-        ctx.accounts.input_token_program.to_account_info(),
-        Some(order_pda_seeds),
-    )?;
-
     // Collect fees
     collect_fees(
         tips,
@@ -297,19 +349,39 @@ pub fn commission_fill_order_handler<'a>(
         ctx.accounts.payer.to_account_info(),
         &ctx.accounts.instructions_sysvar.to_account_info(),
         ORDER_MIN_RENT,
+        Some(max_fees),
     )?;
 
     // Emit event
-    emit_cpi!(FillOrderEvent {
+    let order = &ctx.accounts.order_pda;
+    emit_cpi!(PartialFillOrderEvent {
         order_id: order.order_id,
         payer,
         maker,
         input_token_mint,
         output_token_mint,
-        making_amount: order.making_amount,
+        making_amount,
         taking_amount: actual_taking_amount,
+        filled_making_amount: order.filled_making_amount,
+        filled_taking_amount: order.filled_taking_amount,
         update_ts: current_ts,
     });
+    if is_fully_filled {
+        emit_cpi!(FillOrderEvent {
+            order_id: order.order_id,
+            payer,
+            maker,
+            receiver,
+            input_token_mint,
+            output_token_mint,
+            making_amount: order.making_amount,
+            taking_amount: order.filled_taking_amount,
+            update_ts: current_ts,
+        });
+
+        // Close the order PDA
+        ctx.accounts.order_pda.close(ctx.accounts.maker.to_account_info())?;
+    }
     Ok(())
 }
 
@@ -345,6 +417,7 @@ fn handle_sol_output<'info>(
             fee_amount,
             output_token_mint.decimals,
             None,
+            &[],
         )?;
 
         real_amount_out =
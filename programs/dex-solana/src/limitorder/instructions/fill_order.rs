@@ -1,7 +1,7 @@
 use crate::constants::*;
 use crate::error::LimitOrderError;
 use crate::processor::proxy_swap_processor::ProxySwapProcessor;
-use crate::state::{config::*, event::*, order::*};
+use crate::state::{config::*, event::*, order::*, resolver::*};
 use crate::utils::*;
 use crate::{SwapArgs, common_swap};
 use anchor_lang::{prelude::*, solana_program::clock::Clock, solana_program::sysvar};
@@ -27,7 +27,6 @@ pub struct FillOrder<'info> {
         ],
         bump = global_config.load()?.bump,
         constraint = !global_config.load()?.paused @ LimitOrderError::TradingPaused,
-        constraint = global_config.load()?.is_resolver(payer.key()) @ LimitOrderError::OnlyResolver,
     )]
     pub global_config: AccountLoader<'info, GlobalConfig>,
 
@@ -46,10 +45,9 @@ pub struct FillOrder<'info> {
     #[account(mut)]
     pub output_token_sa: Option<UncheckedAccount<'info>>,
 
-    /// The order PDA account
+    /// The order PDA account, closed to the maker once fully filled
     #[account(
         mut,
-        close = maker,
         seeds = [
             ORDER_V1_SEED.as_bytes(),
             &order_id.to_le_bytes(),
@@ -59,7 +57,7 @@ pub struct FillOrder<'info> {
     )]
     pub order_pda: Box<Account<'info, OrderV1>>,
 
-    /// The escrow token account for the order
+    /// The escrow token account for the order, closed once fully filled
     #[account(
         mut,
         token::mint = input_token_mint,
@@ -104,6 +102,24 @@ pub struct FillOrder<'info> {
     /// CHECK: Solana Instructions Sysvar
     #[account(address = sysvar::instructions::ID)]
     pub instructions_sysvar: UncheckedAccount<'info>,
+
+    /// The resolver PDA of the payer
+    #[account(
+        mut,
+        seeds = [
+            RESOLVER_SEED.as_bytes(),
+            payer.key().as_ref(),
+        ],
+        bump = resolver_pda.load()?.bump,
+    )]
+    pub resolver_pda: AccountLoader<'info, Resolver>,
+
+    /// CHECK: price account of a conditional order, checked against the order trigger
+    pub oracle: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: The designated receiver of the order, receives native sol output
+    #[account(mut)]
+    pub receiver: Option<UncheckedAccount<'info>>,
 }
 
 pub fn fill_order_by_resolver_handler<'a>(
@@ -119,6 +135,16 @@ pub fn fill_order_by_resolver_handler<'a>(
     let current_ts = Clock::get()?.unix_timestamp as u64;
     require_gte!(order.deadline, current_ts, LimitOrderError::OrderExpired);
 
+    // Check and count the fill against the resolver limits
+    ctx.accounts.resolver_pda.load_mut()?.record_fill(
+        order.input_token_mint,
+        order.output_token_mint,
+        Clock::get()?.slot,
+    )?;
+
+    // Conditional orders fill only once the oracle price crosses the trigger price
+    order.trigger.check(ctx.accounts.oracle.as_deref(), current_ts, Clock::get()?.slot)?;
+
     // update on 2025-05-23: fix tax token issue start
     // Check amount_in
     // require!(
@@ -129,8 +155,18 @@ pub fn fill_order_by_resolver_handler<'a>(
     msg!("FillOrder amount_in: {}, escrow_token_amount: {}", args.amount_in, escrow_token_amount);
     // update on 2025-05-23: fix tax token issue end
 
+    // Fill a chunk of the order, a fill of the whole escrow balance settles the order
+    let fill_amount = args.amount_in;
+    order.check_fill_amount(fill_amount, escrow_token_amount)?;
+    let is_final_fill = fill_amount == escrow_token_amount;
+    let (expect_taking_amount, min_return_amount) =
+        order.fill_taking_amounts(fill_amount, is_final_fill)?;
+    let escrow_fee = order.to_account_info().lamports().saturating_sub(ORDER_MIN_RENT);
+    let max_fees = order.fill_fee_cap(escrow_fee, fill_amount, is_final_fill)?;
+
     let payer = ctx.accounts.payer.key();
     let maker = ctx.accounts.maker.key();
+    let receiver = order.output_receiver();
     let input_token_mint = ctx.accounts.input_token_mint.key();
     let output_token_mint = ctx.accounts.output_token_mint.key();
     let order_pda_seeds: &[&[&[u8]]] = &[&[
@@ -160,9 +196,10 @@ pub fn fill_order_by_resolver_handler<'a>(
                 temp_input_token_account.to_account_info(),
                 ctx.accounts.input_token_mint.to_account_info(),
                 ctx.accounts.input_token_program.to_account_info(),
-                args.amount_in,
+                fill_amount,
                 ctx.accounts.input_token_mint.decimals,
                 Some(order_pda_seeds),
+                ctx.remaining_accounts,
             )?;
             source_token_account = temp_input_token_account;
         }
@@ -172,21 +209,21 @@ pub fn fill_order_by_resolver_handler<'a>(
     let output_token_account = &mut ctx.accounts.output_token_account;
     if is_wsol_output {
         // Owner is payer, support toToken is sol, The following instruction will close output_token_account for user and recover the rent through tips.
-        // Owner is maker, support toToken is wsol
+        // Owner is receiver, support toToken is wsol
         require!(
-            output_token_account.owner == payer || output_token_account.owner == maker,
+            output_token_account.owner == payer || output_token_account.owner == receiver,
             LimitOrderError::InvalidOutputTokenOwner
         );
     } else {
-        // Owner is maker, support other toToken
-        require!(output_token_account.owner == maker, LimitOrderError::InvalidOutputTokenOwner);
+        // Owner is receiver, support other toToken
+        require!(output_token_account.owner == receiver, LimitOrderError::InvalidOutputTokenOwner);
     }
 
     // Reset swap args
     let mut _args = args.clone();
-    _args.amount_in = escrow_token_amount;
-    _args.expect_amount_out = order.expect_taking_amount;
-    _args.min_return = order.min_return_amount;
+    _args.amount_in = fill_amount;
+    _args.expect_amount_out = expect_taking_amount;
+    _args.min_return = min_return_amount;
 
     // Swap
     let actual_taking_amount = common_swap(
@@ -211,38 +248,51 @@ pub fn fill_order_by_resolver_handler<'a>(
         None,
         None,
         None,
-    )?;
+    )?
+    .amount_out;
 
     if is_wsol_output && output_token_account.owner == payer {
-        // Output token is sol, close the output_token_account and transfer sol to maker
+        // Output token is sol, close the output_token_account and transfer sol to the receiver
+        let receiver_account =
+            sol_receiver_account(receiver, &ctx.accounts.maker, &ctx.accounts.receiver)?;
         handle_sol_output(
             output_token_account,
             &ctx.accounts.payer.to_account_info(),
-            &ctx.accounts.maker.to_account_info(),
+            &receiver_account,
             &ctx.accounts.output_token_program,
             actual_taking_amount,
         )?;
     }
 
-    // Harvest the transfer fee if it exists
-    if get_transfer_fee(&ctx.accounts.input_token_mint.to_account_info(), args.amount_in)? > 0 {
-        harvest_withheld_tokens_to_mint(
-            ctx.accounts.input_token_program.to_account_info(),
-            ctx.accounts.input_token_mint.to_account_info(),
+    // Record the fill with the amount actually taken from the escrow
+    ctx.accounts.escrow_token_account.reload()?;
+    let making_amount = escrow_token_amount
+        .checked_sub(ctx.accounts.escrow_token_account.amount)
+        .ok_or(LimitOrderError::MathOverflow)?;
+    let is_fully_filled = ctx.accounts.escrow_token_account.amount == 0;
+    ctx.accounts.order_pda.record_fill(making_amount, actual_taking_amount, is_fully_filled)?;
+
+    if is_fully_filled {
+        // Harvest the transfer fee if it exists
+        if get_transfer_fee(&ctx.accounts.input_token_mint.to_account_info(), fill_amount)? > 0 {
+            harvest_withheld_tokens_to_mint(
+                ctx.accounts.input_token_program.to_account_info(),
+                ctx.accounts.input_token_mint.to_account_info(),
+                ctx.accounts.escrow_token_account.to_account_info(),
+                Some(order_pda_seeds),
+            )?;
+        }
+
+        // Close the escrow token account
+        close_token_account(
             ctx.accounts.escrow_token_account.to_account_info(),
+            ctx.accounts.maker.to_account_info(),
+            ctx.accounts.order_pda.to_account_info(),
+            ctx.accounts.input_token_program.to_account_info(),
             Some(order_pda_seeds),
         )?;
     }
 
-    // Close the escrow token account
-    close_token_account(
-        ctx.accounts.escrow_token_account.to_account_info(),
-        ctx.accounts.maker.to_account_info(),
-        ctx.accounts.order_pda.to_account_info(),
-        ctx.accounts.input_token_program.to_account_info(),
-        Some(order_pda_seeds),
-    )?;
-
     // Collect fees
     collect_fees(
         tips,
@@ -251,23 +301,57 @@ pub fn fill_order_by_resolver_handler<'a>(
         ctx.accounts.payer.to_account_info(),
         &ctx.accounts.instructions_sysvar.to_account_info(),
         ORDER_MIN_RENT,
+        Some(max_fees),
     )?;
 
     // Emit event
-    emit_cpi!(FillOrderEvent {
+    let order = &ctx.accounts.order_pda;
+    emit_cpi!(PartialFillOrderEvent {
         order_id: order.order_id,
         payer,
         maker,
         input_token_mint,
         output_token_mint,
-        making_amount: order.making_amount,
+        making_amount,
         taking_amount: actual_taking_amount,
+        filled_making_amount: order.filled_making_amount,
+        filled_taking_amount: order.filled_taking_amount,
         update_ts: current_ts,
     });
+    if is_fully_filled {
+        emit_cpi!(FillOrderEvent {
+            order_id: order.order_id,
+            payer,
+            maker,
+            receiver,
+            input_token_mint,
+            output_token_mint,
+            making_amount: order.making_amount,
+            taking_amount: order.filled_taking_amount,
+            update_ts: current_ts,
+        });
+
+        // Close the order PDA
+        ctx.accounts.order_pda.close(ctx.accounts.maker.to_account_info())?;
+    }
     Ok(())
 }
 
-fn handle_sol_output<'info>(
+/// The account native sol output is paid to, a designated receiver must be passed in
+pub(crate) fn sol_receiver_account<'info>(
+    receiver: Pubkey,
+    maker: &AccountInfo<'info>,
+    receiver_account: &Option<UncheckedAccount<'info>>,
+) -> Result<AccountInfo<'info>> {
+    if receiver == maker.key() {
+        return Ok(maker.clone());
+    }
+    let receiver_account = receiver_account.as_ref().ok_or(LimitOrderError::InvalidReceiver)?;
+    require_keys_eq!(receiver_account.key(), receiver, LimitOrderError::InvalidReceiver);
+    Ok(receiver_account.to_account_info())
+}
+
+pub(crate) fn handle_sol_output<'info>(
     output_token_account: &InterfaceAccount<'info, TokenAccount>,
     payer: &AccountInfo<'info>,
     maker: &AccountInfo<'info>,
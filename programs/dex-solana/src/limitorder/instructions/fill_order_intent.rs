@@ -0,0 +1,298 @@
+use crate::constants::*;
+use crate::error::LimitOrderError;
+use crate::limitorder_instructions::handle_sol_output;
+use crate::processor::proxy_swap_processor::ProxySwapProcessor;
+use crate::state::{config::*, event::*, intent::*, resolver::*};
+use crate::utils::*;
+use crate::{SwapArgs, common_swap};
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::{prelude::*, solana_program::clock::Clock, solana_program::sysvar};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(intent: OrderIntent)]
+pub struct FillOrderIntent<'info> {
+    /// The payer of the transaction
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: The intent maker, the signature is checked against the ed25519 instruction
+    #[account(
+        mut,
+        address = intent.maker @ LimitOrderError::InvalidIntent,
+    )]
+    pub maker: AccountInfo<'info>,
+
+    /// The global config account
+    #[account(
+        seeds = [
+            GLOBAL_CONFIG_SEED.as_bytes(),
+        ],
+        bump = global_config.load()?.bump,
+        constraint = !global_config.load()?.paused @ LimitOrderError::TradingPaused,
+    )]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    /// The resolver PDA of the payer
+    #[account(
+        mut,
+        seeds = [
+            RESOLVER_SEED.as_bytes(),
+            payer.key().as_ref(),
+        ],
+        bump = resolver_pda.load()?.bump,
+    )]
+    pub resolver_pda: AccountLoader<'info, Resolver>,
+
+    /// The nonce bitmap page of the intent nonce, paid by the resolver
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = IntentNonces::LEN,
+        seeds = [
+            INTENT_NONCES_SEED.as_bytes(),
+            maker.key().as_ref(),
+            &intent.nonce_page().to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub intent_nonces: Box<Account<'info, IntentNonces>>,
+
+    /// CHECK: The token delegate the maker approves on the input token account
+    #[account(
+        seeds = [
+            INTENT_DELEGATE_SEED.as_bytes(),
+            maker.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub intent_delegate: UncheckedAccount<'info>,
+
+    /// CHECK: sa_authority
+    #[account(
+        seeds = [
+            SEED_SA,
+        ],
+        bump = BUMP_SA,
+    )]
+    pub sa_authority: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub input_token_sa: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub output_token_sa: Option<UncheckedAccount<'info>>,
+
+    /// The maker token account for input token
+    #[account(
+        mut,
+        token::mint = input_token_mint,
+        token::authority = maker,
+        token::token_program = input_token_program,
+    )]
+    pub input_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The escrow token account the making amount is swapped from, closed after the swap
+    #[account(
+        init_if_needed,
+        payer = payer,
+        token::mint = input_token_mint,
+        token::authority = intent_delegate,
+        token::token_program = input_token_program,
+        seeds = [
+            ESCROW_TOKEN_SEED.as_bytes(),
+            intent_delegate.key().as_ref(),
+            input_token_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The user token account for output token
+    #[account(
+        mut,
+        token::mint = output_token_mint,
+        token::token_program = output_token_program,
+    )]
+    pub output_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of input token
+    #[account(
+        mut,
+        address = intent.input_token_mint @ LimitOrderError::InvalidIntent,
+    )]
+    pub input_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of output token
+    #[account(
+        address = intent.output_token_mint @ LimitOrderError::InvalidIntent,
+    )]
+    pub output_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub input_token_program: Interface<'info, TokenInterface>,
+    pub output_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Solana Instructions Sysvar
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+/// Fills an order intent signed off-chain by the maker, the making amount is pulled from the
+/// maker token account through the intent delegate and swapped from a temporary escrow
+pub fn fill_order_intent_handler<'a>(
+    ctx: Context<'_, '_, 'a, 'a, FillOrderIntent<'a>>,
+    intent: OrderIntent,
+    args: SwapArgs,
+) -> Result<()> {
+    msg!("FillOrderIntent maker: {}, nonce: {}", intent.maker, intent.nonce);
+
+    // Check intent
+    let current_ts = Clock::get()?.unix_timestamp as u64;
+    require_gte!(intent.deadline, current_ts, LimitOrderError::OrderExpired);
+    require!(
+        intent.making_amount > 0 && intent.min_return_amount > 0,
+        LimitOrderError::InvalidIntent
+    );
+    require!(
+        intent.input_token_mint != intent.output_token_mint,
+        LimitOrderError::InputAndOutputTokenSame
+    );
+
+    // Check the maker signed the intent
+    verify_ed25519_signature(
+        &ctx.accounts.instructions_sysvar.to_account_info(),
+        &intent.maker,
+        &intent.message()?,
+    )?;
+
+    // Record the nonce, a nonce page is created on its first use
+    let intent_nonces = &mut ctx.accounts.intent_nonces;
+    if intent_nonces.maker == Pubkey::default() {
+        intent_nonces.bump = ctx.bumps.intent_nonces;
+        intent_nonces.maker = intent.maker;
+        intent_nonces.page = intent.nonce_page();
+    }
+    intent_nonces.use_nonce(intent.nonce)?;
+
+    // Check and count the fill against the resolver limits
+    ctx.accounts.resolver_pda.load_mut()?.record_fill(
+        intent.input_token_mint,
+        intent.output_token_mint,
+        Clock::get()?.slot,
+    )?;
+
+    // Check the maker approved the intent delegate for the making amount
+    let input_token_account = &ctx.accounts.input_token_account;
+    require!(
+        input_token_account.delegate == COption::Some(ctx.accounts.intent_delegate.key())
+            && input_token_account.delegated_amount >= intent.making_amount,
+        LimitOrderError::InvalidIntentDelegate
+    );
+
+    let payer = ctx.accounts.payer.key();
+    let maker = ctx.accounts.maker.key();
+    let intent_delegate_seeds: &[&[&[u8]]] =
+        &[&[INTENT_DELEGATE_SEED.as_bytes(), maker.as_ref(), &[ctx.bumps.intent_delegate]]];
+
+    // Check output token owner, a payer owned wsol account is unwrapped to the maker
+    let output_token_account = &mut ctx.accounts.output_token_account;
+    let is_sol_output = ctx.accounts.output_token_mint.key() == wsol_program::ID
+        && output_token_account.owner == payer;
+    require!(
+        is_sol_output || output_token_account.owner == maker,
+        LimitOrderError::InvalidOutputTokenOwner
+    );
+
+    // Pull the making amount into the escrow, the swap authority must own the source account
+    transfer_token(
+        ctx.accounts.intent_delegate.to_account_info(),
+        ctx.accounts.input_token_account.to_account_info(),
+        ctx.accounts.escrow_token_account.to_account_info(),
+        ctx.accounts.input_token_mint.to_account_info(),
+        ctx.accounts.input_token_program.to_account_info(),
+        intent.making_amount,
+        ctx.accounts.input_token_mint.decimals,
+        Some(intent_delegate_seeds),
+        ctx.remaining_accounts,
+    )?;
+    ctx.accounts.escrow_token_account.reload()?;
+    let escrow_token_amount = ctx.accounts.escrow_token_account.amount;
+
+    // Reset swap args, a transfer fee is taken before the escrow receives the making amount
+    let mut _args = args.clone();
+    _args.amount_in = escrow_token_amount;
+    _args.expect_amount_out = args.expect_amount_out.max(intent.min_return_amount);
+    _args.min_return = intent.min_return_amount;
+
+    // Swap
+    let actual_taking_amount = common_swap(
+        &ProxySwapProcessor,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.intent_delegate.to_account_info(),
+        Some(intent_delegate_seeds),
+        &mut ctx.accounts.escrow_token_account,
+        output_token_account,
+        &ctx.accounts.input_token_mint,
+        &ctx.accounts.output_token_mint,
+        &ctx.accounts.sa_authority,
+        &mut ctx.accounts.input_token_sa,
+        &mut ctx.accounts.output_token_sa,
+        &Some(ctx.accounts.input_token_program.clone()),
+        &Some(ctx.accounts.output_token_program.clone()),
+        &ctx.accounts.associated_token_program,
+        &Some(ctx.accounts.system_program.clone()),
+        ctx.remaining_accounts,
+        _args,
+        intent.nonce,
+        None,
+        None,
+        None,
+    )?
+    .amount_out;
+
+    // Close the escrow token account, the rent goes back to the payer
+    ctx.accounts.escrow_token_account.reload()?;
+    require!(ctx.accounts.escrow_token_account.amount == 0, LimitOrderError::InvalidFillAmount);
+    if get_transfer_fee(&ctx.accounts.input_token_mint.to_account_info(), escrow_token_amount)? > 0
+    {
+        harvest_withheld_tokens_to_mint(
+            ctx.accounts.input_token_program.to_account_info(),
+            ctx.accounts.input_token_mint.to_account_info(),
+            ctx.accounts.escrow_token_account.to_account_info(),
+            Some(intent_delegate_seeds),
+        )?;
+    }
+    close_token_account(
+        ctx.accounts.escrow_token_account.to_account_info(),
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.intent_delegate.to_account_info(),
+        ctx.accounts.input_token_program.to_account_info(),
+        Some(intent_delegate_seeds),
+    )?;
+
+    if is_sol_output {
+        handle_sol_output(
+            output_token_account,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.maker.to_account_info(),
+            &ctx.accounts.output_token_program,
+            actual_taking_amount,
+        )?;
+    }
+
+    emit_cpi!(FillOrderIntentEvent {
+        maker,
+        nonce: intent.nonce,
+        payer,
+        input_token_mint: intent.input_token_mint,
+        output_token_mint: intent.output_token_mint,
+        making_amount: intent.making_amount,
+        taking_amount: actual_taking_amount,
+        update_ts: current_ts,
+    });
+    Ok(())
+}
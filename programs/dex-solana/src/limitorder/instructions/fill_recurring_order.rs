@@ -0,0 +1,257 @@
+use crate::constants::*;
+use crate::error::LimitOrderError;
+use crate::limitorder_instructions::handle_sol_output;
+use crate::processor::proxy_swap_processor::ProxySwapProcessor;
+use crate::state::{config::*, event::*, recurring_order::*, resolver::*};
+use crate::utils::*;
+use crate::{SwapArgs, common_swap};
+use anchor_lang::{prelude::*, solana_program::clock::Clock, solana_program::sysvar};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(order_id: u64)]
+pub struct FillRecurringOrder<'info> {
+    /// The payer of the transaction
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: The order maker
+    #[account(mut)]
+    pub maker: AccountInfo<'info>,
+
+    /// The global config account
+    #[account(
+        seeds = [
+            GLOBAL_CONFIG_SEED.as_bytes(),
+        ],
+        bump = global_config.load()?.bump,
+        constraint = !global_config.load()?.paused @ LimitOrderError::TradingPaused,
+    )]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    /// The resolver PDA of the payer
+    #[account(
+        mut,
+        seeds = [
+            RESOLVER_SEED.as_bytes(),
+            payer.key().as_ref(),
+        ],
+        bump = resolver_pda.load()?.bump,
+    )]
+    pub resolver_pda: AccountLoader<'info, Resolver>,
+
+    /// CHECK: sa_authority
+    #[account(
+        seeds = [
+            SEED_SA,
+        ],
+        bump = BUMP_SA,
+    )]
+    pub sa_authority: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub input_token_sa: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub output_token_sa: Option<UncheckedAccount<'info>>,
+
+    /// The recurring order PDA account, closed to the maker after the last slice
+    #[account(
+        mut,
+        seeds = [
+            RECURRING_ORDER_SEED.as_bytes(),
+            &order_id.to_le_bytes(),
+            maker.key().as_ref(),
+        ],
+        bump = order_pda.bump,
+    )]
+    pub order_pda: Box<Account<'info, RecurringOrder>>,
+
+    /// The escrow token account for the order, closed after the last slice
+    #[account(
+        mut,
+        token::mint = input_token_mint,
+        token::authority = order_pda,
+        token::token_program = input_token_program,
+        seeds = [
+            ESCROW_TOKEN_SEED.as_bytes(),
+            order_pda.key().as_ref(),
+            input_token_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The user token account for output token
+    #[account(
+        mut,
+        token::mint = output_token_mint,
+        token::token_program = output_token_program,
+    )]
+    pub output_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint of input token
+    #[account(mut)]
+    pub input_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of output token
+    #[account(
+        constraint = output_token_mint.key() == order_pda.output_token_mint,
+    )]
+    pub output_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub input_token_program: Interface<'info, TokenInterface>,
+    pub output_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+    pub system_program: Option<Program<'info, System>>,
+
+    /// CHECK: Solana Instructions Sysvar
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+/// Fills exactly one slice of a recurring order through any route, at most once per interval
+pub fn fill_recurring_order_handler<'a>(
+    ctx: Context<'_, '_, 'a, 'a, FillRecurringOrder<'a>>,
+    order_id: u64,
+    tips: u64,
+    args: SwapArgs,
+) -> Result<()> {
+    msg!("FillRecurringOrder orderId: {}", order_id);
+    let order = &ctx.accounts.order_pda;
+
+    // Check the slice is due
+    let current_ts = Clock::get()?.unix_timestamp as u64;
+    require_gte!(current_ts, order.next_fill_ts, LimitOrderError::SliceNotReady);
+
+    // Check and count the fill against the resolver limits
+    ctx.accounts.resolver_pda.load_mut()?.record_fill(
+        order.input_token_mint,
+        order.output_token_mint,
+        Clock::get()?.slot,
+    )?;
+
+    // The swap must sell exactly the next slice
+    let escrow_token_amount = ctx.accounts.escrow_token_account.amount;
+    let fill_amount = order.next_slice_amount(escrow_token_amount);
+    require!(fill_amount > 0 && args.amount_in == fill_amount, LimitOrderError::InvalidFillAmount);
+    let min_return_amount = order.slice_min_return(fill_amount)?;
+
+    let payer = ctx.accounts.payer.key();
+    let maker = ctx.accounts.maker.key();
+    let order_pda_seeds: &[&[&[u8]]] = &[&[
+        RECURRING_ORDER_SEED.as_bytes(),
+        &order.order_id.to_le_bytes(),
+        maker.as_ref(),
+        &[order.bump],
+    ]];
+
+    // Check output token owner, a payer owned wsol account is unwrapped to the maker
+    let output_token_account = &mut ctx.accounts.output_token_account;
+    let is_sol_output = ctx.accounts.output_token_mint.key() == wsol_program::ID
+        && output_token_account.owner == payer;
+    require!(
+        is_sol_output || output_token_account.owner == maker,
+        LimitOrderError::InvalidOutputTokenOwner
+    );
+
+    // Reset swap args
+    let mut _args = args.clone();
+    _args.expect_amount_out = args.expect_amount_out.max(min_return_amount);
+    _args.min_return = min_return_amount;
+
+    // Swap
+    let actual_taking_amount = common_swap(
+        &ProxySwapProcessor,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.order_pda.to_account_info(),
+        Some(order_pda_seeds),
+        &mut ctx.accounts.escrow_token_account,
+        output_token_account,
+        &ctx.accounts.input_token_mint,
+        &ctx.accounts.output_token_mint,
+        &ctx.accounts.sa_authority,
+        &mut ctx.accounts.input_token_sa,
+        &mut ctx.accounts.output_token_sa,
+        &Some(ctx.accounts.input_token_program.clone()),
+        &Some(ctx.accounts.output_token_program.clone()),
+        &ctx.accounts.associated_token_program,
+        &ctx.accounts.system_program,
+        ctx.remaining_accounts,
+        _args,
+        order_id,
+        None,
+        None,
+        None,
+    )?
+    .amount_out;
+
+    if is_sol_output {
+        handle_sol_output(
+            output_token_account,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.maker.to_account_info(),
+            &ctx.accounts.output_token_program,
+            actual_taking_amount,
+        )?;
+    }
+
+    // Record the slice with the amount actually taken from the escrow
+    ctx.accounts.escrow_token_account.reload()?;
+    let making_amount = escrow_token_amount
+        .checked_sub(ctx.accounts.escrow_token_account.amount)
+        .ok_or(LimitOrderError::MathOverflow)?;
+    let is_fully_filled = ctx.accounts.escrow_token_account.amount == 0;
+    ctx.accounts.order_pda.record_slice(making_amount, actual_taking_amount, current_ts)?;
+
+    if is_fully_filled {
+        // Harvest the transfer fee if it exists
+        if get_transfer_fee(&ctx.accounts.input_token_mint.to_account_info(), fill_amount)? > 0 {
+            harvest_withheld_tokens_to_mint(
+                ctx.accounts.input_token_program.to_account_info(),
+                ctx.accounts.input_token_mint.to_account_info(),
+                ctx.accounts.escrow_token_account.to_account_info(),
+                Some(order_pda_seeds),
+            )?;
+        }
+
+        // Close the escrow token account
+        close_token_account(
+            ctx.accounts.escrow_token_account.to_account_info(),
+            ctx.accounts.maker.to_account_info(),
+            ctx.accounts.order_pda.to_account_info(),
+            ctx.accounts.input_token_program.to_account_info(),
+            Some(order_pda_seeds),
+        )?;
+    }
+
+    // Collect fees
+    collect_fees(
+        tips,
+        ctx.accounts.global_config.load()?.fee_multiplier,
+        ctx.accounts.order_pda.to_account_info(),
+        ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.instructions_sysvar.to_account_info(),
+        Rent::get()?.minimum_balance(RecurringOrder::LEN),
+        None,
+    )?;
+
+    let order = &ctx.accounts.order_pda;
+    emit_cpi!(FillRecurringOrderEvent {
+        order_id: order.order_id,
+        payer,
+        maker,
+        making_amount,
+        taking_amount: actual_taking_amount,
+        filled_slices: order.filled_slices,
+        next_fill_ts: order.next_fill_ts,
+        update_ts: current_ts,
+    });
+    if is_fully_filled {
+        // Close the order PDA, the unused trade fee goes back to the maker
+        ctx.accounts.order_pda.close(ctx.accounts.maker.to_account_info())?;
+    }
+    Ok(())
+}
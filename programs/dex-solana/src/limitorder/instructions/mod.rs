@@ -1,11 +1,19 @@
 pub mod cancel_order;
+pub mod cancel_recurring_order;
 pub mod commission_fill_order;
 pub mod fill_order;
+pub mod fill_order_intent;
+pub mod fill_recurring_order;
 pub mod place_order;
+pub mod place_recurring_order;
 pub mod update_order;
 
 pub use cancel_order::*;
+pub use cancel_recurring_order::*;
 pub use commission_fill_order::*;
 pub use fill_order::*;
+pub use fill_order_intent::*;
+pub use fill_recurring_order::*;
 pub use place_order::*;
+pub use place_recurring_order::*;
 pub use update_order::*;
@@ -77,10 +77,23 @@ pub struct PlaceOrder<'info> {
     pub input_token_program: Interface<'info, TokenInterface>,
     pub output_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
+
+    /// CHECK: The owner of the output token, defaults to the maker
+    pub receiver: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ConditionalOrderArgs {
+    pub making_amount: u64,
+    pub expect_taking_amount: u64,
+    pub min_return_amount: u64,
+    pub deadline: u64,
+    pub trade_fee: u64,
+    pub trigger: OrderTrigger,
 }
 
-pub fn place_order_handler(
-    ctx: Context<PlaceOrder>,
+pub fn place_order_handler<'a>(
+    ctx: Context<'_, '_, 'a, 'a, PlaceOrder<'a>>,
     order_id: u64,
     making_amount: u64,
     expect_taking_amount: u64,
@@ -88,6 +101,44 @@ pub fn place_order_handler(
     deadline: u64,
     trade_fee: u64,
 ) -> Result<()> {
+    process_place_order(
+        ctx,
+        order_id,
+        ConditionalOrderArgs {
+            making_amount,
+            expect_taking_amount,
+            min_return_amount,
+            deadline,
+            trade_fee,
+            trigger: OrderTrigger::default(),
+        },
+    )
+}
+
+/// Places an order fillable only once the oracle price crosses the trigger price, the fill
+/// still has to return the pro-rata min return amount
+pub fn place_conditional_order_handler<'a>(
+    ctx: Context<'_, '_, 'a, 'a, PlaceOrder<'a>>,
+    order_id: u64,
+    args: ConditionalOrderArgs,
+) -> Result<()> {
+    args.trigger.validate()?;
+    process_place_order(ctx, order_id, args)
+}
+
+fn process_place_order<'a>(
+    ctx: Context<'_, '_, 'a, 'a, PlaceOrder<'a>>,
+    order_id: u64,
+    args: ConditionalOrderArgs,
+) -> Result<()> {
+    let ConditionalOrderArgs {
+        making_amount,
+        expect_taking_amount,
+        min_return_amount,
+        deadline,
+        trade_fee,
+        trigger,
+    } = args;
     msg!("PlaceOrder orderId: {}", order_id);
     let global_config = ctx.accounts.global_config.load()?;
 
@@ -129,6 +180,7 @@ pub fn place_order_handler(
         making_amount,
         ctx.accounts.input_token_mint.decimals,
         None,
+        ctx.remaining_accounts,
     )?;
 
     // Calculate the actual making amount
@@ -139,6 +191,7 @@ pub fn place_order_handler(
     require!(actual_making_amount > 0, LimitOrderError::ActualMakingAmountIsZero);
 
     let maker = ctx.accounts.maker.key();
+    let receiver = ctx.accounts.receiver.as_ref().map_or(maker, |receiver| receiver.key());
     let input_token_mint = ctx.accounts.input_token_mint.key();
     let output_token_mint = ctx.accounts.output_token_mint.key();
 
@@ -156,12 +209,17 @@ pub fn place_order_handler(
     order_pda.output_token_mint = output_token_mint;
     order_pda.input_token_program = ctx.accounts.input_token_program.key();
     order_pda.output_token_program = ctx.accounts.output_token_program.key();
+    order_pda.filled_making_amount = 0;
+    order_pda.filled_taking_amount = 0;
     order_pda.bump = ctx.bumps.order_pda;
-    order_pda.padding = [0u8; 128];
+    order_pda.trigger = trigger;
+    order_pda.receiver = receiver;
+    order_pda.padding = [0u8; 17];
 
     emit_cpi!(PlaceOrderEvent {
         order_id,
         maker,
+        receiver,
         input_token_mint,
         output_token_mint,
         making_amount: actual_making_amount,
@@ -171,5 +229,8 @@ pub fn place_order_handler(
         deadline,
         trade_fee,
     });
+    if trigger.condition != TriggerCondition::None {
+        emit_cpi!(PlaceConditionalOrderEvent { order_id, maker, trigger });
+    }
     Ok(())
 }
@@ -34,6 +34,7 @@ pub trait CommonSwapProcessor<'info> {
         _fee_rate: Option<u32>,
         _fee_direction: Option<bool>,
         _fee_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+        _remaining_accounts: &[AccountInfo<'info>],
     ) -> Result<u64> {
         Ok(_amount_in)
     }
@@ -50,6 +51,7 @@ pub trait CommonSwapProcessor<'info> {
         _fee_rate: Option<u32>,
         _fee_direction: Option<bool>,
         _fee_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+        _remaining_accounts: &[AccountInfo<'info>],
     ) -> Result<()> {
         Ok(())
     }
@@ -1,3 +1,5 @@
+use crate::instructions::ChargedFees;
+use crate::utils::CommissionSplit;
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
@@ -35,11 +37,13 @@ pub trait PlatformFeeV3Processor<'info> {
         _commission_rate: u32,
         _commission_direction: bool,
         _commission_account: &Option<AccountInfo<'info>>,
+        _commission_split: Option<&CommissionSplit<'_, 'info>>,
         // PLATFORM FEE
         _platform_fee_rate: Option<u16>,
         _platform_fee_account: &Option<AccountInfo<'info>>,
-    ) -> Result<u64> {
-        Ok(_amount_in)
+        _remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<(u64, ChargedFees)> {
+        Ok((_amount_in, ChargedFees::default()))
     }
 
     fn after_swap(
@@ -56,6 +60,7 @@ pub trait PlatformFeeV3Processor<'info> {
         _commission_rate: u32,
         _commission_direction: bool,
         _commission_account: &Option<AccountInfo<'info>>,
+        _commission_split: Option<&CommissionSplit<'_, 'info>>,
         // PLATFORM FEE
         _platform_fee_rate: Option<u16>,
         _platform_fee_account: &Option<AccountInfo<'info>>,
@@ -65,7 +70,8 @@ pub trait PlatformFeeV3Processor<'info> {
         _trim_account: Option<&AccountInfo<'info>>,
         _charge_account: Option<&AccountInfo<'info>>,
         _acc_close_flag: bool,
-    ) -> Result<u64> {
-        Ok(_amount_out)
+        _remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<(u64, ChargedFees)> {
+        Ok((_amount_out, ChargedFees::default()))
     }
 }
@@ -77,6 +77,7 @@ impl ProxySwapProcessor {
         source_token_program: &Option<Interface<'info, TokenInterface>>,
         amount: u64,
         owner_seeds: Option<&[&[&[u8]]]>,
+        hook_accounts: &[AccountInfo<'info>],
     ) -> Result<()> {
         if source_token_sa.is_none() || source_token_program.is_none() {
             return Ok(());
@@ -96,6 +97,7 @@ impl ProxySwapProcessor {
             amount,
             source_mint.decimals,
             owner_seeds,
+            hook_accounts,
         )?;
         Ok(())
     }
@@ -110,6 +112,7 @@ impl ProxySwapProcessor {
         destination_token_program: &Option<Interface<'info, TokenInterface>>,
         amount_out: u64,
         owner_seeds: Option<&[&[&[u8]]]>,
+        hook_accounts: &[AccountInfo<'info>],
     ) -> Result<()> {
         if sa_authority.is_none()
             || destination_token_sa.is_none()
@@ -138,6 +141,48 @@ impl ProxySwapProcessor {
             amount_out,
             destination_mint.decimals,
             owner_seeds,
+            hook_accounts,
+        )?;
+        log_transfer_fee_info(
+            false,
+            get_transfer_fee(&destination_mint.to_account_info(), amount_out)?,
+        );
+        Ok(())
+    }
+
+    /// Proxy handle refund of unspent source amount after exact out swap
+    pub fn proxy_handle_refund<'info>(
+        &self,
+        sa_authority: &Option<UncheckedAccount<'info>>,
+        source_token_account: &InterfaceAccount<'info, TokenAccount>,
+        source_mint: &InterfaceAccount<'info, Mint>,
+        source_token_sa: &Option<UncheckedAccount<'info>>,
+        source_token_program: &Option<Interface<'info, TokenInterface>>,
+        amount: u64,
+        owner_seeds: Option<&[&[&[u8]]]>,
+        hook_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        if sa_authority.is_none() || source_token_sa.is_none() || source_token_program.is_none() {
+            return Ok(());
+        }
+
+        let sa_authority = sa_authority.as_ref().unwrap();
+        let source_token_sa_info = source_token_sa.as_ref().unwrap().to_account_info();
+        let source_token_program = source_token_program.as_ref().unwrap();
+        let (acc_owner, acc_mint) = unpack_token_owner_and_mint(&source_token_sa_info)?;
+        require_keys_eq!(acc_owner, authority_pda::ID, ErrorCode::InvalidSourceTokenSa);
+        require_keys_eq!(acc_mint, source_mint.key(), ErrorCode::InvalidSourceTokenSaMint);
+
+        transfer_token(
+            sa_authority.to_account_info(),
+            source_token_sa_info,
+            source_token_account.to_account_info(),
+            source_mint.to_account_info(),
+            source_token_program.to_account_info(),
+            amount,
+            source_mint.decimals,
+            owner_seeds,
+            hook_accounts,
         )?;
         Ok(())
     }
@@ -188,6 +233,7 @@ impl<'info> CommonSwapProcessor<'info> for ProxySwapProcessor {
         _fee_rate: Option<u32>,
         _fee_direction: Option<bool>,
         _fee_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+        remaining_accounts: &[AccountInfo<'info>],
     ) -> Result<u64> {
         self.proxy_handle_before(
             owner,
@@ -197,6 +243,7 @@ impl<'info> CommonSwapProcessor<'info> for ProxySwapProcessor {
             source_token_program,
             amount_in,
             owner_seeds,
+            remaining_accounts,
         )?;
         Ok(amount_in)
     }
@@ -213,6 +260,7 @@ impl<'info> CommonSwapProcessor<'info> for ProxySwapProcessor {
         _fee_rate: Option<u32>,
         _fee_direction: Option<bool>,
         _fee_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+        remaining_accounts: &[AccountInfo<'info>],
     ) -> Result<()> {
         // Proxy handle after swap
         self.proxy_handle_after(
@@ -223,6 +271,7 @@ impl<'info> CommonSwapProcessor<'info> for ProxySwapProcessor {
             destination_token_program,
             amount_out,
             owner_seeds,
+            remaining_accounts,
         )?;
         Ok(())
     }
@@ -1,5 +1,6 @@
 use crate::constants::*;
 use crate::error::ErrorCode;
+use crate::instructions::ChargedFees;
 use crate::processor::platform_fee_processor::PlatformFeeV3Processor;
 use crate::processor::proxy_swap_processor::ProxySwapProcessor;
 use crate::utils::*;
@@ -118,6 +119,7 @@ impl SwapToBProcessor {
         destination_token_sa: &Option<UncheckedAccount<'info>>,
         destination_token_program: &Option<Interface<'info, TokenInterface>>,
         amount_out: u64,
+        hook_accounts: &[AccountInfo<'info>],
     ) -> Result<()> {
         require!(destination_mint.key() == wsol_program::ID, ErrorCode::InvalidMint);
         require!(destination_token_program.is_some(), ErrorCode::DestinationTokenProgramIsNone);
@@ -138,6 +140,7 @@ impl SwapToBProcessor {
                 amount_out,
                 destination_mint.decimals,
                 Some(SA_AUTHORITY_SEED),
+                hook_accounts,
             )?;
         }
 
@@ -162,9 +165,11 @@ impl SwapToBProcessor {
         commission_amount: u64,
         platform_fee_amount: u64,
         commission_account: &Option<AccountInfo<'info>>,
+        commission_split: Option<&CommissionSplit<'_, 'info>>,
         platform_fee_account: &Option<AccountInfo<'info>>,
         is_charge_fee: bool,
         is_charge_sol: bool,
+        hook_accounts: &[AccountInfo<'info>],
     ) -> Result<()> {
         if !is_charge_fee {
             return Ok(());
@@ -181,15 +186,14 @@ impl SwapToBProcessor {
         if is_charge_sol {
             // Transfer SOL commission
             if commission_amount > 0 {
-                let commission_account = commission_account.as_ref().unwrap();
-                transfer_sol_fee(
+                transfer_sol_commission(
                     sa_authority,
-                    commission_account,
+                    commission_account.as_ref().unwrap(),
+                    commission_split,
                     commission_amount,
+                    true,
                     Some(SA_AUTHORITY_SEED),
                 )?;
-                log_commission_info(true, commission_amount);
-                commission_account.key().log();
             }
 
             // Transfer SOL platform fee
@@ -211,18 +215,18 @@ impl SwapToBProcessor {
 
             // Transfer token commission
             if commission_amount > 0 {
-                let commission_account = commission_account.as_ref().unwrap();
-                transfer_token_fee(
+                transfer_token_commission(
                     sa_authority,
                     source_token_sa,
                     source_mint,
                     source_token_program,
-                    commission_account,
+                    commission_account.as_ref().unwrap(),
+                    commission_split,
                     commission_amount,
+                    true,
                     Some(SA_AUTHORITY_SEED),
+                    hook_accounts,
                 )?;
-                log_commission_info(true, commission_amount);
-                commission_account.key().log();
             }
 
             // Transfer token platform fee
@@ -235,7 +239,9 @@ impl SwapToBProcessor {
                     source_token_program,
                     platform_fee_account,
                     platform_fee_amount,
+                    false,
                     Some(SA_AUTHORITY_SEED),
+                    hook_accounts,
                 )?;
                 log_platform_fee_info(platform_fee_amount, &platform_fee_account.key());
             }
@@ -257,8 +263,10 @@ impl SwapToBProcessor {
         commission_amount: u64,
         platform_fee_amount: u64,
         commission_account: &Option<AccountInfo<'info>>,
+        commission_split: Option<&CommissionSplit<'_, 'info>>,
         platform_fee_account: &Option<AccountInfo<'info>>,
         acc_close_flag: bool,
+        hook_accounts: &[AccountInfo<'info>],
     ) -> Result<bool> {
         if commission_amount == 0 && platform_fee_amount == 0 {
             return Ok(false);
@@ -270,6 +278,12 @@ impl SwapToBProcessor {
                     is_system_account(commission_account.as_ref().unwrap()),
                     ErrorCode::InvalidCommissionAccount
                 );
+                if let Some(commission_split) = commission_split {
+                    require!(
+                        commission_split.accounts.iter().all(is_system_account),
+                        ErrorCode::InvalidCommissionAccount
+                    );
+                }
             }
         }
         if platform_fee_amount > 0 {
@@ -292,19 +306,19 @@ impl SwapToBProcessor {
                 destination_token_sa,
                 destination_token_program,
                 amount_out,
+                hook_accounts,
             )?;
 
             // Transfer SOL fees
             if commission_amount > 0 {
-                let commission_account = commission_account.as_ref().unwrap();
-                transfer_sol_fee(
+                transfer_sol_commission(
                     sa_authority,
-                    commission_account,
+                    commission_account.as_ref().unwrap(),
+                    commission_split,
                     commission_amount,
+                    false,
                     Some(SA_AUTHORITY_SEED),
                 )?;
-                log_commission_info(false, commission_amount);
-                commission_account.key().log();
             }
 
             if platform_fee_amount > 0 {
@@ -327,18 +341,18 @@ impl SwapToBProcessor {
 
             // Transfer token fees
             if commission_amount > 0 {
-                let commission_account = commission_account.as_ref().unwrap();
-                transfer_token_fee(
+                transfer_token_commission(
                     sa_authority,
                     destination_token_sa,
                     destination_mint,
                     destination_token_program,
-                    commission_account,
+                    commission_account.as_ref().unwrap(),
+                    commission_split,
                     commission_amount,
+                    false,
                     Some(SA_AUTHORITY_SEED),
+                    hook_accounts,
                 )?;
-                log_commission_info(false, commission_amount);
-                commission_account.key().log();
             }
 
             if platform_fee_amount > 0 {
@@ -350,7 +364,9 @@ impl SwapToBProcessor {
                     destination_token_program,
                     platform_fee_account,
                     platform_fee_amount,
+                    true,
                     Some(SA_AUTHORITY_SEED),
+                    hook_accounts,
                 )?;
                 log_platform_fee_info(platform_fee_amount, &platform_fee_account.key());
             }
@@ -375,6 +391,7 @@ impl SwapToBProcessor {
         charge_account: Option<&AccountInfo<'info>>,
         is_unwrap_wsol_to_sa: bool,
         acc_close_flag: bool,
+        hook_accounts: &[AccountInfo<'info>],
     ) -> Result<bool> {
         if trim_amount == 0 && charge_amount == 0 {
             return Ok(is_unwrap_wsol_to_sa);
@@ -406,6 +423,7 @@ impl SwapToBProcessor {
                     destination_token_sa,
                     destination_token_program,
                     amount_out,
+                    hook_accounts,
                 )?;
             }
 
@@ -439,7 +457,9 @@ impl SwapToBProcessor {
                     destination_token_program,
                     trim_account,
                     trim_amount,
+                    false,
                     Some(SA_AUTHORITY_SEED),
+                    hook_accounts,
                 )?;
                 log_trim_fee_info(trim_amount, &trim_account.key());
             }
@@ -452,7 +472,9 @@ impl SwapToBProcessor {
                     destination_token_program,
                     charge_account.as_ref().unwrap(),
                     charge_amount,
+                    false,
                     Some(SA_AUTHORITY_SEED),
+                    hook_accounts,
                 )?;
                 log_platform_trim_fee_info(charge_amount, &charge_account.unwrap().key());
             }
@@ -472,6 +494,7 @@ impl SwapToBProcessor {
         actual_amount_out: u64,
         is_unwrap_wsol_to_sa: bool,
         acc_close_flag: bool,
+        hook_accounts: &[AccountInfo<'info>],
     ) -> Result<()> {
         if is_unwrap_wsol_to_sa {
             // Transfer remaining SOL & token account rent to payer
@@ -499,7 +522,12 @@ impl SwapToBProcessor {
                 actual_amount_out,
                 destination_mint.decimals,
                 Some(SA_AUTHORITY_SEED),
+                hook_accounts,
             )?;
+            log_transfer_fee_info(
+                false,
+                get_transfer_fee(&destination_mint.to_account_info(), actual_amount_out)?,
+            );
 
             if acc_close_flag
                 && destination_token_account.mint == wsol_program::ID
@@ -563,9 +591,11 @@ impl<'info> PlatformFeeV3Processor<'info> for SwapToBProcessor {
         commission_rate: u32,
         commission_direction: bool,
         commission_account: &Option<AccountInfo<'info>>,
+        commission_split: Option<&CommissionSplit<'_, 'info>>,
         platform_fee_rate: Option<u16>,
         platform_fee_account: &Option<AccountInfo<'info>>,
-    ) -> Result<u64> {
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<(u64, ChargedFees)> {
         // Check if commission is SOL
         let is_charge_sol = is_charge_sol(commission_account, platform_fee_account, source_mint);
 
@@ -588,6 +618,7 @@ impl<'info> PlatformFeeV3Processor<'info> for SwapToBProcessor {
                 source_token_program,
                 amount_in,
                 None,
+                remaining_accounts,
             )?;
             if is_charge_fee {
                 require!(sa_authority.is_some(), ErrorCode::SaAuthorityIsNone);
@@ -614,6 +645,7 @@ impl<'info> PlatformFeeV3Processor<'info> for SwapToBProcessor {
                 source_token_program,
                 actual_amount_in,
                 None,
+                remaining_accounts,
             )?;
         }
 
@@ -626,12 +658,17 @@ impl<'info> PlatformFeeV3Processor<'info> for SwapToBProcessor {
             commission_amount,
             platform_fee_amount,
             commission_account,
+            commission_split,
             platform_fee_account,
             is_charge_fee,
             is_charge_sol,
+            remaining_accounts,
         )?;
 
-        Ok(amount_in)
+        Ok((
+            amount_in,
+            ChargedFees { commission_amount, platform_fee_amount, ..Default::default() },
+        ))
     }
 
     fn after_swap(
@@ -647,6 +684,7 @@ impl<'info> PlatformFeeV3Processor<'info> for SwapToBProcessor {
         commission_rate: u32,
         commission_direction: bool,
         commission_account: &Option<AccountInfo<'info>>,
+        commission_split: Option<&CommissionSplit<'_, 'info>>,
         platform_fee_rate: Option<u16>,
         platform_fee_account: &Option<AccountInfo<'info>>,
         trim_rate: Option<u8>,
@@ -654,14 +692,15 @@ impl<'info> PlatformFeeV3Processor<'info> for SwapToBProcessor {
         trim_account: Option<&AccountInfo<'info>>,
         charge_account: Option<&AccountInfo<'info>>,
         acc_close_flag: bool,
-    ) -> Result<u64> {
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<(u64, ChargedFees)> {
         // Calculate fees and actual amount out if commission is applied to to
         let (
             commission_amount,
             platform_fee_amount,
             trim_amount,
             charge_amount,
-            actual_amount_out,
+            mut actual_amount_out,
             is_charge_fee,
         ) = self.calculate_to_fees(
             amount_out,
@@ -672,6 +711,15 @@ impl<'info> PlatformFeeV3Processor<'info> for SwapToBProcessor {
             trim_rate,
             charge_rate,
         )?;
+        // Output fee payouts are grossed up by their Token-2022 transfer fee out of the user's share
+        actual_amount_out = actual_amount_out
+            .checked_sub(get_payout_transfer_fee(
+                &destination_mint.to_account_info(),
+                commission_amount,
+                commission_split,
+                platform_fee_amount,
+            )?)
+            .ok_or(ErrorCode::CalculationError)?;
 
         // Proxy handle after swap
         if !is_charge_fee {
@@ -683,6 +731,7 @@ impl<'info> PlatformFeeV3Processor<'info> for SwapToBProcessor {
                 destination_token_program,
                 amount_out,
                 Some(SA_AUTHORITY_SEED),
+                remaining_accounts,
             )?;
 
             if acc_close_flag
@@ -717,8 +766,10 @@ impl<'info> PlatformFeeV3Processor<'info> for SwapToBProcessor {
                 commission_amount,
                 platform_fee_amount,
                 commission_account,
+                commission_split,
                 platform_fee_account,
                 acc_close_flag,
+                remaining_accounts,
             )?;
 
             is_unwrap_wsol_to_sa = self.transfer_trim_and_log(
@@ -735,6 +786,7 @@ impl<'info> PlatformFeeV3Processor<'info> for SwapToBProcessor {
                 charge_account,
                 is_unwrap_wsol_to_sa,
                 acc_close_flag,
+                remaining_accounts,
             )?;
 
             self.transfer_to_user(
@@ -747,9 +799,13 @@ impl<'info> PlatformFeeV3Processor<'info> for SwapToBProcessor {
                 actual_amount_out,
                 is_unwrap_wsol_to_sa,
                 acc_close_flag,
+                remaining_accounts,
             )?;
         }
 
-        Ok(actual_amount_out)
+        Ok((
+            actual_amount_out,
+            ChargedFees { commission_amount, platform_fee_amount, trim_amount, charge_amount },
+        ))
     }
 }
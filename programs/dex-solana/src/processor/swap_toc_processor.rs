@@ -1,5 +1,6 @@
 use crate::constants::*;
 use crate::error::ErrorCode;
+use crate::instructions::ChargedFees;
 use crate::processor::platform_fee_processor::PlatformFeeV3Processor;
 use crate::processor::proxy_swap_processor::ProxySwapProcessor;
 use crate::utils::*;
@@ -87,8 +88,10 @@ impl SwapToCProcessor {
         commission_amount: u64,
         platform_fee_amount: u64,
         commission_account: &Option<AccountInfo<'info>>,
+        commission_split: Option<&CommissionSplit<'_, 'info>>,
         platform_fee_account: &Option<AccountInfo<'info>>,
         is_charge_fee: bool,
+        hook_accounts: &[AccountInfo<'info>],
     ) -> Result<()> {
         if !is_charge_fee {
             return Ok(());
@@ -103,11 +106,14 @@ impl SwapToCProcessor {
         if is_charge_sol(commission_account, platform_fee_account, source_mint) {
             // Transfer SOL commission
             if commission_amount > 0 {
-                let commission_account = commission_account.as_ref().unwrap();
-                let actual_fee_amount =
-                    transfer_sol_fee(payer, commission_account, commission_amount, None)?;
-                log_commission_info(true, actual_fee_amount);
-                commission_account.key().log();
+                transfer_sol_commission(
+                    payer,
+                    commission_account.as_ref().unwrap(),
+                    commission_split,
+                    commission_amount,
+                    true,
+                    None,
+                )?;
             }
 
             // Transfer SOL platform fee
@@ -123,18 +129,18 @@ impl SwapToCProcessor {
 
             // Transfer token commission
             if commission_amount > 0 {
-                let commission_account = commission_account.as_ref().unwrap();
-                transfer_token_fee(
+                transfer_token_commission(
                     payer,
                     &source_token_account.to_account_info(),
                     source_mint,
                     source_token_program,
-                    commission_account,
+                    commission_account.as_ref().unwrap(),
+                    commission_split,
                     commission_amount,
+                    true,
                     None,
+                    hook_accounts,
                 )?;
-                log_commission_info(true, commission_amount);
-                commission_account.key().log();
             }
 
             // Transfer token platform fee
@@ -147,7 +153,9 @@ impl SwapToCProcessor {
                     source_token_program,
                     platform_fee_account,
                     platform_fee_amount,
+                    false,
                     None,
+                    hook_accounts,
                 )?;
                 log_platform_fee_info(platform_fee_amount, &platform_fee_account.key());
             }
@@ -166,8 +174,10 @@ impl SwapToCProcessor {
         commission_amount: u64,
         platform_fee_amount: u64,
         commission_account: &Option<AccountInfo<'info>>,
+        commission_split: Option<&CommissionSplit<'_, 'info>>,
         platform_fee_account: &Option<AccountInfo<'info>>,
         is_charge_fee: bool,
+        hook_accounts: &[AccountInfo<'info>],
     ) -> Result<()> {
         if !is_charge_fee {
             return Ok(());
@@ -197,11 +207,14 @@ impl SwapToCProcessor {
 
             // Transfer sol fees
             if commission_amount > 0 {
-                let commission_account = commission_account.as_ref().unwrap();
-                let actual_fee_amount =
-                    transfer_sol_fee(payer, commission_account, commission_amount, None)?;
-                log_commission_info(false, actual_fee_amount);
-                commission_account.key().log();
+                transfer_sol_commission(
+                    payer,
+                    commission_account.as_ref().unwrap(),
+                    commission_split,
+                    commission_amount,
+                    false,
+                    None,
+                )?;
             }
 
             if platform_fee_amount > 0 {
@@ -213,18 +226,18 @@ impl SwapToCProcessor {
         } else {
             // Transfer token fees
             if commission_amount > 0 {
-                let commission_account = commission_account.as_ref().unwrap();
-                transfer_token_fee(
+                transfer_token_commission(
                     payer,
                     &destination_token_account.to_account_info(),
                     destination_mint,
                     destination_token_program,
-                    commission_account,
+                    commission_account.as_ref().unwrap(),
+                    commission_split,
                     commission_amount,
+                    false,
                     None,
+                    hook_accounts,
                 )?;
-                log_commission_info(false, commission_amount);
-                commission_account.key().log();
             }
 
             if platform_fee_amount > 0 {
@@ -236,7 +249,9 @@ impl SwapToCProcessor {
                     destination_token_program,
                     platform_fee_account,
                     platform_fee_amount,
+                    true,
                     None,
+                    hook_accounts,
                 )?;
                 log_platform_fee_info(platform_fee_amount, &platform_fee_account.key());
             }
@@ -290,9 +305,11 @@ impl<'info> PlatformFeeV3Processor<'info> for SwapToCProcessor {
         commission_rate: u32,
         commission_direction: bool,
         commission_account: &Option<AccountInfo<'info>>,
+        commission_split: Option<&CommissionSplit<'_, 'info>>,
         platform_fee_rate: Option<u16>,
         platform_fee_account: &Option<AccountInfo<'info>>,
-    ) -> Result<u64> {
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<(u64, ChargedFees)> {
         // Proxy handle before swap
         ProxySwapProcessor.proxy_handle_before(
             payer,
@@ -302,6 +319,7 @@ impl<'info> PlatformFeeV3Processor<'info> for SwapToCProcessor {
             source_token_program,
             amount_in,
             None,
+            remaining_accounts,
         )?;
 
         // Calculate fees if commission is applied to from
@@ -321,10 +339,15 @@ impl<'info> PlatformFeeV3Processor<'info> for SwapToCProcessor {
             commission_amount,
             platform_fee_amount,
             commission_account,
+            commission_split,
             platform_fee_account,
             is_charge_fee,
+            remaining_accounts,
         )?;
-        Ok(amount_in)
+        Ok((
+            amount_in,
+            ChargedFees { commission_amount, platform_fee_amount, ..Default::default() },
+        ))
     }
 
     fn after_swap(
@@ -340,6 +363,7 @@ impl<'info> PlatformFeeV3Processor<'info> for SwapToCProcessor {
         commission_rate: u32,
         commission_direction: bool,
         commission_account: &Option<AccountInfo<'info>>,
+        commission_split: Option<&CommissionSplit<'_, 'info>>,
         platform_fee_rate: Option<u16>,
         platform_fee_account: &Option<AccountInfo<'info>>,
         _trim_rate: Option<u8>,
@@ -347,7 +371,8 @@ impl<'info> PlatformFeeV3Processor<'info> for SwapToCProcessor {
         _trim_account: Option<&AccountInfo<'info>>,
         _charge_account: Option<&AccountInfo<'info>>,
         _acc_close_flag: bool,
-    ) -> Result<u64> {
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<(u64, ChargedFees)> {
         // Proxy handle after swap
         ProxySwapProcessor.proxy_handle_after(
             sa_authority,
@@ -357,6 +382,7 @@ impl<'info> PlatformFeeV3Processor<'info> for SwapToCProcessor {
             destination_token_program,
             amount_out,
             Some(SA_AUTHORITY_SEED),
+            remaining_accounts,
         )?;
 
         // Calculate fees and actual amount out if commission is applied to to
@@ -367,6 +393,15 @@ impl<'info> PlatformFeeV3Processor<'info> for SwapToCProcessor {
                 commission_direction,
                 platform_fee_rate,
             )?;
+        // Output fee payouts are grossed up by their Token-2022 transfer fee out of the user's share
+        let actual_amount_out = actual_amount_out
+            .checked_sub(get_payout_transfer_fee(
+                &destination_mint.to_account_info(),
+                commission_amount,
+                commission_split,
+                platform_fee_amount,
+            )?)
+            .ok_or(ErrorCode::CalculationError)?;
 
         // Transfer to fees and log results
         self.transfer_to_fees_and_log(
@@ -377,10 +412,15 @@ impl<'info> PlatformFeeV3Processor<'info> for SwapToCProcessor {
             commission_amount,
             platform_fee_amount,
             commission_account,
+            commission_split,
             platform_fee_account,
             is_charge_fee,
+            remaining_accounts,
         )?;
 
-        Ok(actual_amount_out)
+        Ok((
+            actual_amount_out,
+            ChargedFees { commission_amount, platform_fee_amount, ..Default::default() },
+        ))
     }
 }
@@ -1,4 +1,5 @@
-use crate::error::LimitOrderError;
+use crate::constants::claim_authority;
+use crate::error::{ErrorCode, LimitOrderError};
 use anchor_lang::prelude::*;
 
 #[account(zero_copy(unsafe))]
@@ -10,8 +11,8 @@ pub struct GlobalConfig {
     /// The admin of the program.
     pub admin: Pubkey,
 
-    /// Only the resolver can trigger order filled.
-    pub resolvers: [Pubkey; 5],
+    /// Resolvers of the previous registry, moved to resolver PDAs by `migrate_resolver`.
+    pub deprecated_resolvers: [Pubkey; 5],
 
     /// Prepaid trade fee, the remaining amount will be refunded to the user
     pub trade_fee: u64,
@@ -22,8 +23,11 @@ pub struct GlobalConfig {
     /// Fee multiplier for the trade fee
     pub fee_multiplier: u8,
 
+    /// Admin proposed by the admin, takes over once accepted.
+    pub pending_admin: Pubkey,
+
     /// padding for upgrade
-    pub padding: [u8; 127],
+    pub padding: [u8; 95],
 }
 
 impl Default for GlobalConfig {
@@ -31,11 +35,12 @@ impl Default for GlobalConfig {
         GlobalConfig {
             bump: 0,
             admin: Pubkey::default(),
-            resolvers: [Pubkey::default(); 5],
+            deprecated_resolvers: [Pubkey::default(); 5],
             trade_fee: 0,
             paused: false,
             fee_multiplier: 10,
-            padding: [0u8; 127],
+            pending_admin: Pubkey::default(),
+            padding: [0u8; 95],
         }
     }
 }
@@ -49,41 +54,37 @@ impl GlobalConfig {
         Ok(())
     }
 
-    pub fn add_resolver(&mut self, resolver: Pubkey) -> Result<()> {
-        require_keys_neq!(resolver, Pubkey::default(), LimitOrderError::InvalidAccount);
-        for item in self.resolvers {
-            require_keys_neq!(resolver, item, LimitOrderError::ResolverIsExist);
-        }
-        for item in &mut self.resolvers {
-            if *item == Pubkey::default() {
-                *item = resolver;
-                return Ok(());
-            }
-        }
-        return Err(LimitOrderError::ExceedResolverLimit.into());
+    pub fn propose_admin(&mut self, pending_admin: Pubkey) -> Result<()> {
+        require_keys_neq!(pending_admin, Pubkey::default(), LimitOrderError::InvalidAccount);
+        self.pending_admin = pending_admin;
+        Ok(())
+    }
+
+    pub fn accept_admin(&mut self, pending_admin: Pubkey) -> Result<()> {
+        require_keys_neq!(self.pending_admin, Pubkey::default(), LimitOrderError::NoPendingAdmin);
+        require_keys_eq!(pending_admin, self.pending_admin, LimitOrderError::InvalidPendingAdmin);
+        self.set_admin(pending_admin)?;
+        self.pending_admin = Pubkey::default();
+        Ok(())
     }
 
-    pub fn remove_resolver(&mut self, resolver: Pubkey) -> Result<()> {
+    pub fn cancel_admin_transfer(&mut self) -> Result<Pubkey> {
+        let pending_admin = self.pending_admin;
+        require_keys_neq!(pending_admin, Pubkey::default(), LimitOrderError::NoPendingAdmin);
+        self.pending_admin = Pubkey::default();
+        Ok(pending_admin)
+    }
+
+    /// Clears a resolver of the previous registry once it moves to its resolver PDA
+    pub fn take_deprecated_resolver(&mut self, resolver: Pubkey) -> Result<()> {
         require_keys_neq!(resolver, Pubkey::default(), LimitOrderError::InvalidAccount);
-        for item in &mut self.resolvers {
+        for item in &mut self.deprecated_resolvers {
             if *item == resolver {
                 *item = Pubkey::default();
                 return Ok(());
             }
         }
-        return Err(LimitOrderError::ResolverIsNotExist.into());
-    }
-
-    pub fn is_resolver(&self, resolver: Pubkey) -> bool {
-        if resolver == Pubkey::default() {
-            return false;
-        }
-        for item in &self.resolvers {
-            if *item == resolver {
-                return true;
-            }
-        }
-        return false;
+        Err(LimitOrderError::ResolverIsNotExist.into())
     }
 
     pub fn set_trade_fee(&mut self, trade_fee: u64) -> Result<()> {
@@ -103,3 +104,144 @@ impl GlobalConfig {
         Ok(())
     }
 }
+
+#[account(zero_copy(unsafe))]
+#[derive(Debug)]
+pub struct ClaimConfig {
+    /// Bump to identify PDA.
+    pub bump: u8,
+
+    /// The admin of the claim config.
+    pub admin: Pubkey,
+
+    /// Only the claim authority can claim from the SA authority.
+    pub claim_authority: Pubkey,
+
+    /// Allowed claim receivers, any receiver if empty.
+    pub receivers: [Pubkey; 5],
+
+    /// Indicate whether to pause claims.
+    pub paused: bool,
+
+    /// Admin proposed by the admin, takes over once accepted.
+    pub pending_admin: Pubkey,
+
+    /// padding for upgrade
+    pub padding: [u8; 95],
+}
+
+impl Default for ClaimConfig {
+    fn default() -> Self {
+        ClaimConfig {
+            bump: 0,
+            admin: Pubkey::default(),
+            claim_authority: Pubkey::default(),
+            receivers: [Pubkey::default(); 5],
+            paused: false,
+            pending_admin: Pubkey::default(),
+            padding: [0u8; 95],
+        }
+    }
+}
+
+impl ClaimConfig {
+    pub const LEN: usize = 8 + std::mem::size_of::<ClaimConfig>();
+
+    pub fn set_admin(&mut self, admin: Pubkey) -> Result<()> {
+        require_keys_neq!(admin, Pubkey::default(), ErrorCode::InvalidClaimConfigAccount);
+        self.admin = admin;
+        Ok(())
+    }
+
+    pub fn propose_admin(&mut self, pending_admin: Pubkey) -> Result<()> {
+        require_keys_neq!(pending_admin, Pubkey::default(), ErrorCode::InvalidClaimConfigAccount);
+        self.pending_admin = pending_admin;
+        Ok(())
+    }
+
+    pub fn accept_admin(&mut self, pending_admin: Pubkey) -> Result<()> {
+        require_keys_neq!(self.pending_admin, Pubkey::default(), ErrorCode::NoPendingAdmin);
+        require_keys_eq!(pending_admin, self.pending_admin, ErrorCode::InvalidPendingAdmin);
+        self.set_admin(pending_admin)?;
+        self.pending_admin = Pubkey::default();
+        Ok(())
+    }
+
+    pub fn cancel_admin_transfer(&mut self) -> Result<Pubkey> {
+        let pending_admin = self.pending_admin;
+        require_keys_neq!(pending_admin, Pubkey::default(), ErrorCode::NoPendingAdmin);
+        self.pending_admin = Pubkey::default();
+        Ok(pending_admin)
+    }
+
+    pub fn set_claim_authority(&mut self, claim_authority: Pubkey) -> Result<()> {
+        require_keys_neq!(claim_authority, Pubkey::default(), ErrorCode::InvalidClaimConfigAccount);
+        self.claim_authority = claim_authority;
+        Ok(())
+    }
+
+    pub fn add_receiver(&mut self, receiver: Pubkey) -> Result<()> {
+        require_keys_neq!(receiver, Pubkey::default(), ErrorCode::InvalidClaimConfigAccount);
+        for item in self.receivers {
+            require_keys_neq!(receiver, item, ErrorCode::ClaimReceiverIsExist);
+        }
+        for item in &mut self.receivers {
+            if *item == Pubkey::default() {
+                *item = receiver;
+                return Ok(());
+            }
+        }
+        Err(ErrorCode::ExceedClaimReceiverLimit.into())
+    }
+
+    pub fn remove_receiver(&mut self, receiver: Pubkey) -> Result<()> {
+        require_keys_neq!(receiver, Pubkey::default(), ErrorCode::InvalidClaimConfigAccount);
+        for item in &mut self.receivers {
+            if *item == receiver {
+                *item = Pubkey::default();
+                return Ok(());
+            }
+        }
+        Err(ErrorCode::ClaimReceiverIsNotExist.into())
+    }
+
+    pub fn is_allowed_receiver(&self, receiver: Pubkey) -> bool {
+        if self.receivers.iter().all(|item| *item == Pubkey::default()) {
+            return true;
+        }
+        receiver != Pubkey::default() && self.receivers.contains(&receiver)
+    }
+
+    pub fn set_paused(&mut self, paused: bool) -> Result<()> {
+        self.paused = paused;
+        Ok(())
+    }
+
+    /// Checks the signer, the receiver and the pause switch of a claim
+    pub fn check_claim(&self, signer: Pubkey, receiver: Pubkey) -> Result<()> {
+        require_keys_eq!(signer, self.claim_authority, ErrorCode::InvalidSigner);
+        require!(!self.paused, ErrorCode::ClaimPaused);
+        require!(self.is_allowed_receiver(receiver), ErrorCode::InvalidClaimReceiver);
+        Ok(())
+    }
+
+    /// Checks a claim against the claim config PDA, the `claim_authority` constant claims
+    /// until the config is initialized
+    pub fn check_claim_account(
+        claim_config: &AccountInfo,
+        signer: Pubkey,
+        receiver: Pubkey,
+    ) -> Result<()> {
+        if claim_config.owner != &crate::ID {
+            require_keys_eq!(signer, claim_authority::id(), ErrorCode::InvalidSigner);
+            return Ok(());
+        }
+        let data = claim_config.try_borrow_data()?;
+        require!(
+            data.len() >= ClaimConfig::LEN && data[..8] == *ClaimConfig::DISCRIMINATOR,
+            ErrorCode::InvalidClaimConfigAccount
+        );
+        bytemuck::from_bytes::<ClaimConfig>(&data[8..ClaimConfig::LEN])
+            .check_claim(signer, receiver)
+    }
+}
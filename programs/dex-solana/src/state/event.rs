@@ -1,3 +1,5 @@
+use crate::instructions::from_swap::AdaptorID;
+use crate::state::order::OrderTrigger;
 use anchor_lang::prelude::*;
 
 #[event]
@@ -6,11 +8,23 @@ pub struct InitGlobalConfigEvent {
     pub trade_fee: u64,
 }
 
+#[event]
+pub struct ProposeAdminEvent {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
 #[event]
 pub struct SetAdminEvent {
     pub admin: Pubkey,
 }
 
+#[event]
+pub struct CancelAdminTransferEvent {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
 #[event]
 pub struct AddResolverEvent {
     pub resolver: Pubkey,
@@ -21,6 +35,23 @@ pub struct RemoveResolverEvent {
     pub resolver: Pubkey,
 }
 
+#[event]
+pub struct SetResolverLimitsEvent {
+    pub resolver: Pubkey,
+    pub max_orders_per_slot: u16,
+    pub allowed_mints: Vec<Pubkey>,
+}
+
+#[event]
+pub struct SuspendResolverEvent {
+    pub resolver: Pubkey,
+}
+
+#[event]
+pub struct ResumeResolverEvent {
+    pub resolver: Pubkey,
+}
+
 #[event]
 pub struct SetTradeFeeEvent {
     pub trade_fee: u64,
@@ -36,12 +67,114 @@ pub struct SetFeeMultiplierEvent {
     pub fee_multiplier: u8,
 }
 
+// ******************** Claim ******************** //
+
+#[event]
+pub struct InitClaimConfigEvent {
+    pub admin: Pubkey,
+    pub claim_authority: Pubkey,
+}
+
+#[event]
+pub struct ProposeClaimConfigAdminEvent {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+#[event]
+pub struct SetClaimConfigAdminEvent {
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct CancelClaimConfigAdminTransferEvent {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+#[event]
+pub struct SetClaimAuthorityEvent {
+    pub claim_authority: Pubkey,
+}
+
+#[event]
+pub struct AddClaimReceiverEvent {
+    pub receiver: Pubkey,
+}
+
+#[event]
+pub struct RemoveClaimReceiverEvent {
+    pub receiver: Pubkey,
+}
+
+#[event]
+pub struct PauseClaimEvent {
+    pub paused: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ClaimTransfer {
+    pub mint: Pubkey,
+    pub source_token_account: Pubkey,
+    pub destination_token_account: Pubkey,
+    pub amount: u64,
+    pub closed: bool,
+}
+
+#[event]
+pub struct BatchClaimEvent {
+    pub receiver: Pubkey,
+    pub claims: Vec<ClaimTransfer>,
+}
+
+// ******************** Commission ******************** //
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct CommissionPayout {
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct CommissionSplitEvent {
+    pub commission_direction: bool,
+    pub mint: Pubkey,
+    pub payouts: Vec<CommissionPayout>,
+}
+
+// ******************** Bridge ******************** //
+
+#[event]
+pub struct SwapAndBridgeEvent {
+    pub order_id: u64,
+    pub payer: Pubkey,
+    pub source_mint: Pubkey,
+    pub destination_mint: Pubkey,
+    pub amount_in: u64,
+    pub bridge_amount: u64,
+    pub adaptor_id: AdaptorID,
+    pub to_chain_id: u64,
+    pub to: Vec<u8>,
+}
+
+#[event]
+pub struct ReceiveAndSwapEvent {
+    pub order_id: u64,
+    pub recipient: Pubkey,
+    pub source_mint: Pubkey,
+    pub destination_mint: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fallback: bool,
+}
+
 // ******************** Limit Order V1 ******************** //
 
 #[event]
 pub struct PlaceOrderEvent {
     pub order_id: u64,
     pub maker: Pubkey,
+    pub receiver: Pubkey,
     pub input_token_mint: Pubkey,
     pub output_token_mint: Pubkey,
     pub making_amount: u64,
@@ -52,6 +185,60 @@ pub struct PlaceOrderEvent {
     pub trade_fee: u64,
 }
 
+#[event]
+pub struct PlaceConditionalOrderEvent {
+    pub order_id: u64,
+    pub maker: Pubkey,
+    pub trigger: OrderTrigger,
+}
+
+#[event]
+pub struct PlaceRecurringOrderEvent {
+    pub order_id: u64,
+    pub maker: Pubkey,
+    pub input_token_mint: Pubkey,
+    pub output_token_mint: Pubkey,
+    pub making_amount: u64,
+    pub slice_amount: u64,
+    pub slice_count: u16,
+    pub slice_min_return_amount: u64,
+    pub interval: u64,
+    pub create_ts: u64,
+    pub trade_fee: u64,
+}
+
+#[event]
+pub struct FillRecurringOrderEvent {
+    pub order_id: u64,
+    pub payer: Pubkey,
+    pub maker: Pubkey,
+    pub making_amount: u64,
+    pub taking_amount: u64,
+    pub filled_slices: u16,
+    pub next_fill_ts: u64,
+    pub update_ts: u64,
+}
+
+#[event]
+pub struct CancelRecurringOrderEvent {
+    pub order_id: u64,
+    pub maker: Pubkey,
+    pub refund_amount: u64,
+    pub update_ts: u64,
+}
+
+#[event]
+pub struct FillOrderIntentEvent {
+    pub maker: Pubkey,
+    pub nonce: u64,
+    pub payer: Pubkey,
+    pub input_token_mint: Pubkey,
+    pub output_token_mint: Pubkey,
+    pub making_amount: u64,
+    pub taking_amount: u64,
+    pub update_ts: u64,
+}
+
 #[event]
 pub struct UpdateOrderEvent {
     pub order_id: u64,
@@ -81,6 +268,19 @@ pub struct CancelOrderEvent {
 
 #[event]
 pub struct FillOrderEvent {
+    pub order_id: u64,
+    pub payer: Pubkey,
+    pub maker: Pubkey,
+    pub receiver: Pubkey,
+    pub input_token_mint: Pubkey,
+    pub output_token_mint: Pubkey,
+    pub making_amount: u64,
+    pub taking_amount: u64,
+    pub update_ts: u64,
+}
+
+#[event]
+pub struct PartialFillOrderEvent {
     pub order_id: u64,
     pub payer: Pubkey,
     pub maker: Pubkey,
@@ -88,5 +288,7 @@ pub struct FillOrderEvent {
     pub output_token_mint: Pubkey,
     pub making_amount: u64,
     pub taking_amount: u64,
+    pub filled_making_amount: u64,
+    pub filled_taking_amount: u64,
     pub update_ts: u64,
 }
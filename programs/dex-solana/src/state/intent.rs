@@ -0,0 +1,84 @@
+use crate::constants::*;
+use crate::error::LimitOrderError;
+use anchor_lang::prelude::*;
+
+/// Limit order signed off-chain by the maker and filled in one go by a resolver.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct OrderIntent {
+    /// The maker signing the intent.
+    pub maker: Pubkey,
+
+    /// Replay protection nonce, each nonce fills at most once.
+    pub nonce: u64,
+
+    /// Input token mint.
+    pub input_token_mint: Pubkey,
+
+    /// Output token mint.
+    pub output_token_mint: Pubkey,
+
+    /// The making amount pulled from the maker.
+    pub making_amount: u64,
+
+    /// The min taking amount received by the maker.
+    pub min_return_amount: u64,
+
+    /// The deadline of the intent.
+    pub deadline: u64,
+}
+
+impl OrderIntent {
+    /// The message signed by the maker: domain || program id || borsh(intent)
+    pub fn message(&self) -> Result<Vec<u8>> {
+        let mut message = ORDER_INTENT_DOMAIN.to_vec();
+        message.extend_from_slice(crate::ID.as_ref());
+        message.extend_from_slice(&self.try_to_vec()?);
+        Ok(message)
+    }
+
+    /// The nonce page the intent nonce is recorded in
+    pub fn nonce_page(&self) -> u64 {
+        self.nonce / INTENT_NONCES_PER_PAGE
+    }
+}
+
+/// Replay protection bitmap of a maker, one bit per intent nonce.
+#[account]
+#[derive(Debug)]
+pub struct IntentNonces {
+    /// Bump to identify PDA.
+    pub bump: u8,
+
+    /// The maker owning the nonces.
+    pub maker: Pubkey,
+
+    /// The nonce page, covering nonces [page * 1024, (page + 1) * 1024).
+    pub page: u64,
+
+    /// Used nonces of the page.
+    pub bitmap: [u8; 128],
+}
+
+impl Default for IntentNonces {
+    fn default() -> Self {
+        IntentNonces { bump: 0, maker: Pubkey::default(), page: 0, bitmap: [0u8; 128] }
+    }
+}
+
+impl IntentNonces {
+    pub const LEN: usize = 8 + std::mem::size_of::<IntentNonces>();
+
+    pub fn is_used(&self, nonce: u64) -> bool {
+        let index = (nonce % INTENT_NONCES_PER_PAGE) as usize;
+        self.bitmap[index / 8] & (1 << (index % 8)) != 0
+    }
+
+    /// Marks the nonce as used, a nonce can only be used once
+    pub fn use_nonce(&mut self, nonce: u64) -> Result<()> {
+        require!(nonce / INTENT_NONCES_PER_PAGE == self.page, LimitOrderError::InvalidIntent);
+        require!(!self.is_used(nonce), LimitOrderError::IntentNonceUsed);
+        let index = (nonce % INTENT_NONCES_PER_PAGE) as usize;
+        self.bitmap[index / 8] |= 1 << (index % 8);
+        Ok(())
+    }
+}
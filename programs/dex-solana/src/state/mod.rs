@@ -1,3 +1,6 @@
 pub mod config;
 pub mod event;
+pub mod intent;
 pub mod order;
+pub mod recurring_order;
+pub mod resolver;
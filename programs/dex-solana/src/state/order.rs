@@ -1,7 +1,92 @@
+use crate::constants::MAX_ORDER_FILLS;
+use crate::error::LimitOrderError;
+use crate::utils::{OracleStaleness, read_oracle_price};
 use anchor_lang::prelude::*;
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TriggerCondition {
+    /// Plain limit order
+    #[default]
+    None,
+    /// Stop-loss, fillable once the oracle price is at or below the trigger price
+    PriceBelow,
+    /// Take-profit, fillable once the oracle price is at or above the trigger price
+    PriceAbove,
+}
+
+/// Oracle condition gating the fills of a conditional order
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OrderTrigger {
+    pub condition: TriggerCondition,
+
+    /// Pyth `PriceUpdateV2` or Switchboard On-Demand pull feed account.
+    pub oracle: Pubkey,
+
+    /// Trigger price scaled by 10^price_exponent, in the units of the oracle feed.
+    pub trigger_price: i64,
+
+    pub price_exponent: i32,
+
+    /// Max age of a Pyth price, in seconds.
+    pub max_staleness_secs: u64,
+
+    /// Max age of a Switchboard price, in slots.
+    pub max_staleness_slots: u64,
+
+    /// Max confidence interval relative to the price, 0 skips the check.
+    pub max_confidence_bps: u16,
+}
+
+impl OrderTrigger {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.condition != TriggerCondition::None
+                && self.oracle != Pubkey::default()
+                && self.trigger_price > 0
+                && (self.max_staleness_secs > 0 || self.max_staleness_slots > 0),
+            LimitOrderError::InvalidOrderTrigger
+        );
+        Ok(())
+    }
+
+    /// Checks the oracle price crossed the trigger price, plain orders need no oracle
+    pub fn check(
+        &self,
+        oracle: Option<&AccountInfo>,
+        current_ts: u64,
+        current_slot: u64,
+    ) -> Result<()> {
+        if self.condition == TriggerCondition::None {
+            return Ok(());
+        }
+        let oracle = oracle.ok_or(LimitOrderError::InvalidOracle)?;
+        require_keys_eq!(oracle.key(), self.oracle, LimitOrderError::InvalidOracle);
+        let price = read_oracle_price(oracle, current_ts, current_slot)?;
+        let (max_staleness, staleness) = match price.staleness {
+            OracleStaleness::Seconds(staleness) => (self.max_staleness_secs, staleness),
+            OracleStaleness::Slots(staleness) => (self.max_staleness_slots, staleness),
+        };
+        require_gte!(max_staleness, staleness, LimitOrderError::StaleOraclePrice);
+        if self.max_confidence_bps > 0 {
+            require!(
+                price.is_confident(self.max_confidence_bps)?,
+                LimitOrderError::OracleConfidenceTooWide
+            );
+        }
+        let ordering = price.cmp_price(self.trigger_price.into(), self.price_exponent)?;
+        let is_triggered = match self.condition {
+            TriggerCondition::None => true,
+            TriggerCondition::PriceBelow => ordering.is_le(),
+            TriggerCondition::PriceAbove => ordering.is_ge(),
+        };
+        require!(is_triggered, LimitOrderError::TriggerPriceNotReached);
+        msg!("Order triggered at oracle price: {}e{}", price.price, price.exponent);
+        Ok(())
+    }
+}
+
 #[account]
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct OrderV1 {
     /// Bump to identify PDA.
     pub bump: u8,
@@ -42,31 +127,109 @@ pub struct OrderV1 {
     /// The deadline of the order.
     pub deadline: u64,
 
+    /// The making amount filled so far.
+    pub filled_making_amount: u64,
+
+    /// The taking amount received by the maker so far.
+    pub filled_taking_amount: u64,
+
+    /// Oracle trigger of a conditional order.
+    pub trigger: OrderTrigger,
+
+    /// The owner of the output token, the maker unless designated at placement.
+    pub receiver: Pubkey,
+
     /// padding
-    pub padding: [u8; 128],
+    pub padding: [u8; 17],
 }
 
-impl Default for OrderV1 {
-    fn default() -> Self {
-        OrderV1 {
-            bump: 0,
-            order_id: 0,
-            maker: Pubkey::default(),
-            making_amount: 0,
-            expect_taking_amount: 0,
-            min_return_amount: 0,
-            escrow_token_account: Pubkey::default(),
-            input_token_mint: Pubkey::default(),
-            output_token_mint: Pubkey::default(),
-            input_token_program: Pubkey::default(),
-            output_token_program: Pubkey::default(),
-            create_ts: 0,
-            deadline: 0,
-            padding: [0u8; 128],
+impl OrderV1 {
+    pub const LEN: usize = 8 + std::mem::size_of::<OrderV1>();
+
+    /// The owner of the output token, orders placed before receivers existed pay the maker
+    pub fn output_receiver(&self) -> Pubkey {
+        if self.receiver == Pubkey::default() { self.maker } else { self.receiver }
+    }
+
+    /// Checks a fill of `fill_amount` out of the `escrow_token_amount` left: every fill but the
+    /// one settling the order takes at least `making_amount / MAX_ORDER_FILLS`.
+    pub fn check_fill_amount(&self, fill_amount: u64, escrow_token_amount: u64) -> Result<()> {
+        require!(
+            fill_amount > 0 && fill_amount <= escrow_token_amount,
+            LimitOrderError::InvalidFillAmount
+        );
+        let min_fill_amount = self.making_amount.div_ceil(MAX_ORDER_FILLS);
+        require!(
+            fill_amount >= min_fill_amount || fill_amount == escrow_token_amount,
+            LimitOrderError::FillAmountTooSmall
+        );
+        Ok(())
+    }
+
+    /// Returns the share of the prepaid `escrow_fee` a fill of `fill_amount` may spend, pro-rata
+    /// on the making amount left, so small fills cannot drain the fee of the whole order.
+    pub fn fill_fee_cap(&self, escrow_fee: u64, fill_amount: u64, is_final: bool) -> Result<u64> {
+        if is_final {
+            return Ok(escrow_fee);
+        }
+        let unfilled_making_amount = self.making_amount.saturating_sub(self.filled_making_amount);
+        if unfilled_making_amount == 0 {
+            return Ok(escrow_fee);
         }
+        let fee_cap = (escrow_fee as u128)
+            .checked_mul(fill_amount.min(unfilled_making_amount) as u128)
+            .ok_or(LimitOrderError::MathOverflow)?
+            .checked_div(unfilled_making_amount as u128)
+            .ok_or(LimitOrderError::MathOverflow)?;
+        Ok(fee_cap as u64)
     }
-}
 
-impl OrderV1 {
-    pub const LEN: usize = 8 + std::mem::size_of::<OrderV1>();
+    /// Returns the (expect_taking_amount, min_return_amount) of a fill of `fill_amount`.
+    /// Amounts are pro-rata on the cumulative filled amount, so the average price of all
+    /// fills is never worse than `min_return_amount / making_amount`.
+    pub fn fill_taking_amounts(&self, fill_amount: u64, is_final: bool) -> Result<(u64, u64)> {
+        // The last fill settles the whole order, including any rounding dust
+        let filled_making_amount = if is_final {
+            self.making_amount
+        } else {
+            self.filled_making_amount
+                .checked_add(fill_amount)
+                .ok_or(LimitOrderError::MathOverflow)?
+        };
+        let pro_rata = |amount: u64| -> Result<u64> {
+            let cumulative = (amount as u128)
+                .checked_mul(filled_making_amount as u128)
+                .and_then(|x| x.checked_add(self.making_amount as u128 - 1))
+                .and_then(|x| x.checked_div(self.making_amount as u128))
+                .ok_or(LimitOrderError::MathOverflow)?;
+            let cumulative =
+                u64::try_from(cumulative).map_err(|_| LimitOrderError::MathOverflow)?;
+            Ok(cumulative.saturating_sub(self.filled_taking_amount).max(1))
+        };
+        let min_return_amount = pro_rata(self.min_return_amount)?;
+        let expect_taking_amount = pro_rata(self.expect_taking_amount)?.max(min_return_amount);
+        Ok((expect_taking_amount, min_return_amount))
+    }
+
+    /// Records a fill, the last fill marks the whole making amount as filled.
+    pub fn record_fill(
+        &mut self,
+        making_amount: u64,
+        taking_amount: u64,
+        is_final: bool,
+    ) -> Result<()> {
+        self.filled_making_amount = if is_final {
+            self.making_amount
+        } else {
+            self.filled_making_amount
+                .checked_add(making_amount)
+                .ok_or(LimitOrderError::MathOverflow)?
+                .min(self.making_amount)
+        };
+        self.filled_taking_amount = self
+            .filled_taking_amount
+            .checked_add(taking_amount)
+            .ok_or(LimitOrderError::MathOverflow)?;
+        Ok(())
+    }
 }
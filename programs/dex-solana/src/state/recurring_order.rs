@@ -0,0 +1,135 @@
+use crate::error::LimitOrderError;
+use anchor_lang::prelude::*;
+
+/// DCA / TWAP order selling the escrow in equal slices, one slice per interval.
+#[account]
+#[derive(Debug)]
+pub struct RecurringOrder {
+    /// Bump to identify PDA.
+    pub bump: u8,
+
+    /// The order id.
+    pub order_id: u64,
+
+    /// The maker of the order.
+    pub maker: Pubkey,
+
+    /// The escrow token account of the order.
+    pub escrow_token_account: Pubkey,
+
+    /// Input token mint.
+    pub input_token_mint: Pubkey,
+
+    /// Output token mint.
+    pub output_token_mint: Pubkey,
+
+    /// Input token program.
+    pub input_token_program: Pubkey,
+
+    /// Output token program.
+    pub output_token_program: Pubkey,
+
+    /// The total making amount escrowed.
+    pub making_amount: u64,
+
+    /// The making amount of a slice, the last slice also takes the rounding remainder.
+    pub slice_amount: u64,
+
+    /// The number of slices.
+    pub slice_count: u16,
+
+    /// The min taking amount of a full slice.
+    pub slice_min_return_amount: u64,
+
+    /// Seconds between two slices.
+    pub interval: u64,
+
+    /// The create timestamp of the order.
+    pub create_ts: u64,
+
+    /// The timestamp the next slice can be filled at.
+    pub next_fill_ts: u64,
+
+    /// The number of slices filled so far.
+    pub filled_slices: u16,
+
+    /// The making amount filled so far.
+    pub filled_making_amount: u64,
+
+    /// The taking amount received by the maker so far.
+    pub filled_taking_amount: u64,
+
+    /// padding
+    pub padding: [u8; 64],
+}
+
+impl Default for RecurringOrder {
+    fn default() -> Self {
+        RecurringOrder {
+            bump: 0,
+            order_id: 0,
+            maker: Pubkey::default(),
+            escrow_token_account: Pubkey::default(),
+            input_token_mint: Pubkey::default(),
+            output_token_mint: Pubkey::default(),
+            input_token_program: Pubkey::default(),
+            output_token_program: Pubkey::default(),
+            making_amount: 0,
+            slice_amount: 0,
+            slice_count: 0,
+            slice_min_return_amount: 0,
+            interval: 0,
+            create_ts: 0,
+            next_fill_ts: 0,
+            filled_slices: 0,
+            filled_making_amount: 0,
+            filled_taking_amount: 0,
+            padding: [0u8; 64],
+        }
+    }
+}
+
+impl RecurringOrder {
+    pub const LEN: usize = 8 + std::mem::size_of::<RecurringOrder>();
+
+    /// The making amount of the next slice, the last slice empties the escrow
+    pub fn next_slice_amount(&self, escrow_token_amount: u64) -> u64 {
+        if self.filled_slices.saturating_add(1) >= self.slice_count {
+            escrow_token_amount
+        } else {
+            self.slice_amount.min(escrow_token_amount)
+        }
+    }
+
+    /// The min taking amount of a slice of `fill_amount`, rounded up against the resolver
+    pub fn slice_min_return(&self, fill_amount: u64) -> Result<u64> {
+        let min_return = (self.slice_min_return_amount as u128)
+            .checked_mul(fill_amount as u128)
+            .and_then(|x| x.checked_add(self.slice_amount as u128 - 1))
+            .and_then(|x| x.checked_div(self.slice_amount as u128))
+            .ok_or(LimitOrderError::MathOverflow)?;
+        Ok(u64::try_from(min_return).map_err(|_| LimitOrderError::MathOverflow)?.max(1))
+    }
+
+    /// Records a slice and schedules the next one an interval from now
+    pub fn record_slice(
+        &mut self,
+        making_amount: u64,
+        taking_amount: u64,
+        current_ts: u64,
+    ) -> Result<()> {
+        self.filled_slices =
+            self.filled_slices.checked_add(1).ok_or(LimitOrderError::MathOverflow)?;
+        self.filled_making_amount = self
+            .filled_making_amount
+            .checked_add(making_amount)
+            .ok_or(LimitOrderError::MathOverflow)?;
+        self.filled_taking_amount = self
+            .filled_taking_amount
+            .checked_add(taking_amount)
+            .ok_or(LimitOrderError::MathOverflow)?;
+        self.next_fill_ts =
+            current_ts.checked_add(self.interval).ok_or(LimitOrderError::MathOverflow)?;
+        Ok(())
+    }
+}
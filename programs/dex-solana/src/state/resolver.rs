@@ -0,0 +1,117 @@
+use crate::error::LimitOrderError;
+use anchor_lang::prelude::*;
+
+/// Registry entry of a resolver, seeded by the resolver pubkey.
+#[account(zero_copy(unsafe))]
+#[derive(Debug)]
+pub struct Resolver {
+    /// Bump to identify PDA.
+    pub bump: u8,
+
+    /// The resolver allowed to fill and cancel orders.
+    pub resolver: Pubkey,
+
+    /// Indicate whether the resolver is suspended.
+    pub suspended: bool,
+
+    /// Max orders filled in one slot, 0 is unlimited.
+    pub max_orders_per_slot: u16,
+
+    /// Mints the resolver may trade, both order mints must be listed. Empty allows all.
+    pub allowed_mints: [Pubkey; 8],
+
+    /// Number of fills.
+    pub fill_count: u64,
+
+    /// Number of orders cancelled after their deadline.
+    pub cancel_count: u64,
+
+    /// Slot of the last fill.
+    pub last_fill_slot: u64,
+
+    /// Number of fills in the last fill slot.
+    pub slot_fill_count: u16,
+
+    /// padding for upgrade
+    pub padding: [u8; 128],
+}
+
+impl Default for Resolver {
+    fn default() -> Self {
+        Resolver {
+            bump: 0,
+            resolver: Pubkey::default(),
+            suspended: false,
+            max_orders_per_slot: 0,
+            allowed_mints: [Pubkey::default(); 8],
+            fill_count: 0,
+            cancel_count: 0,
+            last_fill_slot: 0,
+            slot_fill_count: 0,
+            padding: [0u8; 128],
+        }
+    }
+}
+
+impl Resolver {
+    pub const LEN: usize = 8 + std::mem::size_of::<Resolver>();
+
+    pub fn set_limits(&mut self, max_orders_per_slot: u16, allowed_mints: &[Pubkey]) -> Result<()> {
+        require!(
+            allowed_mints.len() <= self.allowed_mints.len(),
+            LimitOrderError::ExceedResolverMintLimit
+        );
+        self.max_orders_per_slot = max_orders_per_slot;
+        self.allowed_mints = [Pubkey::default(); 8];
+        for (item, mint) in self.allowed_mints.iter_mut().zip(allowed_mints) {
+            require_keys_neq!(*mint, Pubkey::default(), LimitOrderError::InvalidAccount);
+            *item = *mint;
+        }
+        Ok(())
+    }
+
+    pub fn set_suspended(&mut self, suspended: bool) -> Result<()> {
+        self.suspended = suspended;
+        Ok(())
+    }
+
+    pub fn is_allowed_mint(&self, mint: Pubkey) -> bool {
+        self.allowed_mints.iter().all(|item| *item == Pubkey::default())
+            || self.allowed_mints.contains(&mint)
+    }
+
+    /// Checks the resolver may fill an order of the given mints in this slot and counts the fill
+    pub fn record_fill(
+        &mut self,
+        input_token_mint: Pubkey,
+        output_token_mint: Pubkey,
+        slot: u64,
+    ) -> Result<()> {
+        require!(!self.suspended, LimitOrderError::ResolverSuspended);
+        require!(
+            self.is_allowed_mint(input_token_mint) && self.is_allowed_mint(output_token_mint),
+            LimitOrderError::ResolverMintNotAllowed
+        );
+        if slot != self.last_fill_slot {
+            self.last_fill_slot = slot;
+            self.slot_fill_count = 0;
+        }
+        self.slot_fill_count =
+            self.slot_fill_count.checked_add(1).ok_or(LimitOrderError::MathOverflow)?;
+        if self.max_orders_per_slot > 0 {
+            require!(
+                self.slot_fill_count <= self.max_orders_per_slot,
+                LimitOrderError::ExceedResolverSlotLimit
+            );
+        }
+        self.fill_count = self.fill_count.checked_add(1).ok_or(LimitOrderError::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn record_cancel(&mut self) -> Result<()> {
+        require!(!self.suspended, LimitOrderError::ResolverSuspended);
+        self.cancel_count =
+            self.cancel_count.checked_add(1).ok_or(LimitOrderError::MathOverflow)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use crate::error::LimitOrderError;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+
+/// Offsets header of the ed25519 program instruction data
+const ED25519_DATA_START: usize = 16;
+/// Instruction index marking data stored in the ed25519 instruction itself
+const ED25519_CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Checks the instruction right before the current one is an ed25519 program instruction
+/// verifying `signer`'s signature of exactly `message`
+pub fn verify_ed25519_signature(
+    instruction_sysvar_account_info: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instruction_sysvar_account_info)?;
+    require!(current_index > 0, LimitOrderError::InvalidIntentSignature);
+    let instruction =
+        load_instruction_at_checked(current_index as usize - 1, instruction_sysvar_account_info)?;
+    require_keys_eq!(
+        instruction.program_id,
+        ed25519_program::ID,
+        LimitOrderError::InvalidIntentSignature
+    );
+
+    // A single signature, the ed25519 program fails the transaction if it does not verify
+    let data = &instruction.data;
+    require!(
+        data.len() >= ED25519_DATA_START && data[0] == 1,
+        LimitOrderError::InvalidIntentSignature
+    );
+    let offset = |i: usize| u16::from_le_bytes([data[2 + i * 2], data[3 + i * 2]]);
+
+    // The signature, public key and message must all be read from the same instruction
+    require!(
+        offset(1) == ED25519_CURRENT_INSTRUCTION
+            && offset(3) == ED25519_CURRENT_INSTRUCTION
+            && offset(6) == ED25519_CURRENT_INSTRUCTION,
+        LimitOrderError::InvalidIntentSignature
+    );
+    let (public_key_offset, message_offset) = (offset(2) as usize, offset(4) as usize);
+    let message_size = offset(5) as usize;
+    let public_key = data.get(public_key_offset..public_key_offset + 32);
+    let signed_message = data.get(message_offset..message_offset + message_size);
+    require!(
+        public_key == Some(signer.as_ref()) && signed_message == Some(message),
+        LimitOrderError::InvalidIntentSignature
+    );
+    Ok(())
+}
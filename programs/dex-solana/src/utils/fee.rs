@@ -1,5 +1,6 @@
 use crate::constants::*;
 use crate::error::{ErrorCode, LimitOrderError};
+use crate::state::event::{CommissionPayout, CommissionSplitEvent};
 use crate::utils::*;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::sysvar::instructions::load_instruction_at_checked;
@@ -12,6 +13,7 @@ pub fn collect_fees<'a>(
     payer: AccountInfo<'a>,
     instruction_sysvar_account_info: &AccountInfo<'a>,
     escrow_min_rent: u64,
+    max_fees: Option<u64>,
 ) -> Result<()> {
     let priority_fee = compute_fees(instruction_sysvar_account_info)?;
     let mut fees = priority_fee.checked_add(tips).ok_or(LimitOrderError::MathOverflow)?;
@@ -28,6 +30,11 @@ pub fn collect_fees<'a>(
             .ok_or(LimitOrderError::MathOverflow)?;
     }
 
+    // A partial fill only spends its share of the prepaid fee
+    if let Some(max_fees) = max_fees {
+        fees = fees.min(max_fees);
+    }
+
     let escrow_fee_balance = escrow_fee_account.lamports();
     msg!(
         "Escrow fee: {:?}, tips: {:?}, multiplier: {:?}, collecting fees: {:?}",
@@ -196,6 +203,8 @@ pub fn calculate_trim_amount(
     }
 }
 
+/// Pays `fee_amount` to `fee_account`, grossed up by the Token-2022 transfer fee when
+/// `gross_up_transfer_fee` so the fee account receives all of it
 pub fn transfer_token_fee<'a>(
     authority: &AccountInfo<'a>,
     token_account: &AccountInfo<'a>,
@@ -203,22 +212,32 @@ pub fn transfer_token_fee<'a>(
     token_program: &Interface<'a, TokenInterface>,
     fee_account: &AccountInfo<'a>,
     fee_amount: u64,
+    gross_up_transfer_fee: bool,
     signer_seeds: Option<&[&[&[u8]]]>,
+    hook_accounts: &[AccountInfo<'a>],
 ) -> Result<()> {
     if fee_amount == 0 {
         return Ok(());
     }
     let fee_to_token_account = associate_convert_token_account(fee_account)?;
     require!(fee_to_token_account.mint == token_mint.key(), ErrorCode::InvalidFeeTokenAccount);
+    let transfer_amount = if gross_up_transfer_fee {
+        fee_amount
+            .checked_add(get_transfer_inverse_fee(&token_mint.to_account_info(), fee_amount)?)
+            .ok_or(ErrorCode::CalculationError)?
+    } else {
+        fee_amount
+    };
     transfer_token(
         authority.to_account_info(),
         token_account.to_account_info(),
         fee_to_token_account.to_account_info(),
         token_mint.to_account_info(),
         token_program.to_account_info(),
-        fee_amount,
+        transfer_amount,
         token_mint.decimals,
         signer_seeds,
+        hook_accounts,
     )
 }
 
@@ -277,3 +296,168 @@ pub fn is_charge_sol(
     }
     false
 }
+
+/// Share of a split commission paid to `recipient`, in basis points of the commission
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CommissionShare {
+    pub recipient: Pubkey,
+    pub share_bps: u16,
+}
+
+/// Recipients of a split commission and their accounts, in the same order
+pub struct CommissionSplit<'a, 'info> {
+    pub shares: &'a [CommissionShare],
+    pub accounts: &'a [AccountInfo<'info>],
+}
+
+impl<'a, 'info> CommissionSplit<'a, 'info> {
+    /// Checks every account is its share's recipient and the shares add up to the whole
+    /// commission
+    pub fn new(shares: &'a [CommissionShare], accounts: &'a [AccountInfo<'info>]) -> Result<Self> {
+        require!(
+            !shares.is_empty()
+                && shares.len() <= MAX_COMMISSION_SPLITS
+                && shares.len() == accounts.len(),
+            ErrorCode::InvalidCommissionSplit
+        );
+        let mut total_bps: u64 = 0;
+        for (share, account) in shares.iter().zip(accounts) {
+            require!(share.share_bps > 0, ErrorCode::InvalidCommissionSplit);
+            require_keys_eq!(account.key(), share.recipient, ErrorCode::InvalidCommissionSplit);
+            total_bps += u64::from(share.share_bps);
+        }
+        require!(total_bps == COMMISSION_SPLIT_DENOMINATOR, ErrorCode::InvalidCommissionSplit);
+        Ok(Self { shares, accounts })
+    }
+
+    /// Commission paid to each recipient, the last one takes the rounding remainder
+    pub fn amounts(&self, commission_amount: u64) -> Result<Vec<u64>> {
+        let mut amounts = Vec::with_capacity(self.shares.len());
+        let mut acc_amount: u64 = 0;
+        for (i, share) in self.shares.iter().enumerate() {
+            let amount = if i == self.shares.len() - 1 {
+                commission_amount.checked_sub(acc_amount).ok_or(ErrorCode::CalculationError)?
+            } else {
+                u64::try_from(
+                    u128::from(commission_amount)
+                        .checked_mul(u128::from(share.share_bps))
+                        .and_then(|v| v.checked_div(u128::from(COMMISSION_SPLIT_DENOMINATOR)))
+                        .ok_or(ErrorCode::CalculationError)?,
+                )
+                .map_err(|_| ErrorCode::CalculationError)?
+            };
+            acc_amount = acc_amount.checked_add(amount).ok_or(ErrorCode::CalculationError)?;
+            amounts.push(amount);
+        }
+        Ok(amounts)
+    }
+}
+
+/// Token-2022 transfer fee withheld on the payouts of an output commission and platform fee,
+/// which are grossed up by it out of the user's share
+pub fn get_payout_transfer_fee(
+    token_mint: &AccountInfo,
+    commission_amount: u64,
+    commission_split: Option<&CommissionSplit>,
+    platform_fee_amount: u64,
+) -> Result<u64> {
+    let mut payouts = match commission_split {
+        Some(commission_split) => commission_split.amounts(commission_amount)?,
+        None => vec![commission_amount],
+    };
+    payouts.push(platform_fee_amount);
+    let mut transfer_fee: u64 = 0;
+    for amount in payouts.into_iter().filter(|amount| *amount > 0) {
+        transfer_fee = transfer_fee
+            .checked_add(get_transfer_inverse_fee(token_mint, amount)?)
+            .ok_or(ErrorCode::CalculationError)?;
+    }
+    Ok(transfer_fee)
+}
+
+/// Pays a SOL commission to `commission_account`, or across the recipients of
+/// `commission_split` with a single event listing the payouts
+pub fn transfer_sol_commission<'a>(
+    authority: &AccountInfo<'a>,
+    commission_account: &AccountInfo<'a>,
+    commission_split: Option<&CommissionSplit<'_, 'a>>,
+    commission_amount: u64,
+    commission_direction: bool,
+    signer_seeds: Option<&[&[&[u8]]]>,
+) -> Result<()> {
+    let Some(commission_split) = commission_split else {
+        let actual_fee_amount =
+            transfer_sol_fee(authority, commission_account, commission_amount, signer_seeds)?;
+        log_commission_info(commission_direction, actual_fee_amount);
+        commission_account.key().log();
+        return Ok(());
+    };
+    // Every recipient is paid in lamports, not only the one that selected SOL mode
+    require!(
+        commission_split.accounts.iter().all(is_system_account),
+        ErrorCode::InvalidCommissionAccount
+    );
+    let mut payouts = Vec::with_capacity(commission_split.accounts.len());
+    for (account, amount) in
+        commission_split.accounts.iter().zip(commission_split.amounts(commission_amount)?)
+    {
+        let actual_fee_amount = transfer_sol_fee(authority, account, amount, signer_seeds)?;
+        payouts.push(CommissionPayout { recipient: account.key(), amount: actual_fee_amount });
+    }
+    log_commission_info(commission_direction, payouts.iter().map(|p| p.amount).sum());
+    emit!(CommissionSplitEvent { commission_direction, mint: wsol_program::ID, payouts });
+    Ok(())
+}
+
+/// Pays a token commission to `commission_account`, or across the recipients of
+/// `commission_split` with a single event listing the payouts. An output commission is grossed up
+/// by the Token-2022 transfer fee of each payout.
+pub fn transfer_token_commission<'a>(
+    authority: &AccountInfo<'a>,
+    token_account: &AccountInfo<'a>,
+    token_mint: &InterfaceAccount<'a, Mint>,
+    token_program: &Interface<'a, TokenInterface>,
+    commission_account: &AccountInfo<'a>,
+    commission_split: Option<&CommissionSplit<'_, 'a>>,
+    commission_amount: u64,
+    commission_direction: bool,
+    signer_seeds: Option<&[&[&[u8]]]>,
+    hook_accounts: &[AccountInfo<'a>],
+) -> Result<()> {
+    let Some(commission_split) = commission_split else {
+        transfer_token_fee(
+            authority,
+            token_account,
+            token_mint,
+            token_program,
+            commission_account,
+            commission_amount,
+            !commission_direction,
+            signer_seeds,
+            hook_accounts,
+        )?;
+        log_commission_info(commission_direction, commission_amount);
+        commission_account.key().log();
+        return Ok(());
+    };
+    let mut payouts = Vec::with_capacity(commission_split.accounts.len());
+    for (account, amount) in
+        commission_split.accounts.iter().zip(commission_split.amounts(commission_amount)?)
+    {
+        transfer_token_fee(
+            authority,
+            token_account,
+            token_mint,
+            token_program,
+            account,
+            amount,
+            !commission_direction,
+            signer_seeds,
+            hook_accounts,
+        )?;
+        payouts.push(CommissionPayout { recipient: account.key(), amount });
+    }
+    log_commission_info(commission_direction, commission_amount);
+    emit!(CommissionSplitEvent { commission_direction, mint: token_mint.key(), payouts });
+    Ok(())
+}
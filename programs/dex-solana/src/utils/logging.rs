@@ -56,6 +56,16 @@ pub fn log_commission_info(commission_direction: bool, commission_amount: u64) {
     );
 }
 
+pub fn log_transfer_fee_info(transfer_fee_direction: bool, transfer_fee: u64) {
+    if transfer_fee > 0 {
+        msg!(
+            "transfer_fee_direction: {:?}, transfer_fee: {:?}",
+            transfer_fee_direction,
+            transfer_fee
+        );
+    }
+}
+
 pub fn log_platform_fee_info(amount: u64, fee_account: &Pubkey) {
     msg!("platform_fee_amount: {:?}", amount);
     fee_account.log();
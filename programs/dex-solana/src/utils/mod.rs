@@ -1,9 +1,13 @@
+pub mod ed25519;
 pub mod fee;
 pub mod logging;
+pub mod oracle;
 pub mod swap;
 pub mod token;
 
+pub use ed25519::*;
 pub use fee::*;
 pub use logging::*;
+pub use oracle::*;
 pub use swap::*;
 pub use token::*;
@@ -0,0 +1,222 @@
+use crate::constants::*;
+use crate::error::LimitOrderError;
+use anchor_lang::prelude::*;
+
+/// Anchor discriminator of the Pyth receiver `PriceUpdateV2` account
+pub const PYTH_PRICE_UPDATE_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+/// Anchor discriminator of the Switchboard On-Demand `PullFeedAccountData` account
+pub const SWITCHBOARD_PULL_FEED_DISCRIMINATOR: [u8; 8] = [196, 27, 108, 196, 10, 215, 219, 40];
+
+/// `VerificationLevel::Full`, partially verified updates are rejected
+const PYTH_FULL_VERIFICATION: u8 = 1;
+/// discriminator, write authority, verification level
+const PYTH_MESSAGE_OFFSET: usize = 8 + 32 + 1;
+/// feed id, price, conf, exponent, publish time
+const PYTH_MESSAGE_LEN: usize = 32 + 8 + 8 + 4 + 8;
+
+/// discriminator, 32 submissions of 64 bytes and the feed settings before `result`
+const SWITCHBOARD_RESULT_OFFSET: usize = 8 + 2256;
+/// value, std_dev, mean, range, min_value, max_value, num_samples, submission_idx, padding, slot
+const SWITCHBOARD_RESULT_LEN: usize = 16 * 6 + 8 + 8;
+/// Switchboard values are fixed point with 18 decimals
+const SWITCHBOARD_EXPONENT: i32 = -18;
+/// Submissions held by a pull feed, bounds `num_samples` and `submission_idx`
+const SWITCHBOARD_MAX_SUBMISSIONS: u8 = 32;
+
+/// Age of an oracle price, in the clock of its oracle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OracleStaleness {
+    /// Pyth, since the publish time
+    Seconds(u64),
+    /// Switchboard, since the slot the result was signed at
+    Slots(u64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OraclePrice {
+    /// Price scaled by 10^exponent
+    pub price: i128,
+    /// Confidence interval (Pyth) or standard deviation (Switchboard), scaled by 10^exponent
+    pub confidence: u128,
+    pub exponent: i32,
+    pub staleness: OracleStaleness,
+}
+
+impl OraclePrice {
+    /// Compares two prices in their own exponents
+    pub fn cmp_price(&self, price: i128, exponent: i32) -> Result<std::cmp::Ordering> {
+        let min_exponent = self.exponent.min(exponent);
+        let scale = |value: i128, exponent: i32| -> Result<i128> {
+            10i128
+                .checked_pow(exponent.abs_diff(min_exponent))
+                .and_then(|factor| value.checked_mul(factor))
+                .ok_or(LimitOrderError::MathOverflow.into())
+        };
+        Ok(scale(self.price, self.exponent)?.cmp(&scale(price, exponent)?))
+    }
+
+    /// Whether the confidence is within `max_confidence_bps` of the price
+    pub fn is_confident(&self, max_confidence_bps: u16) -> Result<bool> {
+        let confidence = self
+            .confidence
+            .checked_mul(ORACLE_CONFIDENCE_DENOMINATOR as u128)
+            .ok_or(LimitOrderError::MathOverflow)?;
+        let bound = self
+            .price
+            .unsigned_abs()
+            .checked_mul(max_confidence_bps as u128)
+            .ok_or(LimitOrderError::MathOverflow)?;
+        Ok(confidence <= bound)
+    }
+}
+
+/// Reads a Pyth `PriceUpdateV2` or a Switchboard On-Demand pull feed, told apart by owner
+pub fn read_oracle_price(
+    oracle: &AccountInfo,
+    current_ts: u64,
+    current_slot: u64,
+) -> Result<OraclePrice> {
+    let data = oracle.try_borrow_data()?;
+    let price = if *oracle.owner == pyth_receiver_program::ID {
+        read_pyth_price(&data, current_ts)?
+    } else if *oracle.owner == switchboard_on_demand_program::ID {
+        read_switchboard_price(&data, current_slot)?
+    } else {
+        return Err(LimitOrderError::InvalidOracle.into());
+    };
+    require!(price.price > 0, LimitOrderError::InvalidOracle);
+    Ok(price)
+}
+
+fn read_pyth_price(data: &[u8], current_ts: u64) -> Result<OraclePrice> {
+    require!(
+        data.len() >= PYTH_MESSAGE_OFFSET + PYTH_MESSAGE_LEN
+            && data[..8] == PYTH_PRICE_UPDATE_DISCRIMINATOR
+            && data[PYTH_MESSAGE_OFFSET - 1] == PYTH_FULL_VERIFICATION,
+        LimitOrderError::InvalidOracle
+    );
+    let message = &data[PYTH_MESSAGE_OFFSET + 32..];
+    let price = i64::from_le_bytes(message[0..8].try_into().unwrap());
+    let conf = u64::from_le_bytes(message[8..16].try_into().unwrap());
+    let exponent = i32::from_le_bytes(message[16..20].try_into().unwrap());
+    let publish_time = i64::from_le_bytes(message[20..28].try_into().unwrap());
+    Ok(OraclePrice {
+        price: price.into(),
+        confidence: conf.into(),
+        exponent,
+        staleness: OracleStaleness::Seconds(current_ts.saturating_sub(publish_time.max(0) as u64)),
+    })
+}
+
+fn read_switchboard_price(data: &[u8], current_slot: u64) -> Result<OraclePrice> {
+    require!(
+        data.len() >= SWITCHBOARD_RESULT_OFFSET + SWITCHBOARD_RESULT_LEN
+            && data[..8] == SWITCHBOARD_PULL_FEED_DISCRIMINATOR,
+        LimitOrderError::InvalidOracle
+    );
+    let result = &data[SWITCHBOARD_RESULT_OFFSET..];
+    let value = i128::from_le_bytes(result[0..16].try_into().unwrap());
+    let std_dev = i128::from_le_bytes(result[16..32].try_into().unwrap());
+    let min_value = i128::from_le_bytes(result[64..80].try_into().unwrap());
+    let max_value = i128::from_le_bytes(result[80..96].try_into().unwrap());
+    let num_samples = result[96];
+    let submission_idx = result[97];
+    let slot = u64::from_le_bytes(result[104..112].try_into().unwrap());
+    // The offsets are pinned to the current feed layout, a feed of another layout version fails
+    // these invariants instead of being read at the wrong offsets
+    require!(
+        num_samples > 0
+            && num_samples <= SWITCHBOARD_MAX_SUBMISSIONS
+            && submission_idx < SWITCHBOARD_MAX_SUBMISSIONS
+            && min_value <= value
+            && value <= max_value
+            && slot > 0,
+        LimitOrderError::InvalidOracle
+    );
+    Ok(OraclePrice {
+        price: value,
+        confidence: std_dev.unsigned_abs(),
+        exponent: SWITCHBOARD_EXPONENT,
+        staleness: OracleStaleness::Slots(current_slot.saturating_sub(slot)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn price(price: i128, confidence: u128, exponent: i32) -> OraclePrice {
+        OraclePrice { price, confidence, exponent, staleness: OracleStaleness::Seconds(0) }
+    }
+
+    #[test]
+    pub fn test_cmp_price() {
+        use std::cmp::Ordering;
+        // 150.25 in Pyth and Switchboard exponents
+        assert_eq!(price(15_025_000_000, 0, -8).cmp_price(150_250, -3).unwrap(), Ordering::Equal);
+        assert_eq!(
+            price(150_250_000_000_000_000_000, 0, SWITCHBOARD_EXPONENT).cmp_price(151, 0).unwrap(),
+            Ordering::Less
+        );
+        assert_eq!(price(2, 0, 0).cmp_price(1_999_999, -6).unwrap(), Ordering::Greater);
+        assert!(price(i128::MAX, 0, 0).cmp_price(1, -8).is_err());
+    }
+
+    #[test]
+    pub fn test_is_confident() {
+        // 1% confidence interval
+        assert!(price(10_000, 100, -2).is_confident(100).unwrap());
+        assert!(!price(10_000, 101, -2).is_confident(100).unwrap());
+    }
+
+    #[test]
+    pub fn test_read_pyth_price() {
+        let mut data = PYTH_PRICE_UPDATE_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[0; 32]);
+        data.push(PYTH_FULL_VERIFICATION);
+        data.extend_from_slice(&[0; 32]);
+        data.extend_from_slice(&15_025_000_000i64.to_le_bytes());
+        data.extend_from_slice(&1_000_000u64.to_le_bytes());
+        data.extend_from_slice(&(-8i32).to_le_bytes());
+        data.extend_from_slice(&1_000i64.to_le_bytes());
+        assert_eq!(
+            read_pyth_price(&data, 1_030).unwrap(),
+            OraclePrice {
+                staleness: OracleStaleness::Seconds(30),
+                ..price(15_025_000_000, 1_000_000, -8)
+            }
+        );
+
+        data[PYTH_MESSAGE_OFFSET - 1] = 0;
+        assert!(read_pyth_price(&data, 1_030).is_err());
+    }
+
+    #[test]
+    pub fn test_read_switchboard_price() {
+        let value = 150_250_000_000_000_000_000i128;
+        let mut data = SWITCHBOARD_PULL_FEED_DISCRIMINATOR.to_vec();
+        data.resize(SWITCHBOARD_RESULT_OFFSET, 0);
+        data.extend_from_slice(&value.to_le_bytes());
+        data.extend_from_slice(&1_000i128.to_le_bytes());
+        data.extend_from_slice(&value.to_le_bytes()); // mean
+        data.extend_from_slice(&0i128.to_le_bytes()); // range
+        data.extend_from_slice(&value.to_le_bytes()); // min_value
+        data.extend_from_slice(&value.to_le_bytes()); // max_value
+        data.extend_from_slice(&[1, 0, 0, 0, 0, 0, 0, 0]); // num_samples, submission_idx, padding
+        data.extend_from_slice(&1_000u64.to_le_bytes());
+        assert_eq!(
+            read_switchboard_price(&data, 1_010).unwrap(),
+            OraclePrice {
+                staleness: OracleStaleness::Slots(10),
+                ..price(value, 1_000, SWITCHBOARD_EXPONENT)
+            }
+        );
+
+        // A result outside its own min and max is read at the wrong offsets
+        let mut shifted = data.clone();
+        shifted[SWITCHBOARD_RESULT_OFFSET + 80..SWITCHBOARD_RESULT_OFFSET + 96].fill(0);
+        assert!(read_switchboard_price(&shifted, 1_010).is_err());
+        data[..8].copy_from_slice(&PYTH_PRICE_UPDATE_DISCRIMINATOR);
+        assert!(read_switchboard_price(&data, 1_010).is_err());
+    }
+}
@@ -62,6 +62,7 @@ impl<'info> CommonSwapProcessor<'info> for FillOrderSwapProcessor {
         fee_rate: Option<u32>,
         fee_direction: Option<bool>,
         fee_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+        remaining_accounts: &[AccountInfo<'info>],
     ) -> Result<u64> {
         if source_token_sa.is_none() || source_token_program.is_none() {
             return Ok(amount_in);
@@ -102,6 +103,7 @@ impl<'info> CommonSwapProcessor<'info> for FillOrderSwapProcessor {
                 fee_amount,
                 source_mint.decimals,
                 owner_seeds,
+                remaining_accounts,
             )?;
 
             real_amount_in =
@@ -118,6 +120,7 @@ impl<'info> CommonSwapProcessor<'info> for FillOrderSwapProcessor {
             real_amount_in,
             source_mint.decimals,
             owner_seeds,
+            remaining_accounts,
         )?;
         Ok(real_amount_in)
     }
@@ -134,6 +137,7 @@ impl<'info> CommonSwapProcessor<'info> for FillOrderSwapProcessor {
         fee_rate: Option<u32>,
         fee_direction: Option<bool>,
         fee_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+        remaining_accounts: &[AccountInfo<'info>],
     ) -> Result<()> {
         if sa_authority.is_none()
             || destination_token_sa.is_none()
@@ -178,6 +182,7 @@ impl<'info> CommonSwapProcessor<'info> for FillOrderSwapProcessor {
                 fee_amount,
                 destination_mint.decimals,
                 owner_seeds,
+                remaining_accounts,
             )?;
 
             real_amount_out =
@@ -193,6 +198,7 @@ impl<'info> CommonSwapProcessor<'info> for FillOrderSwapProcessor {
             real_amount_out,
             destination_mint.decimals,
             owner_seeds,
+            remaining_accounts,
         )?;
         Ok(())
     }
@@ -8,12 +8,19 @@ use anchor_spl::token::Token;
 use anchor_spl::token_2022::spl_token_2022::extension::BaseStateWithExtensions;
 use anchor_spl::token_2022::spl_token_2022::{
     self,
-    extension::{StateWithExtensions, transfer_fee::TransferFeeConfig},
+    extension::{
+        StateWithExtensions,
+        transfer_fee::{TransferFeeAmount, TransferFeeConfig},
+        transfer_hook,
+    },
 };
 use anchor_spl::token_2022::{self, Token2022};
 use anchor_spl::token_2022_extensions;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
+/// Transfers with `transfer_checked`. Mints with a transfer hook resolve the hook's extra
+/// accounts from `hook_accounts`, which must hold the mint's ExtraAccountMetaList PDA, the
+/// hook program and every extra account it lists
 pub fn transfer_token<'a>(
     authority: AccountInfo<'a>,
     from: AccountInfo<'a>,
@@ -23,10 +30,25 @@ pub fn transfer_token<'a>(
     amount: u64,
     mint_decimals: u8,
     signer_seeds: Option<&[&[&[u8]]]>,
+    hook_accounts: &[AccountInfo<'a>],
 ) -> Result<()> {
     if amount == 0 {
         return Ok(());
     }
+    if get_transfer_hook_program_id(&mint)?.is_some() {
+        spl_token_2022::onchain::invoke_transfer_checked(
+            token_program.key,
+            from,
+            mint,
+            to,
+            authority,
+            hook_accounts,
+            amount,
+            mint_decimals,
+            signer_seeds.unwrap_or_default(),
+        )?;
+        return Ok(());
+    }
     if let Some(signer_seeds) = signer_seeds {
         token_2022::transfer_checked(
             CpiContext::new_with_signer(
@@ -256,6 +278,47 @@ pub fn get_transfer_fee(mint_info: &AccountInfo, pre_fee_amount: u64) -> Result<
     Ok(fee)
 }
 
+/// Calculate the fee to add to a transfer so that `post_fee_amount` is received
+pub fn get_transfer_inverse_fee(mint_info: &AccountInfo, post_fee_amount: u64) -> Result<u64> {
+    if *mint_info.owner == Token::id() {
+        return Ok(0);
+    }
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+
+    let fee = if let Ok(transfer_fee_config) = mint.get_extension::<TransferFeeConfig>() {
+        transfer_fee_config
+            .calculate_inverse_epoch_fee(Clock::get()?.epoch, post_fee_amount)
+            .ok_or(ErrorCode::CalculationError)?
+    } else {
+        0
+    };
+    Ok(fee)
+}
+
+/// Transfer fees withheld in a Token-2022 account, the difference around a transfer is the fee
+/// it paid
+pub fn get_withheld_amount(token_account: &AccountInfo) -> Result<u64> {
+    if *token_account.owner != Token2022::id() {
+        return Ok(0);
+    }
+    let account_data = token_account.try_borrow_data()?;
+    let account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account_data)?;
+    Ok(account
+        .get_extension::<TransferFeeAmount>()
+        .map_or(0, |transfer_fee_amount| u64::from(transfer_fee_amount.withheld_amount)))
+}
+
+/// The transfer hook program of a Token-2022 mint, if any
+pub fn get_transfer_hook_program_id(mint_info: &AccountInfo) -> Result<Option<Pubkey>> {
+    if *mint_info.owner != Token2022::id() {
+        return Ok(None);
+    }
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    Ok(transfer_hook::get_program_id(&mint))
+}
+
 pub fn harvest_withheld_tokens_to_mint<'a>(
     token_program: AccountInfo<'a>,
     token_mint: AccountInfo<'a>,
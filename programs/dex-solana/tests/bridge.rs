@@ -0,0 +1,391 @@
+mod common;
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::system_program;
+use anchor_spl::associated_token;
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022;
+use common::{Harness, error_code, mock_bridge, mock_token_swap, router_instruction};
+use dex_solana::error::ErrorCode;
+use dex_solana::{
+    AdaptorID, BridgeToArgs, Dex, Route, SwapArgs, SwapType, accounts, instruction,
+    okx_bridge_program,
+};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
+
+const ORDER_ID: u64 = 7;
+const TO_CHAIN_ID: u64 = 56;
+
+struct Setup {
+    h: Harness,
+    mint_a: Pubkey,
+    mint_b: Pubkey,
+    source: Pubkey,
+    destination: Pubkey,
+    pool: common::TokenSwapPool,
+    bridge_vault: Pubkey,
+}
+
+async fn setup() -> Setup {
+    let mut h = Harness::new().await;
+    let (mint_a, mint_b) = (h.create_mint(), h.create_mint());
+    let source = h.create_token_account(mint_a, h.payer(), 10_000_000);
+    let destination = h.create_token_account(mint_b, h.payer(), 0);
+    let pool = h.create_token_swap_pool(mint_a, mint_b, 1_000_000_000, 1_000_000_000);
+    let bridge_vault = h.create_token_account(mint_b, Pubkey::new_unique(), 0);
+    Setup { h, mint_a, mint_b, source, destination, pool, bridge_vault }
+}
+
+fn single_route(amount_in: u64, expect_amount_out: u64, min_return: u64) -> SwapArgs {
+    SwapArgs {
+        amount_in,
+        expect_amount_out,
+        min_return,
+        amounts: vec![amount_in],
+        routes: vec![vec![Route { dexes: vec![Dex::SplTokenSwap], weights: vec![100] }]],
+    }
+}
+
+fn bridge_to_args(adaptor_id: AdaptorID, swap_type: SwapType) -> BridgeToArgs {
+    BridgeToArgs {
+        adaptor_id,
+        to: vec![0xab; 20],
+        order_id: ORDER_ID,
+        to_chain_id: TO_CHAIN_ID,
+        amount: 0,
+        swap_type,
+        data: vec![],
+        ext_data: vec![],
+    }
+}
+
+impl Setup {
+    /// `len` adaptor accounts, the bridge vault first and a record of the accounts the bridge
+    /// receives last
+    fn adaptor_accounts(&mut self, len: usize) -> (Vec<AccountMeta>, Pubkey) {
+        let record = Pubkey::new_unique();
+        self.h.set_account(&record, okx_bridge_program::ID, vec![0; 8]);
+        let mut bridge_accounts = vec![AccountMeta::new(self.bridge_vault, false)];
+        bridge_accounts
+            .extend((2..len).map(|_| AccountMeta::new_readonly(Pubkey::new_unique(), false)));
+        bridge_accounts.push(AccountMeta::new(record, false));
+        (bridge_accounts, record)
+    }
+
+    fn meson_accounts(&mut self) -> Vec<AccountMeta> {
+        self.adaptor_accounts(4).0
+    }
+
+    fn swap_and_bridge_ix(
+        &self,
+        commission_account: Option<Pubkey>,
+        commission_info: u32,
+        bridge_to_args: BridgeToArgs,
+        remaining_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        router_instruction(
+            accounts::FromSwapAccounts {
+                payer: self.h.payer(),
+                source_token_account: self.source,
+                destination_token_account: self.destination,
+                source_mint: self.mint_a,
+                destination_mint: self.mint_b,
+                commission_account,
+                platform_fee_account: None,
+                sa_authority: None,
+                source_token_sa: None,
+                destination_token_sa: None,
+                source_token_program: Some(spl_token::ID),
+                destination_token_program: Some(spl_token::ID),
+                bridge_program: okx_bridge_program::ID,
+                associated_token_program: associated_token::ID,
+                token_program: spl_token::ID,
+                token_2022_program: spl_token_2022::ID,
+                system_program: system_program::ID,
+            },
+            instruction::SwapAndBridge {
+                args: single_route(1_000_000, 1, 1),
+                commission_info,
+                platform_fee_rate: 0,
+                bridge_to_args,
+            },
+            remaining_accounts,
+        )
+    }
+
+    fn swap_accounts(&self) -> Vec<AccountMeta> {
+        self.pool.accounts(self.h.payer(), self.source, self.destination)
+    }
+}
+
+#[tokio::test]
+async fn test_swap_and_bridge_with_commission() {
+    let mut s = setup().await;
+    let commission_account = s.h.create_token_account(s.mint_b, Pubkey::new_unique(), 0);
+
+    // 1% commission on the output, the rest is bridged
+    let mut remaining_accounts = s.swap_accounts();
+    remaining_accounts.extend(s.meson_accounts());
+    let ix = s.swap_and_bridge_ix(
+        Some(commission_account),
+        10_000_000,
+        bridge_to_args(AdaptorID::Meson, SwapType::SWAPANDBRIDGE),
+        remaining_accounts,
+    );
+    s.h.process(&[ix], &[]).await.unwrap();
+
+    let commission = s.h.balance(commission_account).await;
+    assert!(commission > 0);
+    assert_eq!(s.h.balance(s.source).await, 9_000_000);
+    assert_eq!(s.h.balance(s.destination).await, 0);
+    assert_eq!(
+        s.h.balance(s.bridge_vault).await + commission,
+        mock_token_swap::quote(1_000_000_000, 1_000_000_000, 1_000_000)
+    );
+}
+
+#[tokio::test]
+async fn test_swap_and_bridge_sizes_bridge_accounts_by_adaptor() {
+    let mut s = setup().await;
+
+    // Without swap accounts the Meson slice takes all the remaining accounts
+    let meson_accounts = s.meson_accounts();
+    let ix = s.swap_and_bridge_ix(
+        None,
+        0,
+        bridge_to_args(AdaptorID::Meson, SwapType::SWAPANDBRIDGE),
+        meson_accounts,
+    );
+    let result = s.h.process(&[ix], &[]).await;
+    assert_eq!(error_code(result), Some(ErrorCode::InvalidAccountsLength.into()));
+
+    let mut remaining_accounts = s.swap_accounts();
+    remaining_accounts.extend(s.meson_accounts());
+    let ix = s.swap_and_bridge_ix(
+        None,
+        0,
+        bridge_to_args(AdaptorID::Bridge0, SwapType::SWAPANDBRIDGE),
+        remaining_accounts,
+    );
+    let result = s.h.process(&[ix], &[]).await;
+    assert_eq!(error_code(result), Some(ErrorCode::UnsupportedBridgeAdaptor.into()));
+    assert_eq!(s.h.balance(s.source).await, 10_000_000);
+}
+
+#[tokio::test]
+async fn test_swap_and_bridge_passes_adaptor_accounts() {
+    // Adaptor accounts the bridge program reads after its fixed accounts
+    let adaptors = [
+        (AdaptorID::Cctp, 16),
+        (AdaptorID::Wormhole, 14),
+        (AdaptorID::Meson, 4),
+        (AdaptorID::Debridgedln, 12),
+        (AdaptorID::Allbridge, 11),
+        (AdaptorID::MayanSwift, 9),
+    ];
+    for (adaptor_id, len) in adaptors {
+        let mut s = setup().await;
+        let (adaptor_accounts, record) = s.adaptor_accounts(len);
+        let mut remaining_accounts = s.swap_accounts();
+        remaining_accounts.extend(adaptor_accounts);
+        let ix = s.swap_and_bridge_ix(
+            None,
+            0,
+            bridge_to_args(adaptor_id, SwapType::SWAPANDBRIDGE),
+            remaining_accounts,
+        );
+        s.h.process(&[ix], &[]).await.unwrap();
+        assert_eq!(s.h.bridge_adaptor_accounts_len(record).await, len as u64, "{adaptor_id:?}");
+        assert!(s.h.balance(s.bridge_vault).await > 0);
+    }
+}
+
+#[tokio::test]
+async fn test_swap_and_bridge_rejects_invalid_args() {
+    let mut s = setup().await;
+    let mut remaining_accounts = s.swap_accounts();
+    remaining_accounts.extend(s.meson_accounts());
+
+    let ix = s.swap_and_bridge_ix(
+        None,
+        0,
+        bridge_to_args(AdaptorID::Meson, SwapType::BRIDGE),
+        remaining_accounts.clone(),
+    );
+    let result = s.h.process(&[ix], &[]).await;
+    assert_eq!(error_code(result), Some(ErrorCode::InvalidBridgeArgs.into()));
+
+    let mut args = bridge_to_args(AdaptorID::Meson, SwapType::SWAPANDBRIDGE);
+    args.to = vec![];
+    let ix = s.swap_and_bridge_ix(None, 0, args, remaining_accounts);
+    let result = s.h.process(&[ix], &[]).await;
+    assert_eq!(error_code(result), Some(ErrorCode::InvalidBridgeArgs.into()));
+}
+
+/// Tokens bridged in and held by `bridge_authority`, to be delivered to `recipient`
+struct Inbound {
+    h: Harness,
+    mint_a: Pubkey,
+    mint_b: Pubkey,
+    pool: common::TokenSwapPool,
+    bridge_authority: Pubkey,
+    bridge_token_account: Pubkey,
+    recipient: Pubkey,
+    recipient_source: Pubkey,
+    recipient_destination: Pubkey,
+}
+
+async fn inbound(bridge_authority: Pubkey) -> Inbound {
+    let mut h = Harness::new().await;
+    let (mint_a, mint_b) = (h.create_mint(), h.create_mint());
+    let pool = h.create_token_swap_pool(mint_a, mint_b, 1_000_000_000, 1_000_000_000);
+    let bridge_token_account = h.create_token_account(mint_a, bridge_authority, 1_000_000);
+    let recipient = Pubkey::new_unique();
+    let recipient_source = h.create_token_account(mint_a, recipient, 0);
+    let recipient_destination = h.create_token_account(mint_b, recipient, 0);
+    Inbound {
+        h,
+        mint_a,
+        mint_b,
+        pool,
+        bridge_authority,
+        bridge_token_account,
+        recipient,
+        recipient_source,
+        recipient_destination,
+    }
+}
+
+impl Inbound {
+    fn receive_and_swap_ix(&self, args: SwapArgs) -> Instruction {
+        let remaining_accounts = self.pool.accounts(
+            self.bridge_authority,
+            self.bridge_token_account,
+            self.recipient_destination,
+        );
+        self.receive_and_swap_ix_with(args, remaining_accounts)
+    }
+
+    fn receive_and_swap_ix_with(
+        &self,
+        args: SwapArgs,
+        remaining_accounts: Vec<AccountMeta>,
+    ) -> Instruction {
+        router_instruction(
+            accounts::ToSwapAccounts {
+                bridge_authority: self.bridge_authority,
+                bridge_token_account: self.bridge_token_account,
+                recipient: self.recipient,
+                recipient_source_token_account: self.recipient_source,
+                destination_token_account: self.recipient_destination,
+                source_mint: self.mint_a,
+                destination_mint: self.mint_b,
+                commission_account: None,
+                platform_fee_account: None,
+                sa_authority: None,
+                source_token_sa: None,
+                destination_token_sa: None,
+                source_token_program: spl_token::ID,
+                destination_token_program: spl_token::ID,
+                associated_token_program: None,
+                system_program: None,
+                instructions_sysvar: sysvar::instructions::ID,
+            },
+            instruction::ReceiveAndSwap {
+                args,
+                commission_info: 0,
+                platform_fee_rate: 0,
+                order_id: ORDER_ID,
+            },
+            remaining_accounts,
+        )
+    }
+}
+
+#[tokio::test]
+async fn test_receive_and_swap() {
+    let mut s = inbound(mock_bridge::bridge_authority().0).await;
+
+    let ix = mock_bridge::receive_instruction(s.receive_and_swap_ix(single_route(1_000_000, 1, 1)));
+    s.h.process(&[ix], &[]).await.unwrap();
+    assert_eq!(s.h.balance(s.bridge_token_account).await, 0);
+    assert_eq!(s.h.balance(s.recipient_source).await, 0);
+    assert_eq!(
+        s.h.balance(s.recipient_destination).await,
+        mock_token_swap::quote(1_000_000_000, 1_000_000_000, 1_000_000)
+    );
+}
+
+#[tokio::test]
+async fn test_receive_and_swap_falls_back_below_min_return() {
+    let mut s = inbound(mock_bridge::bridge_authority().0).await;
+
+    // The bridge quoted the route below the recipient's min_return
+    let ix = mock_bridge::receive_instruction(
+        s.receive_and_swap_ix(single_route(1_000_000, 900_000, 990_000)),
+    );
+    s.h.process(&[ix], &[]).await.unwrap();
+    assert_eq!(s.h.balance(s.bridge_token_account).await, 0);
+    assert_eq!(s.h.balance(s.recipient_source).await, 1_000_000);
+    assert_eq!(s.h.balance(s.recipient_destination).await, 0);
+}
+
+#[tokio::test]
+async fn test_receive_and_swap_falls_back_when_pool_returns_short() {
+    let mut s = inbound(mock_bridge::bridge_authority().0).await;
+    // The bridge quoted the route at min_return, the pool now pays less
+    let amount_out = mock_token_swap::quote(1_000_000_000, 1_000_000_000, 1_000_000);
+    let min_return = amount_out + 1;
+
+    let ix = mock_bridge::receive_instruction(
+        s.receive_and_swap_ix(single_route(1_000_000, min_return, min_return)),
+    );
+    s.h.process(&[ix], &[]).await.unwrap();
+    assert_eq!(s.h.balance(s.bridge_token_account).await, 0);
+    assert_eq!(s.h.balance(s.recipient_source).await, 1_000_000);
+    assert_eq!(s.h.balance(s.recipient_destination).await, 0);
+    assert_eq!(s.h.balance(s.pool.token_b_account).await, 1_000_000_000);
+}
+
+#[tokio::test]
+async fn test_receive_and_swap_at_min_return() {
+    let mut s = inbound(mock_bridge::bridge_authority().0).await;
+    let amount_out = mock_token_swap::quote(1_000_000_000, 1_000_000_000, 1_000_000);
+
+    let ix = mock_bridge::receive_instruction(
+        s.receive_and_swap_ix(single_route(1_000_000, amount_out, amount_out)),
+    );
+    s.h.process(&[ix], &[]).await.unwrap();
+    assert_eq!(s.h.balance(s.recipient_source).await, 0);
+    assert_eq!(s.h.balance(s.recipient_destination).await, amount_out);
+}
+
+#[tokio::test]
+async fn test_receive_and_swap_rejects_route_without_quote() {
+    let mut s = inbound(mock_bridge::bridge_authority().0).await;
+    let curve = s.h.create_bonding_curve(s.mint_a, s.mint_b, 100_000_000, 1_000_000_000);
+
+    // Bonding curves have no on-chain quote, the fallback could not be decided
+    let mut args = single_route(1_000_000, 1, 1);
+    args.routes[0][0].dexes = vec![Dex::VertigoBuy];
+    let remaining_accounts =
+        curve.accounts(s.bridge_authority, s.bridge_token_account, s.recipient_destination);
+    let ix = mock_bridge::receive_instruction(s.receive_and_swap_ix_with(args, remaining_accounts));
+    let result = s.h.process(&[ix], &[]).await;
+    assert_eq!(error_code(result), Some(ErrorCode::RouteNotQuotable.into()));
+    assert_eq!(s.h.balance(s.bridge_token_account).await, 1_000_000);
+}
+
+#[tokio::test]
+async fn test_receive_and_swap_requires_bridge_caller() {
+    let bridge_authority = Keypair::new();
+    let mut s = inbound(bridge_authority.pubkey()).await;
+
+    let ix = s.receive_and_swap_ix(single_route(1_000_000, 1, 1));
+    let result = s.h.process(&[ix], &[&bridge_authority]).await;
+    assert_eq!(error_code(result), Some(ErrorCode::InvalidBridgeCaller.into()));
+    assert_eq!(s.h.balance(s.bridge_token_account).await, 1_000_000);
+}
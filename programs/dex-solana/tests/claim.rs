@@ -0,0 +1,329 @@
+mod common;
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::system_program;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::associated_token::spl_associated_token_account;
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022;
+use common::{Harness, error_code, router_instruction};
+use dex_solana::constants::CLAIM_CONFIG_SEED;
+use dex_solana::error::ErrorCode;
+use dex_solana::{accounts, authority_pda, instruction};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
+use solana_sdk::system_instruction;
+
+struct Setup {
+    h: Harness,
+    claim_config: Pubkey,
+    claim_authority: Keypair,
+    mint: Pubkey,
+    source_token_account: Pubkey,
+}
+
+/// The payer is the claim config admin
+async fn setup() -> Setup {
+    let mut h = Harness::new().await;
+    let claim_authority = Keypair::new();
+    let claim_config = h.create_claim_config(h.payer(), claim_authority.pubkey());
+    let mint = h.create_mint();
+    let source_token_account = h.create_token_account(mint, authority_pda::ID, 1_000);
+    Setup { h, claim_config, claim_authority, mint, source_token_account }
+}
+
+impl Setup {
+    fn claim_ix(&self, signer: Pubkey, receiver: Pubkey) -> Instruction {
+        router_instruction(
+            accounts::ClaimAccounts {
+                signer,
+                receiver,
+                source_token_account: Some(self.source_token_account),
+                destination_token_account: Some(Pubkey::new_unique()),
+                sa_authority: authority_pda::ID,
+                token_mint: Some(self.mint),
+                token_program: Some(spl_token::ID),
+                system_program: system_program::ID,
+                associated_token_program: None,
+                claim_config: self.claim_config,
+            },
+            instruction::Claim {},
+            vec![],
+        )
+    }
+
+    fn batch_claim_ix(
+        &self,
+        receiver: Pubkey,
+        remaining_accounts: Vec<AccountMeta>,
+        close_empty: bool,
+    ) -> Instruction {
+        router_instruction(
+            accounts::BatchClaimAccounts {
+                signer: self.claim_authority.pubkey(),
+                receiver,
+                sa_authority: authority_pda::ID,
+                system_program: system_program::ID,
+                associated_token_program: spl_associated_token_account::ID,
+                claim_config: self.claim_config,
+            },
+            instruction::BatchClaim { close_empty },
+            remaining_accounts,
+        )
+    }
+
+    fn accept_ix(&self, pending_admin: Pubkey) -> Instruction {
+        router_instruction(
+            accounts::AcceptClaimConfigAdmin { pending_admin, claim_config: self.claim_config },
+            instruction::AcceptClaimConfigAdmin {},
+            vec![],
+        )
+    }
+
+    fn update_ix(&self, admin: Pubkey, data: impl anchor_lang::InstructionData) -> Instruction {
+        router_instruction(
+            accounts::UpdateClaimConfig { admin, claim_config: self.claim_config },
+            data,
+            vec![],
+        )
+    }
+}
+
+#[tokio::test]
+async fn test_claim_rejects_other_signer() {
+    let mut s = setup().await;
+    let signer = Keypair::new();
+    let ix = s.claim_ix(signer.pubkey(), Pubkey::new_unique());
+    let result = s.h.process(&[ix], &[&signer]).await;
+    assert_eq!(error_code(result), Some(ErrorCode::InvalidSigner.into()));
+}
+
+#[tokio::test]
+async fn test_claim_checks_receivers_and_pause() {
+    let mut s = setup().await;
+    let (admin, receiver) = (s.h.payer(), Pubkey::new_unique());
+    let ix = s.update_ix(admin, instruction::AddClaimReceiver { receiver });
+    s.h.process(&[ix], &[]).await.unwrap();
+    assert_eq!(s.h.claim_config(s.claim_config).await.receivers[0], receiver);
+
+    let claim_authority = s.claim_authority.insecure_clone();
+    let ix = s.claim_ix(claim_authority.pubkey(), Pubkey::new_unique());
+    let result = s.h.process(&[ix], &[&claim_authority]).await;
+    assert_eq!(error_code(result), Some(ErrorCode::InvalidClaimReceiver.into()));
+
+    let ix = s.update_ix(admin, instruction::PauseClaim {});
+    s.h.process(&[ix], &[]).await.unwrap();
+    let ix = s.claim_ix(claim_authority.pubkey(), receiver);
+    let result = s.h.process(&[ix], &[&claim_authority]).await;
+    assert_eq!(error_code(result), Some(ErrorCode::ClaimPaused.into()));
+}
+
+#[tokio::test]
+async fn test_set_claim_authority() {
+    let mut s = setup().await;
+    let new_claim_authority = Keypair::new();
+    let ix = s.update_ix(
+        s.h.payer(),
+        instruction::SetClaimAuthority { claim_authority: new_claim_authority.pubkey() },
+    );
+    s.h.process(&[ix], &[]).await.unwrap();
+    assert_eq!(
+        s.h.claim_config(s.claim_config).await.claim_authority,
+        new_claim_authority.pubkey()
+    );
+
+    // The previous claim authority is rotated out
+    let claim_authority = s.claim_authority.insecure_clone();
+    let ix = s.claim_ix(claim_authority.pubkey(), Pubkey::new_unique());
+    let result = s.h.process(&[ix], &[&claim_authority]).await;
+    assert_eq!(error_code(result), Some(ErrorCode::InvalidSigner.into()));
+
+    // Only the admin updates the config
+    let ix = s.update_ix(
+        claim_authority.pubkey(),
+        instruction::SetClaimAuthority { claim_authority: claim_authority.pubkey() },
+    );
+    let result = s.h.process(&[ix], &[&claim_authority]).await;
+    assert_eq!(error_code(result), Some(anchor_lang::error::ErrorCode::ConstraintHasOne.into()));
+}
+
+#[tokio::test]
+async fn test_propose_and_accept_claim_config_admin() {
+    let mut s = setup().await;
+    let new_admin = Keypair::new();
+    let ix = s.update_ix(
+        s.h.payer(),
+        instruction::ProposeClaimConfigAdmin { pending_admin: new_admin.pubkey() },
+    );
+    s.h.process(&[ix], &[]).await.unwrap();
+    let config = s.h.claim_config(s.claim_config).await;
+    assert_eq!(config.admin, s.h.payer());
+    assert_eq!(config.pending_admin, new_admin.pubkey());
+
+    // Only the proposed admin accepts
+    let other = Keypair::new();
+    let result = s.h.process(&[s.accept_ix(other.pubkey())], &[&other]).await;
+    assert_eq!(error_code(result), Some(ErrorCode::InvalidPendingAdmin.into()));
+
+    s.h.process(&[s.accept_ix(new_admin.pubkey())], &[&new_admin]).await.unwrap();
+    let config = s.h.claim_config(s.claim_config).await;
+    assert_eq!(config.admin, new_admin.pubkey());
+    assert_eq!(config.pending_admin, Pubkey::default());
+
+    // The previous admin is rotated out
+    let ix = s.update_ix(s.h.payer(), instruction::PauseClaim {});
+    let result = s.h.process(&[ix], &[]).await;
+    assert_eq!(error_code(result), Some(anchor_lang::error::ErrorCode::ConstraintHasOne.into()));
+}
+
+#[tokio::test]
+async fn test_cancel_claim_config_admin_transfer() {
+    let mut s = setup().await;
+    let new_admin = Keypair::new();
+
+    let ix = s.update_ix(s.h.payer(), instruction::CancelClaimConfigAdminTransfer {});
+    let result = s.h.process(&[ix], &[]).await;
+    assert_eq!(error_code(result), Some(ErrorCode::NoPendingAdmin.into()));
+
+    let ix = s.update_ix(
+        s.h.payer(),
+        instruction::ProposeClaimConfigAdmin { pending_admin: new_admin.pubkey() },
+    );
+    s.h.process(&[ix], &[]).await.unwrap();
+    let ix = s.update_ix(s.h.payer(), instruction::CancelClaimConfigAdminTransfer {});
+    s.h.process(&[ix], &[]).await.unwrap();
+    assert_eq!(s.h.claim_config(s.claim_config).await.pending_admin, Pubkey::default());
+
+    let result = s.h.process(&[s.accept_ix(new_admin.pubkey())], &[&new_admin]).await;
+    assert_eq!(error_code(result), Some(ErrorCode::NoPendingAdmin.into()));
+}
+
+#[tokio::test]
+async fn test_claim_before_claim_config_init() {
+    let mut h = Harness::new().await;
+    let mint = h.create_mint();
+    let source_token_account = h.create_token_account(mint, authority_pda::ID, 1_000);
+    let claim_config =
+        Pubkey::find_program_address(&[CLAIM_CONFIG_SEED.as_bytes()], &dex_solana::ID).0;
+    let claim_authority = Keypair::new();
+    let mut s = Setup { h, claim_config, claim_authority, mint, source_token_account };
+
+    // Only the `claim_authority` constant claims until the config is initialized
+    let claim_authority = s.claim_authority.insecure_clone();
+    let ix = s.claim_ix(claim_authority.pubkey(), Pubkey::new_unique());
+    let result = s.h.process(&[ix], &[&claim_authority]).await;
+    assert_eq!(error_code(result), Some(ErrorCode::InvalidSigner.into()));
+
+    // The claim config must be the PDA
+    s.claim_config = Pubkey::new_unique();
+    let ix = s.claim_ix(claim_authority.pubkey(), Pubkey::new_unique());
+    let result = s.h.process(&[ix], &[&claim_authority]).await;
+    assert_eq!(error_code(result), Some(anchor_lang::error::ErrorCode::ConstraintSeeds.into()));
+}
+
+/// Remaining accounts of one batch claim tuple
+fn claim_tuple(
+    source_token_account: Pubkey,
+    destination_token_account: Pubkey,
+    mint: Pubkey,
+    token_program: Pubkey,
+) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(source_token_account, false),
+        AccountMeta::new(destination_token_account, false),
+        AccountMeta::new_readonly(mint, false),
+        AccountMeta::new_readonly(token_program, false),
+    ]
+}
+
+/// Funds the claim authority, which pays for the receiver ATAs batch claim creates
+fn fund_ix(s: &Setup) -> Instruction {
+    system_instruction::transfer(&s.h.payer(), &s.claim_authority.pubkey(), 1_000_000_000)
+}
+
+#[tokio::test]
+async fn test_batch_claim_multiple_accounts() {
+    let mut s = setup().await;
+    let receiver = Pubkey::new_unique();
+    let destination = s.h.create_token_account(s.mint, receiver, 0);
+    // A Token-2022 mint without fee, so the claimed amounts stay exact
+    let mint_2022 = s.h.create_transfer_fee_mint(0);
+    let source_2022 = s.h.create_token_2022_account(mint_2022, authority_pda::ID, 2_000).await;
+    // The receiver has no account of the Token-2022 mint yet, batch claim creates its ATA
+    let destination_2022 =
+        get_associated_token_address_with_program_id(&receiver, &mint_2022, &spl_token_2022::ID);
+
+    let mut remaining_accounts =
+        claim_tuple(s.source_token_account, destination, s.mint, spl_token::ID);
+    remaining_accounts.extend(claim_tuple(
+        source_2022,
+        destination_2022,
+        mint_2022,
+        spl_token_2022::ID,
+    ));
+    let claim_authority = s.claim_authority.insecure_clone();
+    let ix = s.batch_claim_ix(receiver, remaining_accounts, false);
+    s.h.process(&[fund_ix(&s), ix], &[&claim_authority]).await.unwrap();
+
+    assert_eq!(s.h.balance(destination).await, 1_000);
+    assert_eq!(s.h.balance(destination_2022).await, 2_000);
+    assert_eq!(s.h.balance(s.source_token_account).await, 0);
+    assert_eq!(s.h.balance(source_2022).await, 0);
+}
+
+#[tokio::test]
+async fn test_batch_claim_close_empty() {
+    let mut s = setup().await;
+    let receiver = Pubkey::new_unique();
+    let destination = s.h.create_token_account(s.mint, receiver, 0);
+    let rent = s.h.lamports(s.source_token_account).await;
+
+    let claim_authority = s.claim_authority.insecure_clone();
+    let remaining_accounts =
+        claim_tuple(s.source_token_account, destination, s.mint, spl_token::ID);
+    let ix = s.batch_claim_ix(receiver, remaining_accounts, true);
+    s.h.process(&[ix], &[&claim_authority]).await.unwrap();
+
+    // The emptied SA account is closed and its rent goes to the receiver
+    assert_eq!(s.h.balance(destination).await, 1_000);
+    assert_eq!(s.h.lamports(s.source_token_account).await, 0);
+    assert_eq!(s.h.lamports(receiver).await, rent);
+}
+
+#[tokio::test]
+async fn test_batch_claim_rejects_partial_tuple() {
+    let mut s = setup().await;
+    let receiver = Pubkey::new_unique();
+    let destination = s.h.create_token_account(s.mint, receiver, 0);
+    let claim_authority = s.claim_authority.insecure_clone();
+
+    let mut remaining_accounts =
+        claim_tuple(s.source_token_account, destination, s.mint, spl_token::ID);
+    remaining_accounts.push(AccountMeta::new(Pubkey::new_unique(), false));
+    let ix = s.batch_claim_ix(receiver, remaining_accounts, false);
+    let result = s.h.process(&[ix], &[&claim_authority]).await;
+    assert_eq!(error_code(result), Some(ErrorCode::InvalidAccountsLength.into()));
+
+    let ix = s.batch_claim_ix(receiver, vec![], false);
+    let result = s.h.process(&[ix], &[&claim_authority]).await;
+    assert_eq!(error_code(result), Some(ErrorCode::InvalidAccountsLength.into()));
+    assert_eq!(s.h.balance(s.source_token_account).await, 1_000);
+}
+
+#[tokio::test]
+async fn test_batch_claim_rejects_transfer_hook_mint() {
+    let mut s = setup().await;
+    let mint = s.h.create_transfer_hook_mint();
+    let source_token_account = s.h.create_token_2022_account(mint, authority_pda::ID, 1_000).await;
+    let receiver = Pubkey::new_unique();
+    let destination = s.h.create_token_2022_account(mint, receiver, 0).await;
+
+    let claim_authority = s.claim_authority.insecure_clone();
+    let remaining_accounts =
+        claim_tuple(source_token_account, destination, mint, spl_token_2022::ID);
+    let ix = s.batch_claim_ix(receiver, remaining_accounts, false);
+    let result = s.h.process(&[ix], &[&claim_authority]).await;
+    assert_eq!(error_code(result), Some(ErrorCode::TransferHookMintNotSupported.into()));
+    assert_eq!(s.h.balance(source_token_account).await, 1_000);
+}
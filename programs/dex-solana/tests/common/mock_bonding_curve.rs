@@ -0,0 +1,73 @@
+//! Bonding curve with a virtual quote reserve speaking the Vertigo `buy` / `sell` instructions.
+//! The `owner` account is the pool authority owning both vaults.
+use super::{pool_authority, read_amount, transfer_checked};
+use anchor_lang::solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use dex_solana::{VERTIGO_BUY_SELECTOR, VERTIGO_SELL_SELECTOR};
+
+/// Pool data: virtual quote reserve added on top of the quote vault balance
+pub const POOL_LEN: usize = 8;
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let [
+        pool,
+        user,
+        owner,
+        mint_a,
+        mint_b,
+        user_a,
+        user_b,
+        vault_a,
+        vault_b,
+        token_program_a,
+        token_program_b,
+        _system_program,
+        _program,
+    ] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if data.len() != 24 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let amount_in = u64::from_le_bytes(data[8..16].try_into().unwrap());
+    let minimum_amount_out = u64::from_le_bytes(data[16..24].try_into().unwrap());
+
+    let (authority, bump) = pool_authority(program_id, pool.key);
+    if *owner.key != authority {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let seeds: &[&[u8]] = &[pool.key.as_ref(), &[bump]];
+    let virtual_a = u64::from_le_bytes(pool.try_borrow_data()?[..POOL_LEN].try_into().unwrap());
+    let reserve_a = read_amount(vault_a)? + virtual_a;
+    let reserve_b = read_amount(vault_b)?;
+
+    if data[..8] == *VERTIGO_BUY_SELECTOR {
+        let amount_out = quote(reserve_a, reserve_b, amount_in);
+        if amount_out < minimum_amount_out {
+            return Err(ProgramError::Custom(1));
+        }
+        transfer_checked(token_program_a, user_a, mint_a, vault_a, user, amount_in, &[])?;
+        transfer_checked(token_program_b, vault_b, mint_b, user_b, owner, amount_out, seeds)
+    } else if data[..8] == *VERTIGO_SELL_SELECTOR {
+        // The virtual reserve can not be paid out
+        let amount_out = quote(reserve_b, reserve_a, amount_in).min(read_amount(vault_a)?);
+        if amount_out < minimum_amount_out {
+            return Err(ProgramError::Custom(1));
+        }
+        transfer_checked(token_program_b, user_b, mint_b, vault_b, user, amount_in, &[])?;
+        transfer_checked(token_program_a, vault_a, mint_a, user_a, owner, amount_out, seeds)
+    } else {
+        Err(ProgramError::InvalidInstructionData)
+    }
+}
+
+pub fn quote(reserve_in: u64, reserve_out: u64, amount_in: u64) -> u64 {
+    (reserve_out as u128 * amount_in as u128 / (reserve_in as u128 + amount_in as u128)) as u64
+}
@@ -0,0 +1,84 @@
+//! Bridge program stand-in. `bridge_to_log` locks the bridged amount in the first adaptor
+//! account, a token account of the bridged mint, and records how many adaptor accounts it got in
+//! the last one when that is a bridge-owned record account. `RECEIVE_SELECTOR`
+//! forwards the rest of its data to the router, signed by the bridge authority, as the bridge
+//! does when delivering inbound tokens.
+use super::transfer;
+use anchor_lang::AnchorDeserialize;
+use anchor_lang::solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use dex_solana::{BRIDGE_TO_LOG_SELECTOR, BridgeToArgs, okx_bridge_program};
+
+/// payer, token account, mint, associated token, token, token 2022 and system programs
+pub const FIXED_ACCOUNTS_LEN: usize = 7;
+
+pub const RECEIVE_SELECTOR: &[u8; 8] = &[7; 8];
+
+const BRIDGE_AUTHORITY_SEED: &[u8] = b"bridge_authority";
+
+/// PDA owning the bridged tokens
+pub fn bridge_authority() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BRIDGE_AUTHORITY_SEED], &okx_bridge_program::ID)
+}
+
+/// Router instruction `router_ix` delivered through the bridge: the bridge authority signs the
+/// CPI and the router program leads the accounts
+pub fn receive_instruction(router_ix: Instruction) -> Instruction {
+    let (authority, _) = bridge_authority();
+    let mut accounts = vec![AccountMeta::new_readonly(router_ix.program_id, false)];
+    accounts.extend(router_ix.accounts.into_iter().map(|mut meta| {
+        meta.is_signer &= meta.pubkey != authority;
+        meta
+    }));
+    let mut data = RECEIVE_SELECTOR.to_vec();
+    data.extend(router_ix.data);
+    Instruction { program_id: okx_bridge_program::ID, accounts, data }
+}
+
+fn receive(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [router, accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let (authority, bump) = bridge_authority();
+    let metas = accounts
+        .iter()
+        .map(|account| AccountMeta {
+            pubkey: *account.key,
+            is_signer: account.is_signer || *account.key == authority,
+            is_writable: account.is_writable,
+        })
+        .collect();
+    let ix = Instruction { program_id: *router.key, accounts: metas, data: data.to_vec() };
+    invoke_signed(&ix, accounts, &[&[BRIDGE_AUTHORITY_SEED, &[bump]]])
+}
+
+pub fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    if data.len() >= 8 && data[..8] == *RECEIVE_SELECTOR {
+        return receive(accounts, &data[8..]);
+    }
+    if data.len() < 8 || data[..8] != *BRIDGE_TO_LOG_SELECTOR {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let args = BridgeToArgs::deserialize(&mut &data[8..])?;
+    let [payer, token_account, _mint, _associated_token_program, token_program, _, _, vault, ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let record = &accounts[accounts.len() - 1];
+    if record.owner == &okx_bridge_program::ID && record.is_writable {
+        let adaptor_accounts_len = (accounts.len() - FIXED_ACCOUNTS_LEN) as u64;
+        record.try_borrow_mut_data()?[..8].copy_from_slice(&adaptor_accounts_len.to_le_bytes());
+    }
+    transfer(token_program, token_account, vault, payer, args.amount, &[])
+}
@@ -0,0 +1,126 @@
+//! Single-range concentrated liquidity pool speaking the Whirlpool `swap` instruction.
+//! The pool account is a PDA owning both vaults and stops at the sqrt price limit,
+//! leaving the rest of the input unspent.
+use super::transfer;
+use anchor_lang::solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use dex_solana::SWAP_SELECTOR;
+
+/// Pool data: sqrt_price (x64), liquidity, pda seed, bump
+pub const POOL_LEN: usize = 16 + 16 + 32 + 1;
+pub const SEED_POOL: &[u8] = b"pool";
+
+pub fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let [
+        token_program,
+        authority,
+        pool,
+        owner_a,
+        vault_a,
+        owner_b,
+        vault_b,
+        _tick_array0,
+        _tick_array1,
+        _tick_array2,
+        _oracle,
+    ] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if data.len() != 42 || data[..8] != *SWAP_SELECTOR {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let amount = u64::from_le_bytes(data[8..16].try_into().unwrap());
+    let threshold = u64::from_le_bytes(data[16..24].try_into().unwrap());
+    let sqrt_price_limit = i128::from_le_bytes(data[24..40].try_into().unwrap()) as u128;
+    let amount_specified_is_input = data[40] == 1;
+    let a_to_b = data[41] == 1;
+
+    let (sqrt_price, liquidity, seed, bump) = {
+        let pool_data = pool.try_borrow_data()?;
+        (
+            u128::from_le_bytes(pool_data[..16].try_into().unwrap()),
+            u128::from_le_bytes(pool_data[16..32].try_into().unwrap()),
+            Pubkey::new_from_array(pool_data[32..64].try_into().unwrap()),
+            pool_data[64],
+        )
+    };
+    let (amount_in, amount_out, next_sqrt_price) = swap_step(
+        sqrt_price,
+        liquidity,
+        amount,
+        amount_specified_is_input,
+        a_to_b,
+        sqrt_price_limit,
+    )
+    .ok_or(ProgramError::ArithmeticOverflow)?;
+    if amount_specified_is_input && amount_out < threshold
+        || !amount_specified_is_input && amount_in > threshold
+    {
+        return Err(ProgramError::Custom(0x1794)); // AmountOutBelowMinimum / AmountInAboveMaximum
+    }
+    pool.try_borrow_mut_data()?[..16].copy_from_slice(&next_sqrt_price.to_le_bytes());
+
+    let seeds: &[&[u8]] = &[SEED_POOL, seed.as_ref(), &[bump]];
+    if a_to_b {
+        transfer(token_program, owner_a, vault_a, authority, amount_in, &[])?;
+        transfer(token_program, vault_b, owner_b, pool, amount_out, seeds)
+    } else {
+        transfer(token_program, owner_b, vault_b, authority, amount_in, &[])?;
+        transfer(token_program, vault_a, owner_a, pool, amount_out, seeds)
+    }
+}
+
+/// Returns (amount_in, amount_out, next_sqrt_price) of a swap within one liquidity range
+pub fn swap_step(
+    sqrt_price: u128,
+    liquidity: u128,
+    amount: u64,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+    sqrt_price_limit: u128,
+) -> Option<(u64, u64, u128)> {
+    let amount = amount as u128;
+    // Token a amount between two prices: liquidity * (1 / lower - 1 / upper)
+    let delta_a = |lower: u128, upper: u128| -> Option<u128> {
+        Some(((liquidity.checked_mul(upper - lower)? / upper) << 64) / lower)
+    };
+    // Token b amount between two prices: liquidity * (upper - lower)
+    let delta_b = |lower: u128, upper: u128| -> Option<u128> {
+        Some(liquidity.checked_mul(upper - lower)? >> 64)
+    };
+
+    let (amount_in, amount_out, next) = if a_to_b {
+        let target = if amount_specified_is_input {
+            liquidity.checked_mul(sqrt_price)? / (liquidity + ((amount * sqrt_price) >> 64))
+        } else {
+            // Round the price move up so the output covers the requested amount
+            sqrt_price.checked_sub((amount << 64).div_ceil(liquidity))?
+        };
+        let next = target.max(sqrt_price_limit);
+        (delta_a(next, sqrt_price)? + 1, delta_b(next, sqrt_price)?, next)
+    } else {
+        let target = if amount_specified_is_input {
+            sqrt_price + (amount << 64) / liquidity
+        } else {
+            let denominator = liquidity.checked_sub(((amount * sqrt_price) >> 64) + 1)?;
+            liquidity.checked_mul(sqrt_price)?.div_ceil(denominator)
+        };
+        let next = if sqrt_price_limit == 0 { target } else { target.min(sqrt_price_limit) };
+        (delta_b(sqrt_price, next)? + 1, delta_a(sqrt_price, next)?, next)
+    };
+    let (amount_in, amount_out) = if next == sqrt_price {
+        (0, 0)
+    } else if amount_specified_is_input {
+        (amount_in.min(amount), amount_out)
+    } else {
+        (amount_in, amount_out.min(amount))
+    };
+    Some((u64::try_from(amount_in).ok()?, u64::try_from(amount_out).ok()?, next))
+}
@@ -0,0 +1,75 @@
+//! Single price level book speaking the Manifest `Swap` instruction.
+//! The market account is a PDA owning both vaults. Like Manifest, a swap filling below
+//! `out_atoms` fails as a whole instead of partially filling.
+use super::transfer;
+use anchor_lang::solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// Market data: price in quote atoms per base atom scaled by `PRICE_SCALE`, pda seed, bump
+pub const MARKET_LEN: usize = 41;
+pub const SEED_MARKET: &[u8] = b"market";
+pub const PRICE_SCALE: u128 = 1_000_000_000_000;
+/// Custom error of a swap short of `out_atoms`
+pub const INSUFFICIENT_OUT: u32 = 1;
+
+pub fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let [
+        trader,
+        market,
+        _system_program,
+        base_account,
+        quote_account,
+        base_vault,
+        quote_vault,
+        token_program,
+        _base_mint,
+        _quote_token_program,
+        _quote_mint,
+        _global,
+        _global_vault,
+    ] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    // SwapParams { in_atoms, out_atoms, is_base_in, is_exact_in: true }
+    let [4, params @ .., 1] = data else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+    let in_atoms = u64::from_le_bytes(params[..8].try_into().unwrap());
+    let out_atoms = u64::from_le_bytes(params[8..16].try_into().unwrap());
+    let is_base_in = params[16] == 1;
+
+    let market_data = market.try_borrow_data()?.to_vec();
+    let price = u64::from_le_bytes(market_data[..8].try_into().unwrap()) as u128;
+    let seeds: &[&[u8]] = &[SEED_MARKET, &market_data[8..40], &market_data[40..41]];
+
+    let (filled_out, trader_in, vault_in, vault_out, trader_out) = if is_base_in {
+        (
+            in_atoms as u128 * price / PRICE_SCALE,
+            base_account,
+            base_vault,
+            quote_vault,
+            quote_account,
+        )
+    } else {
+        (
+            in_atoms as u128 * PRICE_SCALE / price,
+            quote_account,
+            quote_vault,
+            base_vault,
+            base_account,
+        )
+    };
+    let filled_out = filled_out as u64;
+    if filled_out < out_atoms {
+        return Err(ProgramError::Custom(INSUFFICIENT_OUT));
+    }
+    transfer(token_program, trader_in, vault_in, trader, in_atoms, &[])?;
+    transfer(token_program, vault_out, trader_out, market, filled_out, seeds)
+}
@@ -0,0 +1,53 @@
+//! Multisig stand-in executing an instruction with its PDA as signer, as a multisig program
+//! does once a proposal is approved. The first account is the program to call.
+use anchor_lang::prelude::AccountMeta;
+use anchor_lang::solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, instruction::Instruction,
+    program::invoke_signed, program_error::ProgramError, pubkey::Pubkey,
+};
+
+pub const ID: Pubkey = Pubkey::new_from_array([7; 32]);
+
+const VAULT_SEED: &[u8] = b"vault";
+
+/// PDA signing the executed instructions
+pub fn vault() -> Pubkey {
+    Pubkey::find_program_address(&[VAULT_SEED], &ID).0
+}
+
+/// Wraps an instruction signed by the vault into a multisig execution
+pub fn execute(instruction: Instruction) -> Instruction {
+    let vault = vault();
+    let mut accounts = vec![AccountMeta::new_readonly(instruction.program_id, false)];
+    accounts.extend(instruction.accounts.into_iter().map(|mut account| {
+        if account.pubkey == vault {
+            account.is_signer = false;
+        }
+        account
+    }));
+    Instruction { program_id: ID, accounts, data: instruction.data }
+}
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let [program, instruction_accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let (vault, bump) = Pubkey::find_program_address(&[VAULT_SEED], program_id);
+    let instruction = Instruction {
+        program_id: *program.key,
+        accounts: instruction_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: *account.key,
+                is_signer: account.is_signer || *account.key == vault,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data: data.to_vec(),
+    };
+    invoke_signed(&instruction, accounts, &[&[VAULT_SEED, &[bump]]])
+}
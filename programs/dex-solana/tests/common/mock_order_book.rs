@@ -0,0 +1,68 @@
+//! Single price level book speaking the Phoenix `Swap` instruction.
+//! The market account is a PDA owning both vaults and fills whole lots only.
+use super::transfer;
+use anchor_lang::solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+/// Market data: price in quote atoms per base lot, pda seed, bump,
+/// with the lot sizes at the offsets the Phoenix adapter reads
+pub const MARKET_LEN: usize = 200;
+pub const SEED_MARKET: &[u8] = b"market";
+const BASE_LOT_SIZE_OFFSET: usize = 112;
+const QUOTE_LOT_SIZE_OFFSET: usize = 192;
+
+pub fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let [
+        _program,
+        _log_authority,
+        market,
+        trader,
+        base_account,
+        quote_account,
+        base_vault,
+        quote_vault,
+        token_program,
+    ] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    // Immediate or cancel order, the price in ticks is ignored
+    let (side, data) = match data {
+        [0, 2, side, 0, rest @ ..] => (*side, rest),
+        [0, 2, side, 1, _, _, _, _, _, _, _, _, rest @ ..] => (*side, rest),
+        _ => return Err(ProgramError::InvalidInstructionData),
+    };
+    let num_base_lots = u64::from_le_bytes(data[..8].try_into().unwrap());
+    let num_quote_lots = u64::from_le_bytes(data[8..16].try_into().unwrap());
+
+    let market_data = market.try_borrow_data()?.to_vec();
+    let read =
+        |offset: usize| u64::from_le_bytes(market_data[offset..offset + 8].try_into().unwrap());
+    let (price, base_lot_size, quote_lot_size) =
+        (read(0), read(BASE_LOT_SIZE_OFFSET), read(QUOTE_LOT_SIZE_OFFSET));
+    let seeds: &[&[u8]] = &[SEED_MARKET, &market_data[8..40], &market_data[40..41]];
+
+    if side == 1 {
+        // Ask: sell whole base lots at the book price
+        transfer(
+            token_program,
+            base_account,
+            base_vault,
+            trader,
+            num_base_lots * base_lot_size,
+            &[],
+        )?;
+        transfer(token_program, quote_vault, quote_account, market, num_base_lots * price, seeds)
+    } else {
+        // Bid: buy as many base lots as the quote lots pay for
+        let base_lots = num_quote_lots * quote_lot_size / price;
+        transfer(token_program, quote_account, quote_vault, trader, base_lots * price, &[])?;
+        transfer(token_program, base_vault, base_account, market, base_lots * base_lot_size, seeds)
+    }
+}
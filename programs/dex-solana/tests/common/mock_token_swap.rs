@@ -0,0 +1,95 @@
+//! Constant product pool speaking the SPL token-swap `Swap` instruction, with the SPL token-swap
+//! `SwapV1` state, fee and rounding math
+use super::{pool_authority, read_amount, transfer};
+use anchor_lang::solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+pub const TRADE_FEE_BPS: u64 = 25;
+pub const OWNER_TRADE_FEE_BPS: u64 = 5;
+
+/// Versioned `SwapV1` length
+pub const SWAP_INFO_LEN: usize = 324;
+const FEES_OFFSET: usize = 227;
+const CURVE_TYPE_OFFSET: usize = 291;
+
+/// `SwapV1` state of a constant product pool charging the mock fees
+pub fn swap_info_data() -> Vec<u8> {
+    let mut data = vec![0; SWAP_INFO_LEN];
+    data[0] = 1; // version
+    data[1] = 1; // is_initialized
+    let fees = [TRADE_FEE_BPS, 10_000, OWNER_TRADE_FEE_BPS, 10_000, 0, 10_000, 0, 10_000];
+    for (i, fee) in fees.iter().enumerate() {
+        let offset = FEES_OFFSET + i * 8;
+        data[offset..offset + 8].copy_from_slice(&fee.to_le_bytes());
+    }
+    data[CURVE_TYPE_OFFSET] = 0; // constant product
+    data
+}
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let [
+        swap_info,
+        authority,
+        user_authority,
+        source,
+        pool_source,
+        pool_destination,
+        destination,
+        _pool_mint,
+        _pool_fee,
+        token_program,
+    ] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if data.len() != 17 || data[0] != 1 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let amount_in = u64::from_le_bytes(data[1..9].try_into().unwrap());
+    let minimum_amount_out = u64::from_le_bytes(data[9..17].try_into().unwrap());
+
+    let amount_out = quote(read_amount(pool_source)?, read_amount(pool_destination)?, amount_in);
+    if amount_out < minimum_amount_out {
+        return Err(ProgramError::Custom(0x10)); // ExceededSlippage
+    }
+
+    let (pool_authority, bump) = pool_authority(program_id, swap_info.key);
+    if *authority.key != pool_authority {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    transfer(token_program, source, pool_source, user_authority, amount_in, &[])?;
+    transfer(
+        token_program,
+        pool_destination,
+        destination,
+        authority,
+        amount_out,
+        &[swap_info.key.as_ref(), &[bump]],
+    )
+}
+
+pub fn quote(reserve_in: u64, reserve_out: u64, amount_in: u64) -> u64 {
+    let fee = |numerator: u64| match amount_in as u128 * numerator as u128 / 10_000 {
+        0 if numerator > 0 => 1,
+        fee => fee,
+    };
+    let amount_in = amount_in as u128 - fee(TRADE_FEE_BPS) - fee(OWNER_TRADE_FEE_BPS);
+    let (reserve_in, reserve_out) = (reserve_in as u128, reserve_out as u128);
+    let invariant = reserve_in * reserve_out;
+    let new_reserve_in = reserve_in + amount_in;
+    // `checked_ceil_div` of spl-math
+    let mut new_reserve_out = invariant / new_reserve_in;
+    if new_reserve_out == 0 {
+        return 0;
+    }
+    if invariant % new_reserve_in > 0 {
+        new_reserve_out += 1;
+    }
+    (reserve_out - new_reserve_out) as u64
+}
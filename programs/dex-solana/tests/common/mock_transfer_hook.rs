@@ -0,0 +1,53 @@
+//! Transfer hook stand-in counting the transfers of its mints. The counter is the single extra
+//! account of the mint's validation list, so a transfer only succeeds when the caller resolved it.
+use anchor_lang::solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, state::ExtraAccountMetaList};
+use spl_transfer_hook_interface::get_extra_account_metas_address;
+use spl_transfer_hook_interface::instruction::{ExecuteInstruction, TransferHookInstruction};
+
+pub const ID: Pubkey = Pubkey::new_from_array([8; 32]);
+
+const COUNTER_SEED: &[u8] = b"counter";
+
+/// Validation account listing the extra accounts of `mint`
+pub fn extra_account_metas(mint: &Pubkey) -> Pubkey {
+    get_extra_account_metas_address(mint, &ID)
+}
+
+/// Number of transfers of `mint`, as a little-endian u64
+pub fn counter(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[COUNTER_SEED, mint.as_ref()], &ID).0
+}
+
+/// Validation account data requiring the writable counter
+pub fn extra_account_metas_data(mint: &Pubkey) -> Vec<u8> {
+    let metas = [ExtraAccountMeta::new_with_pubkey(&counter(mint), false, true).unwrap()];
+    let mut data = vec![0; ExtraAccountMetaList::size_of(metas.len()).unwrap()];
+    ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, &metas).unwrap();
+    data
+}
+
+pub fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let TransferHookInstruction::Execute { .. } = TransferHookInstruction::unpack(data)? else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+    let [_source, mint, _destination, _authority, validation, counter_account, ..] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if *validation.key != extra_account_metas(mint.key) || *counter_account.key != counter(mint.key)
+    {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let mut counter_data = counter_account.try_borrow_mut_data()?;
+    let count = u64::from_le_bytes(counter_data[..8].try_into().unwrap()) + 1;
+    counter_data[..8].copy_from_slice(&count.to_le_bytes());
+    Ok(())
+}
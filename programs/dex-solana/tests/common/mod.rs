@@ -0,0 +1,847 @@
+//! In-process test harness: the router plus mock DEX programs on a local bank.
+//!
+//! The router runs natively unless `cargo test-sbf` / `SBF_OUT_DIR` provides `dex_solana.so`.
+//! The mocks always run natively at the mainnet program ids the adapters check.
+#![allow(dead_code)]
+
+pub mod mock_bonding_curve;
+pub mod mock_bridge;
+pub mod mock_clmm;
+pub mod mock_manifest;
+pub mod mock_multisig;
+pub mod mock_order_book;
+pub mod mock_token_swap;
+pub mod mock_transfer_hook;
+
+use anchor_lang::prelude::AccountMeta;
+use anchor_lang::solana_program::clock::Clock;
+use anchor_lang::solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::Instruction,
+    program::{invoke, invoke_signed},
+    program_option::COption,
+    program_pack::Pack,
+    pubkey::Pubkey,
+};
+use anchor_lang::{Discriminator, InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, BaseStateWithExtensionsMut, ExtensionType, StateWithExtensions,
+    StateWithExtensionsMut,
+    transfer_fee::{TransferFee, TransferFeeConfig},
+    transfer_hook::TransferHook,
+};
+use dex_solana::constants::{CLAIM_CONFIG_SEED, GLOBAL_CONFIG_SEED, RESOLVER_SEED};
+use dex_solana::state::config::{ClaimConfig, GlobalConfig};
+use dex_solana::state::resolver::Resolver;
+use dex_solana::utils::{PYTH_PRICE_UPDATE_DISCRIMINATOR, SWITCHBOARD_PULL_FEED_DISCRIMINATOR};
+use dex_solana::{
+    manifest_program, okx_bridge_program, phoenix_program, pyth_receiver_program,
+    spl_token_swap_program, switchboard_on_demand_program, vertigo_program, whirlpool_program,
+};
+use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext, processor};
+use solana_sdk::{
+    account::Account,
+    rent::Rent,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+    transaction::{Transaction, TransactionError},
+};
+
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    // Anchor ties the slice and account lifetimes together
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    dex_solana::entry(program_id, accounts, data)
+}
+
+/// Vault authority of the mock pools: PDA of the pool key
+pub fn pool_authority(program_id: &Pubkey, pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[pool.as_ref()], program_id)
+}
+
+pub fn read_amount(token_account: &AccountInfo) -> Result<u64, anchor_lang::prelude::ProgramError> {
+    // Token-2022 extensions follow the base account
+    let data = token_account.try_borrow_data()?;
+    Ok(spl_token::state::Account::unpack(&data[..spl_token::state::Account::LEN])?.amount)
+}
+
+pub fn transfer<'a>(
+    token_program: &AccountInfo<'a>,
+    from: &AccountInfo<'a>,
+    to: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    amount: u64,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let ix = spl_token::instruction::transfer(
+        token_program.key,
+        from.key,
+        to.key,
+        authority.key,
+        &[],
+        amount,
+    )?;
+    let account_infos = [from.clone(), to.clone(), authority.clone(), token_program.clone()];
+    if seeds.is_empty() {
+        invoke(&ix, &account_infos)
+    } else {
+        invoke_signed(&ix, &account_infos, &[seeds])
+    }
+}
+
+/// `transfer_checked`, which Token-2022 requires for mints charging a transfer fee
+pub fn transfer_checked<'a>(
+    token_program: &AccountInfo<'a>,
+    from: &AccountInfo<'a>,
+    mint: &AccountInfo<'a>,
+    to: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    amount: u64,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let decimals = {
+        let data = mint.try_borrow_data()?;
+        spl_token::state::Mint::unpack(&data[..spl_token::state::Mint::LEN])?.decimals
+    };
+    let ix = spl_token_2022::instruction::transfer_checked(
+        token_program.key,
+        from.key,
+        mint.key,
+        to.key,
+        authority.key,
+        &[],
+        amount,
+        decimals,
+    )?;
+    let account_infos =
+        [from.clone(), mint.clone(), to.clone(), authority.clone(), token_program.clone()];
+    if seeds.is_empty() {
+        invoke(&ix, &account_infos)
+    } else {
+        invoke_signed(&ix, &account_infos, &[seeds])
+    }
+}
+
+pub struct Harness {
+    pub context: ProgramTestContext,
+}
+
+impl Harness {
+    pub async fn new() -> Self {
+        let prefer_bpf =
+            std::env::var("BPF_OUT_DIR").is_ok() || std::env::var("SBF_OUT_DIR").is_ok();
+        let mut program_test = ProgramTest::default();
+        // Mocks have no shared object
+        program_test.prefer_bpf(false);
+        program_test.add_program(
+            "mock_token_swap",
+            spl_token_swap_program::ID,
+            processor!(mock_token_swap::process_instruction),
+        );
+        program_test.add_program(
+            "mock_clmm",
+            whirlpool_program::ID,
+            processor!(mock_clmm::process_instruction),
+        );
+        program_test.add_program(
+            "mock_bonding_curve",
+            vertigo_program::ID,
+            processor!(mock_bonding_curve::process_instruction),
+        );
+        program_test.add_program(
+            "mock_order_book",
+            phoenix_program::ID,
+            processor!(mock_order_book::process_instruction),
+        );
+        program_test.add_program(
+            "mock_manifest",
+            manifest_program::ID,
+            processor!(mock_manifest::process_instruction),
+        );
+        program_test.add_program(
+            "mock_multisig",
+            mock_multisig::ID,
+            processor!(mock_multisig::process_instruction),
+        );
+        program_test.add_program(
+            "mock_bridge",
+            okx_bridge_program::ID,
+            processor!(mock_bridge::process_instruction),
+        );
+        program_test.add_program(
+            "mock_transfer_hook",
+            mock_transfer_hook::ID,
+            processor!(mock_transfer_hook::process_instruction),
+        );
+        program_test.prefer_bpf(prefer_bpf);
+        program_test.add_program("dex_solana", dex_solana::ID, processor!(process_instruction));
+        Self { context: program_test.start_with_context().await }
+    }
+
+    pub fn payer(&self) -> Pubkey {
+        self.context.payer.pubkey()
+    }
+
+    pub fn set_account(&mut self, address: &Pubkey, owner: Pubkey, data: Vec<u8>) {
+        let account = Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner,
+            executable: false,
+            rent_epoch: 0,
+        };
+        self.context.set_account(address, &account.into());
+    }
+
+    pub fn create_mint(&mut self) -> Pubkey {
+        let mint = Pubkey::new_unique();
+        self.set_mint(mint, 6);
+        mint
+    }
+
+    /// Wrapped SOL mint, so fees on it are charged in lamports
+    pub fn create_native_mint(&mut self) -> Pubkey {
+        self.set_mint(spl_token::native_mint::ID, 9);
+        spl_token::native_mint::ID
+    }
+
+    fn set_mint(&mut self, mint: Pubkey, decimals: u8) {
+        let mut data = vec![0; spl_token::state::Mint::LEN];
+        spl_token::state::Mint {
+            mint_authority: COption::None,
+            supply: u64::MAX / 2,
+            decimals,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        }
+        .pack_into_slice(&mut data);
+        self.set_account(&mint, spl_token::ID, data);
+    }
+
+    pub fn create_token_account(&mut self, mint: Pubkey, owner: Pubkey, amount: u64) -> Pubkey {
+        let address = Pubkey::new_unique();
+        let mut data = vec![0; spl_token::state::Account::LEN];
+        spl_token::state::Account {
+            mint,
+            owner,
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        }
+        .pack_into_slice(&mut data);
+        self.set_account(&address, spl_token::ID, data);
+        address
+    }
+
+    /// Token-2022 mint with `extensions`, filled in by `init`
+    fn create_token_2022_mint(
+        &mut self,
+        extensions: &[ExtensionType],
+        init: impl FnOnce(&mut StateWithExtensionsMut<spl_token_2022::state::Mint>),
+    ) -> Pubkey {
+        let mint = Pubkey::new_unique();
+        let len =
+            ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(extensions)
+                .unwrap();
+        let mut data = vec![0; len];
+        let mut state =
+            StateWithExtensionsMut::<spl_token_2022::state::Mint>::unpack_uninitialized(&mut data)
+                .unwrap();
+        init(&mut state);
+        state.base = spl_token_2022::state::Mint {
+            mint_authority: COption::None,
+            supply: u64::MAX / 2,
+            decimals: 6,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        state.pack_base();
+        state.init_account_type().unwrap();
+        self.set_account(&mint, spl_token_2022::ID, data);
+        mint
+    }
+
+    /// Token-2022 mint whose transfers call the mock transfer hook, with its validation and
+    /// counter accounts
+    pub fn create_transfer_hook_mint(&mut self) -> Pubkey {
+        let mint = self.create_token_2022_mint(&[ExtensionType::TransferHook], |state| {
+            let extension = state.init_extension::<TransferHook>(true).unwrap();
+            extension.program_id = Some(mock_transfer_hook::ID).try_into().unwrap();
+        });
+        self.set_account(
+            &mock_transfer_hook::extra_account_metas(&mint),
+            mock_transfer_hook::ID,
+            mock_transfer_hook::extra_account_metas_data(&mint),
+        );
+        self.set_account(&mock_transfer_hook::counter(&mint), mock_transfer_hook::ID, vec![0; 8]);
+        mint
+    }
+
+    /// Token-2022 mint withholding `transfer_fee_basis_points` of every transfer, without cap
+    pub fn create_transfer_fee_mint(&mut self, transfer_fee_basis_points: u16) -> Pubkey {
+        self.create_token_2022_mint(&[ExtensionType::TransferFeeConfig], |state| {
+            let extension = state.init_extension::<TransferFeeConfig>(true).unwrap();
+            let transfer_fee = TransferFee {
+                epoch: 0.into(),
+                maximum_fee: u64::MAX.into(),
+                transfer_fee_basis_points: transfer_fee_basis_points.into(),
+            };
+            extension.older_transfer_fee = transfer_fee;
+            extension.newer_transfer_fee = transfer_fee;
+        })
+    }
+
+    /// Token-2022 account with the account extensions its mint requires
+    pub async fn create_token_2022_account(
+        &mut self,
+        mint: Pubkey,
+        owner: Pubkey,
+        amount: u64,
+    ) -> Pubkey {
+        let address = Pubkey::new_unique();
+        let mint_data = self.context.banks_client.get_account(mint).await.unwrap().unwrap().data;
+        let mint_extensions =
+            StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)
+                .unwrap()
+                .get_extension_types()
+                .unwrap();
+        let extensions = ExtensionType::get_required_init_account_extensions(&mint_extensions);
+        let len =
+            ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(&extensions)
+                .unwrap();
+        let mut data = vec![0; len];
+        let mut state =
+            StateWithExtensionsMut::<spl_token_2022::state::Account>::unpack_uninitialized(
+                &mut data,
+            )
+            .unwrap();
+        for extension in extensions {
+            state.init_account_extension_from_type(extension).unwrap();
+        }
+        state.base = spl_token_2022::state::Account {
+            mint,
+            owner,
+            amount,
+            state: spl_token_2022::state::AccountState::Initialized,
+            ..Default::default()
+        };
+        state.pack_base();
+        state.init_account_type().unwrap();
+        self.set_account(&address, spl_token_2022::ID, data);
+        address
+    }
+
+    /// Limit order global config with a single resolver and no trade fee
+    pub fn create_global_config(&mut self, admin: Pubkey) -> Pubkey {
+        let (address, bump) =
+            Pubkey::find_program_address(&[GLOBAL_CONFIG_SEED.as_bytes()], &dex_solana::ID);
+        let global_config = GlobalConfig { bump, admin, ..Default::default() };
+        let mut data = GlobalConfig::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(bytemuck::bytes_of(&global_config));
+        self.set_account(&address, dex_solana::ID, data);
+        address
+    }
+
+    /// Active resolver PDA without limits
+    pub fn create_resolver(&mut self, resolver: Pubkey) -> Pubkey {
+        let (address, bump) = Pubkey::find_program_address(
+            &[RESOLVER_SEED.as_bytes(), resolver.as_ref()],
+            &dex_solana::ID,
+        );
+        let resolver_pda = Resolver { bump, resolver, ..Default::default() };
+        let mut data = Resolver::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(bytemuck::bytes_of(&resolver_pda));
+        self.set_account(&address, dex_solana::ID, data);
+        address
+    }
+
+    pub async fn resolver(&mut self, address: Pubkey) -> Option<Resolver> {
+        let account = self.context.banks_client.get_account(address).await.unwrap()?;
+        Some(*bytemuck::from_bytes(&account.data[8..]))
+    }
+
+    pub async fn global_config(&mut self, address: Pubkey) -> GlobalConfig {
+        let account = self.context.banks_client.get_account(address).await.unwrap().unwrap();
+        *bytemuck::from_bytes(&account.data[8..])
+    }
+
+    /// Claim config with an admin, a claim authority and no receiver allowlist
+    pub fn create_claim_config(&mut self, admin: Pubkey, claim_authority: Pubkey) -> Pubkey {
+        let (address, bump) =
+            Pubkey::find_program_address(&[CLAIM_CONFIG_SEED.as_bytes()], &dex_solana::ID);
+        let claim_config = ClaimConfig { bump, admin, claim_authority, ..Default::default() };
+        let mut data = ClaimConfig::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(bytemuck::bytes_of(&claim_config));
+        self.set_account(&address, dex_solana::ID, data);
+        address
+    }
+
+    pub async fn claim_config(&mut self, address: Pubkey) -> ClaimConfig {
+        let account = self.context.banks_client.get_account(address).await.unwrap().unwrap();
+        *bytemuck::from_bytes(&account.data[8..])
+    }
+
+    /// Fully verified Pyth `PriceUpdateV2` account
+    pub fn set_pyth_price(
+        &mut self,
+        address: Pubkey,
+        price: i64,
+        conf: u64,
+        exponent: i32,
+        publish_time: i64,
+    ) {
+        let mut data = PYTH_PRICE_UPDATE_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&[0; 32]); // write_authority
+        data.push(1); // VerificationLevel::Full
+        data.extend_from_slice(&[0; 32]); // feed_id
+        data.extend_from_slice(&price.to_le_bytes());
+        data.extend_from_slice(&conf.to_le_bytes());
+        data.extend_from_slice(&exponent.to_le_bytes());
+        data.extend_from_slice(&publish_time.to_le_bytes());
+        data.resize(134, 0);
+        self.set_account(&address, pyth_receiver_program::ID, data);
+    }
+
+    /// Switchboard On-Demand pull feed with an 18 decimals result signed at `slot`
+    pub fn set_switchboard_price(
+        &mut self,
+        address: Pubkey,
+        value: i128,
+        std_dev: i128,
+        slot: u64,
+    ) {
+        let mut data = SWITCHBOARD_PULL_FEED_DISCRIMINATOR.to_vec();
+        data.resize(8 + 2256, 0);
+        data.extend_from_slice(&value.to_le_bytes());
+        data.extend_from_slice(&std_dev.to_le_bytes());
+        data.extend_from_slice(&value.to_le_bytes()); // mean
+        data.extend_from_slice(&0i128.to_le_bytes()); // range
+        data.extend_from_slice(&value.to_le_bytes()); // min_value
+        data.extend_from_slice(&value.to_le_bytes()); // max_value
+        data.push(1); // num_samples
+        data.extend_from_slice(&[0; 7]); // submission_idx, padding
+        data.extend_from_slice(&slot.to_le_bytes());
+        data.resize(3208, 0);
+        self.set_account(&address, switchboard_on_demand_program::ID, data);
+    }
+
+    pub async fn slot(&mut self) -> u64 {
+        let clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.slot
+    }
+
+    /// Moves the clock forward, keeping the slot
+    pub async fn warp_forward(&mut self, seconds: i64) {
+        let mut clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp += seconds;
+        self.context.set_sysvar(&clock);
+    }
+
+    pub async fn unix_timestamp(&mut self) -> u64 {
+        let clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp as u64
+    }
+
+    pub async fn balance(&mut self, token_account: Pubkey) -> u64 {
+        let account = self.context.banks_client.get_account(token_account).await.unwrap().unwrap();
+        // Token-2022 extensions follow the base account
+        spl_token::state::Account::unpack(&account.data[..spl_token::state::Account::LEN])
+            .unwrap()
+            .amount
+    }
+
+    pub async fn lamports(&mut self, address: Pubkey) -> u64 {
+        self.context.banks_client.get_balance(address).await.unwrap()
+    }
+
+    /// Transfers seen by the mock transfer hook for `mint`
+    pub async fn transfer_hook_count(&mut self, mint: Pubkey) -> u64 {
+        let counter = mock_transfer_hook::counter(&mint);
+        let account = self.context.banks_client.get_account(counter).await.unwrap().unwrap();
+        u64::from_le_bytes(account.data[..8].try_into().unwrap())
+    }
+
+    /// Adaptor accounts the mock bridge received, as written to its `record` account
+    pub async fn bridge_adaptor_accounts_len(&mut self, record: Pubkey) -> u64 {
+        let account = self.context.banks_client.get_account(record).await.unwrap().unwrap();
+        u64::from_le_bytes(account.data[..8].try_into().unwrap())
+    }
+
+    pub async fn process(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<(), BanksClientError> {
+        let blockhash = self.context.get_new_latest_blockhash().await.unwrap();
+        let mut all_signers = vec![&self.context.payer];
+        all_signers.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.context.payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+        self.context.banks_client.process_transaction(transaction).await
+    }
+
+    /// Simulates a transaction, returning its result and return data
+    pub async fn simulate(
+        &mut self,
+        instructions: &[Instruction],
+    ) -> (Result<(), TransactionError>, Option<Vec<u8>>) {
+        let blockhash = self.context.get_new_latest_blockhash().await.unwrap();
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.context.payer.pubkey()),
+            &[&self.context.payer],
+            blockhash,
+        );
+        let simulation = self.context.banks_client.simulate_transaction(transaction).await.unwrap();
+        let return_data = simulation.simulation_details.and_then(|details| details.return_data);
+        (simulation.result.unwrap(), return_data.map(|return_data| return_data.data))
+    }
+
+    /// Constant product pool holding `reserve_a` / `reserve_b`
+    pub fn create_token_swap_pool(
+        &mut self,
+        mint_a: Pubkey,
+        mint_b: Pubkey,
+        reserve_a: u64,
+        reserve_b: u64,
+    ) -> TokenSwapPool {
+        let swap_info = Pubkey::new_unique();
+        let (authority, _) = pool_authority(&spl_token_swap_program::ID, &swap_info);
+        self.set_account(&swap_info, spl_token_swap_program::ID, mock_token_swap::swap_info_data());
+        let pool_mint = self.create_mint();
+        TokenSwapPool {
+            swap_info,
+            authority,
+            token_a_account: self.create_token_account(mint_a, authority, reserve_a),
+            token_b_account: self.create_token_account(mint_b, authority, reserve_b),
+            pool_mint,
+            pool_fee: self.create_token_account(pool_mint, authority, 0),
+        }
+    }
+
+    /// Concentrated liquidity pool at `sqrt_price` (x64) with vaults holding `reserve` of each token
+    pub fn create_clmm_pool(
+        &mut self,
+        mint_a: Pubkey,
+        mint_b: Pubkey,
+        sqrt_price: u128,
+        liquidity: u128,
+        reserve: u64,
+    ) -> ClmmPool {
+        let seed = Pubkey::new_unique();
+        let (whirlpool, bump) = Pubkey::find_program_address(
+            &[mock_clmm::SEED_POOL, seed.as_ref()],
+            &whirlpool_program::ID,
+        );
+        let mut data = Vec::with_capacity(mock_clmm::POOL_LEN);
+        data.extend_from_slice(&sqrt_price.to_le_bytes());
+        data.extend_from_slice(&liquidity.to_le_bytes());
+        data.extend_from_slice(seed.as_ref());
+        data.push(bump);
+        self.set_account(&whirlpool, whirlpool_program::ID, data);
+        ClmmPool {
+            whirlpool,
+            token_vault_a: self.create_token_account(mint_a, whirlpool, reserve),
+            token_vault_b: self.create_token_account(mint_b, whirlpool, reserve),
+        }
+    }
+
+    pub async fn clmm_sqrt_price(&mut self, pool: &ClmmPool) -> u128 {
+        let account = self.context.banks_client.get_account(pool.whirlpool).await.unwrap().unwrap();
+        u128::from_le_bytes(account.data[..16].try_into().unwrap())
+    }
+
+    /// Bonding curve selling `reserve_b` of `mint_b` against a virtual `mint_a` reserve
+    pub fn create_bonding_curve(
+        &mut self,
+        mint_a: Pubkey,
+        mint_b: Pubkey,
+        virtual_a: u64,
+        reserve_b: u64,
+    ) -> BondingCurve {
+        let pool = Pubkey::new_unique();
+        let (owner, _) = pool_authority(&vertigo_program::ID, &pool);
+        self.set_account(&pool, vertigo_program::ID, virtual_a.to_le_bytes().to_vec());
+        BondingCurve {
+            pool,
+            owner,
+            mint_a,
+            mint_b,
+            vault_a: self.create_token_account(mint_a, owner, 0),
+            vault_b: self.create_token_account(mint_b, owner, reserve_b),
+            token_program_b: spl_token::ID,
+        }
+    }
+
+    /// Bonding curve of `create_bonding_curve` whose token `mint_b` is a Token-2022 mint
+    pub async fn create_token_2022_bonding_curve(
+        &mut self,
+        mint_a: Pubkey,
+        mint_b: Pubkey,
+        virtual_a: u64,
+        reserve_b: u64,
+    ) -> BondingCurve {
+        let mut curve = self.create_bonding_curve(mint_a, mint_b, virtual_a, 0);
+        curve.vault_b = self.create_token_2022_account(mint_b, curve.owner, reserve_b).await;
+        curve.token_program_b = spl_token_2022::ID;
+        curve
+    }
+
+    /// Order book market of `mint_base` / `mint_quote` filling at `price` quote atoms per base lot
+    pub fn create_order_book(
+        &mut self,
+        mint_base: Pubkey,
+        mint_quote: Pubkey,
+        price: u64,
+        base_lot_size: u64,
+        quote_lot_size: u64,
+        reserve: u64,
+    ) -> OrderBook {
+        let seed = Pubkey::new_unique();
+        let (market, bump) = Pubkey::find_program_address(
+            &[mock_order_book::SEED_MARKET, seed.as_ref()],
+            &phoenix_program::ID,
+        );
+        let mut data = vec![0; mock_order_book::MARKET_LEN];
+        data[..8].copy_from_slice(&price.to_le_bytes());
+        data[8..40].copy_from_slice(seed.as_ref());
+        data[40] = bump;
+        data[112..120].copy_from_slice(&base_lot_size.to_le_bytes());
+        data[192..200].copy_from_slice(&quote_lot_size.to_le_bytes());
+        self.set_account(&market, phoenix_program::ID, data);
+        OrderBook {
+            market,
+            base_vault: self.create_token_account(mint_base, market, reserve),
+            quote_vault: self.create_token_account(mint_quote, market, reserve),
+        }
+    }
+
+    /// Manifest market of `mint_base` / `mint_quote` filling at `price` quote atoms per base atom
+    /// scaled by `mock_manifest::PRICE_SCALE`
+    pub fn create_manifest_market(
+        &mut self,
+        mint_base: Pubkey,
+        mint_quote: Pubkey,
+        price: u64,
+        reserve: u64,
+    ) -> ManifestMarket {
+        let seed = Pubkey::new_unique();
+        let (market, bump) = Pubkey::find_program_address(
+            &[mock_manifest::SEED_MARKET, seed.as_ref()],
+            &manifest_program::ID,
+        );
+        let mut data = vec![0; mock_manifest::MARKET_LEN];
+        data[..8].copy_from_slice(&price.to_le_bytes());
+        data[8..40].copy_from_slice(seed.as_ref());
+        data[40] = bump;
+        self.set_account(&market, manifest_program::ID, data);
+        ManifestMarket {
+            market,
+            base_mint: mint_base,
+            quote_mint: mint_quote,
+            base_vault: self.create_token_account(mint_base, market, reserve),
+            quote_vault: self.create_token_account(mint_quote, market, reserve),
+        }
+    }
+}
+
+pub struct TokenSwapPool {
+    pub swap_info: Pubkey,
+    pub authority: Pubkey,
+    pub token_a_account: Pubkey,
+    pub token_b_account: Pubkey,
+    pub pool_mint: Pubkey,
+    pub pool_fee: Pubkey,
+}
+
+impl TokenSwapPool {
+    /// Remaining accounts of `Dex::SplTokenSwap`
+    pub fn accounts(
+        &self,
+        swap_authority: Pubkey,
+        source: Pubkey,
+        destination: Pubkey,
+    ) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new_readonly(spl_token_swap_program::ID, false),
+            AccountMeta::new(swap_authority, false),
+            AccountMeta::new(source, false),
+            AccountMeta::new(destination, false),
+            AccountMeta::new_readonly(self.swap_info, false),
+            AccountMeta::new_readonly(self.authority, false),
+            AccountMeta::new(self.token_a_account, false),
+            AccountMeta::new(self.token_b_account, false),
+            AccountMeta::new(self.pool_mint, false),
+            AccountMeta::new(self.pool_fee, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ]
+    }
+}
+
+pub struct ClmmPool {
+    pub whirlpool: Pubkey,
+    pub token_vault_a: Pubkey,
+    pub token_vault_b: Pubkey,
+}
+
+impl ClmmPool {
+    /// Remaining accounts of `Dex::Whirlpool`
+    pub fn accounts(
+        &self,
+        swap_authority: Pubkey,
+        source: Pubkey,
+        destination: Pubkey,
+    ) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new_readonly(whirlpool_program::ID, false),
+            AccountMeta::new(swap_authority, false),
+            AccountMeta::new(source, false),
+            AccountMeta::new(destination, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new(self.whirlpool, false),
+            AccountMeta::new(self.token_vault_a, false),
+            AccountMeta::new(self.token_vault_b, false),
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new_readonly(Pubkey::new_unique(), false),
+        ]
+    }
+}
+
+pub struct BondingCurve {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub vault_a: Pubkey,
+    pub vault_b: Pubkey,
+    pub token_program_b: Pubkey,
+}
+
+impl BondingCurve {
+    /// Remaining accounts of `Dex::VertigoBuy` / `Dex::VertigoSell`
+    pub fn accounts(
+        &self,
+        swap_authority: Pubkey,
+        source: Pubkey,
+        destination: Pubkey,
+    ) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new_readonly(vertigo_program::ID, false),
+            AccountMeta::new(swap_authority, false),
+            AccountMeta::new(source, false),
+            AccountMeta::new(destination, false),
+            AccountMeta::new(self.pool, false),
+            AccountMeta::new_readonly(self.owner, false),
+            AccountMeta::new_readonly(self.mint_a, false),
+            AccountMeta::new_readonly(self.mint_b, false),
+            AccountMeta::new(self.vault_a, false),
+            AccountMeta::new(self.vault_b, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(self.token_program_b, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ]
+    }
+}
+
+pub struct OrderBook {
+    pub market: Pubkey,
+    pub base_vault: Pubkey,
+    pub quote_vault: Pubkey,
+}
+
+impl OrderBook {
+    /// Remaining accounts of `Dex::Phoenix`
+    pub fn accounts(
+        &self,
+        swap_authority: Pubkey,
+        source: Pubkey,
+        destination: Pubkey,
+    ) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new_readonly(phoenix_program::ID, false),
+            AccountMeta::new(swap_authority, false),
+            AccountMeta::new(source, false),
+            AccountMeta::new(destination, false),
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new(self.market, false),
+            AccountMeta::new(self.base_vault, false),
+            AccountMeta::new(self.quote_vault, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ]
+    }
+}
+
+pub struct ManifestMarket {
+    pub market: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub base_vault: Pubkey,
+    pub quote_vault: Pubkey,
+}
+
+impl ManifestMarket {
+    /// Remaining accounts of `Dex::Manifest`
+    pub fn accounts(
+        &self,
+        swap_authority: Pubkey,
+        source: Pubkey,
+        destination: Pubkey,
+    ) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new_readonly(manifest_program::ID, false),
+            AccountMeta::new(swap_authority, false),
+            AccountMeta::new(source, false),
+            AccountMeta::new(destination, false),
+            AccountMeta::new(self.market, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(self.base_vault, false),
+            AccountMeta::new(self.quote_vault, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(self.base_mint, false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(self.quote_mint, false),
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new(Pubkey::new_unique(), false),
+        ]
+    }
+}
+
+/// Router instruction from anchor accounts, args and the dex remaining accounts
+pub fn router_instruction(
+    accounts: impl ToAccountMetas,
+    data: impl InstructionData,
+    remaining_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.extend(remaining_accounts);
+    Instruction { program_id: dex_solana::ID, accounts: account_metas, data: data.data() }
+}
+
+/// Custom program error code of a failed router transaction
+pub fn error_code(result: Result<(), BanksClientError>) -> Option<u32> {
+    use solana_sdk::{instruction::InstructionError, transaction::TransactionError};
+    match result {
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(code),
+        )))
+        | Err(BanksClientError::SimulationError {
+            err: TransactionError::InstructionError(_, InstructionError::Custom(code)),
+            ..
+        }) => Some(code),
+        _ => None,
+    }
+}
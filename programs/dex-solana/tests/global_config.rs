@@ -0,0 +1,194 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::system_program;
+use anchor_lang::{Discriminator, InstructionData};
+use common::{Harness, error_code, mock_multisig, router_instruction};
+use dex_solana::constants::{GLOBAL_CONFIG_SEED, RESOLVER_SEED};
+use dex_solana::error::LimitOrderError;
+use dex_solana::state::config::GlobalConfig;
+use dex_solana::{accounts, instruction};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
+
+fn update_ix(global_config: Pubkey, admin: Pubkey, data: impl InstructionData) -> Instruction {
+    router_instruction(accounts::UpdateGlobalConfig { admin, global_config }, data, vec![])
+}
+
+fn accept_ix(global_config: Pubkey, pending_admin: Pubkey) -> Instruction {
+    router_instruction(
+        accounts::AcceptAdmin { pending_admin, global_config },
+        instruction::AcceptAdmin {},
+        vec![],
+    )
+}
+
+#[tokio::test]
+async fn test_propose_and_accept_admin() {
+    let mut h = Harness::new().await;
+    // The payer is the admin
+    let global_config = h.create_global_config(h.payer());
+    let new_admin = Keypair::new();
+
+    let ix = update_ix(
+        global_config,
+        h.payer(),
+        instruction::ProposeAdmin { pending_admin: new_admin.pubkey() },
+    );
+    h.process(&[ix], &[]).await.unwrap();
+    let config = h.global_config(global_config).await;
+    assert_eq!(config.admin, h.payer());
+    assert_eq!(config.pending_admin, new_admin.pubkey());
+
+    // Only the proposed admin accepts
+    let other = Keypair::new();
+    let result = h.process(&[accept_ix(global_config, other.pubkey())], &[&other]).await;
+    assert_eq!(error_code(result), Some(LimitOrderError::InvalidPendingAdmin.into()));
+
+    h.process(&[accept_ix(global_config, new_admin.pubkey())], &[&new_admin]).await.unwrap();
+    let config = h.global_config(global_config).await;
+    assert_eq!(config.admin, new_admin.pubkey());
+    assert_eq!(config.pending_admin, Pubkey::default());
+
+    // The previous admin is rotated out
+    let ix = update_ix(global_config, h.payer(), instruction::PauseTrading {});
+    let result = h.process(&[ix], &[]).await;
+    assert_eq!(error_code(result), Some(anchor_lang::error::ErrorCode::ConstraintHasOne.into()));
+}
+
+#[tokio::test]
+async fn test_cancel_admin_transfer() {
+    let mut h = Harness::new().await;
+    let global_config = h.create_global_config(h.payer());
+    let new_admin = Keypair::new();
+
+    let ix = update_ix(global_config, h.payer(), instruction::CancelAdminTransfer {});
+    let result = h.process(&[ix], &[]).await;
+    assert_eq!(error_code(result), Some(LimitOrderError::NoPendingAdmin.into()));
+
+    let ix = update_ix(
+        global_config,
+        h.payer(),
+        instruction::ProposeAdmin { pending_admin: new_admin.pubkey() },
+    );
+    h.process(&[ix], &[]).await.unwrap();
+    let ix = update_ix(global_config, h.payer(), instruction::CancelAdminTransfer {});
+    h.process(&[ix], &[]).await.unwrap();
+    assert_eq!(h.global_config(global_config).await.pending_admin, Pubkey::default());
+
+    let result = h.process(&[accept_ix(global_config, new_admin.pubkey())], &[&new_admin]).await;
+    assert_eq!(error_code(result), Some(LimitOrderError::NoPendingAdmin.into()));
+}
+
+#[tokio::test]
+async fn test_multisig_admin() {
+    let mut h = Harness::new().await;
+    let global_config = h.create_global_config(h.payer());
+    let vault = mock_multisig::vault();
+
+    // Hand the config over to the multisig, which accepts through CPI
+    let ix =
+        update_ix(global_config, h.payer(), instruction::ProposeAdmin { pending_admin: vault });
+    h.process(&[ix], &[]).await.unwrap();
+    h.process(&[mock_multisig::execute(accept_ix(global_config, vault))], &[]).await.unwrap();
+    assert_eq!(h.global_config(global_config).await.admin, vault);
+
+    let ix = update_ix(global_config, vault, instruction::SetTradeFee { trade_fee: 5_000 });
+    h.process(&[mock_multisig::execute(ix)], &[]).await.unwrap();
+    let ix = update_ix(global_config, vault, instruction::PauseTrading {});
+    h.process(&[mock_multisig::execute(ix)], &[]).await.unwrap();
+    let config = h.global_config(global_config).await;
+    assert_eq!({ config.trade_fee }, 5_000);
+    assert!(config.paused);
+}
+
+#[tokio::test]
+async fn test_add_and_remove_resolver() {
+    let mut h = Harness::new().await;
+    let global_config = h.create_global_config(h.payer());
+    let (resolver, mint, rent_receiver) =
+        (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let resolver_pda = Pubkey::find_program_address(
+        &[RESOLVER_SEED.as_bytes(), resolver.as_ref()],
+        &dex_solana::ID,
+    )
+    .0;
+
+    let ix = router_instruction(
+        accounts::AddResolver {
+            payer: h.payer(),
+            admin: h.payer(),
+            global_config,
+            resolver_pda,
+            system_program: system_program::ID,
+        },
+        instruction::AddResolver { resolver, max_orders_per_slot: 3, allowed_mints: vec![mint] },
+        vec![],
+    );
+    h.process(&[ix], &[]).await.unwrap();
+    let state = h.resolver(resolver_pda).await.unwrap();
+    assert_eq!(state.resolver, resolver);
+    assert_eq!({ state.max_orders_per_slot }, 3);
+    assert!(state.is_allowed_mint(mint));
+    assert!(!state.is_allowed_mint(Pubkey::new_unique()));
+
+    let ix = router_instruction(
+        accounts::RemoveResolver { admin: h.payer(), global_config, resolver_pda, rent_receiver },
+        instruction::RemoveResolver { resolver },
+        vec![],
+    );
+    h.process(&[ix], &[]).await.unwrap();
+    assert!(h.resolver(resolver_pda).await.is_none());
+    assert!(h.context.banks_client.get_balance(rent_receiver).await.unwrap() > 0);
+}
+
+#[tokio::test]
+async fn test_migrate_resolver() {
+    let mut h = Harness::new().await;
+    let resolver = Pubkey::new_unique();
+    let (address, bump) =
+        Pubkey::find_program_address(&[GLOBAL_CONFIG_SEED.as_bytes()], &dex_solana::ID);
+    let mut deprecated_resolvers = [Pubkey::default(); 5];
+    deprecated_resolvers[1] = resolver;
+    let config =
+        GlobalConfig { bump, admin: h.payer(), deprecated_resolvers, ..Default::default() };
+    let mut data = GlobalConfig::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(&config));
+    h.set_account(&address, dex_solana::ID, data);
+
+    let migrate_ix = |h: &Harness, resolver: Pubkey| {
+        let resolver_pda = Pubkey::find_program_address(
+            &[RESOLVER_SEED.as_bytes(), resolver.as_ref()],
+            &dex_solana::ID,
+        )
+        .0;
+        router_instruction(
+            accounts::MigrateResolver {
+                payer: h.payer(),
+                admin: h.payer(),
+                global_config: address,
+                resolver_pda,
+                system_program: system_program::ID,
+            },
+            instruction::MigrateResolver { resolver },
+            vec![],
+        )
+    };
+
+    // Only resolvers of the previous registry migrate
+    let result = h.process(&[migrate_ix(&h, Pubkey::new_unique())], &[]).await;
+    assert_eq!(error_code(result), Some(LimitOrderError::ResolverIsNotExist.into()));
+
+    h.process(&[migrate_ix(&h, resolver)], &[]).await.unwrap();
+    assert_eq!(h.global_config(address).await.deprecated_resolvers, [Pubkey::default(); 5]);
+    let resolver_pda = Pubkey::find_program_address(
+        &[RESOLVER_SEED.as_bytes(), resolver.as_ref()],
+        &dex_solana::ID,
+    )
+    .0;
+    let state = h.resolver(resolver_pda).await.unwrap();
+    assert_eq!(state.resolver, resolver);
+    assert!(!state.suspended);
+    assert_eq!({ state.max_orders_per_slot }, 0);
+}
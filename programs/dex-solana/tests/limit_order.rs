@@ -0,0 +1,421 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas, system_program};
+use anchor_spl::token::spl_token;
+use common::{Harness, TokenSwapPool, error_code, mock_token_swap, router_instruction};
+use dex_solana::constants::{ESCROW_TOKEN_SEED, MAX_ORDER_FILLS, ORDER_MIN_RENT, ORDER_V1_SEED};
+use dex_solana::error::{ErrorCode, LimitOrderError};
+use dex_solana::state::order::OrderV1;
+use dex_solana::{
+    ConditionalOrderArgs, Dex, OrderTrigger, Route, SwapArgs, TriggerCondition, accounts,
+    instruction,
+};
+use solana_sdk::instruction::Instruction;
+
+const ORDER_ID: u64 = 7;
+const MAKING_AMOUNT: u64 = 1_000_000;
+
+struct Order {
+    mint_a: Pubkey,
+    mint_b: Pubkey,
+    global_config: Pubkey,
+    resolver_pda: Pubkey,
+    oracle: Option<Pubkey>,
+    receiver: Option<Pubkey>,
+    order_pda: Pubkey,
+    escrow_token_account: Pubkey,
+    output_token_account: Pubkey,
+    pool: TokenSwapPool,
+}
+
+fn event_authority() -> Pubkey {
+    Pubkey::find_program_address(&[b"__event_authority"], &dex_solana::ID).0
+}
+
+async fn place_order(h: &mut Harness, min_return_amount: u64) -> Order {
+    place(h, min_return_amount, None, None).await
+}
+
+/// Places an order selling `MAKING_AMOUNT` of token a against a 1:1 pool, the maker is also
+/// the admin and a resolver
+async fn place(
+    h: &mut Harness,
+    min_return_amount: u64,
+    trigger: Option<OrderTrigger>,
+    receiver: Option<Pubkey>,
+) -> Order {
+    let maker = h.payer();
+    let (mint_a, mint_b) = (h.create_mint(), h.create_mint());
+    let pool = h.create_token_swap_pool(mint_a, mint_b, 1_000_000_000, 1_000_000_000);
+    let global_config = h.create_global_config(maker);
+    let resolver_pda = h.create_resolver(maker);
+    let input_token_account = h.create_token_account(mint_a, maker, MAKING_AMOUNT);
+    let output_token_account = h.create_token_account(mint_b, receiver.unwrap_or(maker), 0);
+    let order_pda = Pubkey::find_program_address(
+        &[ORDER_V1_SEED.as_bytes(), &ORDER_ID.to_le_bytes(), maker.as_ref()],
+        &dex_solana::ID,
+    )
+    .0;
+    let escrow_token_account = Pubkey::find_program_address(
+        &[ESCROW_TOKEN_SEED.as_bytes(), order_pda.as_ref(), mint_a.as_ref()],
+        &dex_solana::ID,
+    )
+    .0;
+
+    let deadline = h.unix_timestamp().await + 3600;
+    // Prepaid fees of the resolver, charged on every fill
+    let trade_fee = 100_000;
+    let data = match trigger {
+        Some(trigger) => instruction::PlaceConditionalOrder {
+            order_id: ORDER_ID,
+            args: ConditionalOrderArgs {
+                making_amount: MAKING_AMOUNT,
+                expect_taking_amount: min_return_amount,
+                min_return_amount,
+                deadline,
+                trade_fee,
+                trigger,
+            },
+        }
+        .data(),
+        None => instruction::PlaceOrder {
+            order_id: ORDER_ID,
+            making_amount: MAKING_AMOUNT,
+            expect_taking_amount: min_return_amount,
+            min_return_amount,
+            deadline,
+            trade_fee,
+        }
+        .data(),
+    };
+    let ix = Instruction {
+        program_id: dex_solana::ID,
+        accounts: accounts::PlaceOrder {
+            maker,
+            global_config,
+            order_pda,
+            escrow_token_account,
+            input_token_account,
+            input_token_mint: mint_a,
+            output_token_mint: mint_b,
+            input_token_program: spl_token::ID,
+            output_token_program: spl_token::ID,
+            system_program: system_program::ID,
+            event_authority: event_authority(),
+            program: dex_solana::ID,
+            receiver,
+        }
+        .to_account_metas(None),
+        data,
+    };
+    h.process(&[ix], &[]).await.unwrap();
+
+    Order {
+        mint_a,
+        mint_b,
+        global_config,
+        resolver_pda,
+        oracle: trigger.map(|trigger| trigger.oracle),
+        receiver,
+        order_pda,
+        escrow_token_account,
+        output_token_account,
+        pool,
+    }
+}
+
+fn fill_ix(h: &Harness, order: &Order, amount_in: u64) -> Instruction {
+    fill_ix_with_tips(h, order, amount_in, 0)
+}
+
+fn fill_ix_with_tips(h: &Harness, order: &Order, amount_in: u64, tips: u64) -> Instruction {
+    let args = SwapArgs {
+        amount_in,
+        expect_amount_out: 1,
+        min_return: 1,
+        amounts: vec![amount_in],
+        routes: vec![vec![Route { dexes: vec![Dex::SplTokenSwap], weights: vec![100] }]],
+    };
+    router_instruction(
+        accounts::FillOrder {
+            payer: h.payer(),
+            maker: h.payer(),
+            global_config: order.global_config,
+            sa_authority: None,
+            input_token_sa: None,
+            output_token_sa: None,
+            order_pda: order.order_pda,
+            escrow_token_account: order.escrow_token_account,
+            temp_input_token_account: None,
+            output_token_account: order.output_token_account,
+            input_token_mint: order.mint_a,
+            output_token_mint: order.mint_b,
+            input_token_program: spl_token::ID,
+            output_token_program: spl_token::ID,
+            associated_token_program: None,
+            system_program: None,
+            instructions_sysvar: sysvar::instructions::ID,
+            event_authority: event_authority(),
+            program: dex_solana::ID,
+            resolver_pda: order.resolver_pda,
+            oracle: order.oracle,
+            receiver: order.receiver,
+        },
+        instruction::FillOrderByResolver { order_id: ORDER_ID, tips, args },
+        order.pool.accounts(
+            order.order_pda,
+            order.escrow_token_account,
+            order.output_token_account,
+        ),
+    )
+}
+
+fn update_resolver_ix(h: &Harness, order: &Order, data: impl InstructionData) -> Instruction {
+    router_instruction(
+        accounts::UpdateResolver {
+            admin: h.payer(),
+            global_config: order.global_config,
+            resolver_pda: order.resolver_pda,
+        },
+        data,
+        vec![],
+    )
+}
+
+async fn load_order(h: &mut Harness, order_pda: Pubkey) -> Option<OrderV1> {
+    let account = h.context.banks_client.get_account(order_pda).await.unwrap()?;
+    Some(OrderV1::try_deserialize(&mut account.data.as_slice()).unwrap())
+}
+
+#[tokio::test]
+async fn test_fill_order_in_chunks() {
+    let mut h = Harness::new().await;
+    let order = place_order(&mut h, 900_000).await;
+
+    let ix = fill_ix(&h, &order, 400_000);
+    h.process(&[ix], &[]).await.unwrap();
+
+    let first_taking = mock_token_swap::quote(1_000_000_000, 1_000_000_000, 400_000);
+    let state = load_order(&mut h, order.order_pda).await.unwrap();
+    assert_eq!(state.filled_making_amount, 400_000);
+    assert_eq!(state.filled_taking_amount, first_taking);
+    assert_eq!(h.balance(order.escrow_token_account).await, 600_000);
+    assert_eq!(h.balance(order.output_token_account).await, first_taking);
+
+    // Filling the rest of the escrow settles the order
+    let ix = fill_ix(&h, &order, 600_000);
+    h.process(&[ix], &[]).await.unwrap();
+
+    let second_taking =
+        mock_token_swap::quote(1_000_400_000, 1_000_000_000 - first_taking, 600_000);
+    assert_eq!(h.balance(order.output_token_account).await, first_taking + second_taking);
+    assert!(load_order(&mut h, order.order_pda).await.is_none());
+    assert!(
+        h.context.banks_client.get_account(order.escrow_token_account).await.unwrap().is_none()
+    );
+}
+
+#[tokio::test]
+async fn test_fill_order_chunk_below_pro_rata_price() {
+    let mut h = Harness::new().await;
+    // A 1:1 price leaves no room for the pool fee
+    let order = place_order(&mut h, MAKING_AMOUNT).await;
+
+    let ix = fill_ix(&h, &order, 100_000);
+    let result = h.process(&[ix], &[]).await;
+    assert_eq!(error_code(result), Some(ErrorCode::MinReturnNotReached.into()));
+    assert_eq!(h.balance(order.escrow_token_account).await, MAKING_AMOUNT);
+    assert_eq!(load_order(&mut h, order.order_pda).await.unwrap().filled_making_amount, 0);
+}
+
+#[tokio::test]
+async fn test_fill_order_in_small_chunks() {
+    let mut h = Harness::new().await;
+    let order = place_order(&mut h, 900_000).await;
+    let min_fill_amount = MAKING_AMOUNT / MAX_ORDER_FILLS;
+
+    let ix = fill_ix(&h, &order, min_fill_amount - 1);
+    let result = h.process(&[ix], &[]).await;
+    assert_eq!(error_code(result), Some(LimitOrderError::FillAmountTooSmall.into()));
+
+    // Tips of the whole prepaid fee are capped to the share of each fill
+    let escrow_fee = h.lamports(order.order_pda).await - ORDER_MIN_RENT;
+    for fill in 1..MAX_ORDER_FILLS {
+        let ix = fill_ix_with_tips(&h, &order, min_fill_amount, escrow_fee);
+        h.process(&[ix], &[]).await.unwrap();
+        assert_eq!(
+            h.lamports(order.order_pda).await - ORDER_MIN_RENT,
+            escrow_fee - escrow_fee / MAX_ORDER_FILLS * fill
+        );
+    }
+    let state = load_order(&mut h, order.order_pda).await.unwrap();
+    assert_eq!(state.filled_making_amount, MAKING_AMOUNT - min_fill_amount);
+
+    // The last fill settles the order with the rest of the fee
+    let ix = fill_ix_with_tips(&h, &order, min_fill_amount, escrow_fee);
+    h.process(&[ix], &[]).await.unwrap();
+    assert!(load_order(&mut h, order.order_pda).await.is_none());
+}
+
+#[tokio::test]
+async fn test_fill_order_exceeds_escrow_balance() {
+    let mut h = Harness::new().await;
+    let order = place_order(&mut h, 900_000).await;
+
+    let ix = fill_ix(&h, &order, MAKING_AMOUNT + 1);
+    let result = h.process(&[ix], &[]).await;
+    assert_eq!(error_code(result), Some(LimitOrderError::InvalidFillAmount.into()));
+}
+
+#[tokio::test]
+async fn test_fill_order_suspended_resolver() {
+    let mut h = Harness::new().await;
+    let order = place_order(&mut h, 900_000).await;
+
+    let ix = update_resolver_ix(&h, &order, instruction::SuspendResolver { resolver: h.payer() });
+    h.process(&[ix], &[]).await.unwrap();
+    let result = h.process(&[fill_ix(&h, &order, 400_000)], &[]).await;
+    assert_eq!(error_code(result), Some(LimitOrderError::ResolverSuspended.into()));
+
+    let ix = update_resolver_ix(&h, &order, instruction::ResumeResolver { resolver: h.payer() });
+    h.process(&[ix], &[]).await.unwrap();
+    h.process(&[fill_ix(&h, &order, 400_000)], &[]).await.unwrap();
+    let resolver = h.resolver(order.resolver_pda).await.unwrap();
+    assert_eq!({ resolver.fill_count }, 1);
+}
+
+#[tokio::test]
+async fn test_fill_order_resolver_limits() {
+    let mut h = Harness::new().await;
+    let order = place_order(&mut h, 900_000).await;
+
+    // Only token a is allowed, the order buys token b
+    let ix = update_resolver_ix(
+        &h,
+        &order,
+        instruction::SetResolverLimits {
+            resolver: h.payer(),
+            max_orders_per_slot: 1,
+            allowed_mints: vec![order.mint_a],
+        },
+    );
+    h.process(&[ix], &[]).await.unwrap();
+    let result = h.process(&[fill_ix(&h, &order, 400_000)], &[]).await;
+    assert_eq!(error_code(result), Some(LimitOrderError::ResolverMintNotAllowed.into()));
+
+    // One fill per slot
+    let ix = update_resolver_ix(
+        &h,
+        &order,
+        instruction::SetResolverLimits {
+            resolver: h.payer(),
+            max_orders_per_slot: 1,
+            allowed_mints: vec![order.mint_a, order.mint_b],
+        },
+    );
+    h.process(&[ix], &[]).await.unwrap();
+    let ixs = [fill_ix(&h, &order, 400_000), fill_ix(&h, &order, 100_000)];
+    let result = h.process(&ixs, &[]).await;
+    assert_eq!(error_code(result), Some(LimitOrderError::ExceedResolverSlotLimit.into()));
+    h.process(&ixs[..1], &[]).await.unwrap();
+    assert_eq!(h.balance(order.escrow_token_account).await, 600_000);
+}
+
+fn stop_loss(oracle: Pubkey) -> OrderTrigger {
+    // Sell once the feed is at or below 100.00
+    OrderTrigger {
+        condition: TriggerCondition::PriceBelow,
+        oracle,
+        trigger_price: 10_000,
+        price_exponent: -2,
+        max_staleness_secs: 60,
+        max_staleness_slots: 0,
+        max_confidence_bps: 100,
+    }
+}
+
+#[tokio::test]
+async fn test_fill_stop_loss_order() {
+    let mut h = Harness::new().await;
+    let oracle = Pubkey::new_unique();
+    let now = h.unix_timestamp().await as i64;
+    h.set_pyth_price(oracle, 12_000_000_000, 1_000_000, -8, now);
+    let order = place(&mut h, 900_000, Some(stop_loss(oracle)), None).await;
+
+    let result = h.process(&[fill_ix(&h, &order, 400_000)], &[]).await;
+    assert_eq!(error_code(result), Some(LimitOrderError::TriggerPriceNotReached.into()));
+
+    // Stale and uncertain prices do not trigger the order
+    h.set_pyth_price(oracle, 9_000_000_000, 1_000_000, -8, now - 61);
+    let result = h.process(&[fill_ix(&h, &order, 400_000)], &[]).await;
+    assert_eq!(error_code(result), Some(LimitOrderError::StaleOraclePrice.into()));
+    h.set_pyth_price(oracle, 9_000_000_000, 100_000_000, -8, now);
+    let result = h.process(&[fill_ix(&h, &order, 400_000)], &[]).await;
+    assert_eq!(error_code(result), Some(LimitOrderError::OracleConfidenceTooWide.into()));
+
+    // The fill is still held to the order's min return
+    h.set_pyth_price(oracle, 9_000_000_000, 1_000_000, -8, now);
+    h.process(&[fill_ix(&h, &order, 400_000)], &[]).await.unwrap();
+    assert_eq!(h.balance(order.escrow_token_account).await, 600_000);
+}
+
+#[tokio::test]
+async fn test_fill_take_profit_order_with_switchboard() {
+    let mut h = Harness::new().await;
+    let oracle = Pubkey::new_unique();
+    let slot = h.slot().await;
+    let trigger = OrderTrigger {
+        condition: TriggerCondition::PriceAbove,
+        max_staleness_slots: 25,
+        ..stop_loss(oracle)
+    };
+    h.set_switchboard_price(oracle, 99 * 10i128.pow(18), 0, slot);
+    let order = place(&mut h, 900_000, Some(trigger), None).await;
+
+    let result = h.process(&[fill_ix(&h, &order, 400_000)], &[]).await;
+    assert_eq!(error_code(result), Some(LimitOrderError::TriggerPriceNotReached.into()));
+
+    h.set_switchboard_price(oracle, 101 * 10i128.pow(18), 10i128.pow(17), slot);
+    h.process(&[fill_ix(&h, &order, 400_000)], &[]).await.unwrap();
+    assert_eq!(h.balance(order.escrow_token_account).await, 600_000);
+}
+
+#[tokio::test]
+async fn test_fill_conditional_order_requires_its_oracle() {
+    let mut h = Harness::new().await;
+    let (oracle, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let now = h.unix_timestamp().await as i64;
+    h.set_pyth_price(oracle, 9_000_000_000, 0, -8, now);
+    h.set_pyth_price(other, 9_000_000_000, 0, -8, now);
+    let mut order = place(&mut h, 900_000, Some(stop_loss(oracle)), None).await;
+
+    for oracle in [None, Some(other)] {
+        order.oracle = oracle;
+        let result = h.process(&[fill_ix(&h, &order, 400_000)], &[]).await;
+        assert_eq!(error_code(result), Some(LimitOrderError::InvalidOracle.into()));
+    }
+}
+
+#[tokio::test]
+async fn test_fill_order_pays_designated_receiver() {
+    let mut h = Harness::new().await;
+    let receiver = Pubkey::new_unique();
+    let mut order = place(&mut h, 900_000, None, Some(receiver)).await;
+    let state = load_order(&mut h, order.order_pda).await.unwrap();
+    assert_eq!(state.receiver, receiver);
+    assert_eq!(state.output_receiver(), receiver);
+
+    // The maker's own token account is not the receiver's
+    let receiver_token_account = order.output_token_account;
+    order.output_token_account = h.create_token_account(order.mint_b, h.payer(), 0);
+    let result = h.process(&[fill_ix(&h, &order, MAKING_AMOUNT)], &[]).await;
+    assert_eq!(error_code(result), Some(LimitOrderError::InvalidOutputTokenOwner.into()));
+
+    order.output_token_account = receiver_token_account;
+    h.process(&[fill_ix(&h, &order, MAKING_AMOUNT)], &[]).await.unwrap();
+    let taking = mock_token_swap::quote(1_000_000_000, 1_000_000_000, MAKING_AMOUNT);
+    assert_eq!(h.balance(receiver_token_account).await, taking);
+    assert!(load_order(&mut h, order.order_pda).await.is_none());
+}
@@ -0,0 +1,248 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::{ed25519_program, sysvar};
+use anchor_lang::system_program;
+use anchor_spl::token::spl_token;
+use common::{Harness, TokenSwapPool, error_code, mock_token_swap, router_instruction};
+use dex_solana::constants::{
+    ESCROW_TOKEN_SEED, INTENT_DELEGATE_SEED, INTENT_NONCES_PER_PAGE, INTENT_NONCES_SEED,
+};
+use dex_solana::error::LimitOrderError;
+use dex_solana::{Dex, OrderIntent, Route, SwapArgs, accounts, instruction};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
+
+const MAKING_AMOUNT: u64 = 1_000_000;
+
+struct Setup {
+    h: Harness,
+    maker: Keypair,
+    global_config: Pubkey,
+    resolver_pda: Pubkey,
+    intent_delegate: Pubkey,
+    input_token_account: Pubkey,
+    output_token_account: Pubkey,
+    pool: TokenSwapPool,
+    intent: OrderIntent,
+}
+
+fn event_authority() -> Pubkey {
+    Pubkey::find_program_address(&[b"__event_authority"], &dex_solana::ID).0
+}
+
+/// Ed25519 program instruction verifying a single signature stored inline
+fn ed25519_instruction(signer: &Keypair, message: &[u8]) -> Instruction {
+    const PUBLIC_KEY_OFFSET: u16 = 16;
+    const SIGNATURE_OFFSET: u16 = PUBLIC_KEY_OFFSET + 32;
+    const MESSAGE_OFFSET: u16 = SIGNATURE_OFFSET + 64;
+    let mut data = vec![1, 0];
+    for offset in [
+        SIGNATURE_OFFSET,
+        u16::MAX,
+        PUBLIC_KEY_OFFSET,
+        u16::MAX,
+        MESSAGE_OFFSET,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&offset.to_le_bytes());
+    }
+    data.extend_from_slice(signer.pubkey().as_ref());
+    data.extend_from_slice(signer.sign_message(message).as_ref());
+    data.extend_from_slice(message);
+    Instruction { program_id: ed25519_program::ID, accounts: vec![], data }
+}
+
+/// A maker approving the intent delegate to sell `MAKING_AMOUNT` of token a against a 1:1
+/// pool, the payer is the resolver
+async fn setup() -> Setup {
+    let mut h = Harness::new().await;
+    let maker = Keypair::new();
+    let (mint_a, mint_b) = (h.create_mint(), h.create_mint());
+    let pool = h.create_token_swap_pool(mint_a, mint_b, 1_000_000_000, 1_000_000_000);
+    let global_config = h.create_global_config(h.payer());
+    let resolver_pda = h.create_resolver(h.payer());
+    let input_token_account = h.create_token_account(mint_a, maker.pubkey(), 2 * MAKING_AMOUNT);
+    let output_token_account = h.create_token_account(mint_b, maker.pubkey(), 0);
+    let intent_delegate = Pubkey::find_program_address(
+        &[INTENT_DELEGATE_SEED.as_bytes(), maker.pubkey().as_ref()],
+        &dex_solana::ID,
+    )
+    .0;
+
+    let ix = spl_token::instruction::approve(
+        &spl_token::ID,
+        &input_token_account,
+        &intent_delegate,
+        &maker.pubkey(),
+        &[],
+        2 * MAKING_AMOUNT,
+    )
+    .unwrap();
+    h.process(&[ix], &[&maker]).await.unwrap();
+
+    let intent = OrderIntent {
+        maker: maker.pubkey(),
+        nonce: 7,
+        input_token_mint: mint_a,
+        output_token_mint: mint_b,
+        making_amount: MAKING_AMOUNT,
+        min_return_amount: 990_000,
+        deadline: h.unix_timestamp().await + 600,
+    };
+    Setup {
+        h,
+        maker,
+        global_config,
+        resolver_pda,
+        intent_delegate,
+        input_token_account,
+        output_token_account,
+        pool,
+        intent,
+    }
+}
+
+impl Setup {
+    fn intent_nonces(&self) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                INTENT_NONCES_SEED.as_bytes(),
+                self.maker.pubkey().as_ref(),
+                &(self.intent.nonce / INTENT_NONCES_PER_PAGE).to_le_bytes(),
+            ],
+            &dex_solana::ID,
+        )
+        .0
+    }
+
+    fn escrow_token_account(&self, intent: &OrderIntent) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                ESCROW_TOKEN_SEED.as_bytes(),
+                self.intent_delegate.as_ref(),
+                intent.input_token_mint.as_ref(),
+            ],
+            &dex_solana::ID,
+        )
+        .0
+    }
+
+    fn fill_ix(&self, intent: OrderIntent) -> Instruction {
+        let escrow_token_account = self.escrow_token_account(&intent);
+        let args = SwapArgs {
+            amount_in: intent.making_amount,
+            expect_amount_out: 1,
+            min_return: 1,
+            amounts: vec![intent.making_amount],
+            routes: vec![vec![Route { dexes: vec![Dex::SplTokenSwap], weights: vec![100] }]],
+        };
+        router_instruction(
+            accounts::FillOrderIntent {
+                payer: self.h.payer(),
+                maker: self.maker.pubkey(),
+                global_config: self.global_config,
+                resolver_pda: self.resolver_pda,
+                intent_nonces: self.intent_nonces(),
+                intent_delegate: self.intent_delegate,
+                sa_authority: None,
+                input_token_sa: None,
+                output_token_sa: None,
+                input_token_account: self.input_token_account,
+                escrow_token_account,
+                output_token_account: self.output_token_account,
+                input_token_mint: intent.input_token_mint,
+                output_token_mint: intent.output_token_mint,
+                input_token_program: spl_token::ID,
+                output_token_program: spl_token::ID,
+                associated_token_program: None,
+                system_program: system_program::ID,
+                instructions_sysvar: sysvar::instructions::ID,
+                event_authority: event_authority(),
+                program: dex_solana::ID,
+            },
+            instruction::FillOrderIntent { intent, args },
+            self.pool.accounts(
+                self.intent_delegate,
+                escrow_token_account,
+                self.output_token_account,
+            ),
+        )
+    }
+
+    /// The signed intent fill: the ed25519 verification right before the router instruction
+    fn signed_fill(&self, signer: &Keypair) -> [Instruction; 2] {
+        [
+            ed25519_instruction(signer, &self.intent.message().unwrap()),
+            self.fill_ix(self.intent.clone()),
+        ]
+    }
+}
+
+#[tokio::test]
+async fn test_fill_order_intent_once() {
+    let mut s = setup().await;
+    let maker = s.maker.insecure_clone();
+    s.h.process(&s.signed_fill(&maker), &[]).await.unwrap();
+    let taking = mock_token_swap::quote(1_000_000_000, 1_000_000_000, MAKING_AMOUNT);
+    assert_eq!(s.h.balance(s.input_token_account).await, MAKING_AMOUNT);
+    assert_eq!(s.h.balance(s.output_token_account).await, taking);
+
+    // The nonce is spent, replaying the same signed intent fails
+    let result = s.h.process(&s.signed_fill(&maker), &[]).await;
+    assert_eq!(error_code(result), Some(LimitOrderError::IntentNonceUsed.into()));
+    assert_eq!(s.h.balance(s.input_token_account).await, MAKING_AMOUNT);
+
+    // Another nonce of the same page fills
+    s.intent.nonce += 1;
+    s.h.process(&s.signed_fill(&maker), &[]).await.unwrap();
+    assert_eq!(s.h.balance(s.input_token_account).await, 0);
+}
+
+#[tokio::test]
+async fn test_fill_order_intent_rejects_bad_signature() {
+    let mut s = setup().await;
+
+    // Signed by someone else
+    let result = s.h.process(&s.signed_fill(&Keypair::new()), &[]).await;
+    assert_eq!(error_code(result), Some(LimitOrderError::InvalidIntentSignature.into()));
+
+    // The resolver lowers the min return of a signed intent
+    let maker = s.maker.insecure_clone();
+    let mut intent = s.intent.clone();
+    intent.min_return_amount = 1;
+    let ixs = [ed25519_instruction(&maker, &s.intent.message().unwrap()), s.fill_ix(intent)];
+    let result = s.h.process(&ixs, &[]).await;
+    assert_eq!(error_code(result), Some(LimitOrderError::InvalidIntentSignature.into()));
+
+    // No verification instruction
+    let result = s.h.process(&[s.fill_ix(s.intent.clone())], &[]).await;
+    assert_eq!(error_code(result), Some(LimitOrderError::InvalidIntentSignature.into()));
+    assert_eq!(s.h.balance(s.input_token_account).await, 2 * MAKING_AMOUNT);
+}
+
+#[tokio::test]
+async fn test_fill_order_intent_requires_delegate_and_min_return() {
+    let mut s = setup().await;
+    let maker = s.maker.insecure_clone();
+
+    // The pool fee leaves less than the min return
+    s.intent.min_return_amount = MAKING_AMOUNT;
+    let result = s.h.process(&s.signed_fill(&maker), &[]).await;
+    assert_eq!(error_code(result), Some(dex_solana::error::ErrorCode::MinReturnNotReached.into()));
+
+    // The maker revokes the delegate
+    let ix = spl_token::instruction::revoke(
+        &spl_token::ID,
+        &s.input_token_account,
+        &maker.pubkey(),
+        &[],
+    )
+    .unwrap();
+    s.h.process(&[ix], &[&maker]).await.unwrap();
+    s.intent.min_return_amount = 990_000;
+    let result = s.h.process(&s.signed_fill(&maker), &[]).await;
+    assert_eq!(error_code(result), Some(LimitOrderError::InvalidIntentDelegate.into()));
+}
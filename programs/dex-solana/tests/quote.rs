@@ -0,0 +1,149 @@
+mod common;
+
+use anchor_lang::AnchorDeserialize;
+use anchor_lang::prelude::Pubkey;
+use common::{Harness, error_code, mock_bonding_curve, mock_token_swap, router_instruction};
+use dex_solana::error::ErrorCode;
+use dex_solana::{Dex, QuoteResult, Route, SwapArgs, accounts, instruction};
+use solana_sdk::instruction::{Instruction, InstructionError};
+use solana_sdk::transaction::TransactionError;
+
+const Q64: u128 = 1 << 64;
+
+struct Setup {
+    h: Harness,
+    mint_a: Pubkey,
+    mint_b: Pubkey,
+    source: Pubkey,
+    destination: Pubkey,
+}
+
+async fn setup() -> Setup {
+    let mut h = Harness::new().await;
+    let (mint_a, mint_b) = (h.create_mint(), h.create_mint());
+    let source = h.create_token_account(mint_a, h.payer(), 10_000_000);
+    let destination = h.create_token_account(mint_b, h.payer(), 0);
+    Setup { h, mint_a, mint_b, source, destination }
+}
+
+impl Setup {
+    fn quote_ix(
+        &self,
+        args: SwapArgs,
+        commission_info: u32,
+        trim_rate: u8,
+        remaining_accounts: Vec<anchor_lang::prelude::AccountMeta>,
+    ) -> Instruction {
+        router_instruction(
+            accounts::QuoteAccounts {
+                payer: self.h.payer(),
+                source_token_account: self.source,
+                destination_token_account: self.destination,
+                source_mint: self.mint_a,
+                destination_mint: self.mint_b,
+            },
+            instruction::Quote {
+                args,
+                commission_info,
+                platform_fee_rate: 0,
+                trim_rate,
+                charge_rate: 0,
+            },
+            remaining_accounts,
+        )
+    }
+}
+
+fn quote_only() -> Result<(), TransactionError> {
+    Err(TransactionError::InstructionError(
+        0,
+        InstructionError::Custom(ErrorCode::QuoteOnly.into()),
+    ))
+}
+
+#[tokio::test]
+async fn test_quote_split_route() {
+    let mut s = setup().await;
+    let amm = s.h.create_token_swap_pool(s.mint_a, s.mint_b, 1_000_000_000, 1_000_000_000);
+    let clmm = s.h.create_clmm_pool(s.mint_a, s.mint_b, Q64, 1_000_000_000, 1_000_000_000);
+
+    let mut remaining_accounts = amm.accounts(s.h.payer(), s.source, s.destination);
+    remaining_accounts.extend(clmm.accounts(s.h.payer(), s.source, s.destination));
+    let args = SwapArgs {
+        amount_in: 1_000_000,
+        expect_amount_out: 990_000,
+        min_return: 1,
+        amounts: vec![1_000_000],
+        routes: vec![vec![Route {
+            dexes: vec![Dex::SplTokenSwap, Dex::Whirlpool],
+            weights: vec![50, 50],
+        }]],
+    };
+    // 1% commission on the output
+    let ix = s.quote_ix(args, 10_000_000, 0, remaining_accounts);
+    let (result, return_data) = s.h.simulate(&[ix]).await;
+    assert_eq!(result, quote_only());
+
+    let quote = QuoteResult::try_from_slice(&return_data.unwrap()).unwrap();
+    assert_eq!(quote.amount_in, 1_000_000);
+    assert_eq!(quote.forks.len(), 2);
+    assert_eq!(quote.forks[0].dex, Dex::SplTokenSwap);
+    assert_eq!(quote.forks[0].amount_in, 500_000);
+    assert_eq!(
+        quote.forks[0].amount_out,
+        mock_token_swap::quote(1_000_000_000, 1_000_000_000, 500_000)
+    );
+    assert_eq!(quote.forks[1].dex, Dex::Whirlpool);
+    assert_eq!(quote.amount_out, quote.forks[0].amount_out + quote.forks[1].amount_out);
+    assert_eq!(quote.commission_amount, quote.amount_out / 100);
+    assert_eq!(quote.actual_amount_out, quote.amount_out - quote.commission_amount);
+}
+
+#[tokio::test]
+async fn test_quote_has_no_effect() {
+    let mut s = setup().await;
+    let pool = s.h.create_token_swap_pool(s.mint_a, s.mint_b, 1_000_000_000, 1_000_000_000);
+
+    let args = SwapArgs {
+        amount_in: 1_000_000,
+        expect_amount_out: 1,
+        min_return: 1,
+        amounts: vec![1_000_000],
+        routes: vec![vec![Route { dexes: vec![Dex::SplTokenSwap], weights: vec![100] }]],
+    };
+    let ix = s.quote_ix(args, 0, 0, pool.accounts(s.h.payer(), s.source, s.destination));
+    let result = s.h.process(&[ix], &[]).await;
+    assert_eq!(error_code(result), Some(ErrorCode::QuoteOnly.into()));
+    assert_eq!(s.h.balance(s.source).await, 10_000_000);
+    assert_eq!(s.h.balance(s.destination).await, 0);
+}
+
+#[tokio::test]
+async fn test_quote_transfer_fee_output() {
+    let mut h = Harness::new().await;
+    let mint_a = h.create_mint();
+    // 1% withheld on every transfer of the bought token
+    let mint_b = h.create_transfer_fee_mint(100);
+    let curve = h.create_token_2022_bonding_curve(mint_a, mint_b, 100_000_000, 1_000_000_000).await;
+    let source = h.create_token_account(mint_a, h.payer(), 10_000_000);
+    let destination = h.create_token_2022_account(mint_b, h.payer(), 0).await;
+    let mut s = Setup { h, mint_a, mint_b, source, destination };
+
+    let args = SwapArgs {
+        amount_in: 10_000_000,
+        expect_amount_out: 1,
+        min_return: 1,
+        amounts: vec![10_000_000],
+        routes: vec![vec![Route { dexes: vec![Dex::VertigoBuy], weights: vec![100] }]],
+    };
+    let ix = s.quote_ix(args, 0, 0, curve.accounts(s.h.payer(), s.source, s.destination));
+    let (result, return_data) = s.h.simulate(&[ix]).await;
+    assert_eq!(result, quote_only());
+
+    let quote = QuoteResult::try_from_slice(&return_data.unwrap()).unwrap();
+    let gross = mock_bonding_curve::quote(100_000_000, 1_000_000_000, 10_000_000);
+    let fee = gross.div_ceil(100);
+    assert_eq!(quote.forks[0].transfer_fee, fee);
+    assert_eq!(quote.forks[0].amount_out, gross - fee);
+    assert_eq!(quote.amount_out, gross - fee);
+}
@@ -0,0 +1,210 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::{AccountDeserialize, system_program};
+use anchor_spl::token::spl_token;
+use common::{Harness, TokenSwapPool, error_code, mock_token_swap, router_instruction};
+use dex_solana::constants::{ESCROW_TOKEN_SEED, RECURRING_ORDER_SEED};
+use dex_solana::error::LimitOrderError;
+use dex_solana::state::recurring_order::RecurringOrder;
+use dex_solana::{Dex, Route, SwapArgs, accounts, instruction};
+use solana_sdk::instruction::Instruction;
+
+const ORDER_ID: u64 = 11;
+const MAKING_AMOUNT: u64 = 1_000_001;
+const SLICE_COUNT: u16 = 4;
+const INTERVAL: u64 = 3600;
+
+struct Order {
+    mint_a: Pubkey,
+    mint_b: Pubkey,
+    global_config: Pubkey,
+    resolver_pda: Pubkey,
+    order_pda: Pubkey,
+    escrow_token_account: Pubkey,
+    input_token_account: Pubkey,
+    output_token_account: Pubkey,
+    pool: TokenSwapPool,
+}
+
+fn event_authority() -> Pubkey {
+    Pubkey::find_program_address(&[b"__event_authority"], &dex_solana::ID).0
+}
+
+/// Sells `MAKING_AMOUNT` of token a in 4 hourly slices against a 1:1 pool, the maker is also
+/// a resolver
+async fn place_order(h: &mut Harness, slice_min_return_amount: u64) -> Order {
+    let maker = h.payer();
+    let (mint_a, mint_b) = (h.create_mint(), h.create_mint());
+    let pool = h.create_token_swap_pool(mint_a, mint_b, 1_000_000_000, 1_000_000_000);
+    let global_config = h.create_global_config(maker);
+    let resolver_pda = h.create_resolver(maker);
+    let input_token_account = h.create_token_account(mint_a, maker, MAKING_AMOUNT);
+    let output_token_account = h.create_token_account(mint_b, maker, 0);
+    let order_pda = Pubkey::find_program_address(
+        &[RECURRING_ORDER_SEED.as_bytes(), &ORDER_ID.to_le_bytes(), maker.as_ref()],
+        &dex_solana::ID,
+    )
+    .0;
+    let escrow_token_account = Pubkey::find_program_address(
+        &[ESCROW_TOKEN_SEED.as_bytes(), order_pda.as_ref(), mint_a.as_ref()],
+        &dex_solana::ID,
+    )
+    .0;
+
+    let ix = router_instruction(
+        accounts::PlaceRecurringOrder {
+            maker,
+            global_config,
+            order_pda,
+            escrow_token_account,
+            input_token_account,
+            input_token_mint: mint_a,
+            output_token_mint: mint_b,
+            input_token_program: spl_token::ID,
+            output_token_program: spl_token::ID,
+            system_program: system_program::ID,
+            event_authority: event_authority(),
+            program: dex_solana::ID,
+        },
+        instruction::PlaceRecurringOrder {
+            order_id: ORDER_ID,
+            making_amount: MAKING_AMOUNT,
+            slice_count: SLICE_COUNT,
+            slice_min_return_amount,
+            interval: INTERVAL,
+            trade_fee: 100_000,
+        },
+        vec![],
+    );
+    h.process(&[ix], &[]).await.unwrap();
+
+    Order {
+        mint_a,
+        mint_b,
+        global_config,
+        resolver_pda,
+        order_pda,
+        escrow_token_account,
+        input_token_account,
+        output_token_account,
+        pool,
+    }
+}
+
+fn fill_ix(h: &Harness, order: &Order, amount_in: u64) -> Instruction {
+    let args = SwapArgs {
+        amount_in,
+        expect_amount_out: 1,
+        min_return: 1,
+        amounts: vec![amount_in],
+        routes: vec![vec![Route { dexes: vec![Dex::SplTokenSwap], weights: vec![100] }]],
+    };
+    router_instruction(
+        accounts::FillRecurringOrder {
+            payer: h.payer(),
+            maker: h.payer(),
+            global_config: order.global_config,
+            resolver_pda: order.resolver_pda,
+            sa_authority: None,
+            input_token_sa: None,
+            output_token_sa: None,
+            order_pda: order.order_pda,
+            escrow_token_account: order.escrow_token_account,
+            output_token_account: order.output_token_account,
+            input_token_mint: order.mint_a,
+            output_token_mint: order.mint_b,
+            input_token_program: spl_token::ID,
+            output_token_program: spl_token::ID,
+            associated_token_program: None,
+            system_program: None,
+            instructions_sysvar: sysvar::instructions::ID,
+            event_authority: event_authority(),
+            program: dex_solana::ID,
+        },
+        instruction::FillRecurringOrder { order_id: ORDER_ID, tips: 0, args },
+        order.pool.accounts(
+            order.order_pda,
+            order.escrow_token_account,
+            order.output_token_account,
+        ),
+    )
+}
+
+async fn load_order(h: &mut Harness, order_pda: Pubkey) -> Option<RecurringOrder> {
+    let account = h.context.banks_client.get_account(order_pda).await.unwrap()?;
+    Some(RecurringOrder::try_deserialize(&mut account.data.as_slice()).unwrap())
+}
+
+#[tokio::test]
+async fn test_fill_recurring_order_one_slice_per_interval() {
+    let mut h = Harness::new().await;
+    let order = place_order(&mut h, 240_000).await;
+
+    // Only the exact slice is accepted
+    let result = h.process(&[fill_ix(&h, &order, 250_001)], &[]).await;
+    assert_eq!(error_code(result), Some(LimitOrderError::InvalidFillAmount.into()));
+    h.process(&[fill_ix(&h, &order, 250_000)], &[]).await.unwrap();
+    assert_eq!(h.balance(order.escrow_token_account).await, 750_001);
+
+    // The next slice waits for the interval
+    let result = h.process(&[fill_ix(&h, &order, 250_000)], &[]).await;
+    assert_eq!(error_code(result), Some(LimitOrderError::SliceNotReady.into()));
+
+    for _ in 0..2 {
+        h.warp_forward(INTERVAL as i64).await;
+        h.process(&[fill_ix(&h, &order, 250_000)], &[]).await.unwrap();
+    }
+    let state = load_order(&mut h, order.order_pda).await.unwrap();
+    assert_eq!(state.filled_slices, 3);
+    assert_eq!(state.filled_making_amount, 750_000);
+    assert_eq!(h.balance(order.output_token_account).await, state.filled_taking_amount);
+
+    // The last slice takes the rounding remainder and settles the order
+    h.warp_forward(INTERVAL as i64).await;
+    h.process(&[fill_ix(&h, &order, 250_001)], &[]).await.unwrap();
+    assert!(load_order(&mut h, order.order_pda).await.is_none());
+    assert!(
+        h.context.banks_client.get_account(order.escrow_token_account).await.unwrap().is_none()
+    );
+}
+
+#[tokio::test]
+async fn test_fill_recurring_order_below_slice_min_return() {
+    let mut h = Harness::new().await;
+    // A 1:1 price leaves no room for the pool fee
+    let order = place_order(&mut h, 250_000).await;
+
+    let result = h.process(&[fill_ix(&h, &order, 250_000)], &[]).await;
+    assert_eq!(error_code(result), Some(dex_solana::error::ErrorCode::MinReturnNotReached.into()));
+    assert_eq!(h.balance(order.escrow_token_account).await, MAKING_AMOUNT);
+}
+
+#[tokio::test]
+async fn test_cancel_recurring_order_refunds_remainder() {
+    let mut h = Harness::new().await;
+    let order = place_order(&mut h, 240_000).await;
+    h.process(&[fill_ix(&h, &order, 250_000)], &[]).await.unwrap();
+    let taking = mock_token_swap::quote(1_000_000_000, 1_000_000_000, 250_000);
+    assert_eq!(h.balance(order.output_token_account).await, taking);
+
+    let ix = router_instruction(
+        accounts::CancelRecurringOrder {
+            maker: h.payer(),
+            global_config: order.global_config,
+            order_pda: order.order_pda,
+            escrow_token_account: order.escrow_token_account,
+            input_token_account: order.input_token_account,
+            input_token_mint: order.mint_a,
+            input_token_program: spl_token::ID,
+            event_authority: event_authority(),
+            program: dex_solana::ID,
+        },
+        instruction::CancelRecurringOrder { order_id: ORDER_ID },
+        vec![],
+    );
+    h.process(&[ix], &[]).await.unwrap();
+    assert_eq!(h.balance(order.input_token_account).await, 750_001);
+    assert!(load_order(&mut h, order.order_pda).await.is_none());
+}
@@ -0,0 +1,382 @@
+mod common;
+
+use anchor_lang::AnchorDeserialize;
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use common::{Harness, error_code, mock_bonding_curve, mock_token_swap, router_instruction};
+use dex_solana::error::ErrorCode;
+use dex_solana::{
+    Dex, Route, SwapArgs, SwapResult, SwapResultV1, accounts, authority_pda, instruction,
+};
+use dex_solana_client::{Hop, RawAccounts, RouteBuilder};
+use solana_sdk::instruction::Instruction;
+
+const Q64: u128 = 1 << 64;
+
+fn route(dexes: Vec<Dex>, weights: Vec<u8>) -> Route {
+    Route { dexes, weights }
+}
+
+fn swap_args(amount_in: u64, min_return: u64, routes: Vec<Vec<Route>>) -> SwapArgs {
+    SwapArgs {
+        amount_in,
+        expect_amount_out: min_return,
+        min_return,
+        amounts: vec![amount_in],
+        routes,
+    }
+}
+
+fn swap_ix(
+    h: &Harness,
+    (source, source_mint): (Pubkey, Pubkey),
+    (destination, destination_mint): (Pubkey, Pubkey),
+    args: SwapArgs,
+    remaining_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    router_instruction(
+        accounts::SwapAccounts {
+            payer: h.payer(),
+            source_token_account: source,
+            destination_token_account: destination,
+            source_mint,
+            destination_mint,
+        },
+        instruction::Swap { data: args, order_id: 1 },
+        remaining_accounts,
+    )
+}
+
+#[tokio::test]
+async fn test_swap_single_dex() {
+    let mut h = Harness::new().await;
+    let (mint_a, mint_b) = (h.create_mint(), h.create_mint());
+    let pool = h.create_token_swap_pool(mint_a, mint_b, 1_000_000_000, 2_000_000_000);
+    let source = h.create_token_account(mint_a, h.payer(), 10_000_000);
+    let destination = h.create_token_account(mint_b, h.payer(), 0);
+
+    let expected = mock_token_swap::quote(1_000_000_000, 2_000_000_000, 1_000_000);
+    let ix = swap_ix(
+        &h,
+        (source, mint_a),
+        (destination, mint_b),
+        swap_args(1_000_000, expected, vec![vec![route(vec![Dex::SplTokenSwap], vec![100])]]),
+        pool.accounts(h.payer(), source, destination),
+    );
+    h.process(&[ix], &[]).await.unwrap();
+
+    assert_eq!(h.balance(source).await, 9_000_000);
+    assert_eq!(h.balance(destination).await, expected);
+    assert_eq!(h.balance(pool.token_a_account).await, 1_001_000_000);
+}
+
+#[tokio::test]
+async fn test_swap_returns_swap_result() {
+    let mut h = Harness::new().await;
+    let (mint_a, mint_b) = (h.create_mint(), h.create_mint());
+    let pool = h.create_token_swap_pool(mint_a, mint_b, 1_000_000_000, 1_000_000_000);
+    let source = h.create_token_account(mint_a, h.payer(), 10_000_000);
+    let destination = h.create_token_account(mint_b, h.payer(), 0);
+
+    let ix = swap_ix(
+        &h,
+        (source, mint_a),
+        (destination, mint_b),
+        swap_args(1_000_000, 1, vec![vec![route(vec![Dex::SplTokenSwap], vec![100])]]),
+        pool.accounts(h.payer(), source, destination),
+    );
+    let (result, return_data) = h.simulate(&[ix]).await;
+    result.unwrap();
+
+    let expected = mock_token_swap::quote(1_000_000_000, 1_000_000_000, 1_000_000);
+    assert_eq!(
+        SwapResult::try_from_slice(&return_data.unwrap()).unwrap(),
+        SwapResult::V1(SwapResultV1::new(1_000_000, expected))
+    );
+}
+
+#[tokio::test]
+async fn test_swap_split() {
+    let mut h = Harness::new().await;
+    let (mint_a, mint_b) = (h.create_mint(), h.create_mint());
+    let amm = h.create_token_swap_pool(mint_a, mint_b, 1_000_000_000, 1_000_000_000);
+    let clmm = h.create_clmm_pool(mint_a, mint_b, Q64, 1_000_000_000, 1_000_000_000);
+    let source = h.create_token_account(mint_a, h.payer(), 10_000_000);
+    let destination = h.create_token_account(mint_b, h.payer(), 0);
+
+    // Remaining accounts follow the execution order: route, hop, dex
+    let mut remaining_accounts = amm.accounts(h.payer(), source, destination);
+    remaining_accounts.extend(clmm.accounts(h.payer(), source, destination));
+    remaining_accounts.extend(amm.accounts(h.payer(), source, destination));
+    let args = SwapArgs {
+        amount_in: 1_000_000,
+        expect_amount_out: 1,
+        min_return: 1,
+        amounts: vec![700_000, 300_000],
+        routes: vec![
+            vec![route(vec![Dex::SplTokenSwap, Dex::Whirlpool], vec![60, 40])],
+            vec![route(vec![Dex::SplTokenSwap], vec![100])],
+        ],
+    };
+    let ix = swap_ix(&h, (source, mint_a), (destination, mint_b), args, remaining_accounts);
+    h.process(&[ix], &[]).await.unwrap();
+
+    // The first fork gets its weight, the last fork the remainder
+    assert_eq!(h.balance(amm.token_a_account).await, 1_000_000_000 + 420_000 + 300_000);
+    assert_eq!(h.balance(clmm.token_vault_a).await, 1_000_000_000 + 280_000);
+    assert_eq!(h.balance(source).await, 9_000_000);
+    let amm_out = 1_000_000_000 - h.balance(amm.token_b_account).await;
+    let clmm_out = 1_000_000_000 - h.balance(clmm.token_vault_b).await;
+    assert_eq!(h.balance(destination).await, amm_out + clmm_out);
+    assert!(h.clmm_sqrt_price(&clmm).await < Q64);
+}
+
+#[tokio::test]
+async fn test_swap_split_rolls_lot_dust() {
+    let mut h = Harness::new().await;
+    let (mint_a, mint_b) = (h.create_mint(), h.create_mint());
+    let book = h.create_order_book(mint_a, mint_b, 2_000, 1_000, 1, 1_000_000_000);
+    let amm = h.create_token_swap_pool(mint_a, mint_b, 1_000_000_000, 1_000_000_000);
+    let source = h.create_token_account(mint_a, h.payer(), 10_000_000);
+    let destination = h.create_token_account(mint_b, h.payer(), 0);
+
+    let mut remaining_accounts = book.accounts(h.payer(), source, destination);
+    remaining_accounts.extend(amm.accounts(h.payer(), source, destination));
+    let ix = swap_ix(
+        &h,
+        (source, mint_a),
+        (destination, mint_b),
+        swap_args(
+            10_500,
+            1,
+            vec![vec![route(vec![Dex::Phoenix, Dex::SplTokenSwap], vec![50, 50])]],
+        ),
+        remaining_accounts,
+    );
+    h.process(&[ix], &[]).await.unwrap();
+
+    // The book fills 5 whole lots of its 5_250 share, the sub-lot dust goes to the next fork
+    assert_eq!(h.balance(book.base_vault).await, 1_000_000_000 + 5_000);
+    assert_eq!(h.balance(amm.token_a_account).await, 1_000_000_000 + 5_500);
+    assert_eq!(h.balance(source).await, 10_000_000 - 10_500);
+    assert_eq!(
+        h.balance(destination).await,
+        10_000 + mock_token_swap::quote(1_000_000_000, 1_000_000_000, 5_500)
+    );
+}
+
+#[tokio::test]
+async fn test_swap_multi_hop() {
+    let mut h = Harness::new().await;
+    let (mint_a, mint_b, mint_c) = (h.create_mint(), h.create_mint(), h.create_mint());
+    let amm = h.create_token_swap_pool(mint_a, mint_b, 1_000_000_000, 1_000_000_000);
+    let curve = h.create_bonding_curve(mint_b, mint_c, 500_000_000, 1_000_000_000);
+    let source = h.create_token_account(mint_a, h.payer(), 10_000_000);
+    // Intermediate hops are signed by the router's SA authority
+    let middle = h.create_token_account(mint_b, authority_pda::ID, 0);
+    let destination = h.create_token_account(mint_c, h.payer(), 0);
+
+    let mut remaining_accounts = amm.accounts(h.payer(), source, middle);
+    remaining_accounts.extend(curve.accounts(authority_pda::ID, middle, destination));
+    let routes = vec![vec![
+        route(vec![Dex::SplTokenSwap], vec![100]),
+        route(vec![Dex::VertigoBuy], vec![100]),
+    ]];
+    let ix = swap_ix(
+        &h,
+        (source, mint_a),
+        (destination, mint_c),
+        swap_args(1_000_000, 1, routes),
+        remaining_accounts,
+    );
+    h.process(&[ix], &[]).await.unwrap();
+
+    // Every intermediate token is consumed by the second hop
+    let hop1_in = h.balance(curve.vault_a).await;
+    assert_eq!(h.balance(middle).await, 0);
+    assert_eq!(
+        h.balance(destination).await,
+        mock_bonding_curve::quote(500_000_000, 1_000_000_000, hop1_in)
+    );
+}
+
+#[tokio::test]
+async fn test_swap_multi_hop_refunds_order_book_dust() {
+    let mut h = Harness::new().await;
+    let (mint_a, mint_b, mint_c) = (h.create_mint(), h.create_mint(), h.create_mint());
+    let amm = h.create_token_swap_pool(mint_a, mint_b, 1_000_000_000, 1_000_000_000);
+    let book = h.create_order_book(mint_b, mint_c, 2_000, 1_000, 1, 1_000_000_000);
+    let source = h.create_token_account(mint_a, h.payer(), 10_000_000);
+    let middle = h.create_token_account(mint_b, authority_pda::ID, 0);
+    let dust_refund = h.create_token_account(mint_b, h.payer(), 0);
+    let destination = h.create_token_account(mint_c, h.payer(), 0);
+
+    // An order book hop after the first is followed by the dust refund account and its input mint
+    let mut remaining_accounts = amm.accounts(h.payer(), source, middle);
+    remaining_accounts.extend(book.accounts(authority_pda::ID, middle, destination));
+    remaining_accounts.push(AccountMeta::new(dust_refund, false));
+    remaining_accounts.push(AccountMeta::new_readonly(mint_b, false));
+    let routes = vec![vec![
+        route(vec![Dex::SplTokenSwap], vec![100]),
+        route(vec![Dex::Phoenix], vec![100]),
+    ]];
+    let ix = swap_ix(
+        &h,
+        (source, mint_a),
+        (destination, mint_c),
+        swap_args(1_000_000, 1, routes),
+        remaining_accounts,
+    );
+    h.process(&[ix], &[]).await.unwrap();
+
+    // The book fills whole lots of the first hop output, the sub-lot dust goes back to the user
+    let hop1_in = mock_token_swap::quote(1_000_000_000, 1_000_000_000, 1_000_000);
+    assert!(!hop1_in.is_multiple_of(1_000));
+    assert_eq!(h.balance(middle).await, 0);
+    assert_eq!(h.balance(dust_refund).await, hop1_in % 1_000);
+    assert_eq!(h.balance(book.base_vault).await, 1_000_000_000 + hop1_in / 1_000 * 1_000);
+    assert_eq!(h.balance(destination).await, hop1_in / 1_000 * 2_000);
+}
+
+#[tokio::test]
+async fn test_swap_client_route_refunds_order_book_dust() {
+    let mut h = Harness::new().await;
+    let (mint_a, mint_b, mint_c) = (h.create_mint(), h.create_mint(), h.create_mint());
+    let amm = h.create_token_swap_pool(mint_a, mint_b, 1_000_000_000, 1_000_000_000);
+    let book = h.create_order_book(mint_b, mint_c, 2_000, 1_000, 1, 1_000_000_000);
+    let source = h.create_token_account(mint_a, h.payer(), 10_000_000);
+    let middle = h.create_token_account(mint_b, authority_pda::ID, 0);
+    let dust_refund = h.create_token_account(mint_b, h.payer(), 0);
+    let destination = h.create_token_account(mint_c, h.payer(), 0);
+
+    let plan = RouteBuilder::new(1_000_000, 1, 1)
+        .route(
+            1_000_000,
+            vec![
+                Hop::new().dex(
+                    Dex::SplTokenSwap,
+                    100,
+                    RawAccounts(amm.accounts(h.payer(), source, middle)),
+                ),
+                Hop::new()
+                    .dex(
+                        Dex::Phoenix,
+                        100,
+                        RawAccounts(book.accounts(authority_pda::ID, middle, destination)),
+                    )
+                    .dust_refund(dust_refund, mint_b),
+            ],
+        )
+        .build()
+        .unwrap();
+    let ix =
+        swap_ix(&h, (source, mint_a), (destination, mint_c), plan.args, plan.remaining_accounts);
+    h.process(&[ix], &[]).await.unwrap();
+
+    let hop1_in = mock_token_swap::quote(1_000_000_000, 1_000_000_000, 1_000_000);
+    assert_eq!(h.balance(middle).await, 0);
+    assert_eq!(h.balance(dust_refund).await, hop1_in % 1_000);
+    assert_eq!(h.balance(destination).await, hop1_in / 1_000 * 2_000);
+}
+
+#[tokio::test]
+async fn test_swap_multi_hop_rejects_sa_dust_refund_account() {
+    let mut h = Harness::new().await;
+    let (mint_a, mint_b, mint_c) = (h.create_mint(), h.create_mint(), h.create_mint());
+    let amm = h.create_token_swap_pool(mint_a, mint_b, 1_000_000_000, 1_000_000_000);
+    let book = h.create_order_book(mint_b, mint_c, 2_000, 1_000, 1, 1_000_000_000);
+    let source = h.create_token_account(mint_a, h.payer(), 10_000_000);
+    let middle = h.create_token_account(mint_b, authority_pda::ID, 0);
+    let sa_refund = h.create_token_account(mint_b, authority_pda::ID, 0);
+    let destination = h.create_token_account(mint_c, h.payer(), 0);
+
+    let mut remaining_accounts = amm.accounts(h.payer(), source, middle);
+    remaining_accounts.extend(book.accounts(authority_pda::ID, middle, destination));
+    remaining_accounts.push(AccountMeta::new(sa_refund, false));
+    remaining_accounts.push(AccountMeta::new_readonly(mint_b, false));
+    let routes = vec![vec![
+        route(vec![Dex::SplTokenSwap], vec![100]),
+        route(vec![Dex::Phoenix], vec![100]),
+    ]];
+    let ix = swap_ix(
+        &h,
+        (source, mint_a),
+        (destination, mint_c),
+        swap_args(1_000_000, 1, routes),
+        remaining_accounts,
+    );
+    let result = h.process(&[ix], &[]).await;
+    assert_eq!(error_code(result), Some(ErrorCode::InvalidDustRefundAccount.into()));
+}
+
+#[tokio::test]
+async fn test_swap_min_return_not_reached() {
+    let mut h = Harness::new().await;
+    let (mint_a, mint_b) = (h.create_mint(), h.create_mint());
+    let pool = h.create_token_swap_pool(mint_a, mint_b, 1_000_000_000, 1_000_000_000);
+    let source = h.create_token_account(mint_a, h.payer(), 10_000_000);
+    let destination = h.create_token_account(mint_b, h.payer(), 0);
+
+    let quote = mock_token_swap::quote(1_000_000_000, 1_000_000_000, 1_000_000);
+    let ix = swap_ix(
+        &h,
+        (source, mint_a),
+        (destination, mint_b),
+        swap_args(1_000_000, quote + 1, vec![vec![route(vec![Dex::SplTokenSwap], vec![100])]]),
+        pool.accounts(h.payer(), source, destination),
+    );
+    let result = h.process(&[ix], &[]).await;
+    assert_eq!(error_code(result), Some(ErrorCode::MinReturnNotReached.into()));
+    assert_eq!(h.balance(source).await, 10_000_000);
+}
+
+#[tokio::test]
+async fn test_swap_rejects_invalid_weights() {
+    let mut h = Harness::new().await;
+    let (mint_a, mint_b) = (h.create_mint(), h.create_mint());
+    let pool = h.create_token_swap_pool(mint_a, mint_b, 1_000_000_000, 1_000_000_000);
+    let source = h.create_token_account(mint_a, h.payer(), 10_000_000);
+    let destination = h.create_token_account(mint_b, h.payer(), 0);
+
+    let ix = swap_ix(
+        &h,
+        (source, mint_a),
+        (destination, mint_b),
+        swap_args(1_000_000, 1, vec![vec![route(vec![Dex::SplTokenSwap], vec![90])]]),
+        pool.accounts(h.payer(), source, destination),
+    );
+    let result = h.process(&[ix], &[]).await;
+    assert_eq!(error_code(result), Some(ErrorCode::WeightsMustSumTo100.into()));
+}
+
+#[tokio::test]
+async fn test_swap_bonding_curve_round_trip() {
+    let mut h = Harness::new().await;
+    let (mint_a, mint_b) = (h.create_mint(), h.create_mint());
+    let curve = h.create_bonding_curve(mint_a, mint_b, 100_000_000, 1_000_000_000);
+    let quote_account = h.create_token_account(mint_a, h.payer(), 10_000_000);
+    let token_account = h.create_token_account(mint_b, h.payer(), 0);
+
+    let ix = swap_ix(
+        &h,
+        (quote_account, mint_a),
+        (token_account, mint_b),
+        swap_args(10_000_000, 1, vec![vec![route(vec![Dex::VertigoBuy], vec![100])]]),
+        curve.accounts(h.payer(), quote_account, token_account),
+    );
+    h.process(&[ix], &[]).await.unwrap();
+    let bought = h.balance(token_account).await;
+    assert_eq!(bought, mock_bonding_curve::quote(100_000_000, 1_000_000_000, 10_000_000));
+
+    let ix = swap_ix(
+        &h,
+        (token_account, mint_b),
+        (quote_account, mint_a),
+        swap_args(bought, 1, vec![vec![route(vec![Dex::VertigoSell], vec![100])]]),
+        curve.accounts(h.payer(), token_account, quote_account),
+    );
+    h.process(&[ix], &[]).await.unwrap();
+    assert_eq!(h.balance(token_account).await, 0);
+    // Selling back everything returns the quote paid, minus rounding
+    let refunded = h.balance(quote_account).await;
+    assert!(refunded > 9_999_990 && refunded <= 10_000_000);
+}
@@ -0,0 +1,675 @@
+mod common;
+
+use anchor_lang::AnchorDeserialize;
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::system_program;
+use anchor_spl::associated_token::spl_associated_token_account;
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFee;
+use common::{
+    Harness, error_code, mock_bonding_curve, mock_clmm, mock_manifest, mock_token_swap,
+    router_instruction,
+};
+use dex_solana::error::ErrorCode;
+use dex_solana::{
+    CommissionShare, Dex, OrderBookLimit, Route, SwapArgs, SwapArgsV2, SwapExactOutArgs,
+    SwapResult, SwapResultV1, accounts, authority_pda, instruction,
+};
+
+const Q64: u128 = 1 << 64;
+const COMMISSION_FROM_INPUT: u32 = 1 << 31;
+
+struct Setup {
+    h: Harness,
+    mint_a: Pubkey,
+    mint_b: Pubkey,
+    source: Pubkey,
+    destination: Pubkey,
+}
+
+async fn setup() -> Setup {
+    let mut h = Harness::new().await;
+    let (mint_a, mint_b) = (h.create_mint(), h.create_mint());
+    let source = h.create_token_account(mint_a, h.payer(), 10_000_000);
+    let destination = h.create_token_account(mint_b, h.payer(), 0);
+    Setup { h, mint_a, mint_b, source, destination }
+}
+
+impl Setup {
+    fn accounts(
+        &self,
+        commission_account: Option<Pubkey>,
+    ) -> accounts::CommissionProxySwapAccountsV3 {
+        accounts::CommissionProxySwapAccountsV3 {
+            payer: self.h.payer(),
+            source_token_account: self.source,
+            destination_token_account: self.destination,
+            source_mint: self.mint_a,
+            destination_mint: self.mint_b,
+            commission_account,
+            platform_fee_account: None,
+            sa_authority: None,
+            source_token_sa: None,
+            destination_token_sa: None,
+            source_token_program: Some(spl_token::ID),
+            destination_token_program: Some(spl_token::ID),
+            associated_token_program: None,
+            system_program: None,
+        }
+    }
+}
+
+fn single_route(dex: Dex, amount_in: u64, min_return: u64) -> SwapArgs {
+    SwapArgs {
+        amount_in,
+        expect_amount_out: min_return,
+        min_return,
+        amounts: vec![amount_in],
+        routes: vec![vec![Route { dexes: vec![dex], weights: vec![100] }]],
+    }
+}
+
+#[tokio::test]
+async fn test_swap_v3_commission_from_input() {
+    let mut s = setup().await;
+    let pool = s.h.create_token_swap_pool(s.mint_a, s.mint_b, 1_000_000_000, 1_000_000_000);
+    let commission_account = s.h.create_token_account(s.mint_a, Pubkey::new_unique(), 0);
+
+    // 1% commission on top of the amount swapped
+    let ix = router_instruction(
+        s.accounts(Some(commission_account)),
+        instruction::SwapV3 {
+            args: single_route(Dex::SplTokenSwap, 1_000_000, 1),
+            commission_info: COMMISSION_FROM_INPUT | 10_000_000,
+            platform_fee_rate: 0,
+            order_id: 1,
+        },
+        pool.accounts(s.h.payer(), s.source, s.destination),
+    );
+    s.h.process(&[ix], &[]).await.unwrap();
+
+    let commission = s.h.balance(commission_account).await;
+    assert!(commission > 0);
+    assert_eq!(s.h.balance(s.source).await, 10_000_000 - 1_000_000 - commission);
+    assert_eq!(
+        s.h.balance(s.destination).await,
+        mock_token_swap::quote(1_000_000_000, 1_000_000_000, 1_000_000)
+    );
+}
+
+#[tokio::test]
+async fn test_swap_v3_commission_from_output() {
+    let mut s = setup().await;
+    let pool = s.h.create_token_swap_pool(s.mint_a, s.mint_b, 1_000_000_000, 1_000_000_000);
+    let commission_account = s.h.create_token_account(s.mint_b, Pubkey::new_unique(), 0);
+
+    let ix = router_instruction(
+        s.accounts(Some(commission_account)),
+        instruction::SwapV3 {
+            args: single_route(Dex::SplTokenSwap, 1_000_000, 1),
+            commission_info: 10_000_000,
+            platform_fee_rate: 0,
+            order_id: 1,
+        },
+        pool.accounts(s.h.payer(), s.source, s.destination),
+    );
+    s.h.process(&[ix], &[]).await.unwrap();
+
+    let commission = s.h.balance(commission_account).await;
+    assert!(commission > 0);
+    assert_eq!(s.h.balance(s.source).await, 9_000_000);
+    assert_eq!(
+        s.h.balance(s.destination).await + commission,
+        mock_token_swap::quote(1_000_000_000, 1_000_000_000, 1_000_000)
+    );
+}
+
+#[tokio::test]
+async fn test_swap_tob_v3_transfer_fee_commission_from_output() {
+    let mut h = Harness::new().await;
+    let mint_a = h.create_mint();
+    // 1% withheld on every transfer of the bought token
+    let mint_b = h.create_transfer_fee_mint(100);
+    let curve = h.create_token_2022_bonding_curve(mint_a, mint_b, 100_000_000, 1_000_000_000).await;
+    let source = h.create_token_account(mint_a, h.payer(), 10_000_000);
+    let destination = h.create_token_2022_account(mint_b, h.payer(), 0).await;
+    let destination_sa = h.create_token_2022_account(mint_b, authority_pda::ID, 0).await;
+    let commission_account = h.create_token_2022_account(mint_b, Pubkey::new_unique(), 0).await;
+
+    let ix = router_instruction(
+        accounts::CommissionProxySwapAccountsV3 {
+            payer: h.payer(),
+            source_token_account: source,
+            destination_token_account: destination,
+            source_mint: mint_a,
+            destination_mint: mint_b,
+            commission_account: Some(commission_account),
+            platform_fee_account: None,
+            sa_authority: Some(authority_pda::ID),
+            source_token_sa: None,
+            destination_token_sa: Some(destination_sa),
+            source_token_program: Some(spl_token::ID),
+            destination_token_program: Some(spl_token_2022::ID),
+            associated_token_program: Some(spl_associated_token_account::ID),
+            system_program: Some(system_program::ID),
+        },
+        instruction::SwapTobV3 {
+            args: single_route(Dex::VertigoBuy, 10_000_000, 1),
+            commission_info: 10_000_000,
+            trim_rate: 0,
+            platform_fee_rate: 0,
+            order_id: 1,
+        },
+        curve.accounts(h.payer(), source, destination_sa),
+    );
+    let (result, return_data) = h.simulate(std::slice::from_ref(&ix)).await;
+    result.unwrap();
+    h.process(&[ix], &[]).await.unwrap();
+
+    // The commission is taken on what reached the SA and its payout is grossed up by the transfer
+    // fee out of the user's share, so the commission account receives all of it
+    let transfer_fee = TransferFee {
+        epoch: 0.into(),
+        maximum_fee: u64::MAX.into(),
+        transfer_fee_basis_points: 100.into(),
+    };
+    let gross = mock_bonding_curve::quote(100_000_000, 1_000_000_000, 10_000_000);
+    let amount_out = gross - transfer_fee.calculate_fee(gross).unwrap();
+    let commission = amount_out / 100;
+    let user_payout =
+        amount_out - commission - transfer_fee.calculate_inverse_fee(commission).unwrap();
+    assert_eq!(h.balance(commission_account).await, commission);
+    assert_eq!(h.balance(destination_sa).await, 0);
+    assert_eq!(
+        h.balance(destination).await,
+        user_payout - transfer_fee.calculate_fee(user_payout).unwrap()
+    );
+    // The result reports the fees charged, not ones recomputed from the rates
+    assert_eq!(
+        SwapResult::try_from_slice(&return_data.unwrap()).unwrap(),
+        SwapResult::V1(SwapResultV1 {
+            amount_in: 10_000_000,
+            amount_out,
+            actual_amount_out: user_payout,
+            commission_amount: commission,
+            platform_fee_amount: 0,
+            trim_amount: 0,
+            charge_amount: 0,
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_swap_v3_returns_swap_result() {
+    let mut s = setup().await;
+    let pool = s.h.create_token_swap_pool(s.mint_a, s.mint_b, 1_000_000_000, 1_000_000_000);
+    let commission_account = s.h.create_token_account(s.mint_b, Pubkey::new_unique(), 0);
+
+    let ix = router_instruction(
+        s.accounts(Some(commission_account)),
+        instruction::SwapV3 {
+            args: single_route(Dex::SplTokenSwap, 1_000_000, 1),
+            commission_info: 10_000_000,
+            platform_fee_rate: 0,
+            order_id: 1,
+        },
+        pool.accounts(s.h.payer(), s.source, s.destination),
+    );
+    let (result, return_data) = s.h.simulate(&[ix]).await;
+    result.unwrap();
+
+    let amount_out = mock_token_swap::quote(1_000_000_000, 1_000_000_000, 1_000_000);
+    let SwapResult::V1(swap_result) = SwapResult::try_from_slice(&return_data.unwrap()).unwrap();
+    assert_eq!(
+        swap_result,
+        SwapResultV1 {
+            amount_in: 1_000_000,
+            amount_out,
+            actual_amount_out: amount_out - amount_out / 100,
+            commission_amount: amount_out / 100,
+            platform_fee_amount: 0,
+            trim_amount: 0,
+            charge_amount: 0,
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_swap_v3_order_book_lot_dust() {
+    let mut s = setup().await;
+    let book = s.h.create_order_book(s.mint_a, s.mint_b, 2_000, 1_000, 1, 1_000_000_000);
+
+    // 10 whole lots are sold, the 500 dust stays with the user
+    let ix = router_instruction(
+        s.accounts(None),
+        instruction::SwapV3 {
+            args: single_route(Dex::Phoenix, 10_500, 1),
+            commission_info: 0,
+            platform_fee_rate: 0,
+            order_id: 1,
+        },
+        book.accounts(s.h.payer(), s.source, s.destination),
+    );
+    let (result, return_data) = s.h.simulate(std::slice::from_ref(&ix)).await;
+    result.unwrap();
+    assert_eq!(
+        SwapResult::try_from_slice(&return_data.unwrap()).unwrap(),
+        SwapResult::V1(SwapResultV1::new(10_000, 20_000))
+    );
+
+    s.h.process(&[ix], &[]).await.unwrap();
+    assert_eq!(s.h.balance(s.source).await, 10_000_000 - 10_000);
+    assert_eq!(s.h.balance(s.destination).await, 20_000);
+    assert_eq!(s.h.balance(book.base_vault).await, 1_000_000_000 + 10_000);
+}
+
+#[tokio::test]
+async fn test_swap_v3_sqrt_price_limit_partial_fill() {
+    let mut s = setup().await;
+    let clmm = s.h.create_clmm_pool(s.mint_a, s.mint_b, Q64, 1_000_000_000, 1_000_000_000);
+
+    // Selling token a lowers the price, stop half way through the input
+    let sqrt_price_limit = Q64 * 1_000_000_000 / 1_000_500_000;
+    let mut args = SwapArgsV2::from(single_route(Dex::Whirlpool, 1_000_000, 1));
+    args.routes[0][0].sqrt_price_limits = Some(vec![sqrt_price_limit]);
+    let ix = router_instruction(
+        s.accounts(None),
+        instruction::SwapV3WithLimits {
+            args,
+            commission_info: 0,
+            platform_fee_rate: 0,
+            order_id: 1,
+        },
+        clmm.accounts(s.h.payer(), s.source, s.destination),
+    );
+    s.h.process(&[ix], &[]).await.unwrap();
+
+    assert_eq!(s.h.clmm_sqrt_price(&clmm).await, sqrt_price_limit);
+    let spent = 10_000_000 - s.h.balance(s.source).await;
+    assert!(spent > 0 && spent < 1_000_000);
+    assert_eq!(s.h.balance(clmm.token_vault_a).await, 1_000_000_000 + spent);
+}
+
+#[tokio::test]
+async fn test_swap_tob_v3_sqrt_price_limit_refunds_source_sa() {
+    let mut s = setup().await;
+    let clmm = s.h.create_clmm_pool(s.mint_a, s.mint_b, Q64, 1_000_000_000, 1_000_000_000);
+    let source_sa = s.h.create_token_account(s.mint_a, authority_pda::ID, 0);
+
+    // The whole input is moved to the source SA, the CLMM stops at the limit half way through
+    let sqrt_price_limit = Q64 * 1_000_000_000 / 1_000_500_000;
+    let mut args = SwapArgsV2::from(single_route(Dex::Whirlpool, 1_000_000, 1));
+    args.routes[0][0].sqrt_price_limits = Some(vec![sqrt_price_limit]);
+    let ix = router_instruction(
+        accounts::CommissionProxySwapAccountsV3 {
+            sa_authority: Some(authority_pda::ID),
+            source_token_sa: Some(source_sa),
+            associated_token_program: Some(spl_associated_token_account::ID),
+            system_program: Some(system_program::ID),
+            ..s.accounts(None)
+        },
+        instruction::SwapTobV3WithLimits {
+            args,
+            commission_info: 0,
+            trim_rate: 0,
+            platform_fee_rate: 0,
+            order_id: 1,
+        },
+        clmm.accounts(authority_pda::ID, source_sa, s.destination),
+    );
+    let (result, return_data) = s.h.simulate(std::slice::from_ref(&ix)).await;
+    result.unwrap();
+    s.h.process(&[ix], &[]).await.unwrap();
+
+    // The unfilled input is refunded from the source SA to the user
+    assert_eq!(s.h.clmm_sqrt_price(&clmm).await, sqrt_price_limit);
+    assert_eq!(s.h.balance(source_sa).await, 0);
+    let spent = 10_000_000 - s.h.balance(s.source).await;
+    assert!(spent > 0 && spent < 1_000_000);
+    assert_eq!(s.h.balance(clmm.token_vault_a).await, 1_000_000_000 + spent);
+    let SwapResult::V1(swap_result) = SwapResult::try_from_slice(&return_data.unwrap()).unwrap();
+    assert_eq!(swap_result.amount_in, spent);
+    assert_eq!(swap_result.actual_amount_out, s.h.balance(s.destination).await);
+}
+
+#[tokio::test]
+async fn test_swap_v3_sqrt_price_limit_rejects_commission_from_input() {
+    let mut s = setup().await;
+    let clmm = s.h.create_clmm_pool(s.mint_a, s.mint_b, Q64, 1_000_000_000, 1_000_000_000);
+    let commission_account = s.h.create_token_account(s.mint_a, Pubkey::new_unique(), 0);
+
+    // The input commission would also be charged on the unfilled amount refunded to the user
+    let mut args = SwapArgsV2::from(single_route(Dex::Whirlpool, 1_000_000, 1));
+    args.routes[0][0].sqrt_price_limits = Some(vec![Q64 * 1_000_000_000 / 1_000_500_000]);
+    let ix = router_instruction(
+        s.accounts(Some(commission_account)),
+        instruction::SwapV3WithLimits {
+            args,
+            commission_info: COMMISSION_FROM_INPUT | 10_000_000,
+            platform_fee_rate: 0,
+            order_id: 1,
+        },
+        clmm.accounts(s.h.payer(), s.source, s.destination),
+    );
+    let result = s.h.process(&[ix], &[]).await;
+    assert_eq!(error_code(result), Some(ErrorCode::PriceLimitsRequireOutputCommission.into()));
+}
+
+#[tokio::test]
+async fn test_swap_v3_rejects_sqrt_price_limit_on_amm() {
+    let mut s = setup().await;
+    let pool = s.h.create_token_swap_pool(s.mint_a, s.mint_b, 1_000_000_000, 1_000_000_000);
+
+    let mut args = SwapArgsV2::from(single_route(Dex::SplTokenSwap, 1_000_000, 1));
+    args.routes[0][0].sqrt_price_limits = Some(vec![Q64 / 2]);
+    let ix = router_instruction(
+        s.accounts(None),
+        instruction::SwapV3WithLimits {
+            args,
+            commission_info: 0,
+            platform_fee_rate: 0,
+            order_id: 1,
+        },
+        pool.accounts(s.h.payer(), s.source, s.destination),
+    );
+    let result = s.h.process(&[ix], &[]).await;
+    assert_eq!(error_code(result), Some(ErrorCode::SqrtPriceLimitNotSupported.into()));
+}
+
+#[tokio::test]
+async fn test_swap_v3_rejects_order_book_limit_on_amm() {
+    let mut s = setup().await;
+    let pool = s.h.create_token_swap_pool(s.mint_a, s.mint_b, 1_000_000_000, 1_000_000_000);
+
+    let mut args = SwapArgsV2::from(single_route(Dex::SplTokenSwap, 1_000_000, 1));
+    args.routes[0][0].order_book_limits =
+        Some(vec![OrderBookLimit { price: 150_250, match_limit: 0 }]);
+    let ix = router_instruction(
+        s.accounts(None),
+        instruction::SwapV3WithLimits {
+            args,
+            commission_info: 0,
+            platform_fee_rate: 0,
+            order_id: 1,
+        },
+        pool.accounts(s.h.payer(), s.source, s.destination),
+    );
+    let result = s.h.process(&[ix], &[]).await;
+    assert_eq!(error_code(result), Some(ErrorCode::OrderBookLimitNotSupported.into()));
+}
+
+#[tokio::test]
+async fn test_swap_v3_manifest_order_book_limit_fills_within_limit() {
+    let mut s = setup().await;
+    let market = s.h.create_manifest_market(s.mint_a, s.mint_b, 1_500_000_000_000, 1_000_000_000);
+
+    let mut args = SwapArgsV2::from(single_route(Dex::Manifest, 1_000_000, 1));
+    args.routes[0][0].order_book_limits =
+        Some(vec![OrderBookLimit { price: 1_400_000_000_000, match_limit: 0 }]);
+    let ix = router_instruction(
+        s.accounts(None),
+        instruction::SwapV3WithLimits {
+            args,
+            commission_info: 0,
+            platform_fee_rate: 0,
+            order_id: 1,
+        },
+        market.accounts(s.h.payer(), s.source, s.destination),
+    );
+    s.h.process(&[ix], &[]).await.unwrap();
+
+    assert_eq!(s.h.balance(s.source).await, 10_000_000 - 1_000_000);
+    assert_eq!(s.h.balance(s.destination).await, 1_500_000);
+}
+
+#[tokio::test]
+async fn test_swap_v3_manifest_order_book_limit_reverts_without_partial_fill() {
+    let mut s = setup().await;
+    let market = s.h.create_manifest_market(s.mint_a, s.mint_b, 1_500_000_000_000, 1_000_000_000);
+
+    // Manifest takes no limit price: a book worse than the limit reverts the whole swap
+    let mut args = SwapArgsV2::from(single_route(Dex::Manifest, 1_000_000, 1));
+    args.routes[0][0].order_book_limits =
+        Some(vec![OrderBookLimit { price: 1_600_000_000_000, match_limit: 0 }]);
+    let ix = router_instruction(
+        s.accounts(None),
+        instruction::SwapV3WithLimits {
+            args: args.clone(),
+            commission_info: 0,
+            platform_fee_rate: 0,
+            order_id: 1,
+        },
+        market.accounts(s.h.payer(), s.source, s.destination),
+    );
+    let result = s.h.process(&[ix], &[]).await;
+    assert_eq!(error_code(result), Some(mock_manifest::INSUFFICIENT_OUT));
+    assert_eq!(s.h.balance(s.source).await, 10_000_000);
+    assert_eq!(s.h.balance(s.destination).await, 0);
+
+    // Nor a match limit
+    args.routes[0][0].order_book_limits =
+        Some(vec![OrderBookLimit { price: 1_400_000_000_000, match_limit: 4 }]);
+    let ix = router_instruction(
+        s.accounts(None),
+        instruction::SwapV3WithLimits {
+            args,
+            commission_info: 0,
+            platform_fee_rate: 0,
+            order_id: 1,
+        },
+        market.accounts(s.h.payer(), s.source, s.destination),
+    );
+    let result = s.h.process(&[ix], &[]).await;
+    assert_eq!(error_code(result), Some(ErrorCode::OrderBookLimitNotSupported.into()));
+}
+
+#[tokio::test]
+async fn test_swap_v3_exact_out() {
+    let mut s = setup().await;
+    let clmm = s.h.create_clmm_pool(s.mint_a, s.mint_b, Q64, 1_000_000_000, 1_000_000_000);
+
+    let (amount_in, _, _) =
+        mock_clmm::swap_step(Q64, 1_000_000_000, 500_000, false, true, 0).unwrap();
+    let args = SwapExactOutArgs {
+        amount_out: 500_000,
+        max_amount_in: 600_000,
+        amounts: vec![500_000],
+        routes: vec![vec![Route { dexes: vec![Dex::Whirlpool], weights: vec![100] }]],
+    };
+    let ix = router_instruction(
+        s.accounts(None),
+        instruction::SwapV3ExactOut { args, commission_info: 0, platform_fee_rate: 0, order_id: 1 },
+        clmm.accounts(s.h.payer(), s.source, s.destination),
+    );
+    s.h.process(&[ix], &[]).await.unwrap();
+
+    assert_eq!(s.h.balance(s.destination).await, 500_000);
+    assert_eq!(s.h.balance(s.source).await, 10_000_000 - amount_in);
+}
+
+#[tokio::test]
+async fn test_swap_v3_exact_out_max_amount_in_exceeded() {
+    let mut s = setup().await;
+    let clmm = s.h.create_clmm_pool(s.mint_a, s.mint_b, Q64, 1_000_000_000, 1_000_000_000);
+
+    let args = SwapExactOutArgs {
+        amount_out: 500_000,
+        max_amount_in: 400_000,
+        amounts: vec![500_000],
+        routes: vec![vec![Route { dexes: vec![Dex::Whirlpool], weights: vec![100] }]],
+    };
+    let remaining_accounts: Vec<AccountMeta> = clmm.accounts(s.h.payer(), s.source, s.destination);
+    let ix = router_instruction(
+        s.accounts(None),
+        instruction::SwapV3ExactOut { args, commission_info: 0, platform_fee_rate: 0, order_id: 1 },
+        remaining_accounts,
+    );
+    assert!(s.h.process(&[ix], &[]).await.is_err());
+    assert_eq!(s.h.balance(s.source).await, 10_000_000);
+}
+
+fn commission_shares(recipients: &[Pubkey], share_bps: &[u16]) -> Vec<CommissionShare> {
+    recipients
+        .iter()
+        .zip(share_bps)
+        .map(|(recipient, share_bps)| CommissionShare {
+            recipient: *recipient,
+            share_bps: *share_bps,
+        })
+        .collect()
+}
+
+fn with_recipients(
+    mut remaining_accounts: Vec<AccountMeta>,
+    recipients: &[Pubkey],
+) -> Vec<AccountMeta> {
+    remaining_accounts
+        .extend(recipients.iter().map(|recipient| AccountMeta::new(*recipient, false)));
+    remaining_accounts
+}
+
+#[tokio::test]
+async fn test_swap_v3_split_commission_from_output() {
+    let mut s = setup().await;
+    let pool = s.h.create_token_swap_pool(s.mint_a, s.mint_b, 1_000_000_000, 1_000_000_000);
+    let recipients: Vec<Pubkey> =
+        (0..3).map(|_| s.h.create_token_account(s.mint_b, Pubkey::new_unique(), 0)).collect();
+
+    let ix = router_instruction(
+        s.accounts(None),
+        instruction::SwapV3SplitCommission {
+            args: single_route(Dex::SplTokenSwap, 1_000_000, 1),
+            commission_info: 10_000_000,
+            commission_shares: commission_shares(&recipients, &[5_000, 3_000, 2_000]),
+            platform_fee_rate: 0,
+            order_id: 1,
+        },
+        with_recipients(pool.accounts(s.h.payer(), s.source, s.destination), &recipients),
+    );
+    s.h.process(&[ix], &[]).await.unwrap();
+
+    let mut payouts = vec![];
+    for recipient in &recipients {
+        payouts.push(s.h.balance(*recipient).await);
+    }
+    let commission: u64 = payouts.iter().sum();
+    assert!(commission > 0);
+    assert_eq!(payouts[0], commission * 5_000 / 10_000);
+    assert_eq!(payouts[1], commission * 3_000 / 10_000);
+    assert_eq!(
+        s.h.balance(s.destination).await + commission,
+        mock_token_swap::quote(1_000_000_000, 1_000_000_000, 1_000_000)
+    );
+}
+
+#[tokio::test]
+async fn test_swap_v3_split_commission_sol_from_input() {
+    let mut h = Harness::new().await;
+    let (wsol, mint_b) = (h.create_native_mint(), h.create_mint());
+    let source = h.create_token_account(wsol, h.payer(), 2_000_000_000);
+    let destination = h.create_token_account(mint_b, h.payer(), 0);
+    let mut s = Setup { h, mint_a: wsol, mint_b, source, destination };
+    let pool = s.h.create_token_swap_pool(wsol, mint_b, 10_000_000_000, 10_000_000_000);
+    let recipients = [Pubkey::new_unique(), Pubkey::new_unique()];
+
+    // 1% commission on top of 1 SOL, paid in lamports 60/40 through the system program
+    let mut accounts = s.accounts(None);
+    accounts.system_program = Some(system_program::ID);
+    let ix = router_instruction(
+        accounts,
+        instruction::SwapV3SplitCommission {
+            args: single_route(Dex::SplTokenSwap, 1_000_000_000, 1),
+            commission_info: COMMISSION_FROM_INPUT | 10_000_000,
+            commission_shares: commission_shares(&recipients, &[6_000, 4_000]),
+            platform_fee_rate: 0,
+            order_id: 1,
+        },
+        with_recipients(pool.accounts(s.h.payer(), s.source, s.destination), &recipients),
+    );
+    s.h.process(&[ix], &[]).await.unwrap();
+
+    let payouts = [s.h.lamports(recipients[0]).await, s.h.lamports(recipients[1]).await];
+    let commission = payouts[0] + payouts[1];
+    assert!(commission > 10_000_000);
+    assert_eq!(payouts[0], commission * 6_000 / 10_000);
+    assert_eq!(s.h.balance(s.source).await, 1_000_000_000);
+}
+
+#[tokio::test]
+async fn test_swap_v3_split_commission_sol_rejects_token_recipient() {
+    let mut h = Harness::new().await;
+    let (wsol, mint_b) = (h.create_native_mint(), h.create_mint());
+    let source = h.create_token_account(wsol, h.payer(), 2_000_000_000);
+    let destination = h.create_token_account(mint_b, h.payer(), 0);
+    let mut s = Setup { h, mint_a: wsol, mint_b, source, destination };
+    let pool = s.h.create_token_swap_pool(wsol, mint_b, 10_000_000_000, 10_000_000_000);
+    // The first recipient selects SOL mode, the second can not take lamports
+    let recipients =
+        [Pubkey::new_unique(), s.h.create_token_account(wsol, Pubkey::new_unique(), 0)];
+
+    let mut accounts = s.accounts(None);
+    accounts.system_program = Some(system_program::ID);
+    let ix = router_instruction(
+        accounts,
+        instruction::SwapV3SplitCommission {
+            args: single_route(Dex::SplTokenSwap, 1_000_000_000, 1),
+            commission_info: COMMISSION_FROM_INPUT | 10_000_000,
+            commission_shares: commission_shares(&recipients, &[6_000, 4_000]),
+            platform_fee_rate: 0,
+            order_id: 1,
+        },
+        with_recipients(pool.accounts(s.h.payer(), s.source, s.destination), &recipients),
+    );
+    let result = s.h.process(&[ix], &[]).await;
+    assert_eq!(error_code(result), Some(ErrorCode::InvalidCommissionAccount.into()));
+}
+
+#[tokio::test]
+async fn test_swap_v3_split_commission_rejects_partial_shares() {
+    let mut s = setup().await;
+    let pool = s.h.create_token_swap_pool(s.mint_a, s.mint_b, 1_000_000_000, 1_000_000_000);
+    let recipients: Vec<Pubkey> =
+        (0..2).map(|_| s.h.create_token_account(s.mint_b, Pubkey::new_unique(), 0)).collect();
+
+    let ix = router_instruction(
+        s.accounts(None),
+        instruction::SwapV3SplitCommission {
+            args: single_route(Dex::SplTokenSwap, 1_000_000, 1),
+            commission_info: 10_000_000,
+            commission_shares: commission_shares(&recipients, &[5_000, 4_000]),
+            platform_fee_rate: 0,
+            order_id: 1,
+        },
+        with_recipients(pool.accounts(s.h.payer(), s.source, s.destination), &recipients),
+    );
+    let result = s.h.process(&[ix], &[]).await;
+    assert_eq!(error_code(result), Some(ErrorCode::InvalidCommissionSplit.into()));
+}
+
+#[tokio::test]
+async fn test_swap_v3_split_commission_rejects_recipient_mismatch() {
+    let mut s = setup().await;
+    let pool = s.h.create_token_swap_pool(s.mint_a, s.mint_b, 1_000_000_000, 1_000_000_000);
+    let recipients: Vec<Pubkey> =
+        (0..2).map(|_| s.h.create_token_account(s.mint_b, Pubkey::new_unique(), 0)).collect();
+    let impostor = s.h.create_token_account(s.mint_b, Pubkey::new_unique(), 0);
+
+    let ix = router_instruction(
+        s.accounts(None),
+        instruction::SwapV3SplitCommission {
+            args: single_route(Dex::SplTokenSwap, 1_000_000, 1),
+            commission_info: 10_000_000,
+            commission_shares: commission_shares(&recipients, &[5_000, 5_000]),
+            platform_fee_rate: 0,
+            order_id: 1,
+        },
+        with_recipients(
+            pool.accounts(s.h.payer(), s.source, s.destination),
+            &[recipients[0], impostor],
+        ),
+    );
+    let result = s.h.process(&[ix], &[]).await;
+    assert_eq!(error_code(result), Some(ErrorCode::InvalidCommissionSplit.into()));
+    assert_eq!(s.h.balance(s.source).await, 10_000_000);
+}
@@ -0,0 +1,140 @@
+mod common;
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::system_program;
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022;
+use common::{Harness, mock_transfer_hook, router_instruction};
+use dex_solana::constants::{ESCROW_TOKEN_SEED, ORDER_V1_SEED};
+use dex_solana::{accounts, instruction};
+use solana_sdk::instruction::Instruction;
+
+const ORDER_ID: u64 = 3;
+const MAKING_AMOUNT: u64 = 1_000_000;
+
+struct Order {
+    mint: Pubkey,
+    global_config: Pubkey,
+    order_pda: Pubkey,
+    escrow_token_account: Pubkey,
+    input_token_account: Pubkey,
+}
+
+fn event_authority() -> Pubkey {
+    Pubkey::find_program_address(&[b"__event_authority"], &dex_solana::ID).0
+}
+
+/// Accounts the token program needs to call the mock hook for `mint`
+fn hook_accounts(mint: &Pubkey) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new_readonly(mock_transfer_hook::extra_account_metas(mint), false),
+        AccountMeta::new_readonly(mock_transfer_hook::ID, false),
+        AccountMeta::new(mock_transfer_hook::counter(mint), false),
+    ]
+}
+
+/// An order selling `MAKING_AMOUNT` of a transfer hook mint, escrowed on placement
+async fn setup(h: &mut Harness) -> Order {
+    let maker = h.payer();
+    let mint = h.create_transfer_hook_mint();
+    let global_config = h.create_global_config(maker);
+    let input_token_account = h.create_token_2022_account(mint, maker, MAKING_AMOUNT).await;
+    let order_pda = Pubkey::find_program_address(
+        &[ORDER_V1_SEED.as_bytes(), &ORDER_ID.to_le_bytes(), maker.as_ref()],
+        &dex_solana::ID,
+    )
+    .0;
+    let escrow_token_account = Pubkey::find_program_address(
+        &[ESCROW_TOKEN_SEED.as_bytes(), order_pda.as_ref(), mint.as_ref()],
+        &dex_solana::ID,
+    )
+    .0;
+    Order { mint, global_config, order_pda, escrow_token_account, input_token_account }
+}
+
+async fn place_ix(
+    h: &mut Harness,
+    order: &Order,
+    remaining_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let deadline = h.unix_timestamp().await + 3600;
+    router_instruction(
+        accounts::PlaceOrder {
+            maker: h.payer(),
+            global_config: order.global_config,
+            order_pda: order.order_pda,
+            escrow_token_account: order.escrow_token_account,
+            input_token_account: order.input_token_account,
+            input_token_mint: order.mint,
+            output_token_mint: h.create_mint(),
+            input_token_program: spl_token_2022::ID,
+            output_token_program: spl_token::ID,
+            system_program: system_program::ID,
+            event_authority: event_authority(),
+            program: dex_solana::ID,
+            receiver: None,
+        },
+        instruction::PlaceOrder {
+            order_id: ORDER_ID,
+            making_amount: MAKING_AMOUNT,
+            expect_taking_amount: 1,
+            min_return_amount: 1,
+            deadline,
+            trade_fee: 0,
+        },
+        remaining_accounts,
+    )
+}
+
+fn cancel_ix(h: &Harness, order: &Order, remaining_accounts: Vec<AccountMeta>) -> Instruction {
+    router_instruction(
+        accounts::CancelOrder {
+            payer: h.payer(),
+            maker: h.payer(),
+            global_config: order.global_config,
+            order_pda: order.order_pda,
+            escrow_token_account: order.escrow_token_account,
+            input_token_account: order.input_token_account,
+            input_token_mint: order.mint,
+            input_token_program: spl_token_2022::ID,
+            instructions_sysvar: sysvar::instructions::ID,
+            resolver_pda: None,
+            event_authority: event_authority(),
+            program: dex_solana::ID,
+        },
+        instruction::CancelOrder { order_id: ORDER_ID, tips: 0 },
+        remaining_accounts,
+    )
+}
+
+#[tokio::test]
+async fn test_place_and_cancel_order_with_transfer_hook() {
+    let mut h = Harness::new().await;
+    let order = setup(&mut h).await;
+
+    let ix = place_ix(&mut h, &order, hook_accounts(&order.mint)).await;
+    h.process(&[ix], &[]).await.unwrap();
+    assert_eq!(h.balance(order.escrow_token_account).await, MAKING_AMOUNT);
+    assert_eq!(h.transfer_hook_count(order.mint).await, 1);
+
+    // The refund out of the escrow is signed by the order PDA and still runs the hook
+    let ix = cancel_ix(&h, &order, hook_accounts(&order.mint));
+    h.process(&[ix], &[]).await.unwrap();
+    assert_eq!(h.balance(order.input_token_account).await, MAKING_AMOUNT);
+    assert_eq!(h.transfer_hook_count(order.mint).await, 2);
+}
+
+#[tokio::test]
+async fn test_transfer_hook_requires_extra_accounts() {
+    let mut h = Harness::new().await;
+    let order = setup(&mut h).await;
+
+    let ix = place_ix(&mut h, &order, vec![]).await;
+    assert!(h.process(&[ix], &[]).await.is_err());
+
+    // The validation account alone does not resolve the counter
+    let ix = place_ix(&mut h, &order, hook_accounts(&order.mint)[..2].to_vec()).await;
+    assert!(h.process(&[ix], &[]).await.is_err());
+    assert_eq!(h.transfer_hook_count(order.mint).await, 0);
+}
@@ -0,0 +1,288 @@
+import { describe, it, expect, beforeAll } from 'vitest';
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { DexSolana } from "../target/types/dex_solana";
+import { PublicKey, Connection, Keypair } from "@solana/web3.js";
+import * as fs from 'fs';
+import * as path from 'path';
+import {
+  TOKEN_PROGRAM_ID,
+  createAccount,
+  getAccount,
+  syncNative,
+} from "@solana/spl-token";
+
+const GLOBAL_CONFIG_SEED = "global_config";
+const ORDER_V1_SEED = "order_v1";
+const ESCROW_TOKEN_SEED = "escrow_token";
+const RESOLVER_SEED = "resolver";
+const MIN_DEADLINE = 300;
+
+describe("Limit Order End-to-End Test", () => {
+  let program: Program<DexSolana>;
+  let connection: Connection;
+  let payer: Keypair;
+
+  // Token mints and accounts
+  const inputMint = new PublicKey("So11111111111111111111111111111111111111112"); // Wrapped SOL
+  const outputMint = new PublicKey("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"); // USDC
+  let inputTokenAccount: PublicKey;
+  let outputTokenAccount: PublicKey;
+
+  let globalConfig: PublicKey;
+  let resolverPda: PublicKey;
+  let tradeFee: BN;
+
+  // Raydium SOL/USDC pool cloned in Anchor.toml
+  const RAYDIUM_PROGRAM_ID = new PublicKey("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8");
+  const RAYDIUM_POOL = new PublicKey("58oQChx4yWmvKdwLLZzBi4ChoCc2fqCUWBkwMihLYQo2");
+  const POOL_AUTHORITY = new PublicKey("5Q544fKrFoe6tsEbD7S8EmxGTJYAKtTVhAW5Q5pge4j1");
+  const AMM_OPEN_ORDERS = new PublicKey("HmiHHzq4Fym9e1D4qzLS6LDDM3tNsCTBPDWHTLZ763jY");
+  const AMM_TARGET_ORDERS = new PublicKey("CZza3Ej4Mc58MnxWA385itCC9jCo3L1D7zc3LKy1bZMR");
+  const POOL_COIN_VAULT = new PublicKey("DQyrAcCrDXQ7NeoqGgDCZwBvWDcYmFCjSb9JtteuvPpz");
+  const POOL_PC_VAULT = new PublicKey("HLmqeL62xR1QoZ1HKKbXRrdN1p3phKpxRMb2VVopvBBz");
+  const SERUM_PROGRAM_ID = new PublicKey("srmqPvymJeFKQ4zGQed1GFppgkRHL9kaELCbyksJtPX");
+  const SERUM_MARKET = new PublicKey("8BnEgHoWFysVcuFFX7QztDmzuH8r5ZFvyP3sYwn1XTh6");
+  const SERUM_BIDS = new PublicKey("96RyJdJVeo5Yr5FjJRn6AaED89myiD9fjp2Fq3zccrfj");
+  const SERUM_ASKS = new PublicKey("48cNXXS5fKsA3ufrYMHxqmV93L2449tu4Ng9mQS2Mxzt");
+  const SERUM_EVENT_QUEUE = new PublicKey("5KKsLVU6TcbVDK4BS6K1DGDxnh4Q9xjYJ8XaDCG5t8ht");
+  const SERUM_COIN_VAULT = new PublicKey("FaFLrnxNpW4z6ivYvmDaoxvHXvi7G78veWcjW81siiE6");
+  const SERUM_PC_VAULT = new PublicKey("BmrxsPxDjYavNotwdYNMJm1Z3ruRY5AXTA7m85XZpSYj");
+  const SERUM_VAULT_SIGNER = new PublicKey("V3gQJJhHGaRKS7uoeUVhMKzKWqbQ6dKofhPqGBxmg2c");
+
+  const findOrderPda = (orderId: BN, maker: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from(ORDER_V1_SEED), orderId.toArrayLike(Buffer, "le", 8), maker.toBuffer()],
+      program.programId
+    )[0];
+
+  const findEscrowTokenAccount = (orderPda: PublicKey, mint: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from(ESCROW_TOKEN_SEED), orderPda.toBuffer(), mint.toBuffer()],
+      program.programId
+    )[0];
+
+  const currentTimestamp = async () => {
+    const slot = await connection.getSlot();
+    return (await connection.getBlockTime(slot)) ?? Math.floor(Date.now() / 1000);
+  };
+
+  const placeOrder = async (orderId: BN, makingAmount: BN, expectTakingAmount: BN, minReturnAmount: BN) => {
+    const deadline = new BN((await currentTimestamp()) + MIN_DEADLINE * 2);
+    await program.methods
+      .placeOrder(orderId, makingAmount, expectTakingAmount, minReturnAmount, deadline, tradeFee)
+      .accountsPartial({
+        maker: payer.publicKey,
+        globalConfig,
+        orderPda: findOrderPda(orderId, payer.publicKey),
+        escrowTokenAccount: findEscrowTokenAccount(findOrderPda(orderId, payer.publicKey), inputMint),
+        inputTokenAccount,
+        inputTokenMint: inputMint,
+        outputTokenMint: outputMint,
+        inputTokenProgram: TOKEN_PROGRAM_ID,
+        outputTokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([payer])
+      .rpc();
+  };
+
+  beforeAll(async () => {
+    program = anchor.workspace.DexSolana;
+    connection = new Connection("http://127.0.0.1:8899", "confirmed");
+
+    const walletPath = path.join(process.env.HOME || '', '.config/solana/id.json');
+    const walletKeypair = JSON.parse(fs.readFileSync(walletPath, 'utf-8'));
+    payer = Keypair.fromSecretKey(new Uint8Array(walletKeypair));
+
+    try {
+      const signature = await connection.requestAirdrop(payer.publicKey, 2 * anchor.web3.LAMPORTS_PER_SOL);
+      await connection.confirmTransaction(signature);
+    } catch (error) {
+      console.log("⚠️  Airdrop failed, using existing wallet balance:", error instanceof Error ? error.message : String(error));
+    }
+
+    // The wallet is the upgrade authority of the local deployment, so it can initialize the config
+    globalConfig = PublicKey.findProgramAddressSync([Buffer.from(GLOBAL_CONFIG_SEED)], program.programId)[0];
+    if (!(await connection.getAccountInfo(globalConfig))) {
+      const programData = PublicKey.findProgramAddressSync(
+        [program.programId.toBuffer()],
+        new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+      )[0];
+      await program.methods
+        .initGlobalConfig(new BN(10_000))
+        .accountsPartial({
+          admin: payer.publicKey,
+          globalConfig,
+          program: program.programId,
+          programData,
+        })
+        .signers([payer])
+        .rpc();
+    }
+
+    // The wallet acts both as maker and as resolver, registered as a resolver PDA without limits
+    resolverPda = PublicKey.findProgramAddressSync(
+      [Buffer.from(RESOLVER_SEED), payer.publicKey.toBuffer()],
+      program.programId
+    )[0];
+    if (!(await connection.getAccountInfo(resolverPda))) {
+      await program.methods
+        .addResolver(payer.publicKey, 0, [])
+        .accountsPartial({
+          payer: payer.publicKey,
+          admin: payer.publicKey,
+          globalConfig,
+          resolverPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([payer])
+        .rpc();
+    }
+    const config = await program.account.globalConfig.fetch(globalConfig);
+    tradeFee = config.tradeFee;
+
+    // Fund a wrapped SOL account for the maker and create the USDC receiving account
+    inputTokenAccount = await createAccount(connection, payer, inputMint, payer.publicKey, Keypair.generate());
+    outputTokenAccount = await createAccount(connection, payer, outputMint, payer.publicKey, Keypair.generate());
+    const fundTx = new anchor.web3.Transaction().add(
+      anchor.web3.SystemProgram.transfer({
+        fromPubkey: payer.publicKey,
+        toPubkey: inputTokenAccount,
+        lamports: 500_000_000,
+      })
+    );
+    await anchor.web3.sendAndConfirmTransaction(connection, fundTx, [payer]);
+    await syncNative(connection, payer, inputTokenAccount);
+  });
+
+  it("should have initialized the global config with the wallet as resolver", async () => {
+    const config = await program.account.globalConfig.fetch(globalConfig);
+    expect(config.admin.equals(payer.publicKey)).toBe(true);
+    expect(config.paused).toBe(false);
+    const resolver = await program.account.resolver.fetch(resolverPda);
+    expect(resolver.resolver.equals(payer.publicKey)).toBe(true);
+    expect(resolver.suspended).toBe(false);
+  });
+
+  it("should place, update and fill an order by resolver", async () => {
+    const orderId = new BN(Date.now());
+    const makingAmount = new BN(100_000_000); // 0.1 SOL
+    const orderPda = findOrderPda(orderId, payer.publicKey);
+    const escrowTokenAccount = findEscrowTokenAccount(orderPda, inputMint);
+
+    // Place
+    await placeOrder(orderId, makingAmount, new BN(20_000_000), new BN(19_000_000));
+    let order = await program.account.orderV1.fetch(orderPda);
+    expect(order.makingAmount.eq(makingAmount)).toBe(true);
+    expect(Number((await getAccount(connection, escrowTokenAccount)).amount)).toBe(makingAmount.toNumber());
+
+    // Update: relax the price so the cloned pool can fill it
+    await program.methods
+      .updateOrder(orderId, new BN(2), new BN(1), new BN(0), new BN(0))
+      .accountsPartial({ maker: payer.publicKey, globalConfig, orderPda })
+      .signers([payer])
+      .rpc();
+    order = await program.account.orderV1.fetch(orderPda);
+    expect(order.expectTakingAmount.toNumber()).toBe(2);
+    expect(order.minReturnAmount.toNumber()).toBe(1);
+
+    // Fill: the escrow is the source account and the order PDA signs the swap
+    const swapArgs = {
+      amountIn: makingAmount,
+      expectAmountOut: order.expectTakingAmount,
+      minReturn: order.minReturnAmount,
+      amounts: [makingAmount],
+      routes: [[{ dexes: [{ raydiumSwap: {} }], weights: Buffer.from([100]) }]],
+    };
+    const raydiumAccounts = [
+      { pubkey: RAYDIUM_PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: orderPda, isSigner: false, isWritable: false },
+      { pubkey: escrowTokenAccount, isSigner: false, isWritable: true },
+      { pubkey: outputTokenAccount, isSigner: false, isWritable: true },
+      { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: RAYDIUM_POOL, isSigner: false, isWritable: true },
+      { pubkey: POOL_AUTHORITY, isSigner: false, isWritable: false },
+      { pubkey: AMM_OPEN_ORDERS, isSigner: false, isWritable: true },
+      { pubkey: AMM_TARGET_ORDERS, isSigner: false, isWritable: true },
+      { pubkey: POOL_COIN_VAULT, isSigner: false, isWritable: true },
+      { pubkey: POOL_PC_VAULT, isSigner: false, isWritable: true },
+      { pubkey: SERUM_PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: SERUM_MARKET, isSigner: false, isWritable: true },
+      { pubkey: SERUM_BIDS, isSigner: false, isWritable: true },
+      { pubkey: SERUM_ASKS, isSigner: false, isWritable: true },
+      { pubkey: SERUM_EVENT_QUEUE, isSigner: false, isWritable: true },
+      { pubkey: SERUM_COIN_VAULT, isSigner: false, isWritable: true },
+      { pubkey: SERUM_PC_VAULT, isSigner: false, isWritable: true },
+      { pubkey: SERUM_VAULT_SIGNER, isSigner: false, isWritable: false },
+    ];
+
+    const outputBefore = await getAccount(connection, outputTokenAccount);
+    await program.methods
+      .fillOrderByResolver(orderId, new BN(0), swapArgs)
+      .accountsPartial({
+        payer: payer.publicKey,
+        maker: payer.publicKey,
+        globalConfig,
+        saAuthority: null,
+        inputTokenSa: null,
+        outputTokenSa: null,
+        orderPda,
+        escrowTokenAccount,
+        tempInputTokenAccount: null,
+        outputTokenAccount,
+        inputTokenMint: inputMint,
+        outputTokenMint: outputMint,
+        inputTokenProgram: TOKEN_PROGRAM_ID,
+        outputTokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: null,
+        systemProgram: null,
+        instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        resolverPda,
+        oracle: null,
+        receiver: null,
+      })
+      .remainingAccounts(raydiumAccounts)
+      .signers([payer])
+      .rpc();
+
+    const outputAfter = await getAccount(connection, outputTokenAccount);
+    expect(Number(outputAfter.amount)).toBeGreaterThan(Number(outputBefore.amount));
+
+    // The order PDA and escrow are closed once filled
+    expect(await connection.getAccountInfo(orderPda)).toBeNull();
+    expect(await connection.getAccountInfo(escrowTokenAccount)).toBeNull();
+  });
+
+  it("should place and cancel an order by maker", async () => {
+    const orderId = new BN(Date.now());
+    const makingAmount = new BN(50_000_000); // 0.05 SOL
+    const orderPda = findOrderPda(orderId, payer.publicKey);
+    const escrowTokenAccount = findEscrowTokenAccount(orderPda, inputMint);
+
+    await placeOrder(orderId, makingAmount, new BN(10_000_000), new BN(9_000_000));
+    const inputBefore = await getAccount(connection, inputTokenAccount);
+
+    await program.methods
+      .cancelOrder(orderId, new BN(0))
+      .accountsPartial({
+        payer: payer.publicKey,
+        maker: payer.publicKey,
+        globalConfig,
+        orderPda,
+        escrowTokenAccount,
+        inputTokenAccount,
+        inputTokenMint: inputMint,
+        inputTokenProgram: TOKEN_PROGRAM_ID,
+        instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        resolverPda: null,
+      })
+      .signers([payer])
+      .rpc();
+
+    // The escrowed amount is refunded and the order accounts are closed
+    const inputAfter = await getAccount(connection, inputTokenAccount);
+    expect(Number(inputAfter.amount) - Number(inputBefore.amount)).toBe(makingAmount.toNumber());
+    expect(await connection.getAccountInfo(orderPda)).toBeNull();
+    expect(await connection.getAccountInfo(escrowTokenAccount)).toBeNull();
+  });
+});